@@ -3,7 +3,8 @@ use std::path::Path;
 use colored::*;
 use walkdir::WalkDir;
 use crate::analyzer::types::{ComplexityReport, ComplexityInfo};
-use crate::analyzer::structure::{IGNORED_DIRS, SOURCE_EXTENSIONS};
+use crate::analyzer::structure::IGNORED_DIRS;
+use crate::analyzer::languages::language_registry;
 
 const COMPLEXITY_THRESHOLD: u32 = 10;
 const LARGE_FILE_LINES: usize = 500;
@@ -17,9 +18,9 @@ pub async fn analyze_complexity(path: &str) -> Result<ComplexityReport> {
     for entry in walk_source_files(root_path) {
         let path = entry.path();
         
-        if path.is_file() && is_source_file(path) {
+        if path.is_file() && language_registry().is_source_path(path) {
             if let Ok(content) = std::fs::read_to_string(path) {
-                let complexity = calculate_complexity(&content);
+                let complexity = calculate_complexity(&content, path);
                 let lines = content.lines().count();
                 
                 if complexity > COMPLEXITY_THRESHOLD || lines > LARGE_FILE_LINES {
@@ -80,19 +81,13 @@ fn walk_source_files(root_path: &Path) -> impl Iterator<Item = walkdir::DirEntry
         })
 }
 
-fn is_source_file(path: &Path) -> bool {
-    path.extension()
-        .and_then(|e| e.to_str())
-        .map(|e| SOURCE_EXTENSIONS.contains(&e))
-        .unwrap_or(false)
-}
 
-fn calculate_complexity(content: &str) -> u32 {
+pub(crate) fn calculate_complexity(content: &str, path: &Path) -> u32 {
     let mut complexity = 1;
     
     for line in content.lines() {
         let trimmed = line.trim();
-        if trimmed.starts_with("//") || trimmed.starts_with("#") {
+        if language_registry().is_comment_line(path, trimmed) {
             continue;
         }
         
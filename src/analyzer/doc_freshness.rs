@@ -0,0 +1,335 @@
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+use crate::analyzer::languages::language_registry;
+use crate::analyzer::structure::IGNORED_DIRS;
+
+/// 코드와 맞지 않는 문서 항목
+#[derive(Debug, Clone)]
+pub struct StaleDoc {
+    /// 분석 루트 기준 상대 경로
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+/// 문서 주석의 매개변수 설명과 README의 명령줄 옵션을 현재 코드와 비교합니다
+pub fn check_doc_freshness(root: &Path) -> Vec<StaleDoc> {
+    // 파일 하나를 확인할 때는 상대 경로가 비지 않도록 그 파일의 디렉토리를 기준으로 함
+    let base = if root.is_dir() { root } else { root.parent().unwrap_or(Path::new("")) };
+    let sources: Vec<(String, String)> = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| !IGNORED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && language_registry().is_source_path(entry.path()))
+        .filter_map(|entry| {
+            let content = std::fs::read_to_string(entry.path()).ok()?;
+            let relative = entry.path().strip_prefix(base).unwrap_or(entry.path()).to_string_lossy().to_string();
+            Some((relative, content))
+        })
+        .collect();
+
+    let mut stale = Vec::new();
+    for (file, content) in &sources {
+        let documented = match Path::new(file).extension().and_then(|e| e.to_str()) {
+            Some("rs") => rust_documented_fns(content),
+            Some("js" | "jsx" | "ts" | "tsx" | "mjs") => jsdoc_documented_fns(content),
+            Some("py") => python_documented_fns(content),
+            _ => Vec::new(),
+        };
+        for function in documented {
+            stale.extend(function.problems(file));
+        }
+    }
+
+    let readme = if root.is_dir() { root.join("README.md") } else { root.to_path_buf() };
+    if readme.file_name().is_some_and(|n| n.to_string_lossy().eq_ignore_ascii_case("README.md")) {
+        if let Ok(content) = std::fs::read_to_string(&readme) {
+            let name = readme.strip_prefix(base).unwrap_or(&readme).to_string_lossy().to_string();
+            stale.extend(stale_readme_flags(&name, &content, &sources));
+        }
+    }
+    stale
+}
+
+/// 매개변수 설명이 있는 함수
+struct DocumentedFn {
+    name: String,
+    line: usize,
+    params: Vec<String>,
+    documented: Vec<String>,
+}
+
+impl DocumentedFn {
+    fn problems(&self, file: &str) -> Vec<StaleDoc> {
+        let mut problems = Vec::new();
+        // 구조 분해 매개변수가 있으면 문서의 이름과 대응시킬 수 없으므로 남은 설명을 오래된 것으로 보지 않음
+        let destructured = self.params.iter().any(|p| is_destructured(p));
+        let removed: Vec<&str> = self.documented.iter()
+            .filter(|d| !self.params.contains(d))
+            .map(String::as_str)
+            .collect();
+        if !removed.is_empty() && !destructured {
+            problems.push(StaleDoc {
+                file: file.to_string(),
+                line: self.line,
+                message: format!("`{}`: 문서에 있는 매개변수가 시그니처에 없습니다: {}", self.name, removed.join(", ")),
+            });
+        }
+        let undocumented: Vec<&str> = self.params.iter()
+            .filter(|p| !is_destructured(p) && !self.documented.contains(p))
+            .map(String::as_str)
+            .collect();
+        if !undocumented.is_empty() {
+            problems.push(StaleDoc {
+                file: file.to_string(),
+                line: self.line,
+                message: format!("`{}`: 문서에 없는 매개변수: {}", self.name, undocumented.join(", ")),
+            });
+        }
+        problems
+    }
+}
+
+fn is_destructured(param: &str) -> bool {
+    param.starts_with(['{', '['])
+}
+
+/// `/// # Arguments` 목록(`* `name` - …`)이 있는 Rust 함수
+fn rust_documented_fns(content: &str) -> Vec<DocumentedFn> {
+    static FN: OnceLock<Regex> = OnceLock::new();
+    static ARG: OnceLock<Regex> = OnceLock::new();
+    let fn_re = FN.get_or_init(|| Regex::new(r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?fn\s+(\w+)").unwrap());
+    let arg_re = ARG.get_or_init(|| Regex::new(r"^\s*[*-]\s+`(\w+)`").unwrap());
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut functions = Vec::new();
+    let mut doc: Vec<&str> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if let Some(text) = trimmed.strip_prefix("///") {
+            doc.push(text);
+            continue;
+        }
+        if trimmed.starts_with("#[") {
+            continue;
+        }
+        if let Some(caps) = fn_re.captures(line) {
+            let documented = section_items(&doc, &["# arguments", "# parameters", "# 인자", "# 매개변수"], arg_re);
+            if let Some(documented) = documented {
+                functions.push(DocumentedFn {
+                    name: caps[1].to_string(),
+                    line: index + 1,
+                    params: signature_params(&lines[index..], |param| {
+                        let name = param.split(':').next()?.trim().trim_start_matches("mut ").trim();
+                        (!name.contains("self") && !name.is_empty()).then(|| name.to_string())
+                    }),
+                    documented,
+                });
+            }
+        }
+        doc.clear();
+    }
+    functions
+}
+
+/// `@param` 태그가 있는 JSDoc 함수
+fn jsdoc_documented_fns(content: &str) -> Vec<DocumentedFn> {
+    static FN: OnceLock<Regex> = OnceLock::new();
+    static PARAM: OnceLock<Regex> = OnceLock::new();
+    let fn_re = FN.get_or_init(|| Regex::new(
+        r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?(?:function\s*\*?\s*(\w+)|(?:const|let|var)\s+(\w+)\s*=\s*(?:async\s*)?(?:function\b|\()|(?:(?:public|private|protected|static)\s+)*(\w+)\s*\()"
+    ).unwrap());
+    let param_re = PARAM.get_or_init(|| Regex::new(r"@param\s+(?:\{[^}]*\}\s+)?\[?(\w+)").unwrap());
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut functions = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        if !lines[index].trim_start().starts_with("/**") {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < lines.len() && !lines[index].contains("*/") {
+            index += 1;
+        }
+        let mut documented: Vec<String> = lines[start..=index.min(lines.len() - 1)].iter()
+            .filter_map(|l| param_re.captures(l).map(|c| c[1].to_string()))
+            .collect();
+        // `@param options.name`처럼 속성을 설명한 줄은 같은 이름으로 잡힘
+        documented.dedup();
+        index += 1;
+        let Some(line) = lines.get(index) else { break };
+        let Some(caps) = fn_re.captures(line) else { continue };
+        if documented.is_empty() {
+            continue;
+        }
+        let name = caps.get(1).or(caps.get(2)).or(caps.get(3)).map_or("", |m| m.as_str()).to_string();
+        functions.push(DocumentedFn {
+            name,
+            line: index + 1,
+            params: signature_params(&lines[index..], |param| {
+                if is_destructured(param) {
+                    return Some(param.to_string());
+                }
+                let name = param.trim_start_matches("...").split(['=', ':', '?']).next()?.trim();
+                (!name.is_empty()).then(|| name.to_string())
+            }),
+            documented,
+        });
+    }
+    functions
+}
+
+/// docstring에 `Args:`/`Parameters:` 목록이나 `:param name:`이 있는 Python 함수
+fn python_documented_fns(content: &str) -> Vec<DocumentedFn> {
+    static DEF: OnceLock<Regex> = OnceLock::new();
+    static SPHINX: OnceLock<Regex> = OnceLock::new();
+    static GOOGLE: OnceLock<Regex> = OnceLock::new();
+    let def_re = DEF.get_or_init(|| Regex::new(r"^\s*(?:async\s+)?def\s+(\w+)\s*\(").unwrap());
+    let sphinx_re = SPHINX.get_or_init(|| Regex::new(r":param\s+(?:\w+\s+)?(\w+):").unwrap());
+    let google_re = GOOGLE.get_or_init(|| Regex::new(r"^\s*\*{0,2}(\w+)\s*(?:\([^)]*\))?\s*:").unwrap());
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut functions = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let Some(caps) = def_re.captures(line) else { continue };
+
+        // 시그니처 다음의 docstring
+        let body_start = (index..lines.len()).find(|&i| lines[i].trim_end().ends_with(':')).map_or(lines.len(), |i| i + 1);
+        let Some(first) = lines.get(body_start).map(|l| l.trim_start()) else { continue };
+        let Some(quote) = ["\"\"\"", "'''"].into_iter().find(|q| first.starts_with(q)) else { continue };
+        let docstring_end = (body_start..lines.len())
+            .find(|&i| lines[i].matches(quote).count() >= if i == body_start { 2 } else { 1 })
+            .unwrap_or(lines.len() - 1);
+        let docstring: Vec<&str> = lines[body_start..=docstring_end].to_vec();
+
+        let mut documented: Vec<String> = docstring.iter()
+            .flat_map(|l| sphinx_re.captures_iter(l).map(|c| c[1].to_string()).collect::<Vec<_>>())
+            .collect();
+        if let Some(items) = section_items(&docstring, &["args:", "arguments:", "parameters:"], google_re) {
+            documented.extend(items);
+        }
+        if documented.is_empty() {
+            continue;
+        }
+        functions.push(DocumentedFn {
+            name: caps[1].to_string(),
+            line: index + 1,
+            params: signature_params(&lines[index..], |param| {
+                let name = param.trim_start_matches('*').split([':', '=']).next()?.trim();
+                (!name.is_empty() && name != "self" && name != "cls" && name != "/").then(|| name.to_string())
+            }),
+            documented,
+        });
+    }
+    functions
+}
+
+/// 문서에서 `headers` 중 하나로 시작하는 목록의 항목 이름. 해당 섹션이 없으면 None
+fn section_items(doc: &[&str], headers: &[&str], item: &Regex) -> Option<Vec<String>> {
+    let start = doc.iter().position(|l| headers.contains(&l.trim().to_lowercase().as_str()))?;
+    let mut items = Vec::new();
+    for line in &doc[start + 1..] {
+        let trimmed = line.trim();
+        if trimmed.is_empty() && items.is_empty() {
+            continue;
+        }
+        // 빈 줄 또는 다음 섹션 제목(`# Returns`, `Returns:`)에서 끝
+        let is_header = trimmed.starts_with('#') || (trimmed.ends_with(':') && !trimmed.contains(' '));
+        if trimmed.is_empty() || is_header {
+            break;
+        }
+        if let Some(caps) = item.captures(line) {
+            items.push(caps[1].to_string());
+        }
+    }
+    Some(items)
+}
+
+/// 시그니처의 괄호 안 매개변수 이름 (여러 줄에 걸친 시그니처 지원)
+fn signature_params(lines: &[&str], name_of: impl Fn(&str) -> Option<String>) -> Vec<String> {
+    let text: String = lines.iter().take(20).copied().collect::<Vec<_>>().join(" ");
+    let Some(open) = text.find('(') else { return Vec::new() };
+
+    let mut depth = 0;
+    let mut params = Vec::new();
+    let mut current = String::new();
+    for c in text[open + 1..].chars() {
+        match c {
+            '(' | '<' | '[' | '{' => depth += 1,
+            ')' if depth == 0 => break,
+            ')' | '>' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                params.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    params.push(current);
+
+    params.iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .filter_map(name_of)
+        .collect()
+}
+
+/// README의 명령어 예시에 자주 나오는 다른 도구
+const EXTERNAL_TOOLS: &[&str] = &[
+    "cargo", "rustup", "npm", "npx", "yarn", "pnpm", "node", "pip", "pip3", "python", "python3",
+    "go", "git", "docker", "kubectl", "make", "brew", "curl", "apt", "apt-get",
+];
+
+/// README에서 `--옵션` 형식으로 언급했지만 코드에 없는 명령줄 옵션
+fn stale_readme_flags(readme: &str, content: &str, sources: &[(String, String)]) -> Vec<StaleDoc> {
+    static FLAG: OnceLock<Regex> = OnceLock::new();
+    let flag_re = FLAG.get_or_init(|| Regex::new(r"(?:^|[\s`\[(])--([a-z][a-z0-9-]+)").unwrap());
+
+    let mut seen = BTreeSet::new();
+    let mut stale = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        for caps in flag_re.captures_iter(line) {
+            // `cargo build --release`처럼 다른 도구에 넘기는 옵션은 제외
+            let start = caps.get(0).map_or(0, |m| m.start());
+            let command = line[..start].rsplit('`').next().unwrap_or_default();
+            let program = command.trim_start_matches(['$', ' ']).split_whitespace().next().unwrap_or_default();
+            if EXTERNAL_TOOLS.contains(&program) {
+                continue;
+            }
+            let flag = caps[1].to_string();
+            if !seen.insert(flag.clone()) {
+                continue;
+            }
+            // clap derive는 필드 이름(snake_case)으로 옵션을 만듦
+            let field = flag.replace('-', "_");
+            let defined = sources.iter().any(|(_, source)| {
+                source.contains(&format!("--{}", flag))
+                    || source.contains(&format!("\"{}\"", flag))
+                    || contains_identifier(source, &field)
+            });
+            if !defined {
+                stale.push(StaleDoc {
+                    file: readme.to_string(),
+                    line: index + 1,
+                    message: format!("코드에서 찾을 수 없는 옵션: --{}", flag),
+                });
+            }
+        }
+    }
+    stale
+}
+
+fn contains_identifier(source: &str, name: &str) -> bool {
+    source.match_indices(name).any(|(i, _)| {
+        let before = source[..i].chars().next_back();
+        let after = source[i + name.len()..].chars().next();
+        let is_ident = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        !is_ident(before) && !is_ident(after)
+    })
+}
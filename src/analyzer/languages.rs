@@ -0,0 +1,172 @@
+use std::path::Path;
+use std::sync::OnceLock;
+use crate::config::LanguageConfig;
+
+static REGISTRY: OnceLock<LanguageRegistry> = OnceLock::new();
+
+/// 확장자별 언어 정의 모음 (내장 언어 + 설정의 `[[languages]]`)
+#[derive(Debug, Clone)]
+pub struct LanguageRegistry {
+    languages: Vec<LanguageConfig>,
+}
+
+impl LanguageRegistry {
+    /// 내장 언어에 설정의 언어를 덮어쓰거나 추가합니다
+    pub fn new(overrides: &[LanguageConfig]) -> Self {
+        let mut languages = builtin_languages();
+        for language in overrides {
+            match languages.iter_mut().find(|l| l.name.eq_ignore_ascii_case(&language.name)) {
+                Some(existing) => *existing = language.clone(),
+                None => languages.push(language.clone()),
+            }
+        }
+        // 나중에 정의된 언어가 같은 확장자를 가져가도록 앞쪽 정의에서 제거
+        for i in 0..languages.len() {
+            let (before, after) = languages.split_at_mut(i + 1);
+            let current = &mut before[i];
+            current.extensions.retain(|ext| !after.iter().any(|l| l.extensions.contains(ext)));
+        }
+        languages.retain(|l| !l.extensions.is_empty());
+        Self { languages }
+    }
+
+    pub fn for_extension(&self, extension: &str) -> Option<&LanguageConfig> {
+        let extension = extension.to_lowercase();
+        self.languages.iter().find(|l| l.extensions.contains(&extension))
+    }
+
+    pub fn for_path(&self, path: &Path) -> Option<&LanguageConfig> {
+        path.extension().and_then(|e| e.to_str()).and_then(|e| self.for_extension(e))
+    }
+
+    /// 프로젝트 분석 대상 소스 파일 확장자인지
+    pub fn is_source(&self, extension: &str) -> bool {
+        self.for_extension(extension).is_some_and(|l| l.source)
+    }
+
+    pub fn is_source_path(&self, path: &Path) -> bool {
+        self.for_path(path).is_some_and(|l| l.source)
+    }
+
+    /// 언어 이름 (표시용). 등록되지 않은 확장자는 "Other"
+    pub fn language_name(&self, extension: &str) -> &str {
+        self.for_extension(extension).map(|l| l.name.as_str()).unwrap_or("Other")
+    }
+
+    /// 함수 정의 표시 문자열 개수
+    pub fn count_functions(&self, content: &str, extension: &str) -> usize {
+        self.for_extension(extension)
+            .map(|l| l.function_markers.iter().map(|m| content.matches(m.as_str()).count()).sum())
+            .unwrap_or(0)
+    }
+
+    /// 해당 파일의 한 줄 주석인지. 언어를 모르면 `//`, `#`를 주석으로 봅니다
+    pub fn is_comment_line(&self, path: &Path, line: &str) -> bool {
+        match self.for_path(path) {
+            Some(language) => language.line_comments.iter().any(|c| line.starts_with(c.as_str())),
+            None => line.starts_with("//") || line.starts_with('#'),
+        }
+    }
+
+    /// 새 주석을 달 때 쓸 (여는, 닫는) 문자열. 한 줄 주석이 없는 형식은 블록 주석을 쓰고,
+    /// 주석을 쓸 수 없거나(JSON) 모르는 형식이면 `None`
+    pub fn comment_delimiters(&self, path: &Path) -> Option<(String, String)> {
+        if let Some(prefix) = self.for_path(path).and_then(|l| l.line_comments.first()) {
+            return Some((prefix.clone(), String::new()));
+        }
+        let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let (open, close) = match extension.as_str() {
+            "html" | "md" | "xml" => ("<!--", "-->"),
+            "css" | "scss" => ("/*", "*/"),
+            "yml" | "yaml" | "toml" | "tf" | "sh" => ("#", ""),
+            _ if file_name.starts_with("Dockerfile") => ("#", ""),
+            _ => return None,
+        };
+        Some((open.to_string(), close.to_string()))
+    }
+
+    /// 언어 규칙에 따른 테스트 파일 경로 (원본과 같은 디렉토리)
+    pub fn test_file_path(&self, path: &str) -> String {
+        let source = Path::new(path);
+        let stem = source.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let extension = source.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+        let pattern = self.for_extension(&extension)
+            .and_then(|l| l.test_file.as_deref())
+            .unwrap_or("{stem}_test.{ext}");
+        let file_name = pattern.replace("{stem}", &stem).replace("{ext}", &extension);
+        source.with_file_name(file_name).to_string_lossy().to_string()
+    }
+
+    /// 포매터 명령어와 인자. `{path}`는 대상 파일 경로로 치환됩니다
+    pub fn formatter(&self, path: &str) -> Option<(String, Vec<String>)> {
+        let language = self.for_path(Path::new(path))?;
+        let (program, args) = language.formatter.split_first()?;
+        let args = args.iter().map(|a| a.replace("{path}", path)).collect();
+        Some((program.clone(), args))
+    }
+}
+
+/// 설정의 언어 정의로 전역 레지스트리를 초기화합니다. 처음 호출만 적용됩니다
+pub fn init_languages(overrides: &[LanguageConfig]) {
+    let _ = REGISTRY.set(LanguageRegistry::new(overrides));
+}
+
+/// 전역 언어 레지스트리. 초기화 전에는 내장 언어만 사용합니다
+pub fn language_registry() -> &'static LanguageRegistry {
+    REGISTRY.get_or_init(|| LanguageRegistry::new(&[]))
+}
+
+fn language(
+    name: &str,
+    extensions: &[&str],
+    line_comments: &[&str],
+    function_markers: &[&str],
+    test_file: Option<&str>,
+    formatter: &[&str],
+) -> LanguageConfig {
+    let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+    LanguageConfig {
+        name: name.to_string(),
+        extensions: strings(extensions),
+        line_comments: strings(line_comments),
+        function_markers: strings(function_markers),
+        test_file: test_file.map(str::to_string),
+        formatter: strings(formatter),
+        source: true,
+    }
+}
+
+fn builtin_languages() -> Vec<LanguageConfig> {
+    const SLASH: &[&str] = &["//"];
+    const HASH: &[&str] = &["#"];
+    const PRETTIER: &[&str] = &["prettier", "--stdin-filepath", "{path}"];
+
+    let mut markup = language("Markup", &["json", "css", "scss", "html", "md"], &[], &[], None, PRETTIER);
+    markup.source = false;
+
+    vec![
+        language("Rust", &["rs"], SLASH, &["fn "], Some("{stem}_test.rs"),
+            &["rustfmt", "--edition", "2021", "--emit", "stdout"]),
+        language("JavaScript", &["js", "jsx"], SLASH, &["function", "=>"], Some("{stem}.test.{ext}"), PRETTIER),
+        language("TypeScript", &["ts", "tsx"], SLASH, &["function", "=>"], Some("{stem}.test.{ext}"), PRETTIER),
+        language("Vue", &["vue"], SLASH, &["function", "=>"], Some("{stem}.spec.js"), PRETTIER),
+        language("Python", &["py"], HASH, &["def "], Some("test_{stem}.py"), &["black", "--quiet", "-"]),
+        language("Java", &["java"], SLASH, &["public ", "private "], Some("{stem}Test.java"), &[]),
+        language("Kotlin", &["kt"], SLASH, &["fun "], Some("{stem}Test.kt"), &[]),
+        language("Scala", &["scala"], SLASH, &["def "], Some("{stem}Spec.scala"), &[]),
+        language("Go", &["go"], SLASH, &["func "], Some("{stem}_test.go"), &["gofmt"]),
+        language("C/C++", &["c", "cpp", "cc", "h", "hpp"], SLASH, &[], None, &[]),
+        language("C#", &["cs"], SLASH, &[], Some("{stem}Tests.cs"), &[]),
+        language("Swift", &["swift"], SLASH, &["func "], Some("{stem}Tests.swift"), &[]),
+        language("Dart", &["dart"], SLASH, &[], Some("{stem}_test.dart"), &[]),
+        language("Ruby", &["rb"], HASH, &["def "], Some("{stem}_spec.rb"), &[]),
+        language("PHP", &["php"], &["//", "#"], &["function "], Some("{stem}Test.php"), &[]),
+        language("R", &["r"], HASH, &["function("], Some("test-{stem}.R"), &[]),
+        language("SQL", &["sql"], &["--"],
+            &["CREATE FUNCTION", "CREATE OR REPLACE FUNCTION", "CREATE PROCEDURE",
+              "create function", "create or replace function", "create procedure"],
+            None, &[]),
+        markup,
+    ]
+}
@@ -2,11 +2,23 @@ mod types;
 mod structure;
 mod dependencies;
 mod complexity;
+mod tree;
+mod snapshot;
+mod languages;
+mod routes;
+mod doc_freshness;
+mod project_type;
 
 pub use types::*;
-pub use structure::analyze_structure;
+pub use structure::{analyze_structure, IGNORED_DIRS};
+pub use languages::{LanguageRegistry, init_languages, language_registry};
+pub use routes::{Route, RouteFile, detect_routes};
+pub use doc_freshness::{StaleDoc, check_doc_freshness};
+pub use project_type::{DetectedProject, detect_project};
 pub use dependencies::analyze_dependencies;
 pub use complexity::analyze_complexity;
+pub use tree::analyze_tree;
+pub use snapshot::{AnalysisSnapshot, print_snapshot_comparison};
 
 use anyhow::Result;
 use crate::config::Config;
@@ -30,6 +42,10 @@ impl CodeAnalyzer {
         analyze_complexity(path).await
     }
     
+    pub async fn analyze_tree(&self, path: &str) -> Result<DirectoryNode> {
+        analyze_tree(path).await
+    }
+    
     pub async fn analyze_all(&self, path: &str) -> Result<FullAnalysisReport> {
         let structure = self.analyze_structure(path).await?;
         let dependencies = self.analyze_dependencies(path).await?;
@@ -54,9 +70,40 @@ impl CodeAnalyzer {
         complexity::print_complexity_report(complexity);
     }
     
+    pub fn print_tree_report(&self, tree: &DirectoryNode) {
+        tree::print_tree_report(tree);
+    }
+    
     pub fn print_full_report(&self, report: &FullAnalysisReport) {
         self.print_structure_report(&report.structure);
         self.print_dependency_report(&report.dependencies);
         self.print_complexity_report(&report.complexity);
     }
-} 
\ No newline at end of file
+}
+
+impl FullAnalysisReport {
+    /// 공유용 마크다운 요약 (구조, 의존성 수, 평균 복잡도와 복잡한 파일 상위 5개)
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::new();
+        output.push_str("## 프로젝트 구조\n\n");
+        output.push_str(&format!("총 파일: {} | 총 라인: {}\n\n", self.structure.total_files, self.structure.total_lines));
+        let mut languages: Vec<_> = self.structure.languages.iter().collect();
+        languages.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.line_count));
+        for (lang, stats) in languages {
+            output.push_str(&format!("- {}: {} 파일, {} 라인 ({:.1}%)\n", lang, stats.file_count, stats.line_count, stats.percentage));
+        }
+        
+        output.push_str("\n## 의존성\n\n");
+        output.push_str(&format!("직접: {} | 개발: {}\n", self.dependencies.direct_dependencies.len(), self.dependencies.dev_dependencies.len()));
+        
+        output.push_str("\n## 복잡도\n\n");
+        output.push_str(&format!("평균 복잡도: {:.1}\n", self.complexity.average_complexity));
+        if !self.complexity.complex_files.is_empty() {
+            output.push('\n');
+            for file in self.complexity.complex_files.iter().take(5) {
+                output.push_str(&format!("- `{}` - 복잡도: {}, {} 라인\n", file.file, file.complexity, file.lines));
+            }
+        }
+        output
+    }
+}
@@ -0,0 +1,227 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// 매니페스트로 판별한 프로젝트 종류
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedProject {
+    /// 예: "Rust", "Kotlin (Gradle)", "C# (.NET)"
+    pub language: String,
+    /// 의존성으로 찾은 대표 프레임워크 (예: "axum", "Next.js", "Django")
+    pub framework: Option<String>,
+    /// 판별에 쓴 파일 (루트 기준 상대 경로)
+    pub manifest: String,
+}
+
+impl fmt::Display for DetectedProject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.framework {
+            Some(framework) => write!(f, "{} / {}", self.language, framework),
+            None => write!(f, "{}", self.language),
+        }
+    }
+}
+
+/// 생태계 하나를 판별하는 규칙. `markers` 중 하나가 루트에 있으면 해당하고,
+/// `manifests` 내용에서 `frameworks`의 정규식을 순서대로 찾아 첫 번째 프레임워크를 고릅니다.
+/// `*.확장자` 형태는 루트에서 그 확장자를 가진 아무 파일이나 뜻합니다
+struct ProjectDetector {
+    language: &'static str,
+    markers: &'static [&'static str],
+    /// 비어 있으면 `markers`를 그대로 읽음
+    manifests: &'static [&'static str],
+    frameworks: &'static [(&'static str, &'static str)],
+}
+
+/// 위에서부터 검사하므로 더 구체적인 규칙(Bun, Deno, Kotlin DSL)을 일반 규칙보다 앞에 둡니다.
+/// 새 생태계는 여기에 항목을 추가하면 됩니다
+const DETECTORS: &[ProjectDetector] = &[
+    ProjectDetector {
+        language: "Rust",
+        markers: &["Cargo.toml"],
+        manifests: &[],
+        frameworks: &[
+            (r"(?m)^\s*axum\s*[.=]", "axum"),
+            (r"(?m)^\s*actix-web\s*[.=]", "actix-web"),
+            (r"(?m)^\s*rocket\s*[.=]", "Rocket"),
+            (r"(?m)^\s*warp\s*[.=]", "warp"),
+            (r"(?m)^\s*poem\s*[.=]", "Poem"),
+            (r"(?m)^\s*leptos\s*[.=]", "Leptos"),
+            (r"(?m)^\s*tauri\s*[.=]", "Tauri"),
+            (r"(?m)^\s*bevy\s*[.=]", "Bevy"),
+        ],
+    },
+    ProjectDetector {
+        language: "Dart",
+        markers: &["pubspec.yaml"],
+        manifests: &[],
+        frameworks: &[(r"(?m)^\s*sdk:\s*flutter\b|^flutter:", "Flutter")],
+    },
+    ProjectDetector {
+        language: "TypeScript (Deno)",
+        markers: &["deno.json", "deno.jsonc"],
+        manifests: &[],
+        frameworks: &[
+            (r#"\$fresh/|"fresh""#, "Fresh"),
+            (r"\bhono\b", "Hono"),
+            (r"\boak\b", "Oak"),
+        ],
+    },
+    ProjectDetector {
+        language: "JavaScript (Bun)",
+        markers: &["bun.lockb", "bun.lock", "bunfig.toml"],
+        manifests: &["package.json"],
+        frameworks: NODE_FRAMEWORKS,
+    },
+    ProjectDetector {
+        language: "Node.js/JavaScript",
+        markers: &["package.json"],
+        manifests: &[],
+        frameworks: NODE_FRAMEWORKS,
+    },
+    ProjectDetector {
+        language: "Python",
+        markers: &["pyproject.toml", "requirements.txt", "setup.py", "Pipfile"],
+        manifests: &["pyproject.toml", "requirements.txt", "setup.py", "Pipfile"],
+        frameworks: &[
+            (r"(?i)\bdjango\b", "Django"),
+            (r"(?i)\bfastapi\b", "FastAPI"),
+            (r"(?i)\bflask\b", "Flask"),
+            (r"(?i)\bstreamlit\b", "Streamlit"),
+        ],
+    },
+    ProjectDetector {
+        language: "Go",
+        markers: &["go.mod"],
+        manifests: &[],
+        frameworks: &[
+            (r"github\.com/gin-gonic/gin\b", "Gin"),
+            (r"github\.com/labstack/echo\b", "Echo"),
+            (r"github\.com/gofiber/fiber\b", "Fiber"),
+            (r"github\.com/go-chi/chi\b", "chi"),
+        ],
+    },
+    ProjectDetector {
+        language: "Kotlin (Gradle)",
+        markers: &["build.gradle.kts", "settings.gradle.kts"],
+        manifests: &["build.gradle.kts"],
+        frameworks: JVM_FRAMEWORKS,
+    },
+    ProjectDetector {
+        language: "Java (Maven)",
+        markers: &["pom.xml"],
+        manifests: &[],
+        frameworks: JVM_FRAMEWORKS,
+    },
+    ProjectDetector {
+        language: "Java (Gradle)",
+        markers: &["build.gradle"],
+        manifests: &[],
+        frameworks: JVM_FRAMEWORKS,
+    },
+    ProjectDetector {
+        language: "C# (.NET)",
+        markers: &["*.csproj", "*.sln"],
+        manifests: &["*.csproj"],
+        frameworks: &[
+            (r"Microsoft\.NET\.Sdk\.BlazorWebAssembly|Microsoft\.AspNetCore\.Components", "Blazor"),
+            (r"<UseMaui>\s*true", ".NET MAUI"),
+            (r"Microsoft\.NET\.Sdk\.Web\b", "ASP.NET Core"),
+        ],
+    },
+    ProjectDetector {
+        language: "Swift",
+        markers: &["Package.swift", "*.xcodeproj"],
+        manifests: &["Package.swift"],
+        frameworks: &[(r"vapor/vapor", "Vapor")],
+    },
+    ProjectDetector {
+        language: "Elixir",
+        markers: &["mix.exs"],
+        manifests: &[],
+        frameworks: &[(r":phoenix\b", "Phoenix"), (r":nerves\b", "Nerves")],
+    },
+    ProjectDetector {
+        language: "Ruby",
+        markers: &["Gemfile"],
+        manifests: &[],
+        frameworks: &[(r#"gem\s+["']rails["']"#, "Rails"), (r#"gem\s+["']sinatra["']"#, "Sinatra")],
+    },
+    ProjectDetector {
+        language: "PHP",
+        markers: &["composer.json"],
+        manifests: &[],
+        frameworks: &[(r#""laravel/framework""#, "Laravel"), (r#""symfony/framework-bundle""#, "Symfony")],
+    },
+    ProjectDetector {
+        language: "C/C++ (CMake)",
+        markers: &["CMakeLists.txt"],
+        manifests: &[],
+        frameworks: &[(r"\bQt[56]?\b", "Qt")],
+    },
+];
+
+/// package.json의 의존성 이름. 메타 프레임워크를 라이브러리보다 먼저 검사합니다
+const NODE_FRAMEWORKS: &[(&str, &str)] = &[
+    (r#""next"\s*:"#, "Next.js"),
+    (r#""nuxt"\s*:"#, "Nuxt"),
+    (r#""@sveltejs/kit"\s*:"#, "SvelteKit"),
+    (r#""@remix-run/[\w-]+"\s*:"#, "Remix"),
+    (r#""astro"\s*:"#, "Astro"),
+    (r#""@nestjs/core"\s*:"#, "NestJS"),
+    (r#""@angular/core"\s*:"#, "Angular"),
+    (r#""electron"\s*:"#, "Electron"),
+    (r#""react-native"\s*:"#, "React Native"),
+    (r#""react"\s*:"#, "React"),
+    (r#""vue"\s*:"#, "Vue"),
+    (r#""svelte"\s*:"#, "Svelte"),
+    (r#""hono"\s*:"#, "Hono"),
+    (r#""fastify"\s*:"#, "Fastify"),
+    (r#""express"\s*:"#, "Express"),
+];
+
+const JVM_FRAMEWORKS: &[(&str, &str)] = &[
+    (r"org\.springframework\.boot", "Spring Boot"),
+    (r"io\.quarkus", "Quarkus"),
+    (r"io\.ktor", "Ktor"),
+    (r"com\.android\.(application|library)", "Android"),
+];
+
+/// `root`의 매니페스트로 언어와 프레임워크를 판별합니다. 알 수 없으면 `None`
+pub fn detect_project(root: &Path) -> Option<DetectedProject> {
+    DETECTORS.iter().find_map(|detector| {
+        let marker = detector.markers.iter().find_map(|marker| find_marker(root, marker))?;
+        let manifests: Vec<PathBuf> = if detector.manifests.is_empty() {
+            vec![marker.clone()]
+        } else {
+            detector.manifests.iter().filter_map(|manifest| find_marker(root, manifest)).collect()
+        };
+        let contents: Vec<String> = manifests.iter().filter_map(|m| std::fs::read_to_string(m).ok()).collect();
+        let framework = detector.frameworks.iter()
+            .find(|(pattern, _)| {
+                let pattern = Regex::new(pattern).expect("프레임워크 패턴");
+                contents.iter().any(|content| pattern.is_match(content))
+            })
+            .map(|(_, name)| name.to_string());
+        Some(DetectedProject {
+            language: detector.language.to_string(),
+            framework,
+            manifest: marker.strip_prefix(root).unwrap_or(&marker).to_string_lossy().to_string(),
+        })
+    })
+}
+
+fn find_marker(root: &Path, marker: &str) -> Option<PathBuf> {
+    let Some(extension) = marker.strip_prefix("*.") else {
+        let path = root.join(marker);
+        return path.exists().then_some(path);
+    };
+    let mut matches: Vec<PathBuf> = std::fs::read_dir(root).ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|e| e == extension))
+        .collect();
+    matches.sort();
+    matches.into_iter().next()
+}
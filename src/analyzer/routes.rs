@@ -0,0 +1,147 @@
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+use crate::analyzer::structure::IGNORED_DIRS;
+
+const HTTP_METHODS: &str = "get|post|put|delete|patch|head|options";
+
+/// 백엔드 코드에서 찾은 HTTP 라우트
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub framework: &'static str,
+    /// 대문자 HTTP 메서드. 메서드를 알 수 없으면 "ANY"
+    pub method: String,
+    pub path: String,
+    /// 분석 루트 기준 상대 경로
+    pub file: String,
+    pub line: usize,
+}
+
+/// 라우트가 정의된 파일
+#[derive(Debug, Clone)]
+pub struct RouteFile {
+    pub path: String,
+    pub content: String,
+    pub routes: Vec<Route>,
+}
+
+impl Route {
+    /// OpenAPI 형식 경로 (`:id`, `<id>`, `{id:int}` → `{id}`)
+    pub fn openapi_path(&self) -> String {
+        static PARAM: OnceLock<Regex> = OnceLock::new();
+        let param = PARAM.get_or_init(|| {
+            Regex::new(r":(\w+)|<(?:\w+:)?(\w+)>|\{(\w+)(?::[^}]*)?\}").unwrap()
+        });
+        param.replace_all(&self.path, |caps: &regex::Captures| {
+            let name = caps.get(1).or(caps.get(2)).or(caps.get(3)).map_or("", |m| m.as_str());
+            format!("{{{}}}", name)
+        }).to_string()
+    }
+}
+
+/// axum, actix-web, express, FastAPI 라우트 정의를 찾습니다
+pub fn detect_routes(root: &Path) -> Vec<RouteFile> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| !IGNORED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let framework = framework_for(path)?;
+            let content = std::fs::read_to_string(path).ok()?;
+            let framework = framework(&content)?;
+            let relative = path.strip_prefix(root).unwrap_or(path).to_string_lossy().to_string();
+            let routes = find_routes(framework, &content, &relative);
+            (!routes.is_empty()).then_some(RouteFile { path: relative, content, routes })
+        })
+        .collect()
+}
+
+/// 확장자로 후보를 고르고 import 문으로 프레임워크를 판별합니다
+fn framework_for(path: &Path) -> Option<fn(&str) -> Option<&'static str>> {
+    match path.extension()?.to_str()? {
+        "rs" => Some(|content| {
+            if content.contains("actix_web") {
+                Some("actix-web")
+            } else if content.contains("axum") {
+                Some("axum")
+            } else {
+                None
+            }
+        }),
+        "js" | "mjs" | "cjs" | "ts" => Some(|content| content.contains("express").then_some("express")),
+        "py" => Some(|content| content.contains("fastapi").then_some("fastapi")),
+        _ => None,
+    }
+}
+
+fn find_routes(framework: &'static str, content: &str, file: &str) -> Vec<Route> {
+    // (프레임워크 계열, 경로 뒤에 메서드가 이어지는 형태인지, 패턴)
+    static PATTERNS: OnceLock<Vec<(&'static str, bool, Regex)>> = OnceLock::new();
+    let patterns = PATTERNS.get_or_init(|| {
+        let regex = |pattern: String| Regex::new(&pattern).unwrap();
+        vec![
+            // axum / actix: .route("/users", get(list).post(create)), .route("/users", web::get().to(list))
+            ("rust", true, regex(r#"\.route\(\s*"([^"]+)"(.*)"#.to_string())),
+            // actix: #[get("/users/{id}")]
+            ("rust", false, regex(format!(r#"#\[({})\(\s*"([^"]+)""#, HTTP_METHODS))),
+            // actix: web::resource("/users").route(web::get().to(list))
+            ("rust", true, regex(r#"web::resource\(\s*"([^"]+)"\s*\)(.*)"#.to_string())),
+            // express: app.get('/users', ...), router.post("/users/:id", ...)
+            ("express", false, regex(format!(r#"\b\w+\.({})\(\s*['"`]([^'"`]+)['"`]"#, HTTP_METHODS))),
+            // FastAPI: @app.get("/users/{id}"), @router.post("/users")
+            ("fastapi", false, regex(format!(r#"@\w+\.({})\(\s*['"]([^'"]+)['"]"#, HTTP_METHODS))),
+        ]
+    });
+    static CHAINED_METHOD: OnceLock<Regex> = OnceLock::new();
+    let chained = CHAINED_METHOD.get_or_init(|| Regex::new(&format!(r"\b({})\(", HTTP_METHODS)).unwrap());
+
+    let family = match framework {
+        "axum" | "actix-web" => "rust",
+        other => other,
+    };
+
+    let mut routes = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        for (pattern_family, chained_methods, pattern) in patterns {
+            if *pattern_family != family {
+                continue;
+            }
+            let Some(caps) = pattern.captures(line) else { continue };
+            let path = if *chained_methods { &caps[1] } else { &caps[2] };
+            if !path.starts_with('/') {
+                continue;
+            }
+            let route = |method: &str, path: &str| Route {
+                framework,
+                method: method.to_uppercase(),
+                path: path.to_string(),
+                file: file.to_string(),
+                line: number + 1,
+            };
+
+            if *chained_methods {
+                // 메서드가 다음 줄에 있는 경우도 있으므로 다음 라우트 정의 전까지 몇 줄 더 살펴봄
+                let window: String = std::iter::once(&caps[2])
+                    .chain(content.lines().skip(number + 1).take(3))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let window = window.split(".route(\"").next().unwrap_or_default();
+                let methods: Vec<&str> = chained.captures_iter(window)
+                    .map(|c| c.get(1).map_or("", |m| m.as_str()))
+                    .collect();
+                if methods.is_empty() {
+                    routes.push(route("ANY", path));
+                }
+                for method in methods {
+                    routes.push(route(method, path));
+                }
+            } else {
+                routes.push(route(&caps[1], path));
+            }
+        }
+    }
+    routes
+}
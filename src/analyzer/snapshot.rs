@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use colored::*;
+use crate::renderer::{plain, plain_text};
+use serde::{Serialize, Deserialize};
+use std::path::Path;
+use crate::analyzer::types::FullAnalysisReport;
+
+/// 시간에 따른 비교를 위해 저장하는 분석 지표
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnalysisSnapshot {
+    pub created_at: DateTime<Local>,
+    pub total_files: usize,
+    pub total_lines: usize,
+    pub average_complexity: f32,
+    pub dependency_count: usize,
+    pub dev_dependency_count: usize,
+    /// 복잡도 또는 크기 기준을 넘는 파일 수
+    pub issue_count: usize,
+}
+
+impl AnalysisSnapshot {
+    pub fn from_report(report: &FullAnalysisReport) -> Self {
+        Self {
+            created_at: Local::now(),
+            total_files: report.structure.total_files,
+            total_lines: report.structure.total_lines,
+            average_complexity: report.complexity.average_complexity,
+            dependency_count: report.dependencies.direct_dependencies.len(),
+            dev_dependency_count: report.dependencies.dev_dependencies.len(),
+            issue_count: report.complexity.complex_files.len(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("스냅샷 저장 실패: {}", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("스냅샷을 읽을 수 없습니다: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("스냅샷 파싱 실패: {}", path.display()))
+    }
+}
+
+/// 지표가 줄어드는 것이 좋은지 여부
+#[derive(Clone, Copy)]
+enum Trend {
+    Neutral,
+    LowerIsBetter,
+}
+
+pub fn print_snapshot_comparison(before: &AnalysisSnapshot, after: &AnalysisSnapshot) {
+    println!("\n{}", "스냅샷 비교".bright_cyan().bold());
+    println!("{} {} {}\n",
+        before.created_at.format("%Y-%m-%d %H:%M").to_string().dimmed(),
+        plain("→"),
+        after.created_at.format("%Y-%m-%d %H:%M").to_string().dimmed()
+    );
+
+    let rows: [(&str, f64, f64, Trend); 6] = [
+        ("파일 수", before.total_files as f64, after.total_files as f64, Trend::Neutral),
+        ("라인 수", before.total_lines as f64, after.total_lines as f64, Trend::Neutral),
+        ("평균 복잡도", before.average_complexity as f64, after.average_complexity as f64, Trend::LowerIsBetter),
+        ("의존성", before.dependency_count as f64, after.dependency_count as f64, Trend::LowerIsBetter),
+        ("개발 의존성", before.dev_dependency_count as f64, after.dev_dependency_count as f64, Trend::Neutral),
+        ("이슈 파일", before.issue_count as f64, after.issue_count as f64, Trend::LowerIsBetter),
+    ];
+
+    let mut improved = 0;
+    let mut regressed = 0;
+    for (name, old, new, trend) in rows {
+        let delta = new - old;
+        // 소수 첫째 자리까지만 표시하므로 그보다 작은 차이는 변화로 보지 않음
+        let delta_text = if delta.abs() < 0.05 {
+            "변화 없음".dimmed()
+        } else {
+            let text = plain_text(&format!("{}{}", if delta > 0.0 { "▲ +" } else { "▼ " }, format_value(delta))).into_owned();
+            match trend {
+                Trend::Neutral => text.normal(),
+                Trend::LowerIsBetter if delta < 0.0 => {
+                    improved += 1;
+                    text.green()
+                }
+                Trend::LowerIsBetter => {
+                    regressed += 1;
+                    text.red()
+                }
+            }
+        };
+        println!("{}", plain(&format!("  {:<12} {:>10} → {:<10} {}", name, format_value(old), format_value(new), delta_text)));
+    }
+
+    println!();
+    match improved.cmp(&regressed) {
+        std::cmp::Ordering::Greater => println!("{}", plain("📈 코드베이스가 개선되고 있습니다.").green()),
+        std::cmp::Ordering::Less => println!("{}", plain("📉 일부 지표가 나빠졌습니다.").red()),
+        std::cmp::Ordering::Equal => println!("{}", plain("➖ 뚜렷한 변화가 없습니다.").dimmed()),
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.1}", value)
+    }
+}
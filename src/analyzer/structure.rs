@@ -4,11 +4,10 @@ use std::collections::HashMap;
 use colored::*;
 use walkdir::WalkDir;
 use crate::analyzer::types::{ProjectStructure, LanguageStats};
+use crate::analyzer::languages::language_registry;
+use crate::analyzer::project_type::detect_project;
 
 pub const IGNORED_DIRS: &[&str] = &["target", "node_modules", ".git", "dist", "build", "vendor"];
-pub const SOURCE_EXTENSIONS: &[&str] = &[
-    "rs", "js", "ts", "jsx", "tsx", "py", "java", "go", "c", "cpp", "cs", "rb", "php"
-];
 
 pub async fn analyze_structure(path: &str) -> Result<ProjectStructure> {
     let root_path = Path::new(path).canonicalize()?;
@@ -22,14 +21,14 @@ pub async fn analyze_structure(path: &str) -> Result<ProjectStructure> {
         if path.is_file() {
             if let Some(ext) = path.extension() {
                 let ext_str = ext.to_string_lossy();
-                if SOURCE_EXTENSIONS.contains(&ext_str.as_ref()) {
+                if language_registry().is_source(&ext_str) {
                     total_files += 1;
                     
                     if let Ok(content) = std::fs::read_to_string(path) {
                         let line_count = content.lines().count();
                         total_lines += line_count;
                         
-                        let lang = detect_language(&ext_str);
+                        let lang = language_registry().language_name(&ext_str);
                         let stats = languages.entry(lang.to_string()).or_insert(LanguageStats {
                             file_count: 0,
                             line_count: 0,
@@ -49,6 +48,7 @@ pub async fn analyze_structure(path: &str) -> Result<ProjectStructure> {
     }
     
     Ok(ProjectStructure {
+        project: detect_project(&root_path),
         root_path,
         total_files,
         total_lines,
@@ -58,6 +58,9 @@ pub async fn analyze_structure(path: &str) -> Result<ProjectStructure> {
 
 pub fn print_structure_report(structure: &ProjectStructure) {
     println!("\n{}", "프로젝트 구조".bright_cyan().bold());
+    if let Some(project) = &structure.project {
+        println!("유형: {} ({})", project.to_string().green(), project.manifest.dimmed());
+    }
     println!("총 파일: {} | 총 라인: {}\n", 
         structure.total_files.to_string().yellow(),
         structure.total_lines.to_string().yellow()
@@ -87,19 +90,3 @@ fn walk_source_files(root_path: &Path) -> impl Iterator<Item = walkdir::DirEntry
             })
         })
 }
-
-fn detect_language(ext: &str) -> &'static str {
-    match ext {
-        "rs" => "Rust",
-        "js" | "jsx" => "JavaScript",
-        "ts" | "tsx" => "TypeScript",
-        "py" => "Python",
-        "java" => "Java",
-        "go" => "Go",
-        "c" | "cpp" | "cc" => "C/C++",
-        "cs" => "C#",
-        "rb" => "Ruby",
-        "php" => "PHP",
-        _ => "Other",
-    }
-} 
\ No newline at end of file
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use colored::*;
+use crate::renderer::{plain, plain_text};
+use crate::analyzer::types::DirectoryNode;
+use crate::analyzer::complexity::calculate_complexity;
+use crate::analyzer::structure::IGNORED_DIRS;
+use crate::analyzer::languages::language_registry;
+
+/// 이보다 깊은 디렉토리는 집계하지 않음
+const MAX_DEPTH: usize = 32;
+
+pub async fn analyze_tree(path: &str) -> Result<DirectoryNode> {
+    let root_path = Path::new(path).canonicalize()?;
+    let mut root = build_node(&root_path, 0)?;
+    root.name = path.to_string();
+    Ok(root)
+}
+
+/// 디렉토리 하나를 집계합니다. 소스 파일이 없는 하위 디렉토리는 제외.
+/// 심볼릭 링크는 따라가지 않음 (상위 디렉토리를 가리키는 링크로 끝없이 내려가지 않도록)
+fn build_node(dir: &Path, depth: usize) -> Result<DirectoryNode> {
+    let mut node = DirectoryNode {
+        name: dir.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        file_count: 0,
+        line_count: 0,
+        total_complexity: 0,
+        children: Vec::new(),
+    };
+
+    let mut entries: Vec<_> = fs::read_dir(dir)?.filter_map(|e| e.ok()).collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        // `DirEntry::file_type`은 링크 자체의 종류를 알려줌
+        let Ok(file_type) = entry.file_type() else { continue };
+        if file_type.is_symlink() {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            if IGNORED_DIRS.contains(&name.as_str()) || depth >= MAX_DEPTH {
+                continue;
+            }
+            let child = build_node(&path, depth + 1)?;
+            if child.file_count > 0 {
+                node.file_count += child.file_count;
+                node.line_count += child.line_count;
+                node.total_complexity += child.total_complexity;
+                node.children.push(child);
+            }
+        } else if language_registry().is_source_path(&path) {
+            if let Ok(content) = fs::read_to_string(&path) {
+                node.file_count += 1;
+                node.line_count += content.lines().count();
+                node.total_complexity += calculate_complexity(&content, &path);
+            }
+        }
+    }
+
+    Ok(node)
+}
+
+
+pub fn print_tree_report(tree: &DirectoryNode) {
+    println!("\n{}", "디렉토리 트리".bright_cyan().bold());
+    println!("{} {}", tree.name.green().bold(), summary(tree));
+    print_children(tree, "");
+}
+
+fn print_children(node: &DirectoryNode, prefix: &str) {
+    for (i, child) in node.children.iter().enumerate() {
+        let last = i + 1 == node.children.len();
+        let branch = if last { "└── " } else { "├── " };
+        println!("{}{}{} {}", plain_text(prefix).dimmed(), plain(branch).dimmed(), format!("{}/", child.name).green(), summary(child));
+
+        let next_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        print_children(child, &next_prefix);
+    }
+}
+
+fn summary(node: &DirectoryNode) -> String {
+    let avg = node.average_complexity();
+    let avg_text = format!("{:.1}", avg);
+    let avg_colored = if avg > 10.0 { avg_text.red() } else if avg > 5.0 { avg_text.yellow() } else { avg_text.normal() };
+    format!("({} 파일, {} 라인, 평균 복잡도 {})", node.file_count, node.line_count, avg_colored)
+        .dimmed()
+        .to_string()
+}
@@ -1,6 +1,7 @@
 use serde::{Serialize, Deserialize};
 use std::path::PathBuf;
 use std::collections::HashMap;
+use crate::analyzer::project_type::DetectedProject;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProjectStructure {
@@ -8,6 +9,9 @@ pub struct ProjectStructure {
     pub total_files: usize,
     pub total_lines: usize,
     pub languages: HashMap<String, LanguageStats>,
+    /// 매니페스트로 판별한 언어와 프레임워크
+    #[serde(default)]
+    pub project: Option<DetectedProject>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -42,6 +46,26 @@ pub struct ComplexityReport {
     pub complex_files: Vec<ComplexityInfo>,
 }
 
+/// 디렉토리별 집계 (하위 디렉토리 포함)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectoryNode {
+    pub name: String,
+    pub file_count: usize,
+    pub line_count: usize,
+    pub total_complexity: u32,
+    pub children: Vec<DirectoryNode>,
+}
+
+impl DirectoryNode {
+    pub fn average_complexity(&self) -> f32 {
+        if self.file_count > 0 {
+            self.total_complexity as f32 / self.file_count as f32
+        } else {
+            0.0
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FullAnalysisReport {
     pub structure: ProjectStructure,
@@ -0,0 +1,133 @@
+/// 모델별 기능/한도/가격 정보
+#[derive(Debug, Clone, Copy)]
+pub struct ModelCapabilities {
+    /// 입력 + 출력 전체 컨텍스트 크기 (토큰)
+    pub context_window: u32,
+    /// 한 번의 응답에서 생성 가능한 최대 토큰
+    pub max_output_tokens: u32,
+    pub vision: bool,
+    pub tools: bool,
+    /// 추론(reasoning) 모델 여부: temperature 등 샘플링 파라미터를 받지 않음
+    pub reasoning: bool,
+    /// 100만 토큰당 입력 가격 (USD)
+    pub input_price: f64,
+    /// 100만 토큰당 출력 가격 (USD)
+    pub output_price: f64,
+}
+
+/// 컨텍스트 사용량이 이 비율을 넘으면 요약/정리를 권장합니다
+const SUMMARIZATION_RATIO: f64 = 0.75;
+
+const fn caps(
+    context_window: u32,
+    max_output_tokens: u32,
+    vision: bool,
+    tools: bool,
+    input_price: f64,
+    output_price: f64,
+) -> ModelCapabilities {
+    ModelCapabilities { context_window, max_output_tokens, vision, tools, reasoning: false, input_price, output_price }
+}
+
+const fn reasoning(caps: ModelCapabilities) -> ModelCapabilities {
+    ModelCapabilities { reasoning: true, ..caps }
+}
+
+/// 접두사 기준 모델 레지스트리 (더 구체적인 접두사가 먼저 와야 함)
+const REGISTRY: &[(&str, ModelCapabilities)] = &[
+    ("gpt-4o-mini", caps(128_000, 16_384, true, true, 0.15, 0.60)),
+    ("gpt-4o", caps(128_000, 16_384, true, true, 2.50, 10.00)),
+    ("gpt-4-turbo", caps(128_000, 4_096, true, true, 10.00, 30.00)),
+    ("gpt-4-32k", caps(32_768, 4_096, false, true, 60.00, 120.00)),
+    ("gpt-4", caps(8_192, 4_096, false, true, 30.00, 60.00)),
+    ("gpt-3.5-turbo", caps(16_385, 4_096, false, true, 0.50, 1.50)),
+    ("o1-mini", reasoning(caps(128_000, 65_536, false, false, 3.00, 12.00))),
+    ("o1", reasoning(caps(200_000, 100_000, true, true, 15.00, 60.00))),
+    ("o3-mini", reasoning(caps(200_000, 100_000, false, true, 1.10, 4.40))),
+    ("o4-mini", reasoning(caps(200_000, 100_000, true, true, 1.10, 4.40))),
+    ("deepseek-reasoner", reasoning(caps(64_000, 8_192, false, false, 0.55, 2.19))),
+    ("claude-3-5-haiku", caps(200_000, 8_192, false, true, 0.80, 4.00)),
+    ("claude-3-5-sonnet", caps(200_000, 8_192, true, true, 3.00, 15.00)),
+    ("claude-3-opus", caps(200_000, 4_096, true, true, 15.00, 75.00)),
+    ("claude-3-haiku", caps(200_000, 4_096, true, true, 0.25, 1.25)),
+    ("gemini-1.5-pro", caps(2_097_152, 8_192, true, true, 1.25, 5.00)),
+    ("gemini-1.5-flash", caps(1_048_576, 8_192, true, true, 0.075, 0.30)),
+];
+
+/// 레지스트리에 등록된 모델 이름 (자동완성 후보)
+pub fn known_models() -> impl Iterator<Item = &'static str> {
+    REGISTRY.iter().map(|(name, _)| *name)
+}
+
+pub fn capabilities(model: &str) -> Option<ModelCapabilities> {
+    REGISTRY.iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .map(|(_, caps)| *caps)
+}
+
+pub fn context_window(model: &str) -> Option<u32> {
+    capabilities(model).map(|c| c.context_window)
+}
+
+/// 레지스트리에 없더라도 o-시리즈 이름이면 추론 모델로 취급합니다
+pub fn is_reasoning_model(model: &str) -> bool {
+    capabilities(model).map(|c| c.reasoning).unwrap_or_else(|| {
+        let mut chars = model.chars();
+        chars.next() == Some('o') && chars.next().is_some_and(|c| c.is_ascii_digit())
+    })
+}
+
+/// 제공자별 추천 모델 목록. 첫 번째가 해당 제공자의 기본 모델입니다
+pub fn suggested_models(provider: &str) -> &'static [&'static str] {
+    match provider {
+        "anthropic" => &["claude-3-5-sonnet-latest", "claude-3-5-haiku-latest", "claude-3-opus-latest"],
+        "gemini" => &["gemini-1.5-pro", "gemini-1.5-flash"],
+        "openai" => &["gpt-4o", "gpt-4o-mini", "gpt-4-turbo", "gpt-4"],
+        _ => &[],
+    }
+}
+
+/// 모델 이름으로 제공자를 추정합니다 (모르는 이름이면 None)
+pub fn provider_of(model: &str) -> Option<&'static str> {
+    if model.starts_with("claude") {
+        Some("anthropic")
+    } else if model.starts_with("gemini") {
+        Some("gemini")
+    } else if model.starts_with("gpt-") || model.starts_with("chatgpt") || is_reasoning_model(model) && model.starts_with('o') {
+        Some("openai")
+    } else {
+        None
+    }
+}
+
+/// 오타로 보이는 모델 이름에 가장 가까운 알려진 모델 (편집 거리 3 이하)
+pub fn closest_model(model: &str) -> Option<&'static str> {
+    suggested_models("openai").iter()
+        .chain(suggested_models("anthropic"))
+        .chain(suggested_models("gemini"))
+        .copied()
+        .chain(known_models())
+        .map(|known| (edit_distance(model, known), known))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// 이 토큰 수를 넘는 대화는 요약하거나 정리하는 것이 좋습니다
+pub fn summarization_threshold(model: &str) -> Option<usize> {
+    context_window(model).map(|w| (w as f64 * SUMMARIZATION_RATIO) as usize)
+}
@@ -2,42 +2,265 @@ use async_openai::{
     config::OpenAIConfig,
     types::{ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs, 
             ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs,
-            ChatCompletionRequestAssistantMessage},
+            ChatCompletionRequestAssistantMessage, ChatCompletionStreamOptions,
+            CompletionUsage, FinishReason, ReasoningEffort, Stop},
     Client,
 };
 use anyhow::{Context, Result};
+use std::io::{IsTerminal, Write};
 use futures::stream::StreamExt;
 use tokio::sync::mpsc;
-use crate::config::Config;
+use crate::api::{estimate_tokens, estimate_request_cost, is_reasoning_model, TokenUsage};
+use crate::api::usage::{check_budget, record_usage, track_stream_usage};
+use crate::api::reasoning::{Segment, ThinkSplitter, strip_reasoning};
+use crate::api::record::ExchangeRecorder;
+use crate::api::mock::MockProvider;
+use crate::hooks::{run_hooks, HookEvent};
+use crate::assistant::{contains_file_block, FILE_CONTENT_RULE};
+use crate::config::{BudgetConfig, Config, MaxTokens, ModelPreferences};
+use crate::error::RicciError;
+use crate::renderer::{NoticeLevel, OutputSink};
+use std::sync::Arc;
+
+/// 큰 프롬프트 전송 확인 콜백. 예상 토큰/비용 안내를 받아 보낼지 돌려줍니다
+pub type ConfirmFn = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// 클라이언트가 사용자와 주고받는 부분. 지정하지 않으면 표준 에러로 안내하고 터미널에서 확인받습니다
+#[derive(Clone, Default)]
+struct Interaction {
+    sink: Option<Arc<dyn OutputSink>>,
+    confirm: Option<ConfirmFn>,
+}
+
+impl std::fmt::Debug for Interaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Interaction")
+            .field("sink", &self.sink.is_some())
+            .field("confirm", &self.confirm.is_some())
+            .finish()
+    }
+}
+
+/// 단발 요청의 시스템 메시지. 파일 블록이 있으면 그 안의 지시를 따르지 않도록 덧붙입니다
+fn query_system_prompt(prompt: &str) -> String {
+    let mut system = String::from("You are a helpful development assistant.");
+    if contains_file_block(prompt) {
+        system.push(' ');
+        system.push_str(FILE_CONTENT_RULE);
+    }
+    system
+}
+
+/// 스트리밍 응답에서 전달되는 이벤트
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    /// 응답 본문 조각
+    Content(String),
+    /// 추론 모델의 사고 과정 조각 (본문의 `<think>` 블록만. 제공자별 별도 추론 필드는 읽지 않음)
+    Reasoning(String),
+    /// max_tokens 한도에 걸려 응답이 잘림 (finish_reason=length)
+    Truncated,
+    /// 스트림 마지막에 제공자가 보고한 토큰 사용량
+    Usage(TokenUsage),
+}
 
 pub struct OpenAIClient {
     client: Client<OpenAIConfig>,
     model: String,
     temperature: f32,
-    max_tokens: u16,
+    max_tokens: MaxTokens,
+    preferences: ModelPreferences,
+    recorder: Option<ExchangeRecorder>,
+    /// `mock` 제공자일 때 네트워크 대신 사용
+    mock: Option<MockProvider>,
+    /// `--yes`: 큰 프롬프트 확인 생략
+    assume_yes: bool,
+    budget: BudgetConfig,
+    interaction: Interaction,
 }
 
 impl OpenAIClient {
     pub fn new(config: &Config) -> Result<Self> {
+        Self::with_model(config, &config.model_preferences.default_model)
+    }
+    
+    /// 기본 모델 대신 지정한 모델을 사용하는 클라이언트를 생성합니다
+    pub fn with_model(config: &Config, model: &str) -> Result<Self> {
         let api_key = config.get_active_api_key()?;
         
-        let openai_config = OpenAIConfig::new()
+        let mut openai_config = OpenAIConfig::new()
             .with_api_key(api_key);
+        if let Some(base) = &config.api_base {
+            openai_config = openai_config.with_api_base(base);
+        }
         
         let client = Client::with_config(openai_config);
         
+        let mock = if config.model_preferences.default_provider == "mock" {
+            Some(MockProvider::load(config.model_preferences.mock_fixtures.as_deref())?)
+        } else {
+            None
+        };
+        
+        let recorder = config.record_dir.as_deref()
+            .map(ExchangeRecorder::new)
+            .transpose()?;
+        
         Ok(Self {
             client,
-            model: config.model_preferences.default_model.clone(),
+            model: model.to_string(),
             temperature: config.model_preferences.temperature,
             max_tokens: config.model_preferences.max_tokens,
+            preferences: config.model_preferences.clone(),
+            recorder,
+            mock,
+            assume_yes: config.assume_yes,
+            budget: config.budget.clone(),
+            interaction: Interaction::default(),
         })
     }
     
+    /// 확인 안내와 잘림 경고를 표준 에러 대신 보낼 곳 (`DevAssistantBuilder`가 어시스턴트의 출력으로 지정)
+    pub fn set_output(&mut self, sink: Arc<dyn OutputSink>) {
+        self.interaction.sink = Some(sink);
+    }
+    
+    /// 큰 프롬프트를 터미널 입력 대신 콜백으로 확인받습니다
+    pub fn set_confirm(&mut self, confirm: ConfirmFn) {
+        self.interaction.confirm = Some(confirm);
+    }
+    
+    fn warn(&self, message: &str) {
+        match &self.interaction.sink {
+            Some(sink) => sink.notice(NoticeLevel::Warning, message),
+            None => eprintln!("{}", message),
+        }
+    }
+    
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+    
+    /// 이후 요청의 temperature와 max_tokens를 바꿉니다 (대화 모드별 설정)
+    pub fn set_generation(&mut self, temperature: f32, max_tokens: MaxTokens) {
+        self.temperature = temperature;
+        self.max_tokens = max_tokens;
+    }
+    
+    /// 설정된 선택적 생성 파라미터를 요청에 반영합니다
+    fn apply_generation_params(&self, builder: &mut CreateChatCompletionRequestArgs, max_tokens: u32) {
+        let prefs = &self.preferences;
+        if !prefs.stop.is_empty() {
+            builder.stop(Stop::StringArray(prefs.stop.clone()));
+        }
+        if let Some(seed) = prefs.seed {
+            builder.seed(seed);
+        }
+        
+        // 추론 모델은 temperature/top_p/penalty를 받지 않고 max_completion_tokens를 사용
+        if is_reasoning_model(&self.model) {
+            builder.max_completion_tokens(max_tokens);
+            if let Some(effort) = prefs.reasoning_effort.as_deref().and_then(parse_reasoning_effort) {
+                builder.reasoning_effort(effort);
+            }
+            return;
+        }
+        
+        builder
+            .temperature(self.temperature)
+            .max_tokens(max_tokens);
+        if let Some(top_p) = prefs.top_p {
+            builder.top_p(top_p);
+        }
+        if let Some(penalty) = prefs.frequency_penalty {
+            builder.frequency_penalty(penalty);
+        }
+        if let Some(penalty) = prefs.presence_penalty {
+            builder.presence_penalty(penalty);
+        }
+    }
+    
+    fn resolve_max_tokens(&self, prompt_tokens: usize) -> u32 {
+        self.max_tokens.resolve(&self.model, prompt_tokens)
+    }
+    
+    /// 입력이 `confirm_prompt_tokens`를 넘으면 예상 토큰/비용을 보여주고 전송 여부를 묻습니다
+    fn confirm_large_prompt(&self, prompt_tokens: usize) -> Result<()> {
+        self.confirm_requests(&[prompt_tokens])
+    }
+    
+    /// 함께 보낼 단발 요청들(예: 기준별 동시 리뷰)의 입력 합계로 한 번만 확인받습니다.
+    /// 확인받은 요청은 `query_streaming_confirmed`로 보냅니다
+    pub fn confirm_queries(&self, prompts: &[String]) -> Result<()> {
+        let tokens: Vec<usize> = prompts.iter()
+            .map(|prompt| estimate_tokens(&query_system_prompt(prompt)) + estimate_tokens(prompt))
+            .collect();
+        self.confirm_requests(&tokens)
+    }
+    
+    fn confirm_requests(&self, prompt_tokens: &[usize]) -> Result<()> {
+        let threshold = self.preferences.confirm_prompt_tokens;
+        let total: usize = prompt_tokens.iter().sum();
+        if self.assume_yes || threshold == 0 || total <= threshold {
+            return Ok(());
+        }
+        
+        let cost = prompt_tokens.iter()
+            .map(|&tokens| estimate_request_cost(&self.model, tokens, self.resolve_max_tokens(tokens)))
+            .sum::<Option<f64>>()
+            .map(|c| format!("최대 ${:.2}", c))
+            .unwrap_or_else(|| "알 수 없음 (가격 정보 없는 모델)".to_string());
+        let requests = match prompt_tokens.len() {
+            1 => String::new(),
+            n => format!("요청 {}개, ", n),
+        };
+        let message = format!(
+            "[확인] 큰 프롬프트입니다: {}입력 약 {} 토큰 ({}), 예상 비용 {}",
+            requests, total, self.model, cost
+        );
+        if let Some(confirm) = &self.interaction.confirm {
+            self.warn(&message);
+            if !confirm(&message) {
+                return Err(RicciError::Declined("요청을 취소했습니다".to_string()).into());
+            }
+            return Ok(());
+        }
+        
+        eprintln!("{}", message);
+        if !std::io::stdin().is_terminal() {
+            return Err(RicciError::Declined(
+                "입력 확인을 받을 수 없어 전송하지 않았습니다. --yes로 확인 없이 보낼 수 있습니다".to_string()
+            ).into());
+        }
+        eprint!("전송하시겠습니까? (y/n) ");
+        std::io::stderr().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            return Err(RicciError::Declined("요청을 취소했습니다".to_string()).into());
+        }
+        Ok(())
+    }
+    
     pub async fn query(&self, prompt: &str) -> Result<String> {
+        let prompt_tokens = estimate_tokens(prompt);
+        check_budget(&self.budget)?;
+        self.confirm_large_prompt(prompt_tokens)?;
+        
+        if let Some(mock) = &self.mock {
+            let content = mock.respond(prompt).content;
+            record_usage(&self.model, TokenUsage {
+                prompt_tokens,
+                completion_tokens: estimate_tokens(&content),
+                cached_tokens: 0,
+            });
+            return Ok(self.responded(prompt, strip_reasoning(&content)));
+        }
+        
+        let max_tokens = self.resolve_max_tokens(prompt_tokens);
         let messages = vec![
             ChatCompletionRequestSystemMessageArgs::default()
-                .content("You are a helpful development assistant.")
+                .content(query_system_prompt(prompt))
                 .build()?
                 .into(),
             ChatCompletionRequestUserMessageArgs::default()
@@ -46,33 +269,106 @@ impl OpenAIClient {
                 .into(),
         ];
         
-        let request = CreateChatCompletionRequestArgs::default()
+        let mut builder = CreateChatCompletionRequestArgs::default();
+        builder
             .model(&self.model)
-            .messages(messages)
-            .temperature(self.temperature)
-            .max_tokens(self.max_tokens)
-            .build()?;
+            .messages(messages);
+        self.apply_generation_params(&mut builder, max_tokens);
+        let request = builder.build()?;
+        let mut exchange = self.recorder.as_ref().map(|r| r.begin(&request));
         
         let response = self.client
             .chat()
             .create(request)
             .await
-            .context("OpenAI API 호출 실패")?;
+            .map_err(|e| RicciError::Provider(format!("OpenAI API 호출 실패: {}", e)))?;
         
-        let content = response
-            .choices
-            .first()
-            .and_then(|choice| choice.message.content.as_ref())
+        if let Some(exchange) = exchange.as_mut() {
+            exchange.record(&response);
+        }
+        
+        let content_tokens = response.choices.first()
+            .and_then(|c| c.message.content.as_deref())
+            .map(estimate_tokens)
+            .unwrap_or(0);
+        record_usage(&self.model, response.usage.as_ref().map(to_token_usage).unwrap_or(TokenUsage {
+            prompt_tokens,
+            completion_tokens: content_tokens,
+            cached_tokens: 0,
+        }));
+        
+        let choice = response.choices.first()
             .context("응답에서 콘텐츠를 찾을 수 없음")?;
         
-        Ok(content.to_string())
+        if choice.finish_reason == Some(FinishReason::Length) {
+            self.warn(&format!("[경고] 응답이 max_tokens({}) 한도에서 잘렸습니다.", max_tokens));
+        }
+        
+        let content = choice.message.content.as_ref()
+            .context("응답에서 콘텐츠를 찾을 수 없음")?;
+        
+        Ok(self.responded(prompt, strip_reasoning(content)))
+    }
+    
+    /// `query`와 같은 요청을 스트리밍으로 보내고, 본문 조각이 올 때마다 `on_chunk`를 호출하면서
+    /// 전체 응답을 모아 돌려줍니다. 추론 조각은 응답에 넣지 않습니다
+    pub async fn query_streaming(&self, prompt: &str, on_chunk: impl FnMut(&str)) -> Result<String> {
+        self.stream_query(prompt, true, on_chunk).await
+    }
+    
+    /// `confirm_queries`로 이미 확인받은 요청을 다시 묻지 않고 `query_streaming`처럼 보냅니다
+    pub async fn query_streaming_confirmed(&self, prompt: &str, on_chunk: impl FnMut(&str)) -> Result<String> {
+        self.stream_query(prompt, false, on_chunk).await
+    }
+    
+    async fn stream_query(&self, prompt: &str, confirm: bool, mut on_chunk: impl FnMut(&str)) -> Result<String> {
+        let message = crate::assistant::Message {
+            role: "user".to_string(),
+            content: prompt.to_string(),
+            timestamp: chrono::Utc::now(),
+            pinned: false,
+        };
+        let mut stream = self.open_stream(&query_system_prompt(prompt), &[message], confirm).await?;
+        let mut response = String::new();
+        while let Some(event) = stream.recv().await {
+            match event? {
+                StreamEvent::Content(text) => {
+                    on_chunk(&text);
+                    response.push_str(&text);
+                }
+                StreamEvent::Truncated => {
+                    self.warn("[경고] 응답이 max_tokens 한도에서 잘렸습니다.");
+                }
+                StreamEvent::Reasoning(_) | StreamEvent::Usage(_) => {}
+            }
+        }
+        Ok(self.responded(prompt, response))
+    }
+    
+    /// 응답 훅(`on_response`)을 실행하고 응답을 그대로 돌려줍니다
+    fn responded(&self, prompt: &str, response: String) -> String {
+        run_hooks(HookEvent::Response, serde_json::json!({
+            "model": self.model,
+            "prompt": prompt,
+            "response": response,
+        }));
+        response
     }
     
     pub async fn stream_chat(
         &self, 
         system_prompt: &str,
         messages: &[crate::assistant::Message]
-    ) -> Result<mpsc::Receiver<Result<String>>> {
+    ) -> Result<mpsc::Receiver<Result<StreamEvent>>> {
+        self.open_stream(system_prompt, messages, true).await
+    }
+    
+    async fn open_stream(
+        &self,
+        system_prompt: &str,
+        messages: &[crate::assistant::Message],
+        confirm: bool,
+    ) -> Result<mpsc::Receiver<Result<StreamEvent>>> {
         let (tx, rx) = mpsc::channel(100);
         
         let mut chat_messages: Vec<ChatCompletionRequestMessage> = vec![
@@ -91,7 +387,7 @@ impl OpenAIClient {
                     .into(),
                 "assistant" => ChatCompletionRequestMessage::Assistant(
                     ChatCompletionRequestAssistantMessage {
-                        content: Some(msg.content.clone()),
+                        content: Some(msg.content.clone().into()),
                         ..Default::default()
                     }
                 ),
@@ -100,13 +396,31 @@ impl OpenAIClient {
             chat_messages.push(message);
         }
         
-        let request = CreateChatCompletionRequestArgs::default()
+        let prompt_tokens = estimate_tokens(system_prompt)
+            + messages.iter().map(|m| estimate_tokens(&m.content)).sum::<usize>();
+        check_budget(&self.budget)?;
+        if confirm {
+            self.confirm_large_prompt(prompt_tokens)?;
+        }
+        
+        if let Some(mock) = &self.mock {
+            let prompt = messages.iter().rev()
+                .find(|m| m.role == "user")
+                .map(|m| m.content.as_str())
+                .unwrap_or_default();
+            return Ok(track_stream_usage(&self.model, prompt_tokens, mock.stream(prompt, prompt_tokens)));
+        }
+        
+        let mut builder = CreateChatCompletionRequestArgs::default();
+        builder
             .model(&self.model)
             .messages(chat_messages)
-            .temperature(self.temperature)
-            .max_tokens(self.max_tokens)
             .stream(true)
-            .build()?;
+            // 마지막 청크로 사용량(캐시 적중 포함)을 받음
+            .stream_options(ChatCompletionStreamOptions { include_usage: true });
+        self.apply_generation_params(&mut builder, self.resolve_max_tokens(prompt_tokens));
+        let request = builder.build()?;
+        let mut exchange = self.recorder.as_ref().map(|r| r.begin(&request));
         
         let client = self.client.clone();
         
@@ -115,30 +429,77 @@ impl OpenAIClient {
             let mut stream = match client.chat().create_stream(request).await {
                 Ok(s) => s,
                 Err(e) => {
-                    let _ = tx.send(Err(anyhow::anyhow!("스트림 생성 실패: {}", e))).await;
+                    let _ = tx.send(Err(RicciError::Provider(format!("스트림 생성 실패: {}", e)).into())).await;
                     return;
                 }
             };
             
+            let mut splitter = ThinkSplitter::default();
+            
             while let Some(result) = stream.next().await {
                 match result {
                     Ok(response) => {
+                        if let Some(exchange) = exchange.as_mut() {
+                            exchange.record(&response);
+                        }
+                        if let Some(ref usage) = response.usage {
+                            let _ = tx.send(Ok(StreamEvent::Usage(to_token_usage(usage)))).await;
+                        }
                         if let Some(choice) = response.choices.first() {
                             if let Some(ref delta) = choice.delta.content {
-                                if tx.send(Ok(delta.clone())).await.is_err() {
-                                    break;
+                                if !send_segments(&tx, splitter.push(delta)).await {
+                                    return;
                                 }
                             }
+                            if choice.finish_reason == Some(FinishReason::Length) {
+                                send_segments(&tx, splitter.finish()).await;
+                                let _ = tx.send(Ok(StreamEvent::Truncated)).await;
+                            }
                         }
                     }
                     Err(e) => {
-                        let _ = tx.send(Err(anyhow::anyhow!("스트림 오류: {}", e))).await;
-                        break;
+                        let _ = tx.send(Err(RicciError::Provider(format!("스트림 오류: {}", e)).into())).await;
+                        return;
                     }
                 }
             }
+            
+            send_segments(&tx, splitter.finish()).await;
         });
         
-        Ok(rx)
+        Ok(track_stream_usage(&self.model, prompt_tokens, rx))
+    }
+}
+
+/// 분리된 조각을 스트림 이벤트로 전달합니다. 수신 측이 닫혔으면 false
+async fn send_segments(tx: &mpsc::Sender<Result<StreamEvent>>, segments: Vec<Segment>) -> bool {
+    for segment in segments {
+        let event = match segment {
+            Segment::Content(text) => StreamEvent::Content(text),
+            Segment::Reasoning(text) => StreamEvent::Reasoning(text),
+        };
+        if tx.send(Ok(event)).await.is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+fn to_token_usage(usage: &CompletionUsage) -> TokenUsage {
+    TokenUsage {
+        prompt_tokens: usage.prompt_tokens as usize,
+        completion_tokens: usage.completion_tokens as usize,
+        cached_tokens: usage.prompt_tokens_details.as_ref()
+            .and_then(|d| d.cached_tokens)
+            .unwrap_or(0) as usize,
+    }
+}
+
+fn parse_reasoning_effort(value: &str) -> Option<ReasoningEffort> {
+    match value {
+        "low" => Some(ReasoningEffort::Low),
+        "medium" => Some(ReasoningEffort::Medium),
+        "high" => Some(ReasoningEffort::High),
+        _ => None,
     }
-} 
\ No newline at end of file
+}
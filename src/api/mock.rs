@@ -0,0 +1,112 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Mutex;
+use tokio::sync::mpsc;
+use crate::api::client::StreamEvent;
+use crate::api::{estimate_tokens, Segment, ThinkSplitter, TokenUsage};
+
+/// 픽스처 파일 (TOML 또는 JSON)
+#[derive(Debug, Default, Deserialize)]
+struct Fixtures {
+    #[serde(default)]
+    responses: Vec<Fixture>,
+}
+
+/// 미리 준비된 응답 하나
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fixture {
+    /// 마지막 사용자 메시지에 이 문자열이 포함되면 사용. 없으면 순서대로 소비되는 스크립트 응답
+    #[serde(default, rename = "match")]
+    pub pattern: Option<String>,
+    pub content: String,
+    /// max_tokens 한도에서 잘린 것처럼 동작
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// 네트워크 없이 픽스처 응답을 돌려주는 `mock` 제공자
+#[derive(Debug)]
+pub struct MockProvider {
+    fixtures: Vec<Fixture>,
+    cursor: Mutex<usize>,
+}
+
+impl MockProvider {
+    /// 픽스처 파일이 없으면 입력을 그대로 돌려주는 에코 응답을 사용합니다
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let fixtures = match path {
+            Some(path) => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("mock 픽스처 파일을 읽을 수 없습니다: {}", path.display()))?;
+                let parsed: Fixtures = if path.extension().is_some_and(|e| e == "json") {
+                    serde_json::from_str(&content)?
+                } else {
+                    toml::from_str(&content)?
+                };
+                parsed.responses
+            }
+            None => Vec::new(),
+        };
+
+        Ok(Self { fixtures, cursor: Mutex::new(0) })
+    }
+
+    /// 패턴이 맞는 픽스처를 먼저 찾고, 없으면 다음 스크립트 응답을 사용합니다.
+    /// 스크립트 응답을 모두 소비하면 마지막 응답을 반복합니다.
+    pub fn respond(&self, prompt: &str) -> Fixture {
+        if let Some(fixture) = self.fixtures.iter()
+            .find(|f| f.pattern.as_deref().is_some_and(|p| prompt.contains(p)))
+        {
+            return fixture.clone();
+        }
+
+        let scripted: Vec<&Fixture> = self.fixtures.iter().filter(|f| f.pattern.is_none()).collect();
+        let Some(last) = scripted.last() else {
+            return Fixture {
+                pattern: None,
+                content: format!("[mock] {prompt}"),
+                truncated: false,
+            };
+        };
+
+        let mut cursor = self.cursor.lock().unwrap_or_else(|e| e.into_inner());
+        let fixture = scripted.get(*cursor).unwrap_or(last);
+        *cursor += 1;
+        (*fixture).clone()
+    }
+
+    /// 응답을 단어 단위 청크로 나눠 실제 스트림처럼 전달합니다
+    pub fn stream(&self, prompt: &str, prompt_tokens: usize) -> mpsc::Receiver<Result<StreamEvent>> {
+        let fixture = self.respond(prompt);
+        let (tx, rx) = mpsc::channel(100);
+
+        tokio::spawn(async move {
+            let mut splitter = ThinkSplitter::default();
+            let chunks = fixture.content.split_inclusive(char::is_whitespace);
+            let segments = chunks.flat_map(|c| splitter.push(c)).collect::<Vec<_>>()
+                .into_iter()
+                .chain(splitter.finish());
+            
+            for segment in segments {
+                let event = match segment {
+                    Segment::Content(text) => StreamEvent::Content(text),
+                    Segment::Reasoning(text) => StreamEvent::Reasoning(text),
+                };
+                if tx.send(Ok(event)).await.is_err() {
+                    return;
+                }
+            }
+            if fixture.truncated {
+                let _ = tx.send(Ok(StreamEvent::Truncated)).await;
+            }
+            let _ = tx.send(Ok(StreamEvent::Usage(TokenUsage {
+                prompt_tokens,
+                completion_tokens: estimate_tokens(&fixture.content),
+                cached_tokens: 0,
+            }))).await;
+        });
+
+        rx
+    }
+}
@@ -1,3 +1,20 @@
 mod client;
+mod stats;
+mod models;
+mod capabilities;
+mod reasoning;
+mod record;
+mod mock;
+mod usage;
 
-pub use client::OpenAIClient; 
\ No newline at end of file
+pub use client::{ConfirmFn, OpenAIClient, StreamEvent};
+pub use stats::{ResponseStats, StatsRecorder, TokenUsage, estimate_tokens, estimate_request_cost};
+pub use models::{ModelInfo, list_models};
+pub use record::{Exchange, ExchangeRecorder};
+pub use mock::{Fixture, MockProvider};
+pub use usage::{UsageLedger, check_budget, record_usage, track_stream_usage, usage_path};
+pub use reasoning::{Segment, ThinkSplitter};
+pub use capabilities::{
+    ModelCapabilities, capabilities, closest_model, context_window, is_reasoning_model, known_models, provider_of,
+    suggested_models, summarization_threshold,
+};
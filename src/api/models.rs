@@ -0,0 +1,64 @@
+use anyhow::{Result, Context};
+use serde_json::Value;
+use std::time::Duration;
+use crate::api::capabilities::context_window;
+
+/// 제공자 모델 목록의 한 항목
+#[derive(Debug, Clone)]
+pub struct ModelInfo {
+    pub provider: String,
+    pub id: String,
+    pub context_window: Option<u32>,
+}
+
+/// 제공자의 모델 목록 엔드포인트를 조회합니다
+pub async fn list_models(provider: &str, api_key: &str) -> Result<Vec<ModelInfo>> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()?;
+
+    let request = match provider {
+        "openai" => client.get("https://api.openai.com/v1/models").bearer_auth(api_key),
+        "anthropic" => client.get("https://api.anthropic.com/v1/models")
+            .query(&[("limit", "1000")])
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01"),
+        "gemini" => client.get("https://generativelanguage.googleapis.com/v1beta/models")
+            .query(&[("key", api_key), ("pageSize", "1000")]),
+        _ => anyhow::bail!("알 수 없는 제공자: {}", provider),
+    };
+
+    let body: Value = request.send().await
+        .with_context(|| format!("{provider} 모델 목록 요청 실패"))?
+        .error_for_status()?
+        .json().await
+        .with_context(|| format!("{provider} 모델 목록 파싱 실패"))?;
+
+    let mut models: Vec<ModelInfo> = match provider {
+        // Gemini는 모델 이름이 "models/" 접두사를 가지며 입력 토큰 한도를 함께 제공
+        "gemini" => body["models"].as_array().into_iter().flatten()
+            .filter_map(|m| {
+                let id = m["name"].as_str()?.trim_start_matches("models/").to_string();
+                let limit = m["inputTokenLimit"].as_u64().map(|v| v as u32);
+                Some(ModelInfo {
+                    provider: provider.to_string(),
+                    context_window: limit.or_else(|| context_window(&id)),
+                    id,
+                })
+            })
+            .collect(),
+        _ => body["data"].as_array().into_iter().flatten()
+            .filter_map(|m| {
+                let id = m["id"].as_str()?.to_string();
+                Some(ModelInfo {
+                    provider: provider.to_string(),
+                    context_window: context_window(&id),
+                    id,
+                })
+            })
+            .collect(),
+    };
+
+    models.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(models)
+}
@@ -0,0 +1,87 @@
+const THINK_OPEN: &str = "<think>";
+const THINK_CLOSE: &str = "</think>";
+
+/// 스트림 조각을 `<think>...</think>` 안쪽(추론)과 바깥쪽(본문)으로 나눕니다.
+/// 태그가 조각 경계에서 잘려도 올바르게 처리하도록 태그 접두사는 다음 조각까지 보류합니다.
+#[derive(Debug, Default)]
+pub struct ThinkSplitter {
+    buffer: String,
+    in_think: bool,
+}
+
+/// 분리된 조각
+#[derive(Debug, PartialEq)]
+pub enum Segment {
+    Content(String),
+    Reasoning(String),
+}
+
+impl ThinkSplitter {
+    pub fn push(&mut self, chunk: &str) -> Vec<Segment> {
+        self.buffer.push_str(chunk);
+        let mut segments = Vec::new();
+
+        loop {
+            let tag = if self.in_think { THINK_CLOSE } else { THINK_OPEN };
+
+            if let Some(pos) = self.buffer.find(tag) {
+                let before: String = self.buffer.drain(..pos).collect();
+                self.buffer.drain(..tag.len());
+                self.emit(before, &mut segments);
+                self.in_think = !self.in_think;
+                continue;
+            }
+
+            // 버퍼 끝이 태그의 앞부분일 수 있으면 그만큼 남겨둠
+            let keep = partial_tag_suffix(&self.buffer, tag);
+            let emit_len = self.buffer.len() - keep;
+            let text: String = self.buffer.drain(..emit_len).collect();
+            self.emit(text, &mut segments);
+            break;
+        }
+
+        segments
+    }
+
+    /// 스트림 종료 시 보류 중인 텍스트를 내보냅니다
+    pub fn finish(&mut self) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        let rest = std::mem::take(&mut self.buffer);
+        self.emit(rest, &mut segments);
+        segments
+    }
+
+    fn emit(&self, text: String, segments: &mut Vec<Segment>) {
+        if text.is_empty() {
+            return;
+        }
+        segments.push(if self.in_think {
+            Segment::Reasoning(text)
+        } else {
+            Segment::Content(text)
+        });
+    }
+}
+
+/// 전체 응답에서 추론 블록을 제거한 본문만 반환합니다
+pub fn strip_reasoning(text: &str) -> String {
+    let mut splitter = ThinkSplitter::default();
+    let mut segments = splitter.push(text);
+    segments.extend(splitter.finish());
+
+    segments.into_iter()
+        .filter_map(|s| match s {
+            Segment::Content(c) => Some(c),
+            Segment::Reasoning(_) => None,
+        })
+        .collect::<String>()
+        .trim_start()
+        .to_string()
+}
+
+fn partial_tag_suffix(buffer: &str, tag: &str) -> usize {
+    (1..tag.len())
+        .rev()
+        .find(|&n| buffer.len() >= n && buffer.is_char_boundary(buffer.len() - n) && tag.starts_with(&buffer[buffer.len() - n..]))
+        .unwrap_or(0)
+}
@@ -0,0 +1,76 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use colored::*;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 같은 밀리초에 시작한 교환도 순서가 보장되도록 붙이는 일련번호
+static SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+
+/// `--record` 디렉토리에 제공자 요청/응답 원문을 기록합니다
+#[derive(Debug, Clone)]
+pub struct ExchangeRecorder {
+    dir: PathBuf,
+}
+
+/// 요청 하나와 그에 대한 응답 기록
+pub struct Exchange {
+    response_path: PathBuf,
+    file: Option<File>,
+}
+
+impl ExchangeRecorder {
+    pub fn new(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("기록 디렉토리 생성 실패: {}", dir.display()))?;
+        Ok(Self { dir: dir.to_path_buf() })
+    }
+
+    /// 요청을 `<id>-request.json`으로 저장하고 응답 기록용 핸들을 반환합니다
+    pub fn begin<T: Serialize>(&self, request: &T) -> Exchange {
+        let id = format!(
+            "{}-{:04}",
+            Local::now().format("%Y%m%d-%H%M%S%3f"),
+            SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        );
+
+        let request_path = self.dir.join(format!("{id}-request.json"));
+        let written = serde_json::to_string_pretty(request)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| fs::write(&request_path, json).map_err(anyhow::Error::from));
+        if let Err(e) = written {
+            warn_record_failure(&request_path, &e);
+        }
+
+        Exchange {
+            response_path: self.dir.join(format!("{id}-response.jsonl")),
+            file: None,
+        }
+    }
+}
+
+impl Exchange {
+    /// 응답(스트림이면 청크 하나)을 JSON 한 줄로 덧붙입니다
+    pub fn record<T: Serialize>(&mut self, response: &T) {
+        if let Err(e) = self.append(response) {
+            warn_record_failure(&self.response_path, &e);
+        }
+    }
+
+    fn append<T: Serialize>(&mut self, response: &T) -> Result<()> {
+        if self.file.is_none() {
+            self.file = Some(OpenOptions::new().create(true).append(true).open(&self.response_path)?);
+        }
+        if let Some(file) = self.file.as_mut() {
+            writeln!(file, "{}", serde_json::to_string(response)?)?;
+        }
+        Ok(())
+    }
+}
+
+fn warn_record_failure(path: &Path, error: &anyhow::Error) {
+    eprintln!("{} 기록 실패 ({}): {}", "[경고]".yellow(), path.display(), error);
+}
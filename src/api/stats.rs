@@ -0,0 +1,161 @@
+use colored::*;
+use std::time::{Duration, Instant};
+use crate::api::capabilities::capabilities;
+
+/// 캐시된 입력 토큰은 일반 입력 가격의 이 비율로 과금됩니다 (OpenAI 기준)
+const CACHED_INPUT_PRICE_RATIO: f64 = 0.5;
+
+/// 제공자가 보고한 실제 토큰 사용량
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenUsage {
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+    /// 프롬프트 캐시에서 재사용된 입력 토큰
+    pub cached_tokens: usize,
+}
+
+impl TokenUsage {
+    /// 알려진 모델에 한해 USD 기준 예상 비용을 계산합니다
+    pub fn estimated_cost(&self, model: &str) -> Option<f64> {
+        let caps = capabilities(model)?;
+        let uncached = self.prompt_tokens.saturating_sub(self.cached_tokens) as f64;
+        let cached = self.cached_tokens as f64 * CACHED_INPUT_PRICE_RATIO;
+        Some(
+            (uncached + cached) / 1_000_000.0 * caps.input_price
+                + self.completion_tokens as f64 / 1_000_000.0 * caps.output_price,
+        )
+    }
+}
+
+/// 스트리밍 응답 하나에 대한 지연 시간/토큰 통계
+#[derive(Debug, Clone)]
+pub struct ResponseStats {
+    pub model: String,
+    pub time_to_first_token: Option<Duration>,
+    pub total_time: Duration,
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+    pub cached_tokens: usize,
+    /// 토큰 수가 추정치가 아닌 제공자 보고값인지 여부
+    pub exact: bool,
+}
+
+impl ResponseStats {
+    pub fn tokens_per_sec(&self) -> f64 {
+        let secs = self.total_time.as_secs_f64();
+        if secs > 0.0 {
+            self.completion_tokens as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// 알려진 모델에 한해 USD 기준 예상 비용을 계산합니다
+    pub fn estimated_cost(&self) -> Option<f64> {
+        TokenUsage {
+            prompt_tokens: self.prompt_tokens,
+            completion_tokens: self.completion_tokens,
+            cached_tokens: self.cached_tokens,
+        }.estimated_cost(&self.model)
+    }
+
+    pub fn footer_line(&self) -> String {
+        let ttft = self.time_to_first_token
+            .map(|d| format!("{:.2}s", d.as_secs_f64()))
+            .unwrap_or_else(|| "-".to_string());
+        let cost = self.estimated_cost()
+            .map(|c| format!("${:.4}", c))
+            .unwrap_or_else(|| "-".to_string());
+
+        let approx = if self.exact { "" } else { "~" };
+        let mut line = format!(
+            "[통계] 첫 토큰 {} | 전체 {:.2}s | 토큰 {} (입력 {}{}) | {:.1} tok/s | 예상 비용 {}",
+            ttft,
+            self.total_time.as_secs_f64(),
+            self.completion_tokens,
+            approx,
+            self.prompt_tokens,
+            self.tokens_per_sec(),
+            cost
+        );
+        if self.cached_tokens > 0 && self.prompt_tokens > 0 {
+            line.push_str(&format!(
+                " | 캐시 적중 {} ({:.0}%)",
+                self.cached_tokens,
+                self.cached_tokens as f64 / self.prompt_tokens as f64 * 100.0
+            ));
+        }
+        line
+    }
+
+    pub fn print_footer(&self) {
+        println!("{}", self.footer_line().dimmed());
+    }
+}
+
+/// 스트림 수신 중 통계를 누적합니다
+pub struct StatsRecorder {
+    model: String,
+    started: Instant,
+    first_token: Option<Duration>,
+    prompt_tokens: usize,
+    completion_tokens: usize,
+    usage: Option<TokenUsage>,
+}
+
+impl StatsRecorder {
+    pub fn start(model: &str, prompt_tokens: usize) -> Self {
+        Self {
+            model: model.to_string(),
+            started: Instant::now(),
+            first_token: None,
+            prompt_tokens,
+            completion_tokens: 0,
+            usage: None,
+        }
+    }
+
+    /// 스트림 청크 하나를 기록합니다 (OpenAI 스트림은 대체로 청크당 1토큰)
+    pub fn record_chunk(&mut self) {
+        if self.first_token.is_none() {
+            self.first_token = Some(self.started.elapsed());
+        }
+        self.completion_tokens += 1;
+    }
+
+    /// 제공자가 보고한 사용량을 기록합니다. 있으면 추정치 대신 사용
+    pub fn record_usage(&mut self, usage: TokenUsage) {
+        self.usage = Some(usage);
+    }
+    
+    pub fn finish(self) -> ResponseStats {
+        let usage = self.usage.unwrap_or(TokenUsage {
+            prompt_tokens: self.prompt_tokens,
+            completion_tokens: self.completion_tokens,
+            cached_tokens: 0,
+        });
+        ResponseStats {
+            model: self.model,
+            time_to_first_token: self.first_token,
+            total_time: self.started.elapsed(),
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            cached_tokens: usage.cached_tokens,
+            exact: self.usage.is_some(),
+        }
+    }
+}
+
+/// 전송 전 요청 하나의 예상 비용 (USD). 출력은 max_tokens를 모두 쓴다고 가정한 상한입니다
+pub fn estimate_request_cost(model: &str, prompt_tokens: usize, max_output_tokens: u32) -> Option<f64> {
+    let caps = capabilities(model)?;
+    Some(
+        prompt_tokens as f64 / 1_000_000.0 * caps.input_price
+            + max_output_tokens as f64 / 1_000_000.0 * caps.output_price,
+    )
+}
+
+/// 문자 수 기반의 대략적인 토큰 수 추정
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
@@ -0,0 +1,180 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use fd_lock::RwLock;
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tokio::sync::mpsc;
+use crate::api::{estimate_tokens, StreamEvent, TokenUsage};
+use crate::config::{BudgetAction, BudgetConfig};
+use crate::error::RicciError;
+use crate::paths::data_dir;
+
+/// 누적 사용량 기록 (`<data_dir>/usage.json`). 날짜/월이 바뀌면 해당 합계를 새로 시작합니다
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageLedger {
+    /// YYYY-MM-DD
+    pub day: String,
+    pub daily_cost: f64,
+    pub daily_requests: usize,
+    /// YYYY-MM
+    pub month: String,
+    pub monthly_cost: f64,
+    pub monthly_requests: usize,
+    pub monthly_prompt_tokens: usize,
+    pub monthly_completion_tokens: usize,
+}
+
+pub fn usage_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("usage.json"))
+}
+
+impl UsageLedger {
+    /// 저장된 기록을 읽고 오늘 날짜 기준으로 지난 합계를 비웁니다
+    pub fn load() -> Self {
+        let mut ledger: Self = usage_path().ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        ledger.roll_over();
+        ledger
+    }
+
+    /// 임시 파일에 쓴 뒤 바꿔치기하므로 잠그지 않고 읽는 쪽도 반쯤 쓴 파일을 보지 않습니다
+    pub fn save(&self) -> Result<()> {
+        let path = usage_path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, serde_json::to_string_pretty(self)?)
+            .and_then(|()| std::fs::rename(&temp, &path))
+            .with_context(|| format!("사용량 기록 저장 실패: {}", path.display()))
+    }
+
+    fn roll_over(&mut self) {
+        let now = Local::now();
+        let day = now.format("%Y-%m-%d").to_string();
+        let month = now.format("%Y-%m").to_string();
+        if self.day != day {
+            self.day = day;
+            self.daily_cost = 0.0;
+            self.daily_requests = 0;
+        }
+        if self.month != month {
+            self.month = month;
+            self.monthly_cost = 0.0;
+            self.monthly_requests = 0;
+            self.monthly_prompt_tokens = 0;
+            self.monthly_completion_tokens = 0;
+        }
+    }
+
+    fn add(&mut self, model: &str, usage: TokenUsage) {
+        // 가격 정보가 없는 모델은 토큰만 누적
+        let cost = usage.estimated_cost(model).unwrap_or(0.0);
+
+        self.daily_cost += cost;
+        self.daily_requests += 1;
+        self.monthly_cost += cost;
+        self.monthly_requests += 1;
+        self.monthly_prompt_tokens += usage.prompt_tokens;
+        self.monthly_completion_tokens += usage.completion_tokens;
+    }
+}
+
+/// 같은 프로세스 안의 동시 기록 (기준별 동시 리뷰 등)
+static RECORD_LOCK: Mutex<()> = Mutex::new(());
+
+/// 요청 하나의 사용량을 누적합니다. 기록 실패는 요청 자체를 실패시키지 않습니다
+pub fn record_usage(model: &str, usage: TokenUsage) {
+    let _ = update_ledger(|ledger| ledger.add(model, usage));
+}
+
+/// 읽고 고쳐 쓰는 동안 다른 스레드와 다른 ricci 프로세스를 막아 합계가 유실되지 않게 합니다
+fn update_ledger(update: impl FnOnce(&mut UsageLedger)) -> Result<()> {
+    let _thread = RECORD_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = usage_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let lock_file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path.with_extension("lock"))
+        .context("사용량 기록 잠금 파일을 열 수 없습니다")?;
+    let mut lock = RwLock::new(lock_file);
+    let _process = lock.write().context("사용량 기록 잠금 실패")?;
+
+    let mut ledger = UsageLedger::load();
+    update(&mut ledger);
+    ledger.save()
+}
+
+/// 스트림을 그대로 전달하면서 끝날 때 사용량을 기록합니다.
+/// 제공자가 사용량을 보내지 않으면 받은 본문으로 추정합니다
+pub fn track_stream_usage(
+    model: &str,
+    prompt_tokens: usize,
+    mut rx: mpsc::Receiver<Result<StreamEvent>>,
+) -> mpsc::Receiver<Result<StreamEvent>> {
+    let (tx, out) = mpsc::channel(100);
+    let model = model.to_string();
+
+    tokio::spawn(async move {
+        let mut reported = None;
+        let mut completion = String::new();
+        while let Some(event) = rx.recv().await {
+            match &event {
+                Ok(StreamEvent::Usage(usage)) => reported = Some(*usage),
+                Ok(StreamEvent::Content(text)) => completion.push_str(text),
+                _ => {}
+            }
+            if tx.send(event).await.is_err() {
+                break;
+            }
+        }
+        let usage = reported.unwrap_or(TokenUsage {
+            prompt_tokens,
+            completion_tokens: estimate_tokens(&completion),
+            cached_tokens: 0,
+        });
+        record_usage(&model, usage);
+    });
+
+    out
+}
+
+/// 예산 한도를 확인합니다. 경고 비율을 넘으면 알리고, 한도를 넘었고 `block`이면 요청을 거부합니다
+pub fn check_budget(budget: &BudgetConfig) -> Result<()> {
+    if budget.daily_usd.is_none() && budget.monthly_usd.is_none() {
+        return Ok(());
+    }
+    let ledger = UsageLedger::load();
+    let limits = [
+        ("일일", ledger.daily_cost, budget.daily_usd),
+        ("월간", ledger.monthly_cost, budget.monthly_usd),
+    ];
+
+    for (label, spent, limit) in limits {
+        let Some(limit) = limit else { continue };
+        if spent >= limit {
+            let message = format!("{} 예산 ${:.2}을 초과했습니다 (사용 ${:.2})", label, limit, spent);
+            if budget.action == BudgetAction::Block {
+                return Err(RicciError::Provider(
+                    format!("{}. 한도를 늘리거나 `ricci usage reset`으로 기록을 초기화하세요", message)
+                ).into());
+            }
+            eprintln!("{} {}", "[예산]".red(), message);
+        } else if spent >= limit * budget.warn_at {
+            eprintln!(
+                "{} {} 예산의 {:.0}%를 사용했습니다 (${:.2} / ${:.2})",
+                "[예산]".yellow(), label, spent / limit * 100.0, spent, limit
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use similar::TextDiff;
+use std::sync::Mutex;
+
+/// `#lastdiff`에 넣을 최근 수정 파일 수
+const MAX_RECENT_FILES: usize = 5;
+/// `#lastdiff`로 넣을 diff의 최대 길이 (긴 diff가 대화 한도를 채우지 않도록)
+const MAX_EXPANDED_CHARS: usize = 12000;
+
+/// 대화에서 `#lastcmd`, `#lastdiff`로 참조할 수 있는 최근 작업.
+/// 셸 명령어는 대화 루프에서, 파일 수정은 `FileModifier`가 쓸 때 기록하므로 프로세스 전체에서 하나를 공유합니다
+struct RecentActivity {
+    /// 마지막 셸 명령어와 출력 (이미 마크다운으로 정리된 내용)
+    last_command: Option<String>,
+    /// 최근 수정한 파일 (가장 최근이 뒤)
+    files: Vec<ModifiedFile>,
+}
+
+struct ModifiedFile {
+    path: String,
+    /// 이번 실행에서 처음 수정하기 전 내용
+    original: String,
+    current: String,
+}
+
+static ACTIVITY: Mutex<RecentActivity> = Mutex::new(RecentActivity { last_command: None, files: Vec::new() });
+
+/// 대화에서 쓸 수 있는 참조와 설명
+pub const ACTIVITY_REFERENCES: &[(&str, &str)] = &[
+    ("#lastcmd", "마지막으로 실행한 셸 명령어와 출력"),
+    ("#lastdiff", "최근 수정한 파일의 변경 내용 (diff)"),
+];
+
+/// 실행한 셸 명령어를 기록합니다 (`block`은 명령어, 결과, 출력을 정리한 마크다운)
+pub fn record_command(block: String) {
+    ACTIVITY.lock().unwrap().last_command = Some(block);
+}
+
+/// 파일에 쓴 내용을 기록합니다. 같은 파일을 다시 고치면 처음 원본과의 차이를 유지합니다
+pub fn record_file_change(path: &str, original: &str, new: &str) {
+    let mut activity = ACTIVITY.lock().unwrap();
+    let original = match activity.files.iter().position(|f| f.path == path) {
+        Some(index) => activity.files.remove(index).original,
+        None => original.to_string(),
+    };
+    activity.files.push(ModifiedFile { path: path.to_string(), original, current: new.to_string() });
+    if activity.files.len() > MAX_RECENT_FILES {
+        activity.files.remove(0);
+    }
+}
+
+/// 최근 수정한 파일 경로 (가장 최근이 앞)
+pub fn recent_files() -> Vec<String> {
+    ACTIVITY.lock().unwrap().files.iter().rev().map(|f| f.path.clone()).collect()
+}
+
+/// 마지막 셸 명령어가 있는지
+pub fn has_last_command() -> bool {
+    ACTIVITY.lock().unwrap().last_command.is_some()
+}
+
+/// 입력의 `#lastcmd`, `#lastdiff`를 최근 작업 내용으로 바꾼 결과
+pub struct ExpandedInput {
+    pub text: String,
+    /// 펼친 참조
+    pub expanded: Vec<&'static str>,
+    /// 기록이 없어 그대로 둔 참조
+    pub missing: Vec<&'static str>,
+}
+
+/// 입력에 있는 최근 작업 참조를 펼칩니다. 기록이 없는 참조는 그대로 둡니다
+pub fn expand_activity_references(input: &str) -> ExpandedInput {
+    let activity = ACTIVITY.lock().unwrap();
+    let mut result = ExpandedInput { text: input.to_string(), expanded: Vec::new(), missing: Vec::new() };
+    for &(reference, _) in ACTIVITY_REFERENCES {
+        if !contains_reference(&result.text, reference) {
+            continue;
+        }
+        let content = match reference {
+            "#lastcmd" => activity.last_command.clone(),
+            _ => diff_block(&activity.files),
+        };
+        match content {
+            Some(content) => {
+                result.text = replace_reference(&result.text, reference, &content);
+                result.expanded.push(reference);
+            }
+            None => result.missing.push(reference),
+        }
+    }
+    result
+}
+
+fn diff_block(files: &[ModifiedFile]) -> Option<String> {
+    let diffs: Vec<String> = files.iter().rev()
+        .filter(|f| f.original != f.current)
+        .map(|f| TextDiff::from_lines(&f.original, &f.current)
+            .unified_diff()
+            .header(&format!("a/{}", f.path), &format!("b/{}", f.path))
+            .to_string())
+        .collect();
+    (!diffs.is_empty()).then(|| format!("최근 수정한 파일의 변경 내용:\n```diff\n{}```", truncate(&diffs.concat())))
+}
+
+/// `#lastcmd`가 `#lastcmds` 같은 다른 단어의 일부일 때는 참조로 보지 않음
+fn is_boundary(rest: &str) -> bool {
+    !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+}
+
+fn contains_reference(text: &str, reference: &str) -> bool {
+    text.match_indices(reference).any(|(index, _)| is_boundary(&text[index + reference.len()..]))
+}
+
+fn replace_reference(text: &str, reference: &str, content: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find(reference) {
+        let after = &rest[index + reference.len()..];
+        result.push_str(&rest[..index]);
+        if is_boundary(after) {
+            result.push_str("\n\n");
+            result.push_str(content);
+            result.push_str("\n\n");
+        } else {
+            result.push_str(reference);
+        }
+        rest = after;
+    }
+    result.push_str(rest);
+    result
+}
+
+fn truncate(content: &str) -> String {
+    if content.chars().count() <= MAX_EXPANDED_CHARS {
+        return content.to_string();
+    }
+    let kept: String = content.chars().take(MAX_EXPANDED_CHARS).collect();
+    format!("{}\n... (이하 생략)\n", kept)
+}
@@ -0,0 +1,84 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use super::{extract_markdown_block, language_instruction, DevAssistant};
+
+/// ADR 파일을 저장할 디렉토리 (프로젝트 루트 기준)
+const ADR_DIR: &str = "docs/adr";
+
+impl DevAssistant {
+    /// 현재 대화에서 내린 결정을 ADR(맥락, 결정, 결과)로 정리해 `docs/adr/NNNN-제목.md`로 저장합니다
+    pub async fn export_adr(&self, title: &str) -> Result<PathBuf> {
+        if self.context.messages.is_empty() {
+            anyhow::bail!("ADR로 정리할 대화가 없습니다");
+        }
+        crate::permissions::ensure_allowed("ADR 저장")?;
+
+        let english = self.config.output_preferences.language == "en";
+        let (context, decision, consequences) = if english {
+            ("Context", "Decision", "Consequences")
+        } else {
+            ("맥락", "결정", "결과")
+        };
+
+        let transcript: String = self.context.messages.iter()
+            .map(|m| format!("[{}]\n{}\n", m.role, m.content))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let prompt = format!(
+            "다음 대화에서 \"{}\"에 관해 내린 결정을 아키텍처 결정 기록(ADR)으로 정리해주세요.\n\n\
+            `## {}`(결정이 필요했던 배경과 제약), `## {}`(선택한 방안과 이유, 검토한 대안), \
+            `## {}`(장단점, 후속 작업) 세 섹션만 마크다운으로 작성하고 제목은 쓰지 마세요. \
+            대화에 없는 내용은 지어내지 마세요. {}\n\n대화:\n{}",
+            title, context, decision, consequences,
+            language_instruction(&self.config.output_preferences.language),
+            transcript
+        );
+        let body = extract_markdown_block(&self.client.query(&prompt).await?);
+
+        let dir = Path::new(ADR_DIR);
+        std::fs::create_dir_all(dir).context("ADR 디렉토리 생성 실패")?;
+        let number = next_adr_number(dir);
+        let path = dir.join(format!("{:04}-{}.md", number, slugify(title)));
+
+        let date = chrono::Local::now().format("%Y-%m-%d");
+        let header = if english {
+            format!("# {}. {}\n\n- Date: {}\n- Status: Accepted\n", number, title, date)
+        } else {
+            format!("# {}. {}\n\n- 날짜: {}\n- 상태: 채택됨\n", number, title, date)
+        };
+        std::fs::write(&path, format!("{}\n{}\n", header, body.trim()))
+            .with_context(|| format!("ADR 저장 실패: {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// 기존 `NNNN-*.md` 파일 다음 번호 (없으면 1)
+fn next_adr_number(dir: &Path) -> u32 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    name.split('-').next()?.parse::<u32>().ok()
+                })
+                .max()
+                .unwrap_or(0)
+        })
+        .unwrap_or(0)
+        + 1
+}
+
+/// 파일 이름용 제목 (한글 등 유니코드 문자는 유지)
+fn slugify(title: &str) -> String {
+    let slug = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() { "decision".to_string() } else { slug }
+}
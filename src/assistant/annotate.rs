@@ -0,0 +1,82 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::Path;
+use crate::analyzer::language_registry;
+use crate::assistant::types::{IssueSeverity, ReviewIssue};
+
+/// 리뷰 주석 태그 뒤에 붙는 표시 (`// FIXME(ricci): ...`)
+const MARKER: &str = "(ricci)";
+
+/// 리뷰 문제를 주석으로 단 결과
+pub struct Annotation {
+    pub content: String,
+    pub added: usize,
+    /// 같은 주석이 이미 있어 건너뛴 문제 수
+    pub existing: usize,
+    /// 위치에 줄 번호가 없거나 파일 범위를 벗어나 건너뛴 문제 수
+    pub unplaced: usize,
+}
+
+/// 리뷰 이슈 위치(`파일:라인[:열]`, `파일:시작-끝`)에서 줄 번호를 찾습니다
+fn location_line(location: &str) -> Option<usize> {
+    location.split(':')
+        .find_map(|part| part.trim().split(['-', ' ', ',']).next()?.parse().ok())
+        .filter(|&line| line > 0)
+}
+
+fn comment_text(issue: &ReviewIssue, open: &str, close: &str) -> String {
+    let tag = if issue.severity <= IssueSeverity::High { "FIXME" } else { "TODO" };
+    let mut text = format!(
+        "{}{}: [{:?}/{:?}] {}",
+        tag,
+        MARKER,
+        issue.severity,
+        issue.category,
+        issue.description.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+    if let Some(suggestion) = &issue.suggestion {
+        text.push_str(&format!(" (제안: {})", suggestion.split_whitespace().collect::<Vec<_>>().join(" ")));
+    }
+    if close.is_empty() {
+        format!("{} {}", open, text)
+    } else {
+        // 설명 안의 닫는 표시 때문에 주석이 일찍 끝나지 않도록 제거
+        format!("{} {} {}", open, text.replace(close, ""), close)
+    }
+}
+
+/// 각 문제를 가리키는 줄 바로 위에 같은 들여쓰기로 TODO/FIXME 주석을 넣습니다.
+/// Critical/High는 FIXME, 나머지는 TODO로 달고, 바로 위 주석 묶음에 같은 주석이 있으면 건너뜁니다
+pub fn annotate_source(path: &Path, content: &str, issues: &[ReviewIssue]) -> Result<Annotation> {
+    let (open, close) = language_registry().comment_delimiters(path)
+        .ok_or_else(|| anyhow::anyhow!("주석을 달 수 없는 파일 형식입니다: {}", path.display()))?;
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(str::to_string).collect();
+    
+    let mut comments: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    let (mut existing, mut unplaced) = (0, 0);
+    for issue in issues {
+        let Some(line) = location_line(&issue.location).filter(|&line| line <= lines.len()) else {
+            unplaced += 1;
+            continue;
+        };
+        let comment = comment_text(issue, &open, &close);
+        let above = lines[..line - 1].iter().rev().take_while(|l| l.trim_start().starts_with(open.as_str()));
+        let pending = comments.entry(line).or_default();
+        if above.clone().any(|l| l.trim() == comment) || pending.contains(&comment) {
+            existing += 1;
+            continue;
+        }
+        pending.push(comment);
+    }
+    
+    let added = comments.values().map(Vec::len).sum();
+    // 뒤쪽부터 넣어 앞쪽 줄 번호가 밀리지 않게 함
+    for (line, texts) in comments.into_iter().rev() {
+        let target = &lines[line - 1];
+        let indent: String = target.chars().take_while(|c| c.is_whitespace() && *c != '\n' && *c != '\r').collect();
+        let inserted: Vec<String> = texts.into_iter().map(|text| format!("{}{}{}", indent, text, newline)).collect();
+        lines.splice(line - 1..line - 1, inserted);
+    }
+    Ok(Annotation { content: lines.concat(), added, existing, unplaced })
+}
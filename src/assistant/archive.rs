@@ -0,0 +1,64 @@
+use crate::api::estimate_tokens;
+use super::types::Message;
+
+/// 요약에 넣을 메시지 하나의 최대 길이 (긴 코드 블록이 요약 요청을 채우지 않도록)
+const SUMMARY_MESSAGE_CHARS: usize = 2000;
+
+/// 메시지 수나 예상 토큰 합이 한도(0이면 제한 없음)를 넘으면 보관할 앞부분의 길이를 반환합니다.
+/// 저장할 때마다 보관하지 않도록 한도의 절반까지 줄이고, 남는 대화가 사용자 메시지로 시작하도록 맞춥니다
+pub(super) fn archive_split(messages: &[Message], max_messages: usize, max_tokens: usize) -> Option<usize> {
+    let tokens: Vec<usize> = messages.iter().map(|m| estimate_tokens(&m.content)).collect();
+    let over_messages = max_messages > 0 && messages.len() > max_messages;
+    let over_tokens = max_tokens > 0 && tokens.iter().sum::<usize>() > max_tokens;
+    if !over_messages && !over_tokens {
+        return None;
+    }
+
+    let keep_messages = if max_messages > 0 { max_messages / 2 } else { usize::MAX };
+    let keep_tokens = if max_tokens > 0 { max_tokens / 2 } else { usize::MAX };
+    let mut kept = 0;
+    let mut kept_tokens = 0;
+    for &count in tokens.iter().rev() {
+        if kept >= keep_messages || kept_tokens + count > keep_tokens {
+            break;
+        }
+        kept += 1;
+        kept_tokens += count;
+    }
+
+    // 질문과 답이 갈라지지 않도록 다음 사용자 메시지부터 남기고, 없으면 마지막 질문부터 남김
+    let start = messages.len() - kept;
+    let split = messages[start..].iter().position(|m| m.role == "user").map(|i| start + i)
+        .or_else(|| messages.iter().rposition(|m| m.role == "user"))
+        .unwrap_or(messages.len());
+    (split > 0).then_some(split)
+}
+
+/// 보관할 메시지를 요약해 달라는 프롬프트. 이전 요약이 있으면 합쳐서 하나로 만들게 합니다
+pub(super) fn summary_prompt(previous: Option<&str>, archived: &[Message]) -> String {
+    let mut prompt = String::from(
+        "다음은 길어진 대화에서 보관할 앞부분입니다. 이후 대화를 이어가는 데 필요한 사실, 내린 결정, \
+        진행 중인 작업, 사용자의 선호를 15줄 이내의 목록으로 요약해주세요. 요약만 출력하세요.\n\n",
+    );
+    if let Some(previous) = previous {
+        prompt.push_str(&format!("[이전 요약]\n{}\n\n", previous));
+    }
+    prompt.push_str("[대화]\n");
+    for message in archived {
+        let content: String = message.content.chars().take(SUMMARY_MESSAGE_CHARS).collect();
+        prompt.push_str(&format!("{}: {}\n", message.role, content));
+    }
+    prompt
+}
+
+/// 모델 없이 만드는 요약: 사용자 질문의 첫 줄을 날짜와 함께 나열합니다
+pub(super) fn outline_summary(previous: Option<&str>, archived: &[Message]) -> String {
+    let mut lines: Vec<String> = previous.map(|p| p.to_string()).into_iter().collect();
+    lines.extend(archived.iter()
+        .filter(|m| m.role == "user")
+        .map(|m| {
+            let first_line: String = m.content.lines().next().unwrap_or_default().chars().take(80).collect();
+            format!("- {} {}", m.timestamp.format("%Y-%m-%d"), first_line)
+        }));
+    lines.join("\n")
+}
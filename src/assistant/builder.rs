@@ -0,0 +1,201 @@
+use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use crate::api::{ConfirmFn, OpenAIClient};
+use crate::config::Config;
+use crate::renderer::{NullSink, OutputSink};
+use crate::paths::sessions_dir;
+use super::{events, AssistantContext, DevAssistant};
+
+/// 대화 문맥(세션)을 읽고 쓰는 저장소. 기본은 세션 디렉토리의 `current_session.json`
+pub trait ContextStore: Send + Sync {
+    /// 저장된 문맥. 없거나 읽을 수 없으면 `None`
+    fn load(&self) -> Result<Option<AssistantContext>>;
+    fn save(&self, context: &AssistantContext) -> Result<()>;
+    /// 세션에서 덜어낸 오래된 메시지를 보관합니다. 보관한 위치를 반환하며, 기본 구현은 보관하지 않고 버림
+    fn archive(&self, _archived: &AssistantContext) -> Result<Option<String>> {
+        Ok(None)
+    }
+    /// 안내 문구에 보여줄 저장 위치
+    fn location(&self) -> String;
+}
+
+/// JSON 파일 하나에 문맥을 저장합니다
+pub struct FileContextStore {
+    path: PathBuf,
+}
+
+impl FileContextStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// CLI가 쓰는 현재 세션 파일
+    pub fn current_session() -> Result<Self> {
+        Ok(Self::new(sessions_dir()?.join("current_session.json")))
+    }
+}
+
+impl ContextStore for FileContextStore {
+    fn load(&self) -> Result<Option<AssistantContext>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let data = fs::read_to_string(&self.path)?;
+        Ok(serde_json::from_str(&data).ok())
+    }
+
+    fn save(&self, context: &AssistantContext) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(context)?)?;
+        Ok(())
+    }
+
+    /// 세션 파일 옆의 `archive/<이름>_<시각>.json`에 저장
+    fn archive(&self, archived: &AssistantContext) -> Result<Option<String>> {
+        let dir = self.path.parent().map(|parent| parent.join("archive")).unwrap_or_else(|| PathBuf::from("archive"));
+        fs::create_dir_all(&dir)?;
+        let stem = self.path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| "session".to_string());
+        let path = dir.join(format!("{}_{}.json", stem, chrono::Local::now().format("%Y%m%d_%H%M%S")));
+        fs::write(&path, serde_json::to_string_pretty(archived)?)?;
+        Ok(Some(path.display().to_string()))
+    }
+
+    fn location(&self) -> String {
+        self.path.display().to_string()
+    }
+}
+
+/// 파일에 쓰지 않고 메모리에만 두는 저장소 (테스트, 내장용)
+#[derive(Default)]
+pub struct MemoryContextStore {
+    context: Mutex<Option<AssistantContext>>,
+}
+
+impl ContextStore for MemoryContextStore {
+    fn load(&self) -> Result<Option<AssistantContext>> {
+        Ok(self.context.lock().unwrap().clone())
+    }
+
+    fn save(&self, context: &AssistantContext) -> Result<()> {
+        *self.context.lock().unwrap() = Some(context.clone());
+        Ok(())
+    }
+
+    fn location(&self) -> String {
+        "메모리".to_string()
+    }
+}
+
+/// 다른 Rust 프로그램에서 터미널 출력 없이 어시스턴트를 쓰기 위한 구성.
+/// 지정하지 않은 부분은 `DevAssistant::new`와 같은 기본값을 사용합니다
+///
+/// ```no_run
+/// # async fn run(config: ricci_cli::Config) -> anyhow::Result<()> {
+/// use ricci_cli::assistant::{DevAssistant, MemoryContextStore};
+///
+/// let mut assistant = DevAssistant::builder(config)
+///     .silent()
+///     .context_store(MemoryContextStore::default())
+///     .build()?;
+/// let answer = assistant.ask("이 함수의 역할을 설명해줘").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct DevAssistantBuilder {
+    config: Config,
+    client: Option<OpenAIClient>,
+    sink: Option<Arc<dyn OutputSink>>,
+    confirm: Option<ConfirmFn>,
+    store: Option<Box<dyn ContextStore>>,
+    context: AssistantContext,
+}
+
+impl DevAssistantBuilder {
+    pub(super) fn new(config: Config) -> Self {
+        Self {
+            config,
+            client: None,
+            sink: None,
+            confirm: None,
+            store: None,
+            context: AssistantContext::default(),
+        }
+    }
+
+    /// 설정의 기본 제공자 대신 쓸 클라이언트 (예: `OpenAIClient::with_model`)
+    pub fn provider(mut self, client: OpenAIClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// 응답과 안내 문구를 보낼 곳 (`MarkdownRenderer`, `PlainWriter`, `JsonEmitter` 또는 직접 구현한 `OutputSink`)
+    pub fn output(self, sink: impl OutputSink + 'static) -> Self {
+        self.output_sink(Arc::new(sink))
+    }
+
+    /// 여러 어시스턴트나 핸들러가 같은 출력을 나눠 쓸 때
+    pub fn output_sink(mut self, sink: Arc<dyn OutputSink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// 응답 렌더링과 안내 문구를 출력하지 않음. 응답은 반환값과 문맥으로만 받습니다.
+    /// 터미널에서 묻지 않도록 `confirm`을 지정하지 않았으면 큰 프롬프트도 확인 없이 보냅니다
+    pub fn silent(mut self) -> Self {
+        self.confirm.get_or_insert_with(|| Arc::new(|_: &str| true));
+        self.output(NullSink)
+    }
+
+    /// 큰 프롬프트(`confirm_prompt_tokens` 초과)를 보낼지 터미널 입력 대신 묻는 콜백.
+    /// 예상 토큰/비용 안내를 받아 보내려면 true
+    pub fn confirm(mut self, confirm: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        self.confirm = Some(Arc::new(confirm));
+        self
+    }
+
+    pub fn context_store(mut self, store: impl ContextStore + 'static) -> Self {
+        self.store = Some(Box::new(store));
+        self
+    }
+
+    /// 처음 문맥 (이전 대화, 첨부 파일, 모드 등)
+    pub fn context(mut self, context: AssistantContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    pub fn build(self) -> Result<DevAssistant> {
+        let mut client = match self.client {
+            Some(client) => client,
+            None => OpenAIClient::new(&self.config)?,
+        };
+        let sink = self.sink.unwrap_or_else(|| self.config.output_sink());
+        client.set_output(sink.clone());
+        if let Some(confirm) = self.confirm {
+            client.set_confirm(confirm);
+        }
+        let store = match self.store {
+            Some(store) => store,
+            None => Box::new(FileContextStore::current_session()?),
+        };
+        let mut assistant = DevAssistant {
+            client,
+            sink,
+            store,
+            events: events::channel(),
+            context: self.context,
+            show_stats: self.config.output_preferences.show_stats,
+            show_reasoning: self.config.output_preferences.show_reasoning,
+            auto_save: self.config.output_preferences.auto_save_sessions,
+            config: self.config,
+            last_response_truncated: false,
+            last_reasoning: String::new(),
+        };
+        assistant.apply_mode_generation();
+        Ok(assistant)
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::{Context, Result};
+use colored::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use crate::paths::data_dir;
+use super::types::{AssistantContext, ChatMode, Message};
+
+/// 작업 영역(예: `billing-service`)마다 저장해 두고 한 번에 불러오는 문맥 묶음.
+/// 첨부 파일, 페르소나, 대화 모드와 기억(보관된 대화 요약, 고정한 메시지)을 담습니다
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextBundle {
+    pub name: String,
+    pub saved_at: DateTime<Utc>,
+    /// 첨부 파일 (어느 디렉토리에서 불러와도 찾도록 절대 경로)
+    #[serde(default)]
+    pub files: Vec<String>,
+    #[serde(default)]
+    pub persona: Option<String>,
+    #[serde(default)]
+    pub chat_mode: ChatMode,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub pinned: Vec<Message>,
+}
+
+pub fn bundles_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("contexts"))
+}
+
+impl ContextBundle {
+    /// 세션 문맥에서 묶음으로 남길 부분만 떼어 냅니다.
+    /// 세션에 첨부된 뒤 지워지거나 옮겨진 파일은 경고만 하고 빼고 저장합니다
+    pub fn from_context(name: &str, context: &AssistantContext) -> Result<Self> {
+        check_name(name)?;
+        let mut bundle = Self {
+            name: name.to_string(),
+            saved_at: Utc::now(),
+            files: Vec::new(),
+            persona: context.persona.clone(),
+            chat_mode: context.chat_mode,
+            summary: context.archived_summary.clone(),
+            pinned: context.messages.iter().filter(|m| m.pinned).cloned().collect(),
+        };
+        for file in &context.current_files {
+            if let Err(e) = bundle.add_file(Path::new(file)) {
+                eprintln!("{} {:#} (묶음에서 제외)", "경고:".yellow(), e);
+            }
+        }
+        Ok(bundle)
+    }
+
+    /// 첨부 파일을 추가합니다. 이미 있으면 무시
+    pub fn add_file(&mut self, path: &Path) -> Result<()> {
+        let absolute = path.canonicalize()
+            .with_context(|| format!("첨부할 파일을 찾을 수 없습니다: {}", path.display()))?
+            .to_string_lossy()
+            .into_owned();
+        if !self.files.contains(&absolute) {
+            self.files.push(absolute);
+        }
+        Ok(())
+    }
+
+    /// 이 묶음을 현재 세션 문맥으로 만듭니다 (대화는 고정한 메시지만 남음)
+    pub fn to_context(&self) -> AssistantContext {
+        AssistantContext {
+            messages: self.pinned.clone(),
+            current_files: self.files.clone(),
+            chat_mode: self.chat_mode,
+            persona: self.persona.clone(),
+            archived_summary: self.summary.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// `<data_dir>/contexts/<이름>.json`에 저장합니다. 같은 이름은 덮어씀
+    pub fn save(&self) -> Result<PathBuf> {
+        let dir = bundles_dir()?;
+        std::fs::create_dir_all(&dir).context("문맥 묶음 디렉토리 생성 실패")?;
+        let path = dir.join(format!("{}.json", self.name));
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("문맥 묶음 저장 실패: {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn load(name: &str) -> Result<Self> {
+        check_name(name)?;
+        let path = bundles_dir()?.join(format!("{}.json", name));
+        let data = std::fs::read_to_string(&path)
+            .with_context(|| format!("저장된 문맥 묶음이 없습니다: {} (`ricci context list`로 확인)", name))?;
+        serde_json::from_str(&data).with_context(|| format!("문맥 묶음 파싱 실패: {}", path.display()))
+    }
+
+    pub fn delete(name: &str) -> Result<()> {
+        check_name(name)?;
+        let path = bundles_dir()?.join(format!("{}.json", name));
+        std::fs::remove_file(&path).with_context(|| format!("저장된 문맥 묶음이 없습니다: {}", name))
+    }
+
+    /// 저장된 묶음 전체 (이름 순서)
+    pub fn list() -> Result<Vec<Self>> {
+        let Ok(entries) = std::fs::read_dir(bundles_dir()?) else {
+            return Ok(Vec::new());
+        };
+        let mut bundles: Vec<Self> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|p| std::fs::read_to_string(p).ok())
+            .filter_map(|data| serde_json::from_str(&data).ok())
+            .collect();
+        bundles.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(bundles)
+    }
+}
+
+/// 묶음 이름은 파일 이름이 되므로 글자, 숫자, `-`, `_`, `.`만 허용
+fn check_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    anyhow::ensure!(valid, "문맥 묶음 이름에는 글자, 숫자, '-', '_', '.'만 쓸 수 있습니다: {}", name);
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::config::Config;
+
+/// 프로젝트별 문서 템플릿 디렉토리 (`.ricci/templates/<유형>.md`)
+const PROJECT_TEMPLATE_DIR: &str = ".ricci/templates";
+const TEMPLATE_EXTENSIONS: &[&str] = &["md", "hbs", "j2", "jinja"];
+
+/// 문서 유형에 맞는 템플릿 파일을 찾습니다.
+/// 우선순위: `--template` 옵션 > 프로젝트 `.ricci/templates/<유형>.*` > 설정의 `[doc_templates]` >
+/// `ricci sync`로 받은 팀 템플릿 `<data_dir>/shared/templates/<유형>.*`
+pub fn find_doc_template(doc_type: &str, explicit: Option<&Path>, config: &Config) -> Option<PathBuf> {
+    if let Some(path) = explicit {
+        return Some(path.to_path_buf());
+    }
+    let in_dir = |dir: &Path| TEMPLATE_EXTENSIONS.iter()
+        .map(|ext| dir.join(format!("{}.{}", doc_type, ext)))
+        .find(|path| path.is_file());
+    in_dir(Path::new(PROJECT_TEMPLATE_DIR))
+        .or_else(|| config.doc_templates.get(doc_type).map(|path| expand_home(path)))
+        .or_else(|| in_dir(&crate::paths::shared_dir().ok()?.join("templates")))
+}
+
+pub fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// 템플릿에서 사용할 수 있는 변수
+pub fn template_variables(target: &str, doc_type: &str, config: &Config) -> BTreeMap<String, String> {
+    let project_name = std::env::current_dir().ok()
+        .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default();
+    let target_name = Path::new(target).file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| target.to_string());
+
+    BTreeMap::from([
+        ("project_name".to_string(), project_name),
+        ("target".to_string(), target.to_string()),
+        ("target_name".to_string(), target_name),
+        ("doc_type".to_string(), doc_type.to_string()),
+        ("language".to_string(), config.output_preferences.language.clone()),
+        ("date".to_string(), chrono::Local::now().format("%Y-%m-%d").to_string()),
+    ])
+}
+
+/// 템플릿 파일을 읽어 변수를 채웁니다
+pub fn load_doc_template(path: &Path, variables: &BTreeMap<String, String>) -> Result<String> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("문서 템플릿 읽기 실패: {}", path.display()))?;
+    render_template(&source, variables)
+        .with_context(|| format!("문서 템플릿 오류: {}", path.display()))
+}
+
+/// Handlebars/minijinja 문법의 일부를 지원하는 간단한 렌더러.
+/// `{{ 변수 }}`, `{{#if 변수}}…{{else}}…{{/if}}` (`{% if 변수 %}…{% else %}…{% endif %}`),
+/// 주석 `{{! … }}` (`{# … #}`)을 처리합니다. 변수가 비어 있으면 거짓으로 봅니다.
+/// 반복(`each`/`for`), 필터, 부분 템플릿처럼 그 밖의 태그는 그대로 두지 않고 오류로 알립니다
+pub fn render_template(source: &str, variables: &BTreeMap<String, String>) -> Result<String> {
+    let tokens = tokenize(source)?;
+    let mut position = 0;
+    let output = render_block(&tokens, &mut position, variables)?;
+    match tokens.get(position) {
+        None => Ok(output),
+        Some(Token::Else) => anyhow::bail!("짝이 맞지 않는 else"),
+        Some(_) => anyhow::bail!("짝이 맞지 않는 /if (endif)"),
+    }
+}
+
+#[derive(Debug)]
+enum Token<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+    If(&'a str),
+    Else,
+    EndIf,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find('{') {
+        let (open, close) = match &rest[start..] {
+            s if s.starts_with("{{") => ("{{", "}}"),
+            s if s.starts_with("{%") => ("{%", "%}"),
+            s if s.starts_with("{#") => ("{#", "#}"),
+            _ => {
+                tokens.push(Token::Text(&rest[..start + 1]));
+                rest = &rest[start + 1..];
+                continue;
+            }
+        };
+        tokens.push(Token::Text(&rest[..start]));
+
+        let after = &rest[start + open.len()..];
+        let end = after.find(close)
+            .with_context(|| format!("닫히지 않은 태그: {}{}", open, after.lines().next().unwrap_or("")))?;
+        let tag = after[..end].trim();
+        rest = &after[end + close.len()..];
+
+        let token = match open {
+            "{#" => continue,
+            "{{" if tag.starts_with('!') => continue,
+            "{{" => match tag {
+                "else" => Token::Else,
+                "/if" => Token::EndIf,
+                _ => match tag.strip_prefix("#if ") {
+                    Some(name) if is_identifier(name.trim()) => Token::If(name.trim()),
+                    None if is_identifier(tag) => Token::Variable(tag),
+                    _ => anyhow::bail!("지원하지 않는 태그: {{{{{}}}}}", tag),
+                },
+            },
+            _ => match tag {
+                "else" => Token::Else,
+                "endif" => Token::EndIf,
+                _ => match tag.strip_prefix("if ") {
+                    Some(name) if is_identifier(name.trim()) => Token::If(name.trim()),
+                    _ => anyhow::bail!("지원하지 않는 태그: {{% {} %}}", tag),
+                },
+            },
+        };
+        tokens.push(token);
+    }
+    tokens.push(Token::Text(rest));
+    Ok(tokens)
+}
+
+/// else / endif를 만나거나 끝에 도달할 때까지 렌더링합니다
+fn render_block(tokens: &[Token], position: &mut usize, variables: &BTreeMap<String, String>) -> Result<String> {
+    let mut output = String::new();
+    while let Some(token) = tokens.get(*position) {
+        match token {
+            Token::Text(text) => output.push_str(text),
+            Token::Variable(name) => output.push_str(lookup(variables, name)?),
+            Token::If(name) => {
+                let condition = !lookup(variables, name)?.trim().is_empty();
+                *position += 1;
+                let then_branch = render_block(tokens, position, variables)?;
+                let else_branch = if matches!(tokens.get(*position), Some(Token::Else)) {
+                    *position += 1;
+                    render_block(tokens, position, variables)?
+                } else {
+                    String::new()
+                };
+                if !matches!(tokens.get(*position), Some(Token::EndIf)) {
+                    anyhow::bail!("닫히지 않은 if: {}", name);
+                }
+                output.push_str(if condition { &then_branch } else { &else_branch });
+            }
+            Token::Else | Token::EndIf => return Ok(output),
+        }
+        *position += 1;
+    }
+    Ok(output)
+}
+
+/// 변수 이름 (`project_name` 같은 글자, 숫자, `_`). 필터나 식은 지원하지 않음
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn lookup<'a>(variables: &'a BTreeMap<String, String>, name: &str) -> Result<&'a str> {
+    variables.get(name).map(String::as_str).with_context(|| {
+        let known: Vec<&str> = variables.keys().map(String::as_str).collect();
+        format!("알 수 없는 변수: {} (사용 가능: {})", name, known.join(", "))
+    })
+}
@@ -0,0 +1,24 @@
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// 구독자가 따라잡지 못할 때 버리기 전까지 쌓아두는 이벤트 수
+pub(super) const EVENT_CAPACITY: usize = 1024;
+
+/// 어시스턴트가 작업 중에 내보내는 이벤트. TUI, HTTP 서버, 테스트가 같은 흐름을 구독합니다
+/// (`DevAssistant::subscribe`). 터미널 출력(`OutputSink`)과는 별개로 항상 발행됩니다
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AssistantEvent {
+    /// 스트리밍 응답 조각
+    TokenChunk { text: String },
+    /// 모델 대화 외의 작업 시작 (예: `review`, `security_triage`, `analyze_project`)
+    ToolCallStarted { tool: String, target: String },
+    /// 파일 변경을 제안함 (적용 여부는 확인 단계에서 결정)
+    FileChangeProposed { path: String, description: String },
+    /// 응답 하나가 끝남. `response`는 이어받은 부분까지 합친 전체 응답
+    Done { response: String, truncated: bool },
+}
+
+pub(super) fn channel() -> broadcast::Sender<AssistantEvent> {
+    broadcast::channel(EVENT_CAPACITY).0
+}
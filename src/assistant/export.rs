@@ -0,0 +1,56 @@
+use anyhow::Result;
+use serde_json::json;
+use crate::assistant::types::{AssistantContext, Message};
+
+/// 파인튜닝용 JSONL 형식
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// {"messages": [{"role": "system", ...}, {"role": "user", ...}, ...]}
+    OpenaiJsonl,
+    /// {"system": "...", "messages": [{"role": "user", ...}, ...]}
+    AnthropicJsonl,
+}
+
+/// 세션 하나를 파인튜닝용 JSONL 한 줄로 변환합니다.
+/// 어시스턴트 응답이 없는 세션은 None을 반환합니다.
+pub fn export_finetune_jsonl(
+    context: &AssistantContext,
+    format: ExportFormat,
+    system_prompt: &str,
+) -> Result<Option<String>> {
+    let turns = training_turns(&context.messages);
+    if turns.is_empty() {
+        return Ok(None);
+    }
+
+    let messages: Vec<_> = turns.iter()
+        .map(|m| json!({ "role": m.role, "content": m.content }))
+        .collect();
+
+    let record = match format {
+        ExportFormat::OpenaiJsonl => {
+            let mut all = vec![json!({ "role": "system", "content": system_prompt })];
+            all.extend(messages);
+            json!({ "messages": all })
+        }
+        ExportFormat::AnthropicJsonl => json!({
+            "system": system_prompt,
+            "messages": messages,
+        }),
+    };
+
+    Ok(Some(serde_json::to_string(&record)?))
+}
+
+/// user/assistant 턴만 남기고, 응답이 없는 마지막 사용자 메시지는 제외합니다
+fn training_turns(messages: &[Message]) -> Vec<&Message> {
+    let mut turns: Vec<&Message> = messages.iter()
+        .filter(|m| m.role == "user" || m.role == "assistant")
+        .collect();
+
+    while turns.last().is_some_and(|m| m.role != "assistant") {
+        turns.pop();
+    }
+
+    turns
+}
@@ -0,0 +1,133 @@
+use std::fs;
+use std::path::Path;
+use crate::assistant::file_modifier::FileChange;
+use crate::assistant::untrusted::strip_file_blocks;
+
+/// 경로가 표시된 코드 블록 하나
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotatedFile {
+    pub path: String,
+    pub content: String,
+}
+
+/// 응답 하나에 담긴 여러 파일을 경로 표시 기준으로 나눕니다.
+///
+/// 다음 표시를 인식합니다 (앞에 있는 것이 우선):
+/// - 펜스 정보: ` ```rust:src/foo.rs `, ` ```src/foo.rs `, ` ```rust title="src/foo.rs" `
+/// - 블록 첫 줄 주석: `// src/foo.rs`, `# src/foo.py`, `<!-- index.html -->`
+/// - 블록 바로 앞 줄: `### src/foo.rs`, `**src/foo.rs**`, `` `src/foo.rs` ``, `파일: src/foo.rs`
+///
+/// 경로 표시가 없는 블록과 프롬프트의 `<file>` 블록을 되풀이한 부분은 건너뜁니다.
+pub fn extract_annotated_files(text: &str) -> Vec<AnnotatedFile> {
+    let text = strip_file_blocks(text);
+    let mut files = Vec::new();
+    let mut lines = text.lines();
+    let mut previous_line = "";
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        let Some(info) = trimmed.strip_prefix("```") else {
+            if !trimmed.is_empty() {
+                previous_line = trimmed;
+            }
+            continue;
+        };
+
+        let mut body: Vec<&str> = Vec::new();
+        for inner in lines.by_ref() {
+            if inner.trim_start().starts_with("```") {
+                break;
+            }
+            body.push(inner);
+        }
+
+        let mut path = path_from_info(info);
+        if path.is_none() {
+            if let Some(first) = body.first().and_then(|l| path_from_comment(l)) {
+                path = Some(first);
+                body.remove(0);
+            }
+        }
+        if path.is_none() {
+            path = path_from_header(previous_line);
+        }
+
+        if let Some(path) = path {
+            let mut content = body.join("\n");
+            content.push('\n');
+            files.push(AnnotatedFile { path, content });
+        }
+        previous_line = "";
+    }
+
+    files
+}
+
+/// 추출한 파일을 기존 내용과 함께 FileChange로 변환합니다 (새 파일이면 원본은 빈 문자열)
+pub fn annotated_files_to_changes(files: Vec<AnnotatedFile>, description: &str) -> Vec<FileChange> {
+    files.into_iter()
+        .map(|file| FileChange {
+            original_content: fs::read_to_string(&file.path).unwrap_or_default(),
+            path: file.path,
+            new_content: file.content,
+            description: description.to_string(),
+        })
+        .collect()
+}
+
+fn path_from_info(info: &str) -> Option<String> {
+    let info = info.trim();
+    if let Some(start) = info.find("title=") {
+        let title = info[start + "title=".len()..].trim_matches(|c| c == '"' || c == '\'');
+        return looks_like_path(title).then(|| title.to_string());
+    }
+    if let Some((_, path)) = info.split_once(':') {
+        return looks_like_path(path).then(|| path.to_string());
+    }
+    // 언어 이름 대신 경로만 적은 경우 (```src/foo.rs)
+    (info.contains('/') || info.contains('.'))
+        .then_some(info)
+        .filter(|p| looks_like_path(p))
+        .map(String::from)
+}
+
+fn path_from_comment(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let inner = ["//", "#", "--", "<!--", "/*"].iter()
+        .find_map(|prefix| trimmed.strip_prefix(prefix))?
+        .trim_end_matches("-->")
+        .trim_end_matches("*/")
+        .trim();
+    let inner = strip_label(inner);
+    looks_like_path(inner).then(|| inner.to_string())
+}
+
+fn path_from_header(line: &str) -> Option<String> {
+    let inner = line
+        .trim_start_matches('#')
+        .trim()
+        .trim_matches(|c| c == '*' || c == '`' || c == ':')
+        .trim();
+    let inner = strip_label(inner).trim_matches(|c| c == '*' || c == '`').trim();
+    looks_like_path(inner).then(|| inner.to_string())
+}
+
+fn strip_label(text: &str) -> &str {
+    for label in ["File:", "file:", "파일:", "Filename:", "filename:"] {
+        if let Some(rest) = text.strip_prefix(label) {
+            return rest.trim();
+        }
+    }
+    text
+}
+
+/// 확장자가 있고 공백이 없는 상대/절대 경로처럼 보이는지
+fn looks_like_path(text: &str) -> bool {
+    !text.is_empty()
+        && !text.contains(char::is_whitespace)
+        && !text.contains("://")
+        && Path::new(text).extension().is_some_and(|e| {
+            let e = e.to_string_lossy();
+            !e.is_empty() && e.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
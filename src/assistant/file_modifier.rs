@@ -1,13 +1,21 @@
 use anyhow::{Result, Context};
 use colored::*;
+use crate::renderer::plain;
+use crate::hooks::{run_hooks, HookEvent};
+use crate::permissions::{ensure_allowed, ensure_writable, is_read_only};
+use crate::paths::backups_dir;
+use super::activity::record_file_change;
 use dialoguer::Select;
 use std::fs;
-use std::path::Path;
-use similar::{ChangeTag, TextDiff};
+use std::path::{Path, PathBuf};
+use similar::{ChangeTag, DiffTag, TextDiff};
+use std::ops::Range;
 
 pub struct FileModifier {
     auto_confirm: bool,
     show_diff: bool,
+    /// 파일 단위 대신 변경 블록(hunk)마다 적용 여부를 물음
+    by_hunk: bool,
 }
 
 #[derive(Debug)]
@@ -23,9 +31,16 @@ impl FileModifier {
         Self {
             auto_confirm,
             show_diff: true,
+            by_hunk: false,
         }
     }
     
+    /// 파일마다 묻는 대신 변경 블록마다 적용 여부를 묻도록 합니다
+    pub fn by_hunk(mut self) -> Self {
+        self.by_hunk = true;
+        self
+    }
+    
     /// 파일 변경사항을 미리보기하고 사용자 확인을 받습니다
     pub async fn apply_changes(&self, changes: Vec<FileChange>) -> Result<()> {
         if changes.is_empty() {
@@ -34,19 +49,36 @@ impl FileModifier {
         }
         
         println!("\n{}", format!("{}개의 파일을 수정할 예정입니다:", changes.len()).bright_cyan().bold());
+        let mut refused = 0;
         
         for (idx, change) in changes.iter().enumerate() {
             println!("\n{}", format!("파일 {}/{}: {}", idx + 1, changes.len(), change.path).bright_blue().bold());
             println!("{}", format!("설명: {}", change.description).dimmed());
             
-            if self.show_diff {
+            // 블록별 선택에서는 블록마다 따로 보여줌
+            let hunk_prompt = self.by_hunk && !self.auto_confirm && !is_read_only();
+            if self.show_diff && !hunk_prompt {
                 self.show_diff(&change.original_content, &change.new_content);
             }
             
-            if !self.auto_confirm {
+            // 읽기 전용 모드에서는 제안된 변경을 모두 보여주기만 하고 쓰지 않음
+            if is_read_only() {
+                continue;
+            }
+            
+            if let Err(e) = ensure_writable(Path::new(&change.path)) {
+                println!("{} {}", "건너뜀:".red(), e);
+                refused += 1;
+                continue;
+            }
+            
+            if hunk_prompt {
+                self.apply_selected_hunks(change)?;
+            } else if !self.auto_confirm {
                 let choice = self.ask_user_choice(&change.path)?;
                 match choice {
                     UserChoice::Apply => self.apply_single_change(change)?,
+                    UserChoice::Hunks => self.apply_selected_hunks(change)?,
                     UserChoice::Skip => {
                         println!("{}", "건너뛰었습니다.".yellow());
                         continue;
@@ -54,6 +86,7 @@ impl FileModifier {
                     UserChoice::Edit => {
                         let edited_content = self.edit_change(change)?;
                         self.write_file(&change.path, &edited_content)?;
+                        run_apply_hooks(change);
                     }
                     UserChoice::Cancel => {
                         println!("{}", "작업을 취소했습니다.".red());
@@ -65,7 +98,12 @@ impl FileModifier {
             }
         }
         
-        println!("\n{}", "모든 변경사항이 적용되었습니다.".green().bold());
+        ensure_allowed("파일 수정")?;
+        if refused > 0 {
+            println!("\n{}", format!("{}개 파일은 쓰기 허용 경로 밖이라 적용하지 않았습니다.", refused).yellow().bold());
+        } else {
+            println!("\n{}", "모든 변경사항이 적용되었습니다.".green().bold());
+        }
         Ok(())
     }
     
@@ -122,6 +160,7 @@ impl FileModifier {
             "적용 (Apply)",
             "건너뛰기 (Skip)",
             "수정 (Edit)",
+            "변경 블록별 선택 (Hunks)",
             "취소 (Cancel all)",
         ];
         
@@ -135,10 +174,78 @@ impl FileModifier {
             0 => UserChoice::Apply,
             1 => UserChoice::Skip,
             2 => UserChoice::Edit,
+            3 => UserChoice::Hunks,
             _ => UserChoice::Cancel,
         })
     }
     
+    /// 변경 블록마다 적용 여부를 묻고, 고른 블록만 원본에 반영해 씁니다
+    fn apply_selected_hunks(&self, change: &FileChange) -> Result<()> {
+        let old_lines: Vec<&str> = change.original_content.split_inclusive('\n').collect();
+        let new_lines: Vec<&str> = change.new_content.split_inclusive('\n').collect();
+        let hunks = diff_hunks(&change.original_content, &change.new_content);
+        if hunks.is_empty() {
+            println!("{}", "변경된 내용이 없습니다.".yellow());
+            return Ok(());
+        }
+        
+        let options = [
+            "적용 (Apply)",
+            "건너뛰기 (Skip)",
+            "나머지 모두 적용 (Apply rest)",
+            "나머지 모두 건너뛰기 (Skip rest)",
+        ];
+        let mut accepted = vec![false; hunks.len()];
+        let mut rest: Option<bool> = None;
+        for (index, hunk) in hunks.iter().enumerate() {
+            if let Some(all) = rest {
+                accepted[index] = all;
+                continue;
+            }
+            println!("\n{}", format!("변경 블록 {}/{} ({}:{})", index + 1, hunks.len(), change.path, hunk.old.start + 1).bright_blue());
+            print_hunk(hunk, &old_lines, &new_lines);
+            let selection = Select::new()
+                .with_prompt("이 변경 블록을 적용하시겠습니까?")
+                .items(&options)
+                .default(0)
+                .interact()?;
+            match selection {
+                0 => accepted[index] = true,
+                1 => {}
+                2 => {
+                    accepted[index] = true;
+                    rest = Some(true);
+                }
+                _ => rest = Some(false),
+            }
+        }
+        
+        let applied = accepted.iter().filter(|&&a| a).count();
+        if applied == 0 {
+            println!("{}", "건너뛰었습니다.".yellow());
+            return Ok(());
+        }
+        
+        // 고르지 않은 블록은 원본 줄을 유지
+        let mut content = String::new();
+        let mut old_pos = 0;
+        for (hunk, accept) in hunks.iter().zip(&accepted) {
+            content.extend(old_lines[old_pos..hunk.old.start].iter().copied());
+            if *accept {
+                content.extend(new_lines[hunk.new.clone()].iter().copied());
+            } else {
+                content.extend(old_lines[hunk.old.clone()].iter().copied());
+            }
+            old_pos = hunk.old.end;
+        }
+        content.extend(old_lines[old_pos..].iter().copied());
+        
+        self.write_file(&change.path, &content)?;
+        println!("{}", plain(&format!("✓ {} 수정 완료 ({}/{}개 블록)", change.path, applied, hunks.len())).green());
+        run_apply_hooks(change);
+        Ok(())
+    }
+    
     fn edit_change(&self, change: &FileChange) -> Result<String> {
         println!("{}", "수정할 내용을 입력하세요 (Ctrl+D로 종료):".yellow());
         
@@ -162,66 +269,192 @@ impl FileModifier {
     
     fn apply_single_change(&self, change: &FileChange) -> Result<()> {
         self.write_file(&change.path, &change.new_content)?;
-        println!("{}", format!("✓ {} 수정 완료", change.path).green());
+        println!("{}", plain(&format!("✓ {} 수정 완료", change.path)).green());
+        run_apply_hooks(change);
         Ok(())
     }
     
     fn write_file(&self, path: &str, content: &str) -> Result<()> {
         let path = Path::new(path);
+        ensure_writable(path)?;
         
         // 디렉토리가 없으면 생성
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
         
+        // `#lastdiff`로 참조할 수 있도록 쓰기 전 내용과 함께 기록
+        let original = fs::read_to_string(path).unwrap_or_default();
         fs::write(path, content).context("파일 쓰기 실패")?;
+        record_file_change(&path.to_string_lossy(), &original, content);
         Ok(())
     }
 }
 
+/// 파일에 적용한 변경을 `on_apply` 훅에 알립니다
+pub fn run_apply_hooks(change: &FileChange) {
+    run_hooks(HookEvent::Apply, serde_json::json!({
+        "path": change.path,
+        "description": change.description,
+    }));
+}
+
 #[derive(Debug, Clone, Copy)]
 enum UserChoice {
     Apply,
     Skip,
     Edit,
+    Hunks,
     Cancel,
 }
 
+/// 연속된 변경 줄 묶음 (원본과 새 내용의 줄 범위)
+struct Hunk {
+    old: Range<usize>,
+    new: Range<usize>,
+}
+
+/// 블록 앞뒤로 함께 보여줄 변경되지 않은 줄 수
+const HUNK_CONTEXT: usize = 3;
+
+fn diff_hunks(original: &str, new: &str) -> Vec<Hunk> {
+    let diff = TextDiff::from_lines(original, new);
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let mut joinable = false;
+    for op in diff.ops() {
+        let (tag, old, new) = op.as_tag_tuple();
+        if tag == DiffTag::Equal {
+            joinable = false;
+            continue;
+        }
+        match hunks.last_mut() {
+            Some(last) if joinable => {
+                last.old.end = old.end;
+                last.new.end = new.end;
+            }
+            _ => hunks.push(Hunk { old, new }),
+        }
+        joinable = true;
+    }
+    hunks
+}
+
+fn print_hunk(hunk: &Hunk, old_lines: &[&str], new_lines: &[&str]) {
+    let before = hunk.old.start.saturating_sub(HUNK_CONTEXT);
+    let after = (hunk.old.end + HUNK_CONTEXT).min(old_lines.len());
+    let line = |sign: &str, text: &str| format!("{} {}", sign, text.trim_end_matches('\n'));
+    for text in &old_lines[before..hunk.old.start] {
+        println!("{}", line(" ", text).dimmed());
+    }
+    for text in &old_lines[hunk.old.clone()] {
+        println!("{}", line("-", text).red());
+    }
+    for text in &new_lines[hunk.new.clone()] {
+        println!("{}", line("+", text).green());
+    }
+    for text in &old_lines[hunk.old.end..after] {
+        println!("{}", line(" ", text).dimmed());
+    }
+}
+
 /// 백업과 함께 안전한 파일 수정
 pub struct SafeFileModifier {
     modifier: FileModifier,
-    backup_dir: String,
 }
 
 impl SafeFileModifier {
     pub fn new(auto_confirm: bool) -> Self {
         Self {
             modifier: FileModifier::new(auto_confirm),
-            backup_dir: ".ricci_backups".to_string(),
         }
     }
     
+    /// 변경 블록마다 적용 여부를 묻도록 합니다
+    pub fn by_hunk(mut self) -> Self {
+        self.modifier = self.modifier.by_hunk();
+        self
+    }
+    
     pub async fn modify_with_backup(&self, changes: Vec<FileChange>) -> Result<()> {
-        // 백업 디렉토리 생성
-        fs::create_dir_all(&self.backup_dir)?;
-        
-        // 각 파일 백업
-        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-        for change in &changes {
-            if Path::new(&change.path).exists() {
-                let backup_path = format!("{}/{}_{}.bak", 
-                    self.backup_dir, 
-                    change.path.replace('/', "_").replace('\\', "_"),
-                    timestamp
-                );
-                fs::copy(&change.path, backup_path)?;
-            }
+        if is_read_only() {
+            return self.modifier.apply_changes(changes).await;
         }
         
+        let backup_dir = self.backup(&changes)?;
+        
         // 변경사항 적용
         self.modifier.apply_changes(changes).await?;
         
-        println!("\n{}", format!("백업 파일은 {} 디렉토리에 저장되었습니다.", self.backup_dir).dimmed());
+        println!("\n{}", format!("백업 파일은 {} 디렉토리에 저장되었습니다.", backup_dir.display()).dimmed());
         Ok(())
     }
+    
+    /// 모든 파일의 변경 블록을 먼저 보여주고 한 번만 확인받아 한꺼번에 적용합니다.
+    /// 쓰기 허용 경로 밖의 파일이 있으면 아무것도 쓰지 않고, 쓰는 도중 실패하면 이미 쓴 파일을 되돌립니다.
+    /// 적용했으면 true
+    pub fn apply_all_or_nothing(&self, changes: &[FileChange]) -> Result<bool> {
+        let total: usize = changes.iter().map(|c| diff_hunks(&c.original_content, &c.new_content).len()).sum();
+        println!("\n{}", format!("{}개 파일, {}개 변경 블록:", changes.len(), total).bright_cyan().bold());
+        for change in changes {
+            let old_lines: Vec<&str> = change.original_content.split_inclusive('\n').collect();
+            let new_lines: Vec<&str> = change.new_content.split_inclusive('\n').collect();
+            for hunk in diff_hunks(&change.original_content, &change.new_content) {
+                println!("\n{}", format!("{}:{}", change.path, hunk.old.start + 1).bright_blue());
+                print_hunk(&hunk, &old_lines, &new_lines);
+            }
+        }
+        
+        ensure_allowed("파일 수정")?;
+        for change in changes {
+            ensure_writable(Path::new(&change.path))?;
+        }
+        if !self.modifier.auto_confirm {
+            let selection = Select::new()
+                .with_prompt(format!("{}개 파일의 변경을 모두 적용하시겠습니까?", changes.len()))
+                .items(&["모두 적용 (Apply all)", "취소 (Cancel)"])
+                .default(0)
+                .interact()?;
+            if selection != 0 {
+                println!("{}", "작업을 취소했습니다.".red());
+                return Ok(false);
+            }
+        }
+        
+        let backup_dir = self.backup(changes)?;
+        for (index, change) in changes.iter().enumerate() {
+            if let Err(e) = self.modifier.write_file(&change.path, &change.new_content) {
+                for written in &changes[..index] {
+                    if let Err(restore) = fs::write(&written.path, &written.original_content) {
+                        eprintln!("{} {}: {}", "되돌리기 실패:".red(), written.path, restore);
+                    }
+                }
+                return Err(e.context(format!("{} 쓰기 실패로 모든 변경을 되돌렸습니다", change.path)));
+            }
+        }
+        for change in changes {
+            run_apply_hooks(change);
+        }
+        println!("\n{}", plain(&format!("✓ {}개 파일에 적용했습니다", changes.len())).green().bold());
+        println!("{}", format!("백업 파일은 {} 디렉토리에 저장되었습니다.", backup_dir.display()).dimmed());
+        Ok(true)
+    }
+    
+    /// 원본을 데이터 디렉토리의 `backups`에 복사하고 그 디렉토리를 반환합니다.
+    /// 여러 프로젝트의 백업이 섞이지 않도록 파일 이름은 절대 경로로 만듭니다
+    fn backup(&self, changes: &[FileChange]) -> Result<PathBuf> {
+        let backup_dir = backups_dir()?;
+        fs::create_dir_all(&backup_dir)?;
+        
+        // 각 파일 백업
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        for change in changes {
+            let path = Path::new(&change.path);
+            if path.exists() {
+                let absolute = path.canonicalize()?;
+                let name = absolute.to_string_lossy().trim_start_matches(['/', '\\']).replace(['/', '\\', ':'], "_");
+                fs::copy(path, backup_dir.join(format!("{}_{}.bak", name, timestamp)))?;
+            }
+        }
+        Ok(backup_dir)
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,39 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use crate::analyzer::language_registry;
+
+/// 생성된 코드를 언어별 포매터(rustfmt, prettier, black 등 언어 레지스트리 설정)로 정리합니다.
+/// 포매터가 없거나 실패하면(예: 문법 오류), 읽기 전용 모드라 외부 프로그램을 실행할 수 없으면 원본을 그대로 반환합니다.
+pub fn format_code(code: &str, path: &str) -> String {
+    if crate::permissions::ensure_allowed("포매터 실행").is_err() {
+        return code.to_string();
+    }
+    let Some((program, args)) = language_registry().formatter(path) else {
+        return code.to_string();
+    };
+
+    run_formatter(&program, &args, code).unwrap_or_else(|| code.to_string())
+}
+
+fn run_formatter(program: &str, args: &[String], code: &str) -> Option<String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // 큰 파일은 포매터가 출력 파이프를 채운 채 입력을 기다릴 수 있으므로 입력은 따로 쓰고 출력을 함께 읽음
+    let mut stdin = child.stdin.take()?;
+    let input = code.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output().ok()?;
+    writer.join().ok()?.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let formatted = String::from_utf8(output.stdout).ok()?;
+    (!formatted.trim().is_empty()).then_some(formatted)
+}
@@ -0,0 +1,225 @@
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use serde_json::Value;
+use std::path::Path;
+use crate::assistant::types::{AssistantContext, Message};
+use crate::error::RicciError;
+
+/// `ricci session import`로 가져올 수 있는 대화 기록 형식
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ImportFormat {
+    /// ChatGPT 데이터 내보내기의 conversations.json
+    Chatgpt,
+    /// Claude 데이터 내보내기의 conversations.json
+    Claude,
+    /// aider의 .aider.chat.history.md
+    Aider,
+}
+
+/// 가져온 대화 하나
+pub struct ImportedConversation {
+    pub title: String,
+    pub context: AssistantContext,
+}
+
+impl ImportedConversation {
+    /// 마지막 메시지 시각 (가장 최근 대화를 고를 때 사용)
+    pub fn last_activity(&self) -> Option<DateTime<Utc>> {
+        self.context.messages.last().map(|m| m.timestamp)
+    }
+}
+
+impl ImportFormat {
+    /// 파일 이름과 내용으로 형식을 짐작합니다
+    pub fn detect(path: &Path, content: &str) -> Option<Self> {
+        if path.extension().is_some_and(|e| e == "md") || content.trim_start().starts_with(AIDER_HEADER) {
+            return Some(Self::Aider);
+        }
+        let json: Value = serde_json::from_str(content).ok()?;
+        let first = match &json {
+            Value::Array(items) => items.first()?,
+            other => other,
+        };
+        if first.get("mapping").is_some() {
+            Some(Self::Chatgpt)
+        } else if first.get("chat_messages").is_some() {
+            Some(Self::Claude)
+        } else {
+            None
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Chatgpt => "chatgpt",
+            Self::Claude => "claude",
+            Self::Aider => "aider",
+        }
+    }
+}
+
+const AIDER_HEADER: &str = "# aider chat started at";
+
+/// 내보낸 파일의 대화들을 세션 문맥으로 바꿉니다. 메시지가 없는 대화는 건너뜁니다
+pub fn import_conversations(content: &str, format: ImportFormat) -> Result<Vec<ImportedConversation>> {
+    let conversations = match format {
+        ImportFormat::Chatgpt => json_items(content)?.iter().map(chatgpt_conversation).collect(),
+        ImportFormat::Claude => json_items(content)?.iter().map(claude_conversation).collect(),
+        ImportFormat::Aider => aider_conversations(content),
+    };
+    Ok(conversations.into_iter().filter(|c: &ImportedConversation| !c.context.messages.is_empty()).collect())
+}
+
+/// 대화 배열 또는 대화 하나
+fn json_items(content: &str) -> Result<Vec<Value>> {
+    let json: Value = serde_json::from_str(content)
+        .map_err(|e| RicciError::Parse(format!("대화 기록 JSON 파싱 실패: {e}")))?;
+    Ok(match json {
+        Value::Array(items) => items,
+        other => vec![other],
+    })
+}
+
+fn message(role: &str, content: String, timestamp: Option<DateTime<Utc>>) -> Message {
+    Message {
+        role: role.to_string(),
+        content,
+        timestamp: timestamp.unwrap_or_else(Utc::now),
+        pinned: false,
+    }
+}
+
+fn unix_time(value: &Value) -> Option<DateTime<Utc>> {
+    value.as_f64().and_then(|secs| DateTime::from_timestamp(secs as i64, 0))
+}
+
+/// ChatGPT는 메시지를 트리(`mapping`)로 저장하므로 `current_node`에서 부모를 따라 올라가 본 대화 가지만 복원합니다
+fn chatgpt_conversation(item: &Value) -> ImportedConversation {
+    let mapping = &item["mapping"];
+    let mut node_id = item["current_node"].as_str().map(str::to_string);
+    let mut messages = Vec::new();
+    while let Some(id) = node_id.take() {
+        let node = &mapping[&id];
+        let msg = &node["message"];
+        let role = msg["author"]["role"].as_str().unwrap_or_default();
+        let hidden = msg["metadata"]["is_visually_hidden_from_conversation"].as_bool().unwrap_or(false);
+        if (role == "user" || role == "assistant") && !hidden {
+            let content = &msg["content"];
+            let text = match content["parts"].as_array() {
+                Some(parts) => parts.iter().filter_map(Value::as_str).collect::<Vec<_>>().join("\n"),
+                None => content["text"].as_str().unwrap_or_default().to_string(),
+            };
+            if !text.trim().is_empty() {
+                messages.push(message(role, text, unix_time(&msg["create_time"]).or_else(|| unix_time(&item["create_time"]))));
+            }
+        }
+        node_id = node["parent"].as_str().map(str::to_string);
+    }
+    messages.reverse();
+
+    ImportedConversation {
+        title: item["title"].as_str().unwrap_or("ChatGPT 대화").to_string(),
+        context: AssistantContext { messages, ..Default::default() },
+    }
+}
+
+fn claude_conversation(item: &Value) -> ImportedConversation {
+    let messages = item["chat_messages"].as_array().into_iter().flatten()
+        .filter_map(|msg| {
+            let role = match msg["sender"].as_str()? {
+                "human" => "user",
+                "assistant" => "assistant",
+                _ => return None,
+            };
+            // 최근 내보내기는 `content` 블록에, 예전 내보내기는 `text`에 본문이 있음
+            let blocks: Vec<&str> = msg["content"].as_array().into_iter().flatten()
+                .filter(|block| block["type"] == "text")
+                .filter_map(|block| block["text"].as_str())
+                .collect();
+            let text = if blocks.is_empty() { msg["text"].as_str().unwrap_or_default().to_string() } else { blocks.join("\n") };
+            let timestamp = msg["created_at"].as_str().and_then(|t| t.parse().ok());
+            (!text.trim().is_empty()).then(|| message(role, text, timestamp))
+        })
+        .collect();
+
+    ImportedConversation {
+        title: item["name"].as_str().filter(|n| !n.is_empty()).unwrap_or("Claude 대화").to_string(),
+        context: AssistantContext { messages, ..Default::default() },
+    }
+}
+
+/// aider 기록: `# aider chat started at <시각>`마다 대화 하나, `#### `로 시작하는 줄은 사용자 입력,
+/// `>`로 시작하는 줄은 aider 자체 출력(건너뜀), 나머지는 모델 응답입니다
+fn aider_conversations(content: &str) -> Vec<ImportedConversation> {
+    struct Builder {
+        title: String,
+        started: Option<DateTime<Utc>>,
+        messages: Vec<Message>,
+        role: &'static str,
+        buffer: Vec<String>,
+    }
+
+    impl Builder {
+        fn new(title: String, started: Option<DateTime<Utc>>) -> Self {
+            Self { title, started, messages: Vec::new(), role: "assistant", buffer: Vec::new() }
+        }
+
+        fn flush(&mut self) {
+            let text = self.buffer.join("\n").trim().to_string();
+            self.buffer.clear();
+            if text.is_empty() {
+                return;
+            }
+            match self.messages.last_mut() {
+                Some(last) if last.role == self.role => {
+                    last.content.push_str("\n\n");
+                    last.content.push_str(&text);
+                }
+                _ => self.messages.push(message(self.role, text, self.started)),
+            }
+        }
+
+        fn finish(mut self) -> ImportedConversation {
+            self.flush();
+            ImportedConversation {
+                title: self.title,
+                context: AssistantContext { messages: self.messages, ..Default::default() },
+            }
+        }
+
+        fn switch(&mut self, role: &'static str) {
+            if self.role != role {
+                self.flush();
+                self.role = role;
+            }
+        }
+    }
+
+    let mut conversations = Vec::new();
+    let mut current: Option<Builder> = None;
+    for line in content.lines() {
+        if let Some(started) = line.strip_prefix(AIDER_HEADER) {
+            conversations.extend(current.take().map(Builder::finish));
+            let started = started.trim();
+            let time = NaiveDateTime::parse_from_str(started, "%Y-%m-%d %H:%M:%S").ok()
+                .and_then(|t| Local.from_local_datetime(&t).single())
+                .map(|t| t.with_timezone(&Utc));
+            current = Some(Builder::new(format!("aider {}", started), time));
+            continue;
+        }
+        let builder = current.get_or_insert_with(|| Builder::new("aider 대화".to_string(), None));
+        if let Some(input) = line.strip_prefix("#### ") {
+            builder.switch("user");
+            builder.buffer.push(input.to_string());
+        } else if line.starts_with('>') {
+            // aider 출력은 건너뛰고, 그 뒤의 응답은 앞 응답에 이어 붙임
+            builder.flush();
+            builder.role = "tool";
+        } else if builder.role != "tool" || !line.trim().is_empty() {
+            builder.switch("assistant");
+            builder.buffer.push(line.to_string());
+        }
+    }
+    conversations.extend(current.map(Builder::finish));
+    conversations
+}
@@ -0,0 +1,146 @@
+use std::path::Path;
+use crate::analyzer::language_registry;
+
+/// 일반 코드와 다른 기준으로 리뷰하는 인프라/배포 설정 및 SQL 파일 종류
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InfraKind {
+    Dockerfile,
+    Compose,
+    Terraform,
+    Kubernetes,
+    /// 스키마 마이그레이션 (migrations/ 디렉토리, V1__*.sql, *.up.sql 등)
+    SqlMigration,
+    Sql,
+}
+
+impl InfraKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InfraKind::Dockerfile => "Dockerfile",
+            InfraKind::Compose => "docker-compose",
+            InfraKind::Terraform => "Terraform",
+            InfraKind::Kubernetes => "Kubernetes 매니페스트",
+            InfraKind::SqlMigration => "SQL 마이그레이션",
+            InfraKind::Sql => "SQL",
+        }
+    }
+
+    /// 코드 블록 언어 표시
+    pub fn fence(&self) -> &'static str {
+        match self {
+            InfraKind::Dockerfile => "dockerfile",
+            InfraKind::Compose | InfraKind::Kubernetes => "yaml",
+            InfraKind::Terraform => "hcl",
+            InfraKind::SqlMigration | InfraKind::Sql => "sql",
+        }
+    }
+
+    /// 일반 코드 리뷰 기준 대신 사용할 인프라 전용 점검 항목
+    pub fn criteria(&self) -> &'static str {
+        match self {
+            InfraKind::Dockerfile => "\
+                1. 베이스 이미지 고정 (latest 태그 금지, 버전 또는 digest 지정)\n\
+                2. root가 아닌 USER로 실행\n\
+                3. ENV/ARG/COPY로 비밀 값(키, 토큰, .env)이 이미지에 들어가지 않는지\n\
+                4. 멀티 스테이지 빌드와 불필요한 패키지/캐시 정리로 이미지 크기 최소화\n\
+                5. 레이어 캐시를 살리는 명령 순서 (의존성 설치 후 소스 복사)\n\
+                6. ADD 대신 COPY, curl | sh 같은 검증 없는 원격 스크립트 실행 금지\n\
+                7. HEALTHCHECK와 노출 포트 최소화",
+            InfraKind::Compose => "\
+                1. 이미지 태그 고정 (latest 금지)\n\
+                2. environment에 평문 비밀 값 대신 secrets 또는 env_file 사용\n\
+                3. privileged, host 네트워크, docker.sock 마운트 등 과도한 권한\n\
+                4. 불필요하게 외부로 공개된 포트 (127.0.0.1 바인딩 고려)\n\
+                5. restart 정책, healthcheck, 리소스 제한\n\
+                6. 볼륨 마운트 범위와 읽기 전용 여부",
+            InfraKind::Terraform => "\
+                1. provider와 모듈 버전 고정\n\
+                2. 변수/기본값/출력에 하드코딩된 비밀 값 (sensitive 표시 여부)\n\
+                3. 0.0.0.0/0 인바운드 등 과도하게 열린 보안 그룹/방화벽\n\
+                4. 스토리지/DB 암호화, 공개 접근 차단, 로깅 설정\n\
+                5. 원격 상태(backend)와 상태 잠금 설정\n\
+                6. IAM 정책의 와일드카드(*) 권한",
+            InfraKind::Kubernetes => "\
+                1. 이미지 태그 고정과 imagePullPolicy\n\
+                2. Secret 대신 ConfigMap/env에 들어간 비밀 값\n\
+                3. securityContext (runAsNonRoot, readOnlyRootFilesystem, privileged, capabilities)\n\
+                4. resources requests/limits\n\
+                5. liveness/readiness probe\n\
+                6. hostPath, hostNetwork 등 노드 권한 사용과 과도한 RBAC 권한",
+            InfraKind::SqlMigration => "\
+                1. 파괴적 변경 (DROP TABLE/COLUMN, 타입 축소, TRUNCATE)과 데이터 손실 가능성\n\
+                2. 큰 테이블에 긴 잠금을 거는 작업 (NOT NULL 컬럼 추가, 기본값 재작성, 인덱스 생성 시 CONCURRENTLY 미사용)\n\
+                3. 새 외래 키/조회 조건에 필요한 인덱스 누락\n\
+                4. 되돌리기(down) 마이그레이션 존재 여부와 롤백 가능성\n\
+                5. 배포 중 구버전 코드와의 호환성 (컬럼 이름 변경, 단계적 마이그레이션 필요 여부)\n\
+                6. 트랜잭션 사용과 데이터 백필을 별도 단계로 분리했는지",
+            InfraKind::Sql => "\
+                1. 인덱스를 활용하지 못하는 조건 (함수 적용 컬럼, 선행 와일드카드 LIKE)\n\
+                2. SELECT *, 불필요한 조인, N+1을 유발하는 쿼리 구조\n\
+                3. WHERE 없는 UPDATE/DELETE 등 위험한 문장\n\
+                4. 동적 SQL의 주입 가능성\n\
+                5. 제약 조건(NOT NULL, FK, UNIQUE)과 적절한 데이터 타입",
+        }
+    }
+}
+
+/// 파일 이름과 내용으로 인프라 파일 종류를 판별합니다
+pub fn detect_infra_kind(path: &Path, content: &str) -> Option<InfraKind> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+
+    if name == "dockerfile" || name.starts_with("dockerfile.") || name == "containerfile" || extension == "dockerfile" {
+        return Some(InfraKind::Dockerfile);
+    }
+    if extension == "tf" || extension == "tfvars" {
+        return Some(InfraKind::Terraform);
+    }
+    if extension == "sql" {
+        return Some(if is_migration(path) { InfraKind::SqlMigration } else { InfraKind::Sql });
+    }
+    if extension != "yml" && extension != "yaml" {
+        return None;
+    }
+    if name.starts_with("docker-compose") || name.starts_with("compose.") {
+        return Some(InfraKind::Compose);
+    }
+
+    let has_top_level = |key: &str| content.lines().any(|l| l.starts_with(key));
+    if has_top_level("apiVersion:") && has_top_level("kind:") {
+        Some(InfraKind::Kubernetes)
+    } else if has_top_level("services:") {
+        Some(InfraKind::Compose)
+    } else {
+        None
+    }
+}
+
+/// 마이그레이션 디렉토리 안에 있거나 마이그레이션 도구의 파일 이름 규칙을 따르는지
+fn is_migration(path: &Path) -> bool {
+    let in_migration_dir = path.components().any(|c| {
+        let name = c.as_os_str().to_string_lossy().to_lowercase();
+        name.contains("migration") || name == "migrate" || name == "db_migrate"
+    });
+    let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+    // Flyway(V1__init.sql), golang-migrate(1_init.up.sql), 타임스탬프 접두사(20240101120000_add.sql)
+    let versioned = (name.starts_with('v') && name.contains("__"))
+        || name.ends_with(".up.sql")
+        || name.ends_with(".down.sql")
+        || name.split(['_', '-']).next().is_some_and(|p| p.len() >= 8 && p.chars().all(|c| c.is_ascii_digit()));
+    in_migration_dir || versioned
+}
+
+/// 디렉토리 리뷰에서 다룰 파일인지 (소스 파일 또는 인프라 파일)
+pub fn is_reviewable(path: &Path) -> bool {
+    let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+    if language_registry().is_source(&extension) {
+        return true;
+    }
+    // YAML은 내용을 봐야 Kubernetes/compose 여부를 알 수 있음
+    let content = if extension == "yml" || extension == "yaml" {
+        std::fs::read_to_string(path).unwrap_or_default()
+    } else {
+        String::new()
+    };
+    detect_infra_kind(path, &content).is_some()
+}
@@ -0,0 +1,149 @@
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::assistant::types::{IssueCategory, IssueSeverity, ReviewIssue};
+
+/// 파일 확장자에 맞는 린터(cargo clippy, eslint, ruff)가 설치되어 있으면 실행하고
+/// 결과를 리뷰 이슈로 변환합니다. 린터가 없거나 실패하면 빈 목록을 반환합니다.
+pub fn run_linters(path: &Path) -> Vec<ReviewIssue> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    match extension {
+        "rs" => run_clippy(path),
+        "js" | "jsx" | "ts" | "tsx" => run_eslint(path),
+        "py" => run_ruff(path),
+        _ => Vec::new(),
+    }
+}
+
+/// 프롬프트에 넣을 린터 결과 요약
+pub fn describe_issues(issues: &[ReviewIssue]) -> String {
+    issues.iter()
+        .map(|i| format!("- {} {}", i.location, i.description))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_available(tool: &str, args: &[&str]) -> bool {
+    Command::new(tool)
+        .args(args)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+fn run_clippy(path: &Path) -> Vec<ReviewIssue> {
+    let Some(root) = find_ancestor_with(path, "Cargo.toml") else {
+        return Vec::new();
+    };
+    if !is_available("cargo", &["clippy", "--version"]) {
+        return Vec::new();
+    }
+
+    let Ok(output) = Command::new("cargo")
+        .args(["clippy", "--quiet", "--message-format=json"])
+        .current_dir(&root)
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let target = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|v| v["reason"] == "compiler-message")
+        .filter_map(|v| {
+            let message = &v["message"];
+            let span = message["spans"].as_array()?
+                .iter()
+                .find(|s| s["is_primary"] == true)?;
+            let file = root.join(span["file_name"].as_str()?);
+            if file.canonicalize().ok()? != target {
+                return None;
+            }
+
+            let code = message["code"]["code"].as_str().unwrap_or("rustc");
+            Some(ReviewIssue {
+                severity: if message["level"] == "error" { IssueSeverity::High } else { IssueSeverity::Medium },
+                category: IssueCategory::BestPractice,
+                location: format!("{}:{}", path.display(), span["line_start"].as_u64().unwrap_or(0)),
+                description: format!("[{}] {}", code, message["message"].as_str().unwrap_or("")),
+                suggestion: None,
+            })
+        })
+        .collect()
+}
+
+fn run_eslint(path: &Path) -> Vec<ReviewIssue> {
+    // 프로젝트 로컬 설치를 우선 사용
+    let local = find_ancestor_with(path, "node_modules/.bin/eslint")
+        .map(|root| root.join("node_modules/.bin/eslint"));
+    let eslint = local.unwrap_or_else(|| PathBuf::from("eslint"));
+    let eslint = eslint.to_string_lossy();
+    if !is_available(&eslint, &["--version"]) {
+        return Vec::new();
+    }
+
+    let Ok(output) = Command::new(eslint.as_ref())
+        .args(["--format", "json"])
+        .arg(path)
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let Ok(results) = serde_json::from_slice::<Value>(&output.stdout) else {
+        return Vec::new();
+    };
+
+    results.as_array().into_iter().flatten()
+        .flat_map(|file| file["messages"].as_array().cloned().unwrap_or_default())
+        .map(|m| ReviewIssue {
+            severity: if m["severity"] == 2 { IssueSeverity::High } else { IssueSeverity::Medium },
+            category: IssueCategory::Style,
+            location: format!("{}:{}", path.display(), m["line"].as_u64().unwrap_or(0)),
+            description: format!("[{}] {}", m["ruleId"].as_str().unwrap_or("eslint"), m["message"].as_str().unwrap_or("")),
+            suggestion: None,
+        })
+        .collect()
+}
+
+fn run_ruff(path: &Path) -> Vec<ReviewIssue> {
+    if !is_available("ruff", &["--version"]) {
+        return Vec::new();
+    }
+
+    let Ok(output) = Command::new("ruff")
+        .args(["check", "--output-format", "json"])
+        .arg(path)
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let Ok(results) = serde_json::from_slice::<Value>(&output.stdout) else {
+        return Vec::new();
+    };
+
+    results.as_array().into_iter().flatten()
+        .map(|d| {
+            let code = d["code"].as_str().unwrap_or("ruff");
+            ReviewIssue {
+                // F(pyflakes)와 E9(구문 오류)는 실제 버그일 가능성이 높음
+                severity: if code.starts_with('F') || code.starts_with("E9") { IssueSeverity::High } else { IssueSeverity::Medium },
+                category: if code.starts_with('F') { IssueCategory::Bug } else { IssueCategory::Style },
+                location: format!("{}:{}", path.display(), d["location"]["row"].as_u64().unwrap_or(0)),
+                description: format!("[{}] {}", code, d["message"].as_str().unwrap_or("")),
+                suggestion: d["fix"]["message"].as_str().map(String::from),
+            }
+        })
+        .collect()
+}
+
+fn find_ancestor_with(path: &Path, marker: &str) -> Option<PathBuf> {
+    let absolute = path.canonicalize().ok()?;
+    absolute.ancestors()
+        .skip(1)
+        .find(|dir| dir.join(marker).exists())
+        .map(Path::to_path_buf)
+}
@@ -1,15 +1,55 @@
 mod types;
 mod file_modifier;
 mod review;
+mod export;
+mod lint;
+mod formatter;
+mod extract;
+mod security;
+mod infra;
+mod doc_template;
+mod readme_merge;
+mod adr;
+mod vault;
+mod untrusted;
+mod review_cache;
+mod review_history;
+mod annotate;
+mod builder;
+mod events;
+mod archive;
+mod import;
+mod activity;
+mod bundle;
 
 pub use types::*;
-pub use file_modifier::{FileModifier, FileChange, SafeFileModifier};
-pub use review::review_code;
+pub use file_modifier::{FileModifier, FileChange, SafeFileModifier, run_apply_hooks};
+pub use review::{parse_review_target, review_code, summarize_reviews, ReviewGroupBy, ReviewView};
+pub use annotate::{annotate_source, Annotation};
+pub use builder::{ContextStore, DevAssistantBuilder, FileContextStore, MemoryContextStore};
+pub use events::AssistantEvent;
+use archive::{archive_split, outline_summary, summary_prompt};
+pub use review_history::{load_history, score_badge, sparkline, ScoreRecord};
+pub use export::{ExportFormat, export_finetune_jsonl};
+pub use import::{ImportFormat, ImportedConversation, import_conversations};
+pub use bundle::{ContextBundle, bundles_dir};
+pub use activity::{ACTIVITY_REFERENCES, ExpandedInput, expand_activity_references, record_command, record_file_change};
+pub use vault::write_vault_note;
+pub use untrusted::{FILE_CONTENT_RULE, fence_file, contains_file_block, strip_file_blocks};
+pub use formatter::format_code;
+pub use extract::{AnnotatedFile, extract_annotated_files, annotated_files_to_changes};
+pub use infra::{InfraKind, detect_infra_kind, is_reviewable};
+pub use doc_template::{expand_home, find_doc_template, load_doc_template, render_template, template_variables};
+pub use readme_merge::{KEEP_MARKER, MaskedReadme, mask_readme, restore_readme, project_summary, extract_markdown_block};
+pub use security::{SecurityFinding, SecurityReport, TriagedFinding, Verdict, scan_patterns, unreviewed};
 
 use anyhow::{Result, Context};
 use crate::config::Config;
-use crate::api::OpenAIClient;
-use crate::renderer::MarkdownRenderer;
+use crate::api::{OpenAIClient, StreamEvent, StatsRecorder, estimate_tokens, capabilities, summarization_threshold};
+use crate::renderer::{plain, NoticeLevel, OutputSink};
+use crate::hooks::{run_hooks, HookEvent};
+use crate::analyzer::detect_project;
+use crate::paths::history_file;
 use std::path::Path;
 use colored::*;
 use chrono::Utc;
@@ -17,27 +57,57 @@ use rustyline::Editor;
 use rustyline::error::ReadlineError;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// 잘린 응답을 자동으로 이어받을 때의 최대 반복 횟수
+const MAX_CONTINUATIONS: usize = 3;
+const CONTINUE_PROMPT: &str = "이전 응답이 길이 제한으로 잘렸습니다. 잘린 지점부터 바로 이어서 작성하고, 이미 작성한 내용은 반복하지 마세요.";
 
 pub struct DevAssistant {
     client: OpenAIClient,
-    renderer: MarkdownRenderer,
+    /// 응답과 안내 문구를 내보낼 곳 (기본은 터미널)
+    sink: Arc<dyn OutputSink>,
+    store: Box<dyn ContextStore>,
+    /// 구독자가 없으면 보낸 이벤트는 버려짐
+    events: broadcast::Sender<AssistantEvent>,
     context: AssistantContext,
     config: Config,
-    chat_mode: ChatMode,
+    show_stats: bool,
+    show_reasoning: bool,
+    /// 종료할 때 세션을 저장할지 (`auto_save_sessions`, `/autosave`)
+    auto_save: bool,
+    last_response_truncated: bool,
+    /// 마지막 응답의 추론 과정 (대화 기록에는 저장하지 않음)
+    last_reasoning: String,
 }
 
 impl DevAssistant {
     pub fn new(config: Config) -> Result<Self> {
-        let client = OpenAIClient::new(&config)?;
-        let renderer = MarkdownRenderer::new();
-        
-        Ok(Self {
-            client,
-            renderer,
-            context: AssistantContext::default(),
-            config,
-            chat_mode: ChatMode::Normal,
-        })
+        Self::builder(config).build()
+    }
+    
+    /// 제공자, 렌더러, 문맥 저장소를 바꿔 만들 수 있는 빌더 (라이브러리로 쓸 때)
+    pub fn builder(config: Config) -> DevAssistantBuilder {
+        DevAssistantBuilder::new(config)
+    }
+    
+    /// 이후 작업의 이벤트(응답 조각, 작업 시작, 파일 변경 제안, 응답 완료)를 받는 수신자
+    pub fn subscribe(&self) -> broadcast::Receiver<AssistantEvent> {
+        self.events.subscribe()
+    }
+    
+    fn emit(&self, event: AssistantEvent) {
+        // 구독자가 없을 때의 전송 실패는 무시
+        let _ = self.events.send(event);
+    }
+    
+    fn emit_tool(&self, tool: &str, target: &str) {
+        self.emit(AssistantEvent::ToolCallStarted { tool: tool.to_string(), target: target.to_string() });
+    }
+    
+    pub fn context(&self) -> &AssistantContext {
+        &self.context
     }
     
     pub fn get_config(&self) -> &Config {
@@ -45,11 +115,62 @@ impl DevAssistant {
     }
     
     pub fn set_mode(&mut self, mode: ChatMode) {
-        self.chat_mode = mode;
+        self.context.chat_mode = mode;
+        self.apply_mode_generation();
+    }
+    
+    /// 현재 대화 모드의 `[modes.<mode>]` temperature와 max_tokens를 요청에 적용합니다
+    fn apply_mode_generation(&mut self) {
+        let (temperature, max_tokens) = self.config.mode_generation(self.context.chat_mode.label());
+        self.client.set_generation(temperature, max_tokens);
     }
     
     pub fn get_mode(&self) -> ChatMode {
-        self.chat_mode
+        self.context.chat_mode
+    }
+    
+    /// 현재 대화 기록의 추정 토큰 수
+    pub fn context_tokens(&self) -> usize {
+        self.context.messages.iter().map(|m| estimate_tokens(&m.content)).sum()
+    }
+    
+    pub fn persona(&self) -> Option<&str> {
+        self.context.persona.as_deref()
+    }
+    
+    /// 페르소나를 선택합니다. 파일이 없으면 오류
+    pub fn set_persona(&mut self, name: Option<&str>) -> Result<()> {
+        if let Some(name) = name {
+            load_persona(name)?;
+        }
+        self.context.persona = name.map(String::from);
+        Ok(())
+    }
+    
+    /// 응답 통계 표시 여부를 토글하고 변경된 값을 반환합니다
+    pub fn toggle_stats(&mut self) -> bool {
+        self.show_stats = !self.show_stats;
+        self.show_stats
+    }
+    
+    pub fn auto_save(&self) -> bool {
+        self.auto_save
+    }
+    
+    /// 이번 실행에서만 세션 자동 저장을 켜고 끕니다 (설정 파일은 바꾸지 않음)
+    pub fn set_auto_save(&mut self, enabled: bool) {
+        self.auto_save = enabled;
+    }
+    
+    /// 추론 과정 펼침 표시 여부를 토글하고 변경된 값을 반환합니다
+    pub fn toggle_reasoning(&mut self) -> bool {
+        self.show_reasoning = !self.show_reasoning;
+        self.show_reasoning
+    }
+    
+    /// 마지막 응답의 추론 과정 (추론 모델이 아니면 None)
+    pub fn last_reasoning(&self) -> Option<&str> {
+        (!self.last_reasoning.is_empty()).then_some(self.last_reasoning.as_str())
     }
     
     pub fn add_context_file(&mut self, file_path: &str) -> Result<()> {
@@ -59,23 +180,102 @@ impl DevAssistant {
         Ok(())
     }
     
+    /// `number`번째(1부터) 메시지의 고정 여부를 바꾸고 그 메시지를 돌려줍니다
+    pub fn set_pinned(&mut self, number: usize, pinned: bool) -> Result<&Message> {
+        let count = self.context.messages.len();
+        let message = number.checked_sub(1)
+            .and_then(|index| self.context.messages.get_mut(index))
+            .with_context(|| match count {
+                0 => "아직 대화 메시지가 없습니다".to_string(),
+                _ => format!("메시지 번호는 1부터 {}까지입니다: {}", count, number),
+            })?;
+        message.pinned = pinned;
+        Ok(message)
+    }
+    
+    /// 고정한 메시지와 번호(1부터)
+    pub fn pinned_messages(&self) -> Vec<(usize, &Message)> {
+        self.context.messages.iter().enumerate()
+            .filter(|(_, m)| m.pinned)
+            .map(|(index, m)| (index + 1, m))
+            .collect()
+    }
+    
+    /// 지금 대화를 보관하고 문맥 묶음으로 작업 영역을 바꿉니다. 보관한 위치를 반환합니다.
+    /// 페르소나 파일이 없어졌으면 페르소나 없이 불러옴
+    pub fn load_bundle(&mut self, bundle: &ContextBundle) -> Result<Option<String>> {
+        let location = match self.context.messages.is_empty() {
+            true => None,
+            false => self.store.archive(&self.context)?,
+        };
+        let project_info = self.context.project_info.take();
+        self.context = AssistantContext { project_info, ..bundle.to_context() };
+        self.apply_mode_generation();
+        if let Some(persona) = bundle.persona.as_deref() {
+            if load_persona(persona).is_err() {
+                self.sink.notice(NoticeLevel::Warning, &format!("페르소나 '{}'을(를) 찾을 수 없어 적용하지 않았습니다", persona));
+                self.context.persona = None;
+            }
+        }
+        Ok(location)
+    }
+    
     pub fn clear_context(&mut self) {
         self.context.messages.clear();
         self.context.current_files.clear();
+        self.context.archived_summary = None;
     }
     
-    pub async fn generate_documentation(&self, target: &str, doc_type: &str) -> Result<String> {
-        let prompt = self.build_doc_prompt(target, doc_type)?;
+    /// 문서를 생성합니다. 템플릿(`--template`, `.ricci/templates/<유형>.md`, `[doc_templates]`, 팀 템플릿)이 있으면
+    /// 기본 프롬프트 대신 템플릿의 구조를 따르도록 요청합니다
+    pub async fn generate_documentation(&self, target: &str, doc_type: &str, template: Option<&Path>) -> Result<String> {
+        let prompt = self.build_doc_prompt(target, doc_type, template)?;
         self.client.query(&prompt).await
     }
     
-    fn build_doc_prompt(&self, target: &str, doc_type: &str) -> Result<String> {
-        let content = if Path::new(target).exists() {
-            std::fs::read_to_string(target)?
+    /// 기존 README를 병합합니다. 배지, 라이선스, `<!-- ricci:keep -->` 섹션과
+    /// `sections`에 없는 섹션은 원문 그대로 두고 나머지만 다시 작성합니다.
+    /// 모델이 빠뜨려 끝에 덧붙인 보존 구역의 개수를 함께 반환합니다
+    pub async fn merge_readme(&self, dir: &Path, existing: &str, sections: &[String]) -> Result<(String, usize)> {
+        let masked = mask_readme(existing, sections);
+        let prompt = format!(
+            "다음 프로젝트의 기존 README.md를 현재 코드에 맞게 갱신해주세요.\n\n\
+            규칙:\n\
+            1. `<!-- ricci:preserved N -->` 주석은 사용자가 관리하는 내용의 자리이므로 그대로, 같은 위치에 두세요\n\
+            2. 그 외 섹션은 프로젝트 정보에 맞게 고쳐 쓰되 기존 제목, 순서, 문서 언어를 유지하세요\n\
+            3. 새 섹션은 꼭 필요할 때만 추가하세요\n\n\
+            기존 README:\n{}\n\n프로젝트 정보:\n{}\n\n\
+            설명 없이 ```markdown 코드 블록 하나로 전체 README만 응답해주세요.",
+            fence_file("README.md", "markdown", &masked.text), project_summary(dir)
+        );
+        let response = self.client.query(&prompt).await?;
+        let generated = extract_markdown_block(&strip_file_blocks(&response));
+        let (merged, missing) = restore_readme(&generated, &masked.preserved);
+        Ok((merged, missing.len()))
+    }
+    
+    fn build_doc_prompt(&self, target: &str, doc_type: &str, template: Option<&Path>) -> Result<String> {
+        let content = if Path::new(target).is_dir() {
+            project_summary(Path::new(target))
+        } else if Path::new(target).exists() {
+            let language = Path::new(target).extension().and_then(|e| e.to_str()).unwrap_or("");
+            fence_file(target, language, &std::fs::read_to_string(target)?)
         } else {
             target.to_string()
         };
         
+        if let Some(path) = find_doc_template(doc_type, template, &self.config) {
+            let variables = template_variables(target, doc_type, &self.config);
+            let skeleton = load_doc_template(&path, &variables)?;
+            return Ok(format!(
+                "다음 코드/프로젝트에 대한 {} 문서를 아래 템플릿에 맞춰 작성해주세요.\n\
+                템플릿의 섹션 순서, 제목, 배지, 고정 문구는 그대로 두고 안내 문구만 실제 내용으로 채워주세요. \
+                템플릿에 없는 섹션은 추가하지 말고, 문서는 템플릿과 같은 언어로 작성해주세요.\n\n\
+                템플릿:\n```markdown\n{}\n```\n\n대상:\n{}",
+                doc_type, skeleton.trim(), content
+            ));
+        }
+        
         let prompt = match doc_type {
             "api" => format!(
                 "다음 코드에 대한 API 문서를 작성해주세요:\n\n{}\n\n\
@@ -103,11 +303,7 @@ impl DevAssistant {
         println!("{}", "명령어: /clear, /mode [normal|concise|detailed|code], /save [파일명]".dimmed());
         
         let mut rl = Editor::<(), rustyline::history::DefaultHistory>::new()?;
-        let history_path = dirs::data_local_dir()
-            .unwrap_or_else(|| std::path::PathBuf::from("."))
-            .join("ricci")
-            .join("history.txt");
-        
+        let history_path = history_file()?;
         let _ = rl.load_history(&history_path);
         
         loop {
@@ -142,19 +338,29 @@ impl DevAssistant {
                     let mut response = String::new();
                     while let Some(chunk) = stream.recv().await {
                         match chunk {
-                            Ok(text) => {
+                            Ok(StreamEvent::Content(text)) => {
                                 response.push_str(&text);
-                                self.renderer.render_chunk(&text)?;
+                                self.sink.chunk(&text)?;
+                                self.emit(AssistantEvent::TokenChunk { text });
+                            }
+                            Ok(StreamEvent::Reasoning(text)) => {
+                                self.sink.reasoning(&text)?;
+                            }
+                            Ok(StreamEvent::Usage(_)) => {}
+                            Ok(StreamEvent::Truncated) => {
+                                self.sink.notice(NoticeLevel::Warning, "\n[경고] 응답이 max_tokens 한도에서 잘렸습니다.");
                             }
                             Err(e) => {
-                                eprintln!("\n{}: {}", "스트림 오류".red(), e);
+                                self.sink.notice(NoticeLevel::Error, &format!("\n스트림 오류: {}", e));
                                 break;
                             }
                         }
                     }
                     
-                    println!("\n");
+                    self.sink.end()?;
+                    println!();
                     self.add_message("assistant", &response);
+                    self.emit(AssistantEvent::Done { response, truncated: false });
                 }
                 Err(ReadlineError::Interrupted) => {
                     println!("\n{}", "중단됨. 계속하려면 Enter를 누르세요.".yellow());
@@ -171,6 +377,9 @@ impl DevAssistant {
             }
         }
         
+        if let Some(parent) = history_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
         let _ = rl.save_history(&history_path);
         Ok(())
     }
@@ -178,7 +387,7 @@ impl DevAssistant {
     fn handle_command(&mut self, command: &str) -> Result<()> {
         let parts: Vec<&str> = command.split_whitespace().collect();
         
-        match parts.get(0).map(|s| *s) {
+        match parts.first().copied() {
             Some("clear") => {
                 self.clear_context();
                 println!("{}", "대화 기록이 삭제되었습니다.".green());
@@ -199,7 +408,7 @@ impl DevAssistant {
                     self.set_mode(mode);
                     println!("{} {:?}", "모드 변경:".green(), mode);
                 } else {
-                    println!("{} {:?}", "현재 모드:".blue(), self.chat_mode);
+                    println!("{} {:?}", "현재 모드:".blue(), self.context.chat_mode);
                 }
             }
             Some("save") => {
@@ -214,14 +423,39 @@ impl DevAssistant {
         Ok(())
     }
     
+    /// 시스템 프롬프트와 첨부 파일로 이루어진 정적 접두사.
+    /// 매 턴 바이트 단위로 동일해야 제공자의 프롬프트 캐시가 적중합니다.
     fn get_system_prompt(&self) -> String {
-        match self.chat_mode {
-            ChatMode::Normal => "당신은 도움이 되는 개발 어시스턴트입니다. 한국어로 대답해주세요.".to_string(),
-            ChatMode::Concise => "당신은 간결한 어시스턴트입니다. 핵심만 간단명료하게 답변해주세요. 한국어로 대답해주세요.".to_string(),
-            ChatMode::Detailed => "당신은 상세한 어시스턴트입니다. 예제와 함께 종합적인 설명을 제공해주세요. 한국어로 대답해주세요.".to_string(),
-            ChatMode::Code => "당신은 코드 중심 어시스턴트입니다. 코드 예제와 기술적 세부사항을 우선시해주세요. 한국어로 대답해주세요.".to_string(),
-            ChatMode::Planning => "당신은 프로젝트 계획 어시스턴트입니다. 아키텍처, 설계, 계획에 집중해주세요. 한국어로 대답해주세요.".to_string(),
+        let mut prompt = format!("{} {}",
+            self.context.chat_mode.system_prompt(),
+            language_instruction(&self.config.output_preferences.language)
+        );
+        
+        if let Some(persona) = self.context.persona.as_deref().and_then(|p| load_persona(p).ok()) {
+            prompt.push_str("\n\n");
+            prompt.push_str(persona.trim());
+        }
+        
+        if let Some(summary) = &self.context.archived_summary {
+            prompt.push_str("\n\n[보관된 이전 대화 요약]\n");
+            prompt.push_str(summary);
+        }
+        
+        let mut has_files = false;
+        for file in &self.context.current_files {
+            if let Ok(content) = fs::read_to_string(file) {
+                let language = Path::new(file).extension().and_then(|e| e.to_str()).unwrap_or("");
+                prompt.push_str("\n\n");
+                prompt.push_str(&fence_file(file, language, &content));
+                has_files = true;
+            }
+        }
+        if has_files {
+            prompt.push_str("\n\n");
+            prompt.push_str(FILE_CONTENT_RULE);
         }
+        
+        prompt
     }
     
     fn add_message(&mut self, role: &str, content: &str) {
@@ -229,12 +463,14 @@ impl DevAssistant {
             role: role.to_string(),
             content: content.to_string(),
             timestamp: Utc::now(),
+            pinned: false,
         });
     }
     
     pub fn save_conversation(&self, filename: &str) -> Result<()> {
+        crate::permissions::ensure_allowed("대화 내용 저장")?;
         let mut content = String::new();
-        content.push_str(&format!("# 대화 기록\n\n"));
+        content.push_str("# 대화 기록\n\n");
         content.push_str(&format!("생성일: {}\n\n", Utc::now().format("%Y-%m-%d %H:%M:%S")));
         
         for msg in &self.context.messages {
@@ -254,8 +490,19 @@ impl DevAssistant {
         self.client.query(prompt).await
     }
     
-    pub async fn review_code(&self, path: &str, criteria: &str) -> Result<CodeReview> {
-        review_code(&self.client, path, criteria).await
+    pub async fn review_code(&self, path: &str, criteria: &str, lint: bool, no_cache: bool) -> Result<CodeReview> {
+        self.emit_tool("review", path);
+        review_code(&self.client, path, criteria, lint, no_cache).await
+    }
+    
+    pub async fn summarize_reviews(&self, reviews: &[(String, CodeReview)]) -> Result<String> {
+        self.emit_tool("review_summary", &format!("{}개 파일", reviews.len()));
+        summarize_reviews(&self.client, reviews).await
+    }
+    
+    pub async fn triage_security_findings(&self, findings: Vec<SecurityFinding>) -> Result<Vec<TriagedFinding>> {
+        self.emit_tool("security_triage", &format!("{}개 후보", findings.len()));
+        security::triage_findings(&self.client, findings).await
     }
     
     pub async fn apply_code_suggestions(&self, suggestions: Vec<CodeSuggestion>) -> Result<()> {
@@ -264,12 +511,13 @@ impl DevAssistant {
         let changes: Vec<FileChange> = suggestions
             .into_iter()
             .map(|s| FileChange {
+                new_content: format_code(&s.suggested_code, &s.file_path),
                 path: s.file_path,
                 original_content: s.original_code,
-                new_content: s.suggested_code,
                 description: s.reason,
             })
             .collect();
+        self.propose_changes(&changes);
         
         modifier.apply_changes(changes).await?;
         Ok(())
@@ -277,123 +525,340 @@ impl DevAssistant {
     
     pub async fn safe_modify_files(&self, changes: Vec<FileChange>) -> Result<()> {
         let safe_modifier = SafeFileModifier::new(false);
+        self.propose_changes(&changes);
         safe_modifier.modify_with_backup(changes).await
     }
     
+    /// 확인 단계에 들어가기 전 변경 제안을 `FileChangeProposed` 이벤트로 알립니다
+    pub fn propose_changes(&self, changes: &[FileChange]) {
+        for change in changes {
+            self.emit(AssistantEvent::FileChangeProposed {
+                path: change.path.clone(),
+                description: change.description.clone(),
+            });
+        }
+    }
+    
     async fn analyze_project(&self, path: &str) -> Result<ProjectInfo> {
+        self.emit_tool("analyze_project", path);
+        // 언어와 프레임워크는 매니페스트로 판별한 값을 우선하고, 모델에게도 알려줌
+        let detected = detect_project(Path::new(path));
+        let hint = detected.as_ref()
+            .map(|p| format!("\n매니페스트({})로 판별한 유형: {}", p.manifest, p))
+            .unwrap_or_default();
         let prompt = format!(
-            "다음 프로젝트 구조를 분석하고 주요 정보를 추출해주세요:\n{}\n\n\
+            "다음 프로젝트 구조를 분석하고 주요 정보를 추출해주세요:\n{}{}\n\n\
             JSON 형식으로 응답해주세요: {{\"name\": \"\", \"language\": \"\", \"framework\": \"\", \"dependencies\": [], \"structure\": \"\"}}",
-            path
+            path, hint
         );
         
         let response = self.client.query(&prompt).await?;
-        let info: ProjectInfo = serde_json::from_str(&response)
+        let mut info: ProjectInfo = serde_json::from_str(&response)
             .context("프로젝트 정보 파싱 실패")?;
+        if let Some(detected) = detected {
+            info.language = detected.language;
+            info.framework = detected.framework.or(info.framework);
+        }
         
         Ok(info)
     }
     
+    /// 질문을 대화에 추가하고 스트리밍으로 받은 응답을 돌려줍니다 (잘린 응답은 설정에 따라 이어받음)
+    pub async fn ask(&mut self, query: &str) -> Result<String> {
+        self.stream_response(query).await?;
+        Ok(self.context.messages.last().map(|m| m.content.clone()).unwrap_or_default())
+    }
+    
     pub async fn stream_response(&mut self, query: &str) -> Result<()> {
         self.add_message("user", query);
-        let system_prompt = self.get_system_prompt();
+        let messages = self.context.messages.clone();
         
-        match self.client.stream_chat(&system_prompt, &self.context.messages).await {
-            Ok(mut stream) => {
-                let mut response = String::new();
-                let mut has_content = false;
-                
-                while let Some(chunk) = stream.recv().await {
-                    match chunk {
-                        Ok(text) => {
-                            has_content = true;
-                            response.push_str(&text);
-                            self.renderer.render_chunk(&text)?;
-                        }
-                        Err(e) => {
-                            eprintln!("\n{}: {}", "스트림 오류".red(), e);
-                            break;
-                        }
-                    }
-                }
-                
+        match self.stream_turn(&messages).await {
+            Ok((mut response, truncated)) => {
                 // 응답이 있든 없든 저장
-                if !has_content {
+                if response.is_empty() {
                     response = "응답을 받지 못했습니다.".to_string();
-                    println!("\n{}", response.yellow());
+                    self.sink.notice(NoticeLevel::Warning, &response);
                 }
                 
                 // 디버그 로그
-                println!("\n{} 응답 길이: {} 문자", "[디버그]".dimmed(), response.len());
+                self.sink.notice(NoticeLevel::Info, &format!("[디버그] 응답 길이: {} 문자", response.len()));
                 
                 self.add_message("assistant", &response);
+                self.last_response_truncated = truncated;
+                run_hooks(HookEvent::Response, serde_json::json!({
+                    "model": self.client.model(),
+                    "prompt": query,
+                    "response": response,
+                    "truncated": truncated,
+                }));
                 
                 // 대화 저장 확인
-                println!("{} 현재 대화 수: {} (user: {}, assistant: {})", 
-                    "[디버그]".dimmed(), 
+                self.sink.notice(NoticeLevel::Info, &format!(
+                    "[디버그] 현재 대화 수: {} (user: {}, assistant: {})",
                     self.context.messages.len(),
                     self.context.messages.iter().filter(|m| m.role == "user").count(),
                     self.context.messages.iter().filter(|m| m.role == "assistant").count()
-                );
+                ));
+                
+                if truncated {
+                    if self.config.output_preferences.auto_continue {
+                        self.continue_truncated().await?;
+                    } else {
+                        self.sink.notice(NoticeLevel::Warning, "응답이 잘렸습니다. /continue 로 이어서 받을 수 있습니다.");
+                    }
+                }
+                self.emit_done();
                 
                 Ok(())
             }
             Err(e) => {
-                eprintln!("\n{}: {}", "API 오류".red(), e);
+                self.sink.notice(NoticeLevel::Error, &format!("API 오류: {}", e));
                 let error_msg = format!("오류가 발생했습니다: {}", e);
                 self.add_message("assistant", &error_msg);
+                self.last_response_truncated = false;
+                self.emit_done();
                 Err(e)
             }
         }
     }
     
+    /// 잘린 마지막 응답을 이어서 요청하고 기존 응답 뒤에 붙입니다.
+    /// 이어받을 응답이 없으면 false를 반환합니다.
+    pub async fn continue_response(&mut self) -> Result<bool> {
+        let continued = self.continue_truncated().await?;
+        if continued {
+            self.emit_done();
+        }
+        Ok(continued)
+    }
+    
+    /// 마지막 응답 전체로 `Done` 이벤트를 보냅니다
+    fn emit_done(&self) {
+        let response = self.context.messages.last().map(|m| m.content.clone()).unwrap_or_default();
+        self.emit(AssistantEvent::Done { response, truncated: self.last_response_truncated });
+    }
+    
+    async fn continue_truncated(&mut self) -> Result<bool> {
+        let last_is_assistant = self.context.messages.last().is_some_and(|m| m.role == "assistant");
+        if !self.last_response_truncated || !last_is_assistant {
+            return Ok(false);
+        }
+        
+        for _ in 0..MAX_CONTINUATIONS {
+            // 이어쓰기 요청은 대화 기록에 남기지 않음
+            let mut messages = self.context.messages.clone();
+            messages.push(Message {
+                role: "user".to_string(),
+                content: CONTINUE_PROMPT.to_string(),
+                timestamp: Utc::now(),
+                pinned: false,
+            });
+            
+            let (piece, truncated) = self.stream_turn(&messages).await?;
+            if let Some(last) = self.context.messages.last_mut() {
+                last.content.push_str(&piece);
+            }
+            self.last_response_truncated = truncated;
+            
+            if !truncated || !self.config.output_preferences.auto_continue {
+                break;
+            }
+        }
+        
+        if self.last_response_truncated {
+            self.sink.notice(NoticeLevel::Warning, "응답이 아직 끝나지 않았습니다. /continue 로 계속 받을 수 있습니다.");
+        }
+        
+        Ok(true)
+    }
+    
+    /// 주어진 메시지로 한 번 스트리밍하며 출력하고 (응답, 잘림 여부)를 반환합니다
+    async fn stream_turn(&mut self, messages: &[Message]) -> Result<(String, bool)> {
+        let system_prompt = self.get_system_prompt();
+        
+        let prompt_tokens = estimate_tokens(&system_prompt)
+            + messages.iter().map(|m| estimate_tokens(&m.content)).sum::<usize>();
+        self.warn_if_context_overrun(prompt_tokens);
+        let mut recorder = StatsRecorder::start(&self.config.model_preferences.default_model, prompt_tokens);
+        
+        let mut stream = self.client.stream_chat(&system_prompt, messages).await?;
+        let mut response = String::new();
+        let mut truncated = false;
+        let mut stream_error = None;
+        let mut reasoning_open = false;
+        self.last_reasoning.clear();
+        
+        while let Some(chunk) = stream.recv().await {
+            match chunk {
+                Ok(StreamEvent::Content(text)) => {
+                    recorder.record_chunk();
+                    if reasoning_open {
+                        reasoning_open = false;
+                        self.close_reasoning();
+                    }
+                    response.push_str(&text);
+                    self.sink.chunk(&text)?;
+                    self.emit(AssistantEvent::TokenChunk { text });
+                }
+                Ok(StreamEvent::Reasoning(text)) => {
+                    recorder.record_chunk();
+                    if !reasoning_open {
+                        reasoning_open = true;
+                        self.sink.notice(NoticeLevel::Info, &plain("💭 생각 중..."));
+                    }
+                    if self.show_reasoning {
+                        self.sink.reasoning(&text)?;
+                    }
+                    self.last_reasoning.push_str(&text);
+                }
+                Ok(StreamEvent::Usage(usage)) => {
+                    recorder.record_usage(usage);
+                }
+                Ok(StreamEvent::Truncated) => {
+                    truncated = true;
+                }
+                Err(e) => {
+                    stream_error = Some(e);
+                    break;
+                }
+            }
+        }
+        
+        if reasoning_open {
+            self.close_reasoning();
+        }
+        self.sink.end()?;
+        
+        if truncated {
+            self.sink.notice(NoticeLevel::Warning, "[경고] 응답이 max_tokens 한도에서 잘렸습니다. max_tokens를 늘리거나 \"auto\"로 설정하세요.");
+        }
+        if let Some(e) = stream_error {
+            self.sink.notice(NoticeLevel::Error, &format!("스트림 오류: {}", e));
+        }
+        if self.show_stats {
+            self.sink.notice(NoticeLevel::Info, &recorder.finish().footer_line());
+        }
+        
+        Ok((response, truncated))
+    }
+    
+    /// 추론 구간이 끝났음을 표시합니다. 접힌 상태면 한 줄 요약만 출력
+    fn close_reasoning(&self) {
+        if self.show_reasoning {
+            self.sink.notice(NoticeLevel::Info, &format!("\n{}\n", plain("💭 생각 끝")));
+        } else {
+            self.sink.notice(NoticeLevel::Info, &plain(&format!(
+                "💭 생각 완료 (~{} 토큰, /thinking 으로 펼쳐보기)",
+                estimate_tokens(&self.last_reasoning)
+            )));
+        }
+    }
+    
+    /// 모델 컨텍스트 한도를 넘길 것 같으면 요청 전에 경고합니다
+    fn warn_if_context_overrun(&self, prompt_tokens: usize) {
+        let model = &self.config.model_preferences.default_model;
+        let Some(caps) = capabilities(model) else {
+            return;
+        };
+        
+        let max_tokens = self.config.model_preferences.max_tokens.resolve(model, prompt_tokens);
+        let needed = prompt_tokens + max_tokens as usize;
+        if needed > caps.context_window as usize {
+            self.sink.notice(NoticeLevel::Warning, &format!(
+                "[경고] 예상 사용량 ~{} 토큰이 {} 컨텍스트({})를 넘습니다. /new 로 대화를 정리하세요.",
+                needed, model, caps.context_window
+            ));
+        } else if summarization_threshold(model).is_some_and(|t| prompt_tokens > t) {
+            self.sink.notice(NoticeLevel::Info, &format!(
+                "[안내] 대화가 컨텍스트의 {}%를 사용 중입니다. /summary 후 /new 로 정리하는 것을 권장합니다.",
+                prompt_tokens * 100 / caps.context_window as usize
+            ));
+        }
+    }
+    
     pub async fn load_project_context(&mut self, path: &str) -> Result<()> {
         let project_info = self.analyze_project(path).await?;
         self.context.project_info = Some(project_info);
         Ok(())
     }
     
-    pub async fn save_session(&self) -> Result<()> {
-        let session_path = self.get_session_path()?;
-        let session_data = serde_json::to_string_pretty(&self.context)?;
-        fs::write(&session_path, session_data)?;
+    pub async fn save_session(&mut self) -> Result<()> {
+        self.archive_old_messages().await?;
+        self.store.save(&self.context)?;
+        self.sink.notice(NoticeLevel::Info, &format!("[INFO] 세션이 저장되었습니다: {}", self.store.location()));
         
-        println!("{} 세션이 저장되었습니다: {}", 
-            "[INFO]".dimmed(), 
-            session_path.display()
-        );
+        // 보관함 노트는 부가 기능이므로 실패해도 세션 저장은 성공으로 처리
+        if let Some(vault) = &self.config.output_preferences.vault_dir {
+            if let Err(e) = write_vault_note(&self.context, vault, &self.config.model_preferences.default_model) {
+                self.sink.notice(NoticeLevel::Warning, &format!("보관함 노트 저장 실패: {}", e));
+            }
+        }
         
         Ok(())
     }
     
-    pub async fn load_session(&mut self) -> Result<bool> {
-        let session_path = self.get_session_path()?;
+    /// 세션이 `max_session_messages`나 `max_session_tokens`를 넘으면 오래된 메시지를 저장소에 보관하고
+    /// 요약(`archived_summary`)으로 바꿉니다. 요약은 모델로 만들고, 실패하면 질문 목록으로 대신합니다
+    async fn archive_old_messages(&mut self) -> Result<()> {
+        let prefs = &self.config.output_preferences;
+        let Some(split) = archive_split(&self.context.messages, prefs.max_session_messages, prefs.max_session_tokens) else {
+            return Ok(());
+        };
         
-        if session_path.exists() {
-            let session_data = fs::read_to_string(&session_path)?;
-            if let Ok(loaded_context) = serde_json::from_str::<AssistantContext>(&session_data) {
-                self.context = loaded_context;
-                
-                println!("{} 이전 세션을 로드했습니다 (메시지 {}개)", 
-                    "[INFO]".dimmed(),
-                    self.context.messages.len()
-                );
-                
-                return Ok(true);
-            }
+        // 고정한 메시지는 보관하지 않고 남김. 보관에 성공한 뒤에만 세션에서 덜어냄
+        let (pinned, unpinned): (Vec<Message>, Vec<Message>) = self.context.messages[..split].iter()
+            .cloned()
+            .partition(|m| m.pinned);
+        if unpinned.is_empty() {
+            return Ok(());
         }
+        let archived = AssistantContext {
+            messages: unpinned,
+            ..self.context.clone()
+        };
+        let location = self.store.archive(&archived)?;
         
-        Ok(false)
+        let previous = self.context.archived_summary.as_deref();
+        let summary = match self.client.query(&summary_prompt(previous, &archived.messages)).await {
+            Ok(summary) if !summary.trim().is_empty() => summary.trim().to_string(),
+            _ => outline_summary(previous, &archived.messages),
+        };
+        self.context.archived_summary = Some(summary);
+        self.context.messages.splice(..split, pinned);
+        
+        let count = archived.messages.len();
+        self.sink.notice(NoticeLevel::Info, &match location {
+            Some(location) => format!("[INFO] 오래된 메시지 {}개를 요약하고 보관했습니다: {}", count, location),
+            None => format!("[INFO] 오래된 메시지 {}개를 요약으로 바꿨습니다", count),
+        });
+        Ok(())
+    }
+    
+    /// 자동 저장이 켜져 있으면 세션을 저장합니다. 저장했으면 true
+    pub async fn auto_save_session(&mut self) -> Result<bool> {
+        if !self.auto_save {
+            return Ok(false);
+        }
+        self.save_session().await?;
+        Ok(true)
     }
     
-    fn get_session_path(&self) -> Result<PathBuf> {
-        let home = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("홈 디렉토리를 찾을 수 없습니다"))?;
+    pub async fn load_session(&mut self) -> Result<bool> {
+        let Some(loaded_context) = self.store.load()? else {
+            return Ok(false);
+        };
+        self.context = loaded_context;
+        self.apply_mode_generation();
         
-        let session_dir = home.join(".ricci").join("sessions");
-        fs::create_dir_all(&session_dir)?;
+        self.sink.notice(NoticeLevel::Info, &format!(
+            "[INFO] 이전 세션을 로드했습니다 (메시지 {}개, 모드 {}{})",
+            self.context.messages.len(),
+            self.context.chat_mode.label(),
+            self.context.persona.as_deref().map(|p| format!(", 페르소나 {p}")).unwrap_or_default()
+        ));
         
-        Ok(session_dir.join("current_session.json"))
+        Ok(true)
     }
     
     pub fn get_context_summary(&self) -> String {
@@ -406,7 +871,25 @@ impl DevAssistant {
             }
         }
         
+        let (temperature, max_tokens) = self.config.mode_generation(self.context.chat_mode.label());
+        summary.push_str(&format!("대화 모드: {} (temperature {}, max_tokens {})\n",
+            self.context.chat_mode.label(), temperature, max_tokens));
         summary.push_str(&format!("대화 기록: {} 개\n", self.context.messages.len()));
+        if !self.context.current_files.is_empty() {
+            summary.push_str(&format!("첨부 파일: {} 개\n", self.context.current_files.len()));
+        }
+        let pinned = self.context.messages.iter().filter(|m| m.pinned).count();
+        if pinned > 0 {
+            summary.push_str(&format!("고정한 메시지: {} 개 (/pins)\n", pinned));
+        }
+        summary.push_str(&format!("세션 자동 저장: {}\n", if self.auto_save { "켜짐" } else { "꺼짐" }));
+        if activity::has_last_command() {
+            summary.push_str("최근 셸 명령어: 있음 (#lastcmd)\n");
+        }
+        let files = activity::recent_files();
+        if !files.is_empty() {
+            summary.push_str(&format!("최근 수정 파일: {} (#lastdiff)\n", files.join(", ")));
+        }
         summary
     }
     
@@ -414,14 +897,14 @@ impl DevAssistant {
         let mut content = String::new();
         
         // 디버그: 현재 메시지 수 출력
-        println!("{} 저장된 메시지 수: {}", "[디버그]".dimmed(), self.context.messages.len());
+        self.sink.notice(NoticeLevel::Info, &format!("[디버그] 저장된 메시지 수: {}", self.context.messages.len()));
         for (idx, msg) in self.context.messages.iter().enumerate() {
-            println!("{} 메시지 {}: {} - {} 문자", 
-                "[디버그]".dimmed(), 
-                idx + 1, 
-                msg.role, 
+            self.sink.notice(NoticeLevel::Info, &format!(
+                "[디버그] 메시지 {}: {} - {} 문자",
+                idx + 1,
+                msg.role,
                 msg.content.len()
-            );
+            ));
         }
         
         // 대화 내용을 분석하여 주요 작업 추출
@@ -429,7 +912,7 @@ impl DevAssistant {
         
         match format {
             "markdown" => {
-                content.push_str(&format!("# 작업 계획서\n"));
+                content.push_str("# 작업 계획서\n");
                 content.push_str(&format!("**생성일**: {}\n", Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
                 content.push_str(&format!("**대화 메시지 수**: {}\n\n", self.context.messages.len()));
                 
@@ -441,7 +924,7 @@ impl DevAssistant {
                     if let Some(ref fw) = info.framework {
                         content.push_str(&format!("- **프레임워크**: {}\n", fw));
                     }
-                    content.push_str("\n");
+                    content.push('\n');
                 }
                 
                 // 논의된 주요 작업
@@ -577,4 +1060,34 @@ struct TaskItem {
     title: String,
     description: String,
     status: String,
-} 
\ No newline at end of file
+}
+
+/// 공유 페르소나 디렉토리 (`ricci sync`로 채워짐)
+pub fn personas_dir() -> Result<PathBuf> {
+    Ok(crate::paths::shared_dir()?.join("personas"))
+}
+
+/// 사용 가능한 페르소나 이름 목록
+pub fn list_personas() -> Vec<String> {
+    let Ok(dir) = personas_dir() else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = fs::read_dir(dir).into_iter().flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "md" || e == "txt"))
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+fn load_persona(name: &str) -> Result<String> {
+    let dir = personas_dir()?;
+    ["md", "txt"].iter()
+        .map(|ext| dir.join(format!("{name}.{ext}")))
+        .find(|path| path.exists())
+        .map(fs::read_to_string)
+        .transpose()?
+        .with_context(|| format!("페르소나를 찾을 수 없습니다: {} ({})", name, dir.display()))
+}
@@ -0,0 +1,166 @@
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analyzer::{language_registry, IGNORED_DIRS};
+use super::untrusted::fence_file;
+
+/// 사용자가 직접 관리하는 섹션 표시. 이 주석이 들어 있는 섹션은 그대로 유지됩니다
+pub const KEEP_MARKER: &str = "<!-- ricci:keep -->";
+const LICENSE_HEADINGS: &[&str] = &["license", "licence", "라이선스", "라이센스"];
+const PROJECT_MANIFESTS: &[&str] = &["Cargo.toml", "package.json", "pyproject.toml", "go.mod", "pom.xml", "build.gradle"];
+const MAX_MANIFEST_CHARS: usize = 4_000;
+const MAX_LISTED_FILES: usize = 200;
+
+/// README의 제목 단위 구역 (첫 제목 앞부분은 `heading`이 None)
+#[derive(Debug, Clone)]
+struct Section {
+    heading: Option<String>,
+    text: String,
+}
+
+/// 모델에 보낼 README와 자리표시자로 바꾼 원래 내용
+#[derive(Debug)]
+pub struct MaskedReadme {
+    pub text: String,
+    pub preserved: Vec<String>,
+}
+
+fn placeholder(index: usize) -> String {
+    format!("<!-- ricci:preserved {} -->", index)
+}
+
+/// 보존할 부분(배지, 라이선스, `<!-- ricci:keep -->` 섹션, `sections`에 없는 섹션)을 자리표시자로 바꿉니다.
+/// `sections`가 비어 있으면 보존 대상이 아닌 모든 섹션을 다시 생성합니다
+pub fn mask_readme(content: &str, sections: &[String]) -> MaskedReadme {
+    let mut masked = MaskedReadme { text: String::new(), preserved: Vec::new() };
+    let preserve = |masked: &mut MaskedReadme, text: String| {
+        masked.text.push_str(&placeholder(masked.preserved.len()));
+        masked.text.push('\n');
+        masked.preserved.push(text);
+    };
+
+    for section in split_sections(content) {
+        if let Some(heading) = &section.heading {
+            let title = heading.trim_start_matches('#').trim().to_lowercase();
+            let is_license = LICENSE_HEADINGS.iter().any(|h| title.contains(h));
+            // 문서 제목(#)은 --sections와 관계없이 다시 생성 대상 (배지는 아래에서 보존)
+            let not_requested = !sections.is_empty()
+                && !heading.starts_with("# ")
+                && !sections.iter().any(|s| title.contains(&s.trim().to_lowercase()));
+            if is_license || section.text.contains(KEEP_MARKER) || not_requested {
+                preserve(&mut masked, section.text);
+                continue;
+            }
+        }
+
+        // 연속된 배지 줄은 한 덩어리로 보존
+        let mut badges = String::new();
+        for line in section.text.split_inclusive('\n') {
+            if is_badge_line(line) {
+                badges.push_str(line);
+                continue;
+            }
+            if !badges.is_empty() {
+                preserve(&mut masked, std::mem::take(&mut badges));
+            }
+            masked.text.push_str(line);
+        }
+        if !badges.is_empty() {
+            preserve(&mut masked, badges);
+        }
+    }
+    masked
+}
+
+/// 생성된 README의 자리표시자를 원래 내용으로 되돌립니다.
+/// 모델이 빠뜨린 자리표시자는 끝에 덧붙이고 그 번호를 반환합니다
+pub fn restore_readme(generated: &str, preserved: &[String]) -> (String, Vec<usize>) {
+    let mut result = generated.trim_end().to_string();
+    result.push('\n');
+    let mut missing = Vec::new();
+
+    for (index, original) in preserved.iter().enumerate() {
+        let marker = placeholder(index);
+        match result.find(&marker) {
+            Some(start) => {
+                // 자리표시자 뒤의 줄바꿈은 원래 내용에 포함되어 있음
+                let end = start + marker.len();
+                let end = if result[end..].starts_with('\n') { end + 1 } else { end };
+                result.replace_range(start..end, original);
+            }
+            None => missing.push(index),
+        }
+    }
+    for &index in &missing {
+        if !result.ends_with("\n\n") {
+            result.push('\n');
+        }
+        result.push_str(&preserved[index]);
+    }
+    (result, missing)
+}
+
+/// 응답에서 ```markdown 블록을 꺼냅니다. README 안에도 코드 블록이 있으므로 마지막 닫는 펜스까지 사용합니다
+pub fn extract_markdown_block(response: &str) -> String {
+    let trimmed = response.trim();
+    let Some(start) = ["```markdown\n", "```md\n"].iter().find_map(|fence| {
+        trimmed.find(fence).map(|i| i + fence.len())
+    }) else {
+        return trimmed.to_string();
+    };
+    let body = &trimmed[start..];
+    match body.rfind("```") {
+        Some(end) => body[..end].trim_end().to_string(),
+        None => body.trim_end().to_string(),
+    }
+}
+
+/// `#`, `##` 제목을 기준으로 나눕니다. `###` 이하는 상위 섹션에 포함되고,
+/// 코드 블록 안의 `#`은 제목으로 보지 않습니다
+fn split_sections(content: &str) -> Vec<Section> {
+    let mut sections = vec![Section { heading: None, text: String::new() }];
+    let mut in_fence = false;
+
+    for line in content.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let level = line.chars().take_while(|&c| c == '#').count();
+        if !in_fence && (1..=2).contains(&level) && line[level..].starts_with(' ') {
+            sections.push(Section { heading: Some(line.trim_end().to_string()), text: String::new() });
+        }
+        if let Some(current) = sections.last_mut() {
+            current.text.push_str(line);
+        }
+    }
+    sections.retain(|s| s.heading.is_some() || !s.text.is_empty());
+    sections
+}
+
+/// 배지 이미지 링크로만 이루어진 줄인지 (`[![CI](…)](…)`, `![badge](https://img.shields.io/…)`)
+fn is_badge_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    (trimmed.starts_with("[![") || trimmed.starts_with("![") || trimmed.starts_with("<a ") || trimmed.starts_with("<img "))
+        && (trimmed.contains("badge") || trimmed.contains("shields.io") || trimmed.starts_with("[!["))
+}
+
+/// 디렉토리 대상 문서 생성에 사용할 프로젝트 요약 (매니페스트와 소스 파일 목록)
+pub fn project_summary(dir: &Path) -> String {
+    let mut summary = String::new();
+    for manifest in PROJECT_MANIFESTS {
+        if let Ok(content) = std::fs::read_to_string(dir.join(manifest)) {
+            let content: String = content.chars().take(MAX_MANIFEST_CHARS).collect();
+            summary.push_str(&format!("{}\n\n", fence_file(manifest, "", &content)));
+        }
+    }
+
+    let files: Vec<String> = WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|entry| !IGNORED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && language_registry().is_source_path(entry.path()))
+        .take(MAX_LISTED_FILES)
+        .map(|entry| entry.path().strip_prefix(dir).unwrap_or(entry.path()).to_string_lossy().to_string())
+        .collect();
+    summary.push_str(&format!("### 소스 파일\n{}\n", files.join("\n")));
+    summary
+}
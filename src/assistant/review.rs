@@ -1,23 +1,350 @@
 use anyhow::Result;
 use std::path::Path;
 use std::fs;
-use crate::assistant::types::{CodeReview, IssueSeverity};
+use crate::assistant::types::{CodeReview, IssueCategory, IssueSeverity, ReviewIssue};
 use crate::api::OpenAIClient;
+use crate::assistant::infra::detect_infra_kind;
+use crate::assistant::untrusted::fence_file;
+use crate::assistant::review_cache::{content_hash, load_review, store_review, ReviewKey};
+use crate::assistant::review_history::{history_key, record_score, ScoreRecord};
+use crate::assistant::lint::{describe_issues, run_linters};
 use colored::*;
+use futures::future::join_all;
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+use std::time::Instant;
+use crate::renderer::{is_plain, plain};
+
+/// `--criteria all`을 나눠 동시에 검토할 기준
+const REVIEW_CRITERIA: [&str; 3] = ["security", "performance", "style"];
+/// 저장소 요약에 넣을 파일별 결과의 최대 길이와 파일당 문제 수
+const MAX_SUMMARY_INPUT_CHARS: usize = 40_000;
+const MAX_SUMMARY_ISSUES: usize = 8;
+
+/// 스트리밍 리뷰의 기준별 진행 상황. 터미널이면 한 줄을 계속 고쳐 쓰고, 기준이 끝날 때마다 한 줄씩 남깁니다.
+/// 모델 응답은 리뷰 JSON이라 받은 글자를 출력 대상(`OutputSink`)으로 그대로 흘리지 않고, 진행 상황만 표준 오류로
+/// 보여준 뒤 파싱한 결과를 한 번에 출력합니다 (`--output-format json`의 표준 출력도 결과 하나만 남음)
+struct ReviewProgress {
+    entries: Mutex<Vec<ProgressEntry>>,
+    started: Instant,
+    live: bool,
+}
+
+struct ProgressEntry {
+    criterion: String,
+    received: String,
+    done: bool,
+}
+
+impl ProgressEntry {
+    /// 지금까지 받은 JSON에서 시작된 문제 수
+    fn issues(&self) -> usize {
+        self.received.matches("\"severity\"").count()
+    }
+    
+    fn status(&self) -> String {
+        let state = if self.done { "완료" } else { "수신 중" };
+        format!("{} {} ({:.1}KB, 문제 {}건)", self.criterion, state, self.received.len() as f32 / 1024.0, self.issues())
+    }
+}
+
+impl ReviewProgress {
+    fn new(criteria: &[String]) -> Self {
+        let entries = criteria.iter()
+            .map(|criterion| ProgressEntry { criterion: criterion.clone(), received: String::new(), done: false })
+            .collect();
+        Self {
+            entries: Mutex::new(entries),
+            started: Instant::now(),
+            live: std::io::stderr().is_terminal() && !is_plain(),
+        }
+    }
+    
+    fn receive(&self, index: usize, chunk: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries[index].received.push_str(chunk);
+        self.redraw(&entries);
+    }
+    
+    fn finish(&self, index: usize) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries[index].done = true;
+        if self.live {
+            eprint!("\r\x1b[2K");
+        }
+        eprintln!("  {} {}", plain("✓").green(), entries[index].status().dimmed());
+        self.redraw(&entries);
+    }
+    
+    fn redraw(&self, entries: &[ProgressEntry]) {
+        if !self.live {
+            return;
+        }
+        let pending: Vec<String> = entries.iter().filter(|e| !e.done).map(ProgressEntry::status).collect();
+        if pending.is_empty() {
+            return;
+        }
+        eprint!(
+            "\r\x1b[2K{}",
+            format!("  ⏳ {}초 · {}", self.started.elapsed().as_secs(), pending.join(" · ")).dimmed()
+        );
+        let _ = std::io::stderr().flush();
+    }
+    
+    /// 실패한 기준이 남긴 진행 줄을 지웁니다
+    fn clear(&self) {
+        if self.live {
+            eprint!("\r\x1b[2K");
+            let _ = std::io::stderr().flush();
+        }
+    }
+}
+
+/// 범위 리뷰 시 앞뒤로 함께 보여줄 문맥 줄 수
+const RANGE_CONTEXT_LINES: usize = 10;
+
+/// 검토할 줄 범위 (1부터 시작, 끝 포함)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl LineRange {
+    pub fn contains(&self, line: usize) -> bool {
+        (self.start..=self.end).contains(&line)
+    }
+}
+
+/// `src/lib.rs:100-250` 또는 `src/lib.rs:42` 형태를 경로와 줄 범위로 나눕니다.
+/// 그런 이름의 파일이 실제로 있으면 범위로 해석하지 않습니다.
+pub fn parse_review_target(spec: &str) -> Result<(&str, Option<LineRange>)> {
+    if Path::new(spec).exists() {
+        return Ok((spec, None));
+    }
+    let Some((path, range)) = spec.rsplit_once(':') else {
+        return Ok((spec, None));
+    };
+    if range.is_empty() || !range.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return Ok((spec, None));
+    }
+    
+    let (start, end) = range.split_once('-').unwrap_or((range, range));
+    let start: usize = start.parse().map_err(|_| anyhow::anyhow!("잘못된 줄 범위: {}", range))?;
+    let end: usize = end.parse().map_err(|_| anyhow::anyhow!("잘못된 줄 범위: {}", range))?;
+    if start == 0 || end < start {
+        return Err(anyhow::anyhow!("잘못된 줄 범위: {} (시작은 1 이상, 끝은 시작 이상)", range));
+    }
+    Ok((path, Some(LineRange { start, end })))
+}
+
+/// 범위와 앞뒤 문맥만 줄 번호를 붙여 잘라냅니다. 범위 밖 줄은 `|` 대신 `:`로 구분
+fn excerpt(content: &str, range: LineRange) -> (String, LineRange) {
+    let total = content.lines().count();
+    let context = LineRange {
+        start: range.start.saturating_sub(RANGE_CONTEXT_LINES).max(1),
+        end: (range.end + RANGE_CONTEXT_LINES).min(total),
+    };
+    let width = context.end.to_string().len();
+    
+    let text = content.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .filter(|(n, _)| context.contains(*n))
+        .map(|(n, line)| {
+            let marker = if range.contains(n) { '|' } else { ':' };
+            format!("{n:>width$} {marker} {line}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    (text, context)
+}
+
+/// 리뷰 이슈 위치(`파일:라인`)의 줄 번호
+fn issue_line(location: &str) -> Option<usize> {
+    location.rsplit(':').find_map(|part| part.trim().parse().ok())
+}
 
 pub async fn review_code(
     client: &OpenAIClient,
-    path: &str,
-    criteria: &str
+    target: &str,
+    criteria: &str,
+    lint: bool,
+    no_cache: bool,
 ) -> Result<CodeReview> {
-    let code_content = if Path::new(path).is_file() {
+    if lint {
+        crate::permissions::ensure_allowed("린터 실행")?;
+    }
+    let (path, range) = parse_review_target(target)?;
+    let file_content = if Path::new(path).is_file() {
         fs::read_to_string(path)?
     } else {
         return Err(anyhow::anyhow!("경로가 파일이 아닙니다: {}", path));
     };
     
-    let prompt = format!(
-        "다음 코드를 검토하고 {} 기준으로 평가해주세요:\n\n```\n{}\n```\n\n\
+    let total_lines = file_content.lines().count();
+    let (code_content, range_note) = match range {
+        Some(range) if range.start > total_lines => {
+            return Err(anyhow::anyhow!("{}은(는) {}줄까지만 있습니다", path, total_lines));
+        }
+        Some(range) => {
+            let range = LineRange { end: range.end.min(total_lines), ..range };
+            let (text, context) = excerpt(&file_content, range);
+            let note = format!(
+                "이 코드는 {} 파일의 {}-{}행 발췌입니다 (각 줄 앞은 실제 줄 번호). \
+                `|` 표시 줄({}-{}행)만 검토하고, `:` 표시 줄({}-{}행)은 참고용 문맥으로만 사용하세요. \
+                location에는 실제 줄 번호를 사용해주세요.\n\n",
+                path, context.start, context.end, range.start, range.end, context.start, context.end
+            );
+            (text, note)
+        }
+        None => (file_content, String::new()),
+    };
+    
+    // 범위 리뷰면 범위 안의 린터 결과만 사용
+    let lint_issues: Vec<_> = if lint { run_linters(Path::new(path)) } else { Vec::new() }
+        .into_iter()
+        .filter(|issue| match (range, issue_line(&issue.location)) {
+            (Some(range), Some(line)) => range.contains(line),
+            _ => true,
+        })
+        .collect();
+    
+    // 린터가 이미 찾은 문제는 모델이 다시 다루지 않도록 알려줌
+    let lint_note = if lint_issues.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "다음 문제는 린터가 이미 발견했습니다. 이 문제들은 다시 보고하지 말고, \
+            린터가 잡을 수 없는 문제(설계, 로직 오류, 보안, 성능 등)에만 집중해주세요:\n{}\n\n",
+            describe_issues(&lint_issues)
+        )
+    };
+    
+    let rules_note = match team_review_rules() {
+        Some(rules) => format!("팀에서 정한 다음 리뷰 규칙도 함께 적용해주세요:\n\n{}\n\n", rules),
+        None => String::new(),
+    };
+    
+    // Dockerfile, compose, Terraform, Kubernetes는 인프라 전용 점검 항목으로 검토
+    let infra = detect_infra_kind(Path::new(path), &code_content);
+    let subject = |criterion: &str| match infra {
+        Some(kind) => format!(
+            "다음 {} 파일을 검토하고 {} 기준으로 평가해주세요. \
+            특히 다음 항목을 점검해주세요:\n{}",
+            kind.label(), focus(criterion), kind.criteria()
+        ),
+        None => format!("다음 코드를 검토하고 {} 기준으로 평가해주세요:", focus(criterion)),
+    };
+    let file_block = fence_file(path, infra.map(|kind| kind.fence()).unwrap_or(""), &code_content);
+    
+    // `all`은 기준별로 나눠 동시에 요청하고 결과를 합침
+    let criteria_list = split_criteria(criteria);
+    let key = ReviewKey {
+        path: path.to_string(),
+        file_hash: content_hash(&code_content),
+        criteria: criteria_list.join(","),
+        model: client.model().to_string(),
+        lint_hash: content_hash(&lint_note),
+        rules_hash: content_hash(&rules_note),
+    };
+    if let Some(mut review) = load_review(&key).filter(|_| !no_cache) {
+        review.issues.splice(0..0, lint_issues);
+        return Ok(review);
+    }
+    if criteria_list.len() > 1 {
+        eprintln!("{} {}", "기준별 동시 리뷰:".dimmed(), criteria_list.join(", "));
+    }
+    // 응답을 스트리밍으로 받으며 기준별 수신량과 지금까지 나온 문제 수를 보여줌
+    let prompts: Vec<String> = criteria_list.iter()
+        .map(|criterion| review_prompt(&range_note, &subject(criterion), &file_block, &format!("{}{}", rules_note, lint_note)))
+        .collect();
+    // 큰 파일은 기준별 요청을 합친 토큰과 비용으로 한 번만 확인받음
+    client.confirm_queries(&prompts)?;
+    let progress = ReviewProgress::new(&criteria_list);
+    let responses = join_all(prompts.iter().enumerate().map(|(index, prompt)| {
+        let progress = &progress;
+        async move {
+            let response = client.query_streaming_confirmed(prompt, |chunk| progress.receive(index, chunk)).await;
+            if response.is_ok() {
+                progress.finish(index);
+            }
+            response
+        }
+    })).await;
+    progress.clear();
+    
+    let mut reviews = Vec::new();
+    let mut first_error = None;
+    for (criterion, response) in criteria_list.iter().zip(responses) {
+        match response.and_then(|text| Ok(serde_json::from_str::<CodeReview>(&text)?)) {
+            Ok(review) => reviews.push(review),
+            Err(e) if criteria_list.len() > 1 => {
+                eprintln!("{} {} 기준 리뷰 실패: {}", "경고:".yellow(), criterion, e);
+                first_error.get_or_insert(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if reviews.is_empty() {
+        return Err(first_error.unwrap_or_else(|| anyhow::anyhow!("리뷰 기준이 없습니다: {}", criteria)));
+    }
+    let mut review = merge_reviews(reviews);
+    // 일부 기준이 실패한 결과는 다음에 다시 시도하도록 저장하지 않음
+    if first_error.is_none() {
+        if let Err(e) = store_review(&key, &review) {
+            eprintln!("{} {}", "경고:".yellow(), e);
+        }
+    }
+    // 점수 추이는 실제로 리뷰를 실행했을 때만 기록 (캐시 적중은 같은 점수의 반복)
+    let record = ScoreRecord {
+        path: match range {
+            Some(range) => format!("{}:{}-{}", history_key(path), range.start, range.end),
+            None => history_key(path),
+        },
+        score: review.overall_score,
+        criteria: key.criteria.clone(),
+        model: key.model.clone(),
+        reviewed_at: chrono::Utc::now(),
+    };
+    if let Err(e) = record_score(&record) {
+        eprintln!("{} {}", "경고:".yellow(), e);
+    }
+    
+    if !lint_issues.is_empty() {
+        review.issues.splice(0..0, lint_issues);
+    }
+    
+    Ok(review)
+}
+
+/// `all`을 개별 기준으로 펼치고, `security,style`처럼 쉼표로 나열한 기준도 나눕니다
+fn split_criteria(criteria: &str) -> Vec<String> {
+    let mut list = Vec::new();
+    for criterion in criteria.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+        if criterion.eq_ignore_ascii_case("all") {
+            list.extend(REVIEW_CRITERIA.iter().map(|c| c.to_string()));
+        } else {
+            list.push(criterion.to_string());
+        }
+    }
+    list.dedup();
+    list
+}
+
+/// 알려진 기준은 모델이 집중할 항목을 함께 적어 줌
+fn focus(criterion: &str) -> &str {
+    match criterion {
+        "security" => "보안(인젝션, 인증/인가, 민감 정보 노출, 안전하지 않은 API 사용, 입력 검증)",
+        "performance" => "성능(알고리즘 복잡도, 불필요한 할당과 복사, 블로킹 I/O, 캐싱 기회)",
+        "style" => "스타일과 가독성(네이밍, 구조, 중복, 언어 관용구, 주석과 문서)",
+        other => other,
+    }
+}
+
+fn review_prompt(range_note: &str, subject: &str, file_block: &str, lint_note: &str) -> String {
+    format!(
+        "{}{}\n\n{}\n\n{}\
         JSON 형식으로 응답해주세요:\n\
         {{\n\
           \"overall_score\": 0-100,\n\
@@ -33,48 +360,228 @@ pub async fn review_code(
           \"suggestions\": [\"전반적인 개선 제안\"],\n\
           \"positive_aspects\": [\"잘된 점\"]\n\
         }}",
-        criteria, code_content
-    );
+        range_note, subject, file_block, lint_note
+    )
+}
+
+/// `ricci sync`로 받은 팀 리뷰 규칙 (`<data_dir>/shared/review_rules/` 아래 파일을 이름 순으로 이어 붙임)
+fn team_review_rules() -> Option<String> {
+    let dir = crate::paths::shared_dir().ok()?.join("review_rules");
+    let mut files: Vec<_> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    let rules: Vec<String> = files.iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+        .collect();
+    (!rules.is_empty()).then(|| rules.join("\n\n"))
+}
+
+/// 디렉토리 리뷰의 파일별 결과를 모아 저장소 수준 요약(핵심 위험, 반복 패턴, 수정 우선순위)을 만듭니다
+pub async fn summarize_reviews(client: &OpenAIClient, reviews: &[(String, CodeReview)]) -> Result<String> {
+    let mut digest = String::new();
+    for (index, (file, review)) in reviews.iter().enumerate() {
+        let entry = format!("## {} (점수 {}/100)\n{}\n", file, review.overall_score, describe_review(review));
+        if digest.len() + entry.len() > MAX_SUMMARY_INPUT_CHARS {
+            digest.push_str(&format!("\n(이하 {}개 파일 생략)\n", reviews.len() - index));
+            break;
+        }
+        digest.push('\n');
+        digest.push_str(&entry);
+    }
     
-    let response = client.query(&prompt).await?;
-    let review: CodeReview = serde_json::from_str(&response)?;
+    let prompt = format!(
+        "다음은 저장소의 파일 {}개를 하나씩 리뷰한 결과입니다.\n{}\n\
+        이 결과를 종합해 경영진과 팀 리드가 읽을 저장소 수준 요약을 마크다운으로 작성해주세요. \
+        파일별 내용을 되풀이하지 말고 다음 세 섹션만 작성하세요:\n\
+        ## 핵심 위험\n가장 심각한 위험 3~5개와 영향 받는 파일\n\
+        ## 반복되는 패턴\n여러 파일에 걸쳐 나타나는 구조적 문제나 습관\n\
+        ## 수정 우선순위\n먼저 고칠 순서대로 번호를 붙인 목록 (각 항목에 대상 파일과 이유)",
+        reviews.len(), digest
+    );
+    client.query(&prompt).await
+}
+
+/// 요약 입력용으로 줄인 리뷰 (문제는 심각도순 상위만)
+fn describe_review(review: &CodeReview) -> String {
+    let mut issues: Vec<_> = review.issues.iter().collect();
+    issues.sort_by_key(|issue| issue.severity);
+    let mut lines: Vec<String> = issues.iter()
+        .take(MAX_SUMMARY_ISSUES)
+        .map(|issue| format!("- [{:?}/{:?}] {}: {}", issue.severity, issue.category, issue.location, issue.description))
+        .collect();
+    if issues.len() > MAX_SUMMARY_ISSUES {
+        lines.push(format!("- 외 {}건", issues.len() - MAX_SUMMARY_ISSUES));
+    }
+    if lines.is_empty() {
+        lines.push("- 발견된 문제 없음".to_string());
+    }
+    lines.join("\n")
+}
+
+/// 기준별 리뷰를 하나로 합칩니다. 점수는 평균, 문제는 심각도순, 제안과 잘된 점은 중복을 뺌
+fn merge_reviews(mut reviews: Vec<CodeReview>) -> CodeReview {
+    if reviews.len() == 1 {
+        return reviews.remove(0);
+    }
+    let overall_score = reviews.iter().map(|r| r.overall_score).sum::<f32>() / reviews.len() as f32;
+    let mut merged = CodeReview {
+        overall_score: overall_score.round(),
+        issues: Vec::new(),
+        suggestions: Vec::new(),
+        positive_aspects: Vec::new(),
+        cached_at: None,
+    };
+    for review in reviews {
+        for issue in review.issues {
+            let duplicate = merged.issues.iter()
+                .any(|i| i.location == issue.location && i.description == issue.description);
+            if !duplicate {
+                merged.issues.push(issue);
+            }
+        }
+        for suggestion in review.suggestions {
+            if !merged.suggestions.contains(&suggestion) {
+                merged.suggestions.push(suggestion);
+            }
+        }
+        for aspect in review.positive_aspects {
+            if !merged.positive_aspects.contains(&aspect) {
+                merged.positive_aspects.push(aspect);
+            }
+        }
+    }
+    merged.issues.sort_by_key(|issue| issue.severity);
+    merged
+}
+
+/// `--group-by`: 문제를 묶는 기준
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ReviewGroupBy {
+    File,
+    Severity,
+}
+
+/// 리뷰 결과에서 보여줄 문제(`--only`, `--min-severity`)와 묶는 방식(`--group-by`)
+#[derive(Debug, Clone, Default)]
+pub struct ReviewView {
+    pub only: Vec<IssueCategory>,
+    pub min_severity: Option<IssueSeverity>,
+    pub group_by: Option<ReviewGroupBy>,
+}
+
+impl ReviewView {
+    pub fn shows(&self, issue: &ReviewIssue) -> bool {
+        (self.only.is_empty() || self.only.contains(&issue.category))
+            && self.min_severity.is_none_or(|min| issue.severity <= min)
+    }
+}
+
+fn severity_icon(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Critical => "🔴",
+        IssueSeverity::High => "🟠",
+        IssueSeverity::Medium => "🟡",
+        IssueSeverity::Low => "🟢",
+        IssueSeverity::Info => "ℹ️",
+    }
+}
+
+/// `파일:라인`, `파일:시작-끝` 위치의 파일 부분
+fn issue_file(location: &str) -> &str {
+    match location.rsplit_once(':') {
+        Some((file, line)) if !file.is_empty() && line.trim().chars().all(|c| c.is_ascii_digit() || c == '-') => file.trim(),
+        _ => location.trim(),
+    }
+}
+
+fn push_issue(output: &mut String, issue: &ReviewIssue, heading: &str) {
+    output.push_str(&format!("{} {} {:?} - {:?}\n\n",
+        heading, severity_icon(issue.severity), issue.severity, issue.category));
+    output.push_str(&format!("**위치**: {}\n\n", issue.location));
+    output.push_str(&format!("{}\n\n", issue.description));
     
-    Ok(review)
+    if let Some(suggestion) = &issue.suggestion {
+        output.push_str(&format!("**제안**: {}\n\n", suggestion));
+    }
 }
 
 impl CodeReview {
+    /// `view`에 맞는 문제만 남긴 리뷰 (설명, 적용, 저장소 요약에 사용)
+    pub fn filtered(&self, view: &ReviewView) -> CodeReview {
+        CodeReview {
+            issues: self.issues.iter().filter(|issue| view.shows(issue)).cloned().collect(),
+            ..self.clone()
+        }
+    }
+    
     pub fn format_markdown(&self) -> String {
+        self.format_markdown_view(&ReviewView::default())
+    }
+    
+    /// 필터와 묶음 기준을 적용한 마크다운. 숨긴 문제 수를 함께 표시합니다
+    pub fn format_markdown_view(&self, view: &ReviewView) -> String {
         let mut output = String::new();
         
-        output.push_str(&format!("# 코드 리뷰 결과\n\n"));
+        output.push_str("# 코드 리뷰 결과\n\n");
         output.push_str(&format!("**전체 점수**: {}/100\n\n", self.overall_score));
+        match self.cached_at {
+            Some(at) => output.push_str(&format!(
+                "**캐시**: {}에 생성한 결과 재사용 (`--no-cache`로 다시 생성)\n\n",
+                at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+            )),
+            None => output.push_str("**캐시**: 새로 생성\n\n"),
+        }
         
         if !self.positive_aspects.is_empty() {
             output.push_str("## 👍 잘된 점\n\n");
             for aspect in &self.positive_aspects {
                 output.push_str(&format!("- {}\n", aspect));
             }
-            output.push_str("\n");
+            output.push('\n');
         }
         
-        if !self.issues.is_empty() {
+        let shown: Vec<&ReviewIssue> = self.issues.iter().filter(|issue| view.shows(issue)).collect();
+        let hidden = self.issues.len() - shown.len();
+        if !shown.is_empty() || hidden > 0 {
             output.push_str("## 🔍 발견된 문제\n\n");
-            for issue in &self.issues {
-                let severity_icon = match issue.severity {
-                    IssueSeverity::Critical => "🔴",
-                    IssueSeverity::High => "🟠",
-                    IssueSeverity::Medium => "🟡",
-                    IssueSeverity::Low => "🟢",
-                    IssueSeverity::Info => "ℹ️",
-                };
-                
-                output.push_str(&format!("### {} {:?} - {:?}\n\n", 
-                    severity_icon, issue.severity, issue.category));
-                output.push_str(&format!("**위치**: {}\n\n", issue.location));
-                output.push_str(&format!("{}\n\n", issue.description));
-                
-                if let Some(suggestion) = &issue.suggestion {
-                    output.push_str(&format!("**제안**: {}\n\n", suggestion));
+        }
+        if hidden > 0 {
+            output.push_str(&format!("_필터로 {}건을 숨겼습니다 (전체 {}건)_\n\n", hidden, self.issues.len()));
+        }
+        match view.group_by {
+            None => {
+                for issue in &shown {
+                    push_issue(&mut output, issue, "###");
+                }
+            }
+            Some(group_by) => {
+                let mut groups: Vec<(String, Vec<&ReviewIssue>)> = Vec::new();
+                for issue in &shown {
+                    let key = match group_by {
+                        ReviewGroupBy::File => issue_file(&issue.location).to_string(),
+                        ReviewGroupBy::Severity => format!("{} {:?}", severity_icon(issue.severity), issue.severity),
+                    };
+                    match groups.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, issues)) => issues.push(issue),
+                        None => groups.push((key, vec![issue])),
+                    }
+                }
+                match group_by {
+                    ReviewGroupBy::File => groups.sort_by(|a, b| a.0.cmp(&b.0)),
+                    ReviewGroupBy::Severity => groups.sort_by_key(|(_, issues)| issues[0].severity),
+                }
+                for (key, mut issues) in groups {
+                    issues.sort_by_key(|issue| issue.severity);
+                    output.push_str(&format!("### {} ({}건)\n\n", key, issues.len()));
+                    for issue in issues {
+                        push_issue(&mut output, issue, "####");
+                    }
                 }
             }
         }
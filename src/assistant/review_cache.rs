@@ -0,0 +1,72 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use crate::assistant::types::CodeReview;
+use crate::paths::data_dir;
+
+/// 리뷰 결과를 찾는 키. 같은 파일 내용을 같은 기준과 모델로 다시 리뷰하면 같은 키가 됩니다
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewKey {
+    pub path: String,
+    /// 리뷰한 코드(줄 범위 리뷰면 발췌)의 해시
+    pub file_hash: String,
+    pub criteria: String,
+    pub model: String,
+    /// 프롬프트에 들어간 린터 결과의 해시 (린터 결과가 바뀌면 새로 리뷰)
+    pub lint_hash: String,
+    /// 프롬프트에 들어간 팀 리뷰 규칙의 해시 (`ricci sync`로 규칙이 바뀌면 새로 리뷰)
+    #[serde(default)]
+    pub rules_hash: String,
+}
+
+/// `<data_dir>/review_cache/<키 해시>.json`
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    key: ReviewKey,
+    created_at: DateTime<Utc>,
+    review: CodeReview,
+}
+
+impl ReviewKey {
+    fn file_name(&self) -> String {
+        let joined = [&self.path, &self.file_hash, &self.criteria, &self.model, &self.lint_hash, &self.rules_hash]
+            .map(String::as_str)
+            .join("\0");
+        format!("{}.json", content_hash(&joined))
+    }
+}
+
+fn cache_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("review_cache"))
+}
+
+/// 내용의 FNV-1a 64비트 해시 (실행과 Rust 버전이 바뀌어도 같은 값)
+pub fn content_hash(text: &str) -> String {
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// 캐시된 리뷰가 있으면 생성 시각을 표시해 돌려줍니다
+pub fn load_review(key: &ReviewKey) -> Option<CodeReview> {
+    let data = std::fs::read_to_string(cache_dir().ok()?.join(key.file_name())).ok()?;
+    let entry: CacheEntry = serde_json::from_str(&data).ok()?;
+    // 해시가 겹친 다른 항목은 사용하지 않음
+    if entry.key != *key {
+        return None;
+    }
+    let mut review = entry.review;
+    review.cached_at = Some(entry.created_at);
+    Some(review)
+}
+
+pub fn store_review(key: &ReviewKey, review: &CodeReview) -> Result<()> {
+    let dir = cache_dir()?;
+    std::fs::create_dir_all(&dir).context("리뷰 캐시 디렉토리 생성 실패")?;
+    let path = dir.join(key.file_name());
+    let entry = CacheEntry { key: key.clone(), created_at: Utc::now(), review: review.clone() };
+    std::fs::write(&path, serde_json::to_string_pretty(&entry)?)
+        .with_context(|| format!("리뷰 캐시 저장 실패: {}", path.display()))
+}
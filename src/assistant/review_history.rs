@@ -0,0 +1,122 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use crate::paths::data_dir;
+
+/// 리뷰 실행 한 번의 점수 기록 (`<data_dir>/review_history.jsonl`의 한 줄)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreRecord {
+    /// 리뷰한 파일의 절대 경로 (줄 범위 리뷰면 `:시작-끝`이 붙음)
+    pub path: String,
+    pub score: f32,
+    pub criteria: String,
+    pub model: String,
+    pub reviewed_at: DateTime<Utc>,
+}
+
+fn history_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("review_history.jsonl"))
+}
+
+/// 기록에 쓸 경로. 현재 디렉토리와 무관하게 같은 파일은 같은 이름이 되도록 절대 경로로 바꿉니다
+pub fn history_key(path: &str) -> String {
+    Path::new(path).canonicalize()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+pub fn record_score(record: &ScoreRecord) -> Result<()> {
+    let path = history_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)
+        .with_context(|| format!("리뷰 기록 저장 실패: {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// `path`(파일 또는 디렉토리) 아래 파일의 점수 기록을 경로별로, 오래된 순서로 돌려줍니다
+pub fn load_history(path: &str) -> Result<Vec<(String, Vec<ScoreRecord>)>> {
+    let prefix = history_key(path);
+    let data = match std::fs::read_to_string(history_path()?) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).context("리뷰 기록을 읽을 수 없습니다"),
+    };
+    let mut grouped: Vec<(String, Vec<ScoreRecord>)> = Vec::new();
+    // 손상된 줄은 건너뜀
+    for record in data.lines().filter_map(|line| serde_json::from_str::<ScoreRecord>(line).ok()) {
+        if !is_under(&record.path, &prefix) {
+            continue;
+        }
+        match grouped.iter_mut().find(|(path, _)| *path == record.path) {
+            Some((_, records)) => records.push(record),
+            None => grouped.push((record.path.clone(), vec![record])),
+        }
+    }
+    for (_, records) in &mut grouped {
+        records.sort_by_key(|r| r.reviewed_at);
+    }
+    grouped.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(grouped)
+}
+
+/// 기록 경로가 `prefix` 파일(범위 리뷰 `<파일>:<시작>-<끝>` 포함)이거나 그 아래에 있는지.
+/// 구분자는 운영체제 규칙대로 비교 (Windows의 `\\?\C:\...` 경로도 맞음)
+fn is_under(path: &str, prefix: &str) -> bool {
+    Path::new(path).starts_with(prefix)
+        || path.strip_prefix(prefix).is_some_and(|rest| rest.starts_with(':'))
+}
+
+/// 점수 추이를 막대 문자로 그립니다 (0~100점 기준)
+pub fn sparkline(scores: &[f32]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    scores.iter()
+        .map(|score| BARS[((score.clamp(0.0, 100.0) / 100.0 * 7.0).round()) as usize])
+        .collect()
+}
+
+/// README에 넣을 shields.io 형식의 점수 배지 (80점 이상 초록, 60점 이상 노랑, 그 밖은 빨강)
+pub fn score_badge(label: &str, score: f32) -> String {
+    let value = format!("{:.0}/100", score);
+    let color = if score >= 80.0 {
+        "#4c1"
+    } else if score >= 60.0 {
+        "#dfb317"
+    } else {
+        "#e05d44"
+    };
+    // Verdana 11px 기준 대략적인 글자 폭
+    let text_width = |text: &str| text.chars().count() * 7 + 10;
+    let (left, right) = (text_width(label), text_width(&value));
+    let width = left + right;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {value}">
+  <title>{label}: {value}</title>
+  <linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+  <clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{left}" height="20" fill="#555"/>
+    <rect x="{left}" width="{right}" height="20" fill="{color}"/>
+    <rect width="{width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text>
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{value_x}" y="15" fill="#010101" fill-opacity=".3">{value}</text>
+    <text x="{value_x}" y="14">{value}</text>
+  </g>
+</svg>
+"##,
+        label = escape_xml(label),
+        label_x = left as f32 / 2.0,
+        value_x = left as f32 + right as f32 / 2.0,
+    )
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,402 @@
+use anyhow::{Context, Result};
+use colored::*;
+use crate::renderer::plain;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::analyzer::IGNORED_DIRS;
+use crate::api::OpenAIClient;
+use crate::assistant::types::IssueSeverity;
+use crate::assistant::untrusted::fence_file;
+
+/// 발견 위치 앞뒤로 함께 보여줄 줄 수
+const SNIPPET_CONTEXT_LINES: usize = 2;
+/// 한 번의 분류 요청에 담을 최대 발견 수
+const TRIAGE_BATCH_SIZE: usize = 15;
+/// 이보다 큰 파일은 생성/번들 파일로 보고 건너뜀
+const MAX_FILE_SIZE: u64 = 1_000_000;
+
+const RUST: &[&str] = &["rs"];
+const JS: &[&str] = &["js", "jsx", "ts", "tsx", "mjs", "cjs"];
+const PYTHON: &[&str] = &["py"];
+const PHP: &[&str] = &["php"];
+const GO: &[&str] = &["go"];
+const JAVA: &[&str] = &["java", "kt"];
+const ANY: &[&str] = &[];
+
+/// 언어별 위험 패턴
+struct SecurityRule {
+    id: &'static str,
+    title: &'static str,
+    extensions: &'static [&'static str],
+    pattern: &'static str,
+    severity: IssueSeverity,
+}
+
+const fn rule(
+    id: &'static str,
+    title: &'static str,
+    extensions: &'static [&'static str],
+    pattern: &'static str,
+    severity: IssueSeverity,
+) -> SecurityRule {
+    SecurityRule { id, title, extensions, pattern, severity }
+}
+
+const RULES: &[SecurityRule] = &[
+    // Rust
+    rule("rust-unsafe", "unsafe 블록", RUST, r"\bunsafe\s*\{", IssueSeverity::Medium),
+    rule("rust-transmute", "mem::transmute 사용", RUST, r"\btransmute\s*(::<|\()", IssueSeverity::Medium),
+    rule("rust-shell", "셸을 통한 명령 실행", RUST, r#"Command::new\(\s*"(sh|bash|cmd|powershell)""#, IssueSeverity::High),
+    rule("rust-sql-format", "format!으로 만든 SQL", RUST, r#"(?i)format!\(\s*"[^"]*\b(select|insert|update|delete)\b"#, IssueSeverity::High),
+    // JavaScript / TypeScript
+    rule("js-eval", "eval 사용", JS, r"\beval\s*\(", IssueSeverity::High),
+    rule("js-function", "new Function 사용", JS, r"\bnew\s+Function\s*\(", IssueSeverity::High),
+    rule("js-exec", "child_process 명령 실행", JS, r"\b(exec|execSync|spawn)\s*\(|child_process", IssueSeverity::High),
+    rule("js-innerhtml", "innerHTML 대입 (XSS)", JS, r"\.(inner|outer)HTML\s*=|dangerouslySetInnerHTML", IssueSeverity::Medium),
+    rule("js-sql-concat", "문자열 연결로 만든 SQL", JS, r#"(?i)["'`][^"'`]*\b(select|insert|update|delete)\b[^"'`]*["'`]\s*\+|`[^`]*\b(select|insert|update|delete)\b[^`]*\$\{"#, IssueSeverity::High),
+    // Python
+    rule("py-eval", "eval/exec 사용", PYTHON, r"\b(eval|exec)\s*\(", IssueSeverity::High),
+    rule("py-shell", "셸 명령 실행", PYTHON, r"os\.(system|popen)\s*\(|subprocess\.\w+\(.*shell\s*=\s*True", IssueSeverity::High),
+    rule("py-pickle", "신뢰할 수 없는 데이터 역직렬화", PYTHON, r"\bpickle\.loads?\s*\(|\byaml\.load\s*\([^)]*\)", IssueSeverity::Medium),
+    rule("py-sql", "포맷 문자열로 만든 SQL", PYTHON, r#"(?i)\.execute\(\s*(f["']|["'][^"']*\b(select|insert|update|delete)\b[^"']*["']\s*(%|\+|\.format))"#, IssueSeverity::High),
+    // PHP
+    rule("php-eval", "eval 사용", PHP, r"\beval\s*\(", IssueSeverity::High),
+    rule("php-exec", "셸 명령 실행", PHP, r"\b(system|exec|shell_exec|passthru|popen)\s*\(", IssueSeverity::High),
+    rule("php-sql", "변수를 직접 넣은 SQL", PHP, r#"(?i)\b(mysql_query|mysqli_query|->query)\s*\([^)]*\$"#, IssueSeverity::High),
+    // Go
+    rule("go-shell", "셸을 통한 명령 실행", GO, r#"exec\.Command\(\s*"(sh|bash|cmd)""#, IssueSeverity::High),
+    rule("go-sql-sprintf", "Sprintf로 만든 SQL", GO, r#"(?i)fmt\.Sprintf\(\s*"[^"]*\b(select|insert|update|delete)\b"#, IssueSeverity::High),
+    // Java / Kotlin
+    rule("java-exec", "Runtime.exec 명령 실행", JAVA, r"Runtime\.getRuntime\(\)\.exec\s*\(", IssueSeverity::High),
+    rule("java-sql-concat", "문자열 연결로 만든 SQL", JAVA, r#"(?i)"[^"]*\b(select|insert|update|delete)\b[^"]*"\s*\+"#, IssueSeverity::High),
+    // 공통
+    // `OPENAI_API_KEY=sk-...`처럼 앞뒤에 이름이 붙은 키와, dotenv/YAML의 따옴표 없는 값(줄 끝까지)도 포함
+    rule("hardcoded-secret", "하드코딩된 비밀 값", ANY, r#"(?i)\b[_a-z0-9]*(api[_-]?key|secret|password|passwd|token)[_a-z0-9]*\s*[:=]\s*(["'][^"'\s]{8,}["']|[^"'\s(){};,$]{8,}\s*$)"#, IssueSeverity::High),
+];
+
+/// 패턴 검사로 찾은 의심 위치
+#[derive(Debug, Clone, Serialize)]
+pub struct SecurityFinding {
+    pub rule: &'static str,
+    pub title: &'static str,
+    pub path: String,
+    pub line: usize,
+    pub snippet: String,
+    pub severity: IssueSeverity,
+}
+
+/// 모델이 분류한 결과
+#[derive(Debug, Clone, Deserialize)]
+struct Triage {
+    id: usize,
+    verdict: Verdict,
+    severity: IssueSeverity,
+    explanation: String,
+    #[serde(default)]
+    fix: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Vulnerable,
+    NeedsReview,
+    FalsePositive,
+}
+
+/// 분류까지 마친 항목
+#[derive(Debug, Clone)]
+pub struct TriagedFinding {
+    pub finding: SecurityFinding,
+    pub verdict: Verdict,
+    pub severity: IssueSeverity,
+    pub explanation: String,
+    pub fix: Option<String>,
+}
+
+/// 우선순위 순으로 정렬된 취약점 보고서
+#[derive(Debug, Default)]
+pub struct SecurityReport {
+    pub items: Vec<TriagedFinding>,
+    pub scanned_files: usize,
+    /// AI 분류 없이 패턴 검사 결과만 담은 보고서인지
+    pub offline: bool,
+}
+
+/// 파일 또는 디렉토리를 언어별 위험 패턴으로 검사합니다
+pub fn scan_patterns(path: &Path) -> Result<(Vec<SecurityFinding>, usize)> {
+    let compiled: Vec<(&SecurityRule, Regex)> = RULES.iter()
+        .map(|r| Regex::new(r.pattern).map(|re| (r, re)))
+        .collect::<std::result::Result<_, _>>()
+        .context("보안 패턴 컴파일 실패")?;
+
+    let mut findings = Vec::new();
+    let mut scanned = 0;
+
+    let files = WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|e| !IGNORED_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.metadata().is_ok_and(|m| m.len() <= MAX_FILE_SIZE));
+
+    for entry in files {
+        let file = entry.path();
+        let extension = if is_dotenv(file) { "env" } else { file.extension().and_then(|e| e.to_str()).unwrap_or("") };
+        let rules: Vec<_> = compiled.iter()
+            .filter(|(r, _)| r.extensions.contains(&extension) || (r.extensions.is_empty() && is_code_or_config(extension)))
+            .collect();
+        if rules.is_empty() {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(file) else { continue };
+        scanned += 1;
+
+        let lines: Vec<&str> = content.lines().collect();
+        for (index, line) in lines.iter().enumerate() {
+            if is_comment(line) {
+                continue;
+            }
+            for (rule, regex) in &rules {
+                if regex.is_match(line) {
+                    findings.push(SecurityFinding {
+                        rule: rule.id,
+                        title: rule.title,
+                        path: file.display().to_string(),
+                        line: index + 1,
+                        snippet: snippet(&lines, index),
+                        severity: rule.severity,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok((findings, scanned))
+}
+
+/// 패턴 검사 결과 중 의심 코드 조각만 모델에 보내 실제 취약점인지 분류합니다
+pub async fn triage_findings(client: &OpenAIClient, findings: Vec<SecurityFinding>) -> Result<Vec<TriagedFinding>> {
+    let mut triaged = Vec::new();
+    let total_batches = findings.len().div_ceil(TRIAGE_BATCH_SIZE);
+
+    for (batch_index, batch) in findings.chunks(TRIAGE_BATCH_SIZE).enumerate() {
+        println!("{} {}/{}", plain("🤖 AI 분류 중:").yellow(), batch_index + 1, total_batches);
+
+        let listing = batch.iter().enumerate()
+            .map(|(i, f)| format!(
+                "### [{}] {} ({}:{})\n규칙: {}\n{}",
+                i, f.title, f.path, f.line, f.rule, fence_file(&f.path, "", &f.snippet)
+            ))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let prompt = format!(
+            "정적 패턴 검사가 다음 코드 조각들을 보안 위험 후보로 표시했습니다. \
+            각 항목이 실제로 악용 가능한 취약점인지 판단해주세요.\n\n{}\n\n\
+            JSON 배열로만 응답해주세요:\n\
+            [\n\
+              {{\n\
+                \"id\": 항목 번호,\n\
+                \"verdict\": \"vulnerable|needs_review|false_positive\",\n\
+                \"severity\": \"Critical|High|Medium|Low|Info\",\n\
+                \"explanation\": \"판단 근거 (공격 시나리오 포함)\",\n\
+                \"fix\": \"수정 방법\"\n\
+              }}\n\
+            ]",
+            listing
+        );
+
+        let response = client.query(&prompt).await?;
+        // 한 묶음의 응답을 읽지 못해도 다른 묶음의 결과는 살리고, 이 묶음은 검토 필요로 남김
+        let results: Vec<Triage> = match parse_triage(&response) {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!("{} {}/{} 묶음: {} (패턴 검사 결과로 표시)", "AI 분류 응답 파싱 실패:".yellow(), batch_index + 1, total_batches, e);
+                Vec::new()
+            }
+        };
+
+        for (i, finding) in batch.iter().enumerate() {
+            // 모델이 빠뜨린 항목은 패턴 심각도 그대로 검토 필요로 남김
+            let item = match results.iter().find(|t| t.id == i) {
+                Some(t) => TriagedFinding {
+                    finding: finding.clone(),
+                    verdict: t.verdict,
+                    severity: t.severity,
+                    explanation: t.explanation.clone(),
+                    fix: t.fix.clone(),
+                },
+                None => unreviewed(finding.clone()),
+            };
+            triaged.push(item);
+        }
+    }
+
+    Ok(triaged)
+}
+
+/// 모델이 JSON 앞뒤에 설명을 붙여도 배열 부분만 읽습니다
+fn parse_triage(response: &str) -> Result<Vec<Triage>> {
+    let (start, end) = response.find('[').zip(response.rfind(']'))
+        .filter(|(start, end)| start < end)
+        .context("응답에서 JSON 배열을 찾을 수 없습니다")?;
+    Ok(serde_json::from_str(&response[start..=end])?)
+}
+
+/// AI 분류 없이 패턴 검사 결과를 그대로 보고서 항목으로 만듭니다
+pub fn unreviewed(finding: SecurityFinding) -> TriagedFinding {
+    TriagedFinding {
+        severity: finding.severity,
+        explanation: finding.title.to_string(),
+        finding,
+        verdict: Verdict::NeedsReview,
+        fix: None,
+    }
+}
+
+impl SecurityReport {
+    pub fn new(mut items: Vec<TriagedFinding>, scanned_files: usize, offline: bool) -> Self {
+        // 확인된 취약점 → 검토 필요 순, 같은 판정 안에서는 심각도 순
+        items.sort_by_key(|item| (verdict_rank(item.verdict), item.severity));
+        Self { items, scanned_files, offline }
+    }
+
+    pub fn format_markdown(&self) -> String {
+        let mut output = String::from("# 보안 스캔 보고서\n\n");
+        let reported: Vec<_> = self.items.iter()
+            .filter(|i| i.verdict != Verdict::FalsePositive)
+            .collect();
+        let dismissed = self.items.len() - reported.len();
+
+        output.push_str(&format!(
+            "- 검사한 파일: {}\n- 패턴 검사 후보: {}\n- 보고 항목: {}{}\n\n",
+            self.scanned_files,
+            self.items.len(),
+            reported.len(),
+            if dismissed > 0 { format!(" (오탐 {}건 제외)", dismissed) } else { String::new() }
+        ));
+        if self.offline {
+            output.push_str("> AI 분류 없이 패턴 검사 결과만 표시합니다.\n\n");
+        }
+
+        if reported.is_empty() {
+            output.push_str("발견된 취약점이 없습니다. ✓\n");
+            return output;
+        }
+
+        for (rank, item) in reported.iter().enumerate() {
+            let icon = match item.severity {
+                IssueSeverity::Critical => "🔴",
+                IssueSeverity::High => "🟠",
+                IssueSeverity::Medium => "🟡",
+                IssueSeverity::Low => "🟢",
+                IssueSeverity::Info => "ℹ️",
+            };
+            let verdict = match item.verdict {
+                Verdict::Vulnerable => "취약",
+                Verdict::NeedsReview => "검토 필요",
+                Verdict::FalsePositive => "오탐",
+            };
+
+            output.push_str(&format!(
+                "## {}. {} {:?} [{}] {}\n\n**위치**: {}:{} (`{}`)\n\n```\n{}\n```\n\n{}\n\n",
+                rank + 1, icon, item.severity, verdict, item.finding.title,
+                item.finding.path, item.finding.line, item.finding.rule,
+                item.finding.snippet, item.explanation
+            ));
+            if let Some(fix) = &item.fix {
+                output.push_str(&format!("**수정 방법**: {}\n\n", fix));
+            }
+        }
+
+        output
+    }
+}
+
+fn verdict_rank(verdict: Verdict) -> u8 {
+    match verdict {
+        Verdict::Vulnerable => 0,
+        Verdict::NeedsReview => 1,
+        Verdict::FalsePositive => 2,
+    }
+}
+
+/// 공통 규칙(비밀 값)을 적용할 파일
+fn is_code_or_config(extension: &str) -> bool {
+    [RUST, JS, PYTHON, PHP, GO, JAVA].iter().any(|exts| exts.contains(&extension))
+        || matches!(extension, "env" | "toml" | "yaml" | "yml" | "json" | "ini" | "cfg" | "conf" | "rb" | "cs")
+}
+
+/// `.env`, `.env.local`처럼 확장자가 없거나 다른 dotenv 파일
+fn is_dotenv(file: &Path) -> bool {
+    file.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name == ".env" || name.starts_with(".env."))
+}
+
+fn is_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    ["//", "#", "*", "/*", "--"].iter().any(|p| trimmed.starts_with(p))
+        && !trimmed.starts_with("#[")
+        && !trimmed.starts_with("#!")
+}
+
+fn snippet(lines: &[&str], index: usize) -> String {
+    let start = index.saturating_sub(SNIPPET_CONTEXT_LINES);
+    let end = (index + SNIPPET_CONTEXT_LINES + 1).min(lines.len());
+    (start..end)
+        .map(|i| format!("{:>5} {} {}", i + 1, if i == index { '>' } else { ' ' }, lines[i]))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret_rule() -> Regex {
+        let rule = RULES.iter().find(|r| r.id == "hardcoded-secret").unwrap();
+        Regex::new(rule.pattern).unwrap()
+    }
+
+    #[test]
+    fn secret_rule_matches_prefixed_and_unquoted_keys() {
+        let re = secret_rule();
+        assert!(re.is_match("OPENAI_API_KEY=sk-abcdefghijklmnop"));
+        assert!(re.is_match(r#"DB_PASSWORD = "hunter2hunter2""#));
+        assert!(re.is_match("api_key: abcdefgh12345"));
+        assert!(re.is_match(r#"let token = "abcdefgh12345";"#));
+    }
+
+    #[test]
+    fn secret_rule_ignores_lookups_and_placeholders() {
+        let re = secret_rule();
+        assert!(!re.is_match("let password = read_password_from_env();"));
+        assert!(!re.is_match("password: ${DB_PASSWORD}"));
+        assert!(!re.is_match(r#"api_key = os.environ["OPENAI_API_KEY"]"#));
+        assert!(!re.is_match("TOKEN=short"));
+    }
+
+    #[test]
+    fn dotenv_files_are_scanned() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".env"), "OPENAI_API_KEY=sk-abcdefghijklmnop\n").unwrap();
+        std::fs::write(dir.path().join(".env.local"), "DB_PASSWORD=hunter2hunter2\n").unwrap();
+        let (findings, scanned) = scan_patterns(dir.path()).unwrap();
+        assert_eq!(scanned, 2);
+        assert_eq!(findings.iter().filter(|f| f.rule == "hardcoded-secret").count(), 2);
+    }
+
+    #[test]
+    fn triage_is_read_from_prose_around_the_array() {
+        let response = "분류 결과입니다:\n```json\n[{\"id\": 0, \"verdict\": \"false_positive\", \
+            \"severity\": \"Low\", \"explanation\": \"테스트 값\"}]\n```\n참고하세요.";
+        let results = parse_triage(response).unwrap();
+        assert_eq!(results[0].verdict, Verdict::FalsePositive);
+        assert!(parse_triage("배열이 없습니다").is_err());
+    }
+}
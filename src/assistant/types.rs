@@ -1,7 +1,9 @@
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ChatMode {
+    #[default]
     Normal,     // 일반 대화
     Concise,    // 간결한 응답
     Detailed,   // 상세한 응답
@@ -9,11 +11,54 @@ pub enum ChatMode {
     Planning,   // 계획 수립 모드
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl ChatMode {
+    pub const ALL: [ChatMode; 5] = [ChatMode::Normal, ChatMode::Concise, ChatMode::Detailed, ChatMode::Code, ChatMode::Planning];
+    
+    /// 응답 언어 지시를 제외한 모드별 기본 시스템 프롬프트
+    pub fn system_prompt(&self) -> &'static str {
+        match self {
+            ChatMode::Normal => "당신은 도움이 되는 개발 어시스턴트입니다.",
+            ChatMode::Concise => "당신은 간결한 어시스턴트입니다. 핵심만 간단명료하게 답변해주세요.",
+            ChatMode::Detailed => "당신은 상세한 어시스턴트입니다. 예제와 함께 종합적인 설명을 제공해주세요.",
+            ChatMode::Code => "당신은 코드 중심 어시스턴트입니다. 코드 예제와 기술적 세부사항을 우선시해주세요.",
+            ChatMode::Planning => "당신은 프로젝트 계획 어시스턴트입니다. 아키텍처, 설계, 계획에 집중해주세요.",
+        }
+    }
+    
+    /// REPL 프롬프트 등에 표시할 짧은 이름
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChatMode::Normal => "normal",
+            ChatMode::Concise => "concise",
+            ChatMode::Detailed => "detailed",
+            ChatMode::Code => "code",
+            ChatMode::Planning => "planning",
+        }
+    }
+}
+
+/// 설정된 응답 언어에 맞는 지시문
+pub fn language_instruction(language: &str) -> &'static str {
+    match language {
+        "en" => "Please answer in English.",
+        _ => "한국어로 대답해주세요.",
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AssistantContext {
     pub messages: Vec<Message>,
     pub project_info: Option<ProjectInfo>,
     pub current_files: Vec<String>,
+    /// 세션에 저장되어 다음 실행 때 복원되는 대화 모드
+    #[serde(default)]
+    pub chat_mode: ChatMode,
+    /// 선택한 페르소나 이름 (<data_dir>/shared/personas/<이름>.md)
+    #[serde(default)]
+    pub persona: Option<String>,
+    /// 보관 파일로 옮긴 예전 메시지의 요약 (시스템 프롬프트에 포함)
+    #[serde(default)]
+    pub archived_summary: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +66,9 @@ pub struct Message {
     pub role: String,
     pub content: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// `/pin`으로 고정한 메시지. 세션을 보관/요약할 때도 원문 그대로 남김
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,15 +80,18 @@ pub struct ProjectInfo {
     pub structure: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeReview {
     pub overall_score: f32,
     pub issues: Vec<ReviewIssue>,
     pub suggestions: Vec<String>,
     pub positive_aspects: Vec<String>,
+    /// 리뷰 캐시에서 가져온 결과면 처음 생성한 시각
+    #[serde(skip)]
+    pub cached_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewIssue {
     pub severity: IssueSeverity,
     pub category: IssueCategory,
@@ -49,7 +100,7 @@ pub struct ReviewIssue {
     pub suggestion: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, clap::ValueEnum)]
 pub enum IssueSeverity {
     Critical,
     High,
@@ -58,7 +109,7 @@ pub enum IssueSeverity {
     Info,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
 pub enum IssueCategory {
     Security,
     Performance,
@@ -75,13 +126,3 @@ pub struct CodeSuggestion {
     pub suggested_code: String,
     pub reason: String,
 }
-
-impl Default for AssistantContext {
-    fn default() -> Self {
-        Self {
-            messages: Vec::new(),
-            project_info: None,
-            current_files: Vec::new(),
-        }
-    }
-} 
\ No newline at end of file
@@ -0,0 +1,70 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+use crate::assistant::types::AssistantContext;
+use super::expand_home;
+
+/// 세션을 Obsidian 보관함(마크다운 폴더)에 노트로 씁니다. 파일 이름은 첫 메시지 시각과 첫 질문으로
+/// 정해지므로 같은 세션을 다시 저장하면 같은 노트를 갱신합니다. 대화가 없으면 None을 반환합니다
+pub fn write_vault_note(context: &AssistantContext, vault: &Path, model: &str) -> Result<Option<PathBuf>> {
+    let messages: Vec<_> = context.messages.iter()
+        .filter(|m| m.role == "user" || m.role == "assistant")
+        .collect();
+    let (Some(first), Some(last)) = (messages.first(), messages.last()) else {
+        return Ok(None);
+    };
+    let started: DateTime<Local> = first.timestamp.into();
+    let updated: DateTime<Local> = last.timestamp.into();
+    let title = note_title(context);
+
+    let dir = expand_home(vault);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("보관함 디렉토리 생성 실패: {}", dir.display()))?;
+    let path = dir.join(format!("{} {}.md", started.format("%Y-%m-%d %H%M"), title));
+
+    let mut tags = vec!["ricci".to_string(), format!("ricci/{}", context.chat_mode.label())];
+    if let Some(persona) = &context.persona {
+        tags.push(format!("ricci/persona/{}", persona.replace(' ', "-")));
+    }
+    // 문자열은 JSON 따옴표로 감싸 YAML에서도 그대로 읽히게 함
+    let mut note = format!(
+        "---\ntitle: {}\ndate: {}\nupdated: {}\nmodel: {}\nmode: {}\nmessages: {}\ntags: [{}]\n---\n\n# {}\n",
+        serde_json::to_string(&title)?,
+        started.format("%Y-%m-%dT%H:%M:%S%:z"),
+        updated.format("%Y-%m-%dT%H:%M:%S%:z"),
+        serde_json::to_string(model)?,
+        context.chat_mode.label(),
+        messages.len(),
+        tags.join(", "),
+        title,
+    );
+    if let Some(info) = &context.project_info {
+        note.push_str(&format!("\n> 프로젝트: {} ({})\n", info.name, info.language));
+    }
+    for message in messages {
+        let time: DateTime<Local> = message.timestamp.into();
+        let speaker = if message.role == "user" { "질문" } else { "답변" };
+        note.push_str(&format!("\n## {} · {}\n\n{}\n", speaker, time.format("%H:%M"), message.content.trim()));
+    }
+
+    std::fs::write(&path, note).with_context(|| format!("노트 저장 실패: {}", path.display()))?;
+    Ok(Some(path))
+}
+
+/// 첫 질문의 첫 줄로 만든 노트 제목 (Obsidian 파일 이름에 쓸 수 없는 문자는 뺌)
+fn note_title(context: &AssistantContext) -> String {
+    let first_line = context.messages.iter()
+        .find(|m| m.role == "user")
+        .and_then(|m| m.content.lines().find(|line| !line.trim().is_empty()))
+        .unwrap_or("ricci 세션");
+    let cleaned: String = first_line
+        .chars()
+        .filter(|c| !matches!(c, '[' | ']' | '#' | '^' | '|' | '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>'))
+        .collect();
+    let title: String = cleaned.split_whitespace().collect::<Vec<_>>().join(" ").chars().take(50).collect();
+    if title.is_empty() {
+        "ricci 세션".to_string()
+    } else {
+        title
+    }
+}
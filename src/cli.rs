@@ -1,6 +1,19 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap::builder::PossibleValuesParser;
 use clap_complete::Shell;
+use std::path::PathBuf;
+use crate::assistant::{IssueCategory, IssueSeverity, ReviewGroupBy};
+use crate::config::ModelPreferences;
 use crate::handlers::config::ConfigAction;
+use crate::handlers::docs::DocsAction;
+use crate::handlers::completion::CompletionKind;
+use crate::handlers::plan::PlanAction;
+use crate::handlers::session::SessionAction;
+use crate::handlers::context::ContextAction;
+use crate::handlers::deps::DepsAction;
+use crate::handlers::usage::UsageAction;
+use crate::integrations::SHARE_TARGETS;
+use crate::renderer::OutputFormat;
 
 #[derive(Parser)]
 #[clap(name = "ricci")]
@@ -12,6 +25,73 @@ pub struct Cli {
     /// 직접 질문하기 (서브커맨드 없이)
     #[clap(value_name = "QUERY")]
     pub query: Option<String>,
+    
+    #[clap(flatten)]
+    pub generation: GenerationArgs,
+    
+    /// 제공자 요청/응답 원문을 지정한 디렉토리에 기록 (`ricci replay`로 재생)
+    #[clap(long, global = true, value_name = "DIR")]
+    pub record: Option<PathBuf>,
+    
+    /// 큰 프롬프트의 예상 비용 확인을 묻지 않고 바로 전송
+    #[clap(short = 'y', long, global = true)]
+    pub yes: bool,
+    
+    /// 이모지, 상자 그리기 문자, 스플래시 아트와 색상 없이 출력 (화면 낭독기, 단순 터미널용)
+    #[clap(long, global = true)]
+    pub plain: bool,
+    
+    /// 파일 쓰기와 셸 명령어/린터/훅 실행을 막고 읽기만 함 (운영 체크아웃 점검용)
+    #[clap(long, global = true)]
+    pub read_only: bool,
+    
+    /// 응답 출력 형식: terminal(구문 강조), plain(스타일 없는 텍스트), json(이벤트당 JSON 한 줄)
+    #[clap(long, global = true, value_enum, default_value_t)]
+    pub output_format: OutputFormat,
+}
+
+/// 이번 실행에 한해 설정을 덮어쓰는 생성 파라미터
+#[derive(Args, Debug, Default)]
+pub struct GenerationArgs {
+    /// 생성 중단 시퀀스 (여러 번 지정 가능, 최대 4개)
+    #[clap(long, global = true)]
+    pub stop: Vec<String>,
+    
+    /// nucleus 샘플링 확률 (0.0-1.0)
+    #[clap(long, global = true)]
+    pub top_p: Option<f32>,
+    
+    /// 반복 토큰 페널티 (-2.0-2.0)
+    #[clap(long, global = true, allow_hyphen_values = true)]
+    pub frequency_penalty: Option<f32>,
+    
+    /// 새 주제 유도 페널티 (-2.0-2.0)
+    #[clap(long, global = true, allow_hyphen_values = true)]
+    pub presence_penalty: Option<f32>,
+    
+    /// 재현 가능한 샘플링을 위한 시드
+    #[clap(long, global = true)]
+    pub seed: Option<i64>,
+}
+
+impl GenerationArgs {
+    pub fn apply(&self, prefs: &mut ModelPreferences) {
+        if !self.stop.is_empty() {
+            prefs.stop = self.stop.clone();
+        }
+        if self.top_p.is_some() {
+            prefs.top_p = self.top_p;
+        }
+        if self.frequency_penalty.is_some() {
+            prefs.frequency_penalty = self.frequency_penalty;
+        }
+        if self.presence_penalty.is_some() {
+            prefs.presence_penalty = self.presence_penalty;
+        }
+        if self.seed.is_some() {
+            prefs.seed = self.seed;
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -25,20 +105,34 @@ pub enum Commands {
         /// 세션 저장 경로
         #[clap(short, long)]
         save: Option<String>,
+        
+        /// 시작할 때 적용할 공유 페르소나 (`/persona`와 동일)
+        #[clap(long, value_name = "PERSONA")]
+        persona: Option<String>,
     },
-    /// 작업계획서 생성
+    /// 작업계획서 생성 (생성한 계획은 데이터 디렉토리의 `plans`에 저장)
+    #[clap(args_conflicts_with_subcommands = true)]
     Plan {
+        #[clap(subcommand)]
+        action: Option<PlanAction>,
         /// 프로젝트 설명 또는 요구사항
-        description: String,
-        /// 출력 형식 (markdown, json, yaml)
+        #[clap(required = true)]
+        description: Option<String>,
+        /// 출력 형식 (markdown, json, yaml, table, mermaid, csv)
         #[clap(short, long, default_value = "markdown")]
         format: String,
-        /// 상세 레벨 (1-5)
-        #[clap(short, long, default_value = "3")]
+        /// 상세 레벨 (1 단계만, 2 작업, 3 완료 기준, 4 하위 작업, 5 추정·담당·자원·테스트 전략)
+        #[clap(short, long, default_value = "3", value_parser = clap::value_parser!(u8).range(1..=5))]
         detail: u8,
         /// 일정 추정 포함
         #[clap(short, long)]
         estimate: bool,
+        /// 출력을 파일로 저장 (예: `-f csv -o plan.csv`)
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+        /// 결과 마크다운을 팀 채널로 공유하거나 Confluence에 게시 (`[integrations]` 설정 필요)
+        #[clap(long, value_name = "TARGET", value_parser = PossibleValuesParser::new(SHARE_TARGETS))]
+        share: Option<String>,
     },
     
     /// 프로젝트 분석
@@ -47,19 +141,149 @@ pub enum Commands {
         #[clap(default_value = ".")]
         path: String,
         
-        /// 분석 유형 (structure, dependencies, complexity, all)
+        /// 분석 유형 (structure, dependencies, complexity, tree, all)
         #[clap(short, long, default_value = "all")]
         type_: String,
+        
+        /// 지표 스냅샷을 snapshot_<시각>.json으로 저장
+        #[clap(long)]
+        save_snapshot: bool,
+        
+        /// 이전 스냅샷과 지표 비교
+        #[clap(long, value_name = "SNAPSHOT")]
+        compare: Option<String>,
+        
+        /// 결과 마크다운을 팀 채널로 공유하거나 Confluence에 게시 (`[integrations]` 설정 필요)
+        #[clap(long, value_name = "TARGET", value_parser = PossibleValuesParser::new(SHARE_TARGETS))]
+        share: Option<String>,
+    },
+    
+    /// 여러 모델에 같은 질문을 동시에 보내 답변 비교
+    Compare {
+        /// 비교할 질문
+        prompt: String,
+        
+        /// 비교할 모델 목록 (쉼표 구분, 최대 3개)
+        #[clap(short, long, value_delimiter = ',', value_name = "MODEL")]
+        models: Vec<String>,
+    },
+    
+    /// 심볼 이름을 프로젝트 전체에서 바꾸기 (문자열 안의 이름만 AI가 판단)
+    Rename {
+        /// 바꿀 이름
+        old: String,
+        
+        /// 새 이름
+        new: String,
+        
+        /// 검색할 파일 또는 디렉토리
+        #[clap(default_value = ".")]
+        path: String,
+        
+        /// 문자열 리터럴 안의 이름을 AI에게 묻지 않고 그대로 둠
+        #[clap(long)]
+        no_ai: bool,
+    },
+    
+    /// 의존성 정보 조회
+    Deps {
+        #[clap(subcommand)]
+        action: DepsAction,
+    },
+    
+    /// 의존성 버전을 올리고 빌드가 성공할 때까지 깨진 호출 지점을 AI로 고치기
+    Upgrade {
+        /// 올릴 의존성 이름 (Cargo.toml 또는 package.json)
+        dependency: String,
+        
+        /// 올릴 버전 (없으면 레지스트리의 최신 버전)
+        #[clap(long, value_name = "VERSION")]
+        to: Option<String>,
+        
+        /// 프로젝트 디렉토리
+        #[clap(long, default_value = ".")]
+        path: String,
+        
+        /// 빌드 오류를 고치는 최대 횟수
+        #[clap(long, default_value_t = 5)]
+        max_rounds: usize,
     },
     
     /// 코드 리뷰
     Review {
-        /// 리뷰할 파일 또는 디렉토리
+        /// 리뷰할 파일 또는 디렉토리 (`src/lib.rs:100-250`처럼 줄 범위 지정 가능)
         path: String,
         
-        /// 리뷰 기준 (security, performance, style, all)
+        /// 리뷰 기준 (security, performance, style, all). 쉼표로 여러 개를 지정하면 기준별로 동시에 리뷰
         #[clap(short, long, default_value = "all")]
         criteria: String,
+        
+        /// 설치된 린터(cargo clippy, eslint, ruff) 결과를 리뷰에 합치기
+        #[clap(long)]
+        lint: bool,
+        
+        /// 바뀌지 않은 파일도 캐시된 결과 대신 새로 리뷰
+        #[clap(long)]
+        no_cache: bool,
+        
+        /// 결과 마크다운을 팀 채널로 공유하거나 Confluence에 게시 (`[integrations]` 설정 필요)
+        #[clap(long, value_name = "TARGET", value_parser = PossibleValuesParser::new(SHARE_TARGETS))]
+        share: Option<String>,
+        
+        /// 리뷰하지 않고 경로 아래 파일의 점수 추이만 보기
+        #[clap(long, conflicts_with_all = ["lint", "no_cache", "share"])]
+        history: bool,
+        
+        /// 이 분류의 문제만 표시 (쉼표로 여러 개, 예: security,bug)
+        #[clap(long, value_enum, value_delimiter = ',', value_name = "CATEGORY", conflicts_with = "history")]
+        only: Vec<IssueCategory>,
+        
+        /// 이 심각도 이상의 문제만 표시
+        #[clap(long, value_enum, value_name = "SEVERITY", conflicts_with = "history")]
+        min_severity: Option<IssueSeverity>,
+        
+        /// 문제를 파일 또는 심각도별로 묶어 표시
+        #[clap(long, value_enum, value_name = "KEY", conflicts_with = "history")]
+        group_by: Option<ReviewGroupBy>,
+        
+        /// 리뷰 후 번호로 고른 문제를 더 자세히 설명하고 수정 예시를 보여주는 대화형 모드
+        #[clap(long, conflicts_with = "history")]
+        explain: bool,
+        
+        /// 리뷰 후 고른 문제를 모델이 고치게 하고 변경 블록마다 확인받아 적용
+        #[clap(long, conflicts_with = "history")]
+        apply: bool,
+        
+        /// 리뷰 문제를 해당 줄 위에 TODO/FIXME 주석으로 달기 (백업 후 적용)
+        #[clap(long, conflicts_with_all = ["history", "apply"])]
+        annotate: bool,
+        
+        /// 점수 배지(SVG)를 파일로 저장 (README용, `--history`와 함께 쓰면 마지막 기록 점수 사용)
+        #[clap(long, value_name = "FILE")]
+        badge: Option<PathBuf>,
+        
+        /// 점수(디렉토리면 가장 낮은 파일 점수)가 이 값보다 낮으면 종료 코드 4로 실패 (CI용)
+        #[clap(long, value_name = "SCORE", conflicts_with = "history")]
+        fail_under: Option<f32>,
+    },
+    
+    /// 정적 패턴 검사 + AI 분류로 보안 취약점 스캔
+    Scan {
+        /// 검사할 파일 또는 디렉토리
+        #[clap(default_value = ".")]
+        path: String,
+        
+        /// 보안 취약점 스캔 (unsafe, eval, SQL 문자열 조합, 명령 주입 등)
+        #[clap(long)]
+        security: bool,
+        
+        /// AI 분류 없이 패턴 검사 결과만 보고
+        #[clap(long)]
+        offline: bool,
+        
+        /// 보고서를 마크다운 파일로 저장
+        #[clap(short, long)]
+        output: Option<PathBuf>,
     },
     
     /// 문서 생성
@@ -67,9 +291,33 @@ pub enum Commands {
         /// 문서화할 대상
         target: String,
         
-        /// 문서 유형 (api, guide, readme, architecture)
+        /// 문서 유형 (api, guide, readme, architecture, openapi)
         #[clap(short, long, default_value = "readme")]
         type_: String,
+        
+        /// 문서 구조 템플릿 (없으면 .ricci/templates/<유형>.md, 설정의 [doc_templates], ricci sync로 받은 팀 템플릿 순으로 찾음)
+        #[clap(long)]
+        template: Option<PathBuf>,
+        
+        /// 결과를 저장할 파일 (openapi는 기본값 <대상>/openapi.yaml)
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+        
+        /// 기존 README를 병합할 때 다시 작성할 섹션 제목 (쉼표로 구분, 없으면 보존 구역 외 전체)
+        #[clap(long, value_delimiter = ',', value_name = "SECTION")]
+        sections: Vec<String>,
+        
+        /// 문서를 생성하지 않고 문서 주석/README가 현재 코드와 맞는지 확인
+        #[clap(long)]
+        check: bool,
+        
+        /// --check에서 찾은 오래된 문서의 수정안을 받아 diff로 확인 후 적용
+        #[clap(long, requires = "check")]
+        fix: bool,
+        
+        /// 생성한 문서를 팀 채널로 공유하거나 Confluence에 게시 (`[integrations]` 설정 필요)
+        #[clap(long, value_name = "TARGET", value_parser = PossibleValuesParser::new(SHARE_TARGETS), conflicts_with = "check")]
+        share: Option<String>,
     },
     
     /// 설정 관리
@@ -78,6 +326,67 @@ pub enum Commands {
         action: ConfigAction,
     },
     
+    /// 세션 관리
+    Session {
+        #[clap(subcommand)]
+        action: SessionAction,
+    },
+    
+    /// 작업 영역별 문맥 묶음(첨부 파일, 페르소나, 기억) 관리. 대화 중 `/context load <이름>`으로 불러옴
+    Context {
+        #[clap(subcommand)]
+        action: ContextAction,
+    },
+    
+    /// 팀 공유 페르소나/프롬프트 템플릿/리뷰 규칙 동기화 ([team] 설정)
+    Sync {
+        /// 버전이 같아도 다시 가져오기
+        #[clap(long)]
+        force: bool,
+    },
+    
+    /// 제공자별 사용 가능한 모델 목록 조회 및 기본 모델 설정
+    Models {
+        /// 조회할 제공자 (openai, anthropic, gemini; 비워두면 키가 설정된 모든 제공자)
+        #[clap(short, long)]
+        provider: Option<String>,
+    },
+    
+    /// 누적 예상 비용과 예산 한도 확인 ([budget] 설정)
+    Usage {
+        #[clap(subcommand)]
+        action: Option<UsageAction>,
+    },
+    
+    /// 최근 git 커밋과 세션 대화로 데일리 스탠드업 요약 (한 일 / 할 일 / 블로커)
+    Standup {
+        /// 기간 시작 (yesterday, today, 3d, 12h, 1w, 2024-05-01)
+        #[clap(long, default_value = "yesterday")]
+        since: String,
+    },
+    
+    /// 설정, API 키, 터미널, git 등 실행 환경 진단
+    Doctor,
+    
+    /// `--record`로 기록한 세션을 토큰 소모 없이 다시 렌더링
+    Replay {
+        /// 기록 디렉토리
+        dir: PathBuf,
+    },
+    
+    /// 쉘 자동완성 스크립트가 호출하는 동적 후보 출력 (모델, 세션, 페르소나)
+    #[clap(name = "complete-values", hide = true)]
+    Complete {
+        #[clap(value_enum)]
+        kind: CompletionKind,
+    },
+    
+    /// man 페이지와 명령어 레퍼런스 등 배포용 문서 생성
+    Docs {
+        #[clap(subcommand)]
+        action: DocsAction,
+    },
+    
     /// 쉘 완성 스크립트 생성
     Completion {
         /// 대상 쉘
@@ -90,6 +399,10 @@ pub enum Commands {
         /// 대상 쉘 (자동 감지하려면 비워두세요)
         #[clap(value_enum)]
         shell: Option<Shell>,
+        
+        /// 설치한 자동완성 파일과 rc 파일 설정을 제거 (쉘을 비우면 모든 쉘)
+        #[arg(long)]
+        uninstall: bool,
     },
     
     /// 고급 코드 어시스턴트 - 코드 분석, 리팩토링, 최적화, 보안 검사
@@ -108,7 +421,42 @@ pub enum Commands {
         
         #[arg(long, short = 'd', help = "문서화 생성")]
         docs: bool,
+        
+        /// 옵션 선택 메뉴 대신 쓸 프리셋 (default, security, quality, performance, full 또는 설정의 [code_assist_presets])
+        #[arg(long, conflicts_with = "options")]
+        preset: Option<String>,
+        
+        /// 켤 옵션 목록 (analyze, refactor, optimize, security, test, docs, fix 중 쉼표로 구분)
+        #[arg(long, value_delimiter = ',')]
+        options: Vec<String>,
+        
+        /// 경로 아래에서 이 심볼의 정의와 사용 위치를 모아 여러 파일을 함께 고치는 모드 (`--goal` 필요)
+        #[arg(long, requires = "goal", conflicts_with_all = ["preset", "options", "fix", "test", "docs"])]
+        symbol: Option<String>,
+        
+        /// `--symbol`로 할 작업 (예: "load_config로 이름 바꾸기")
+        #[arg(long, requires = "symbol")]
+        goal: Option<String>,
     },
     
 
-} 
\ No newline at end of file
+}
+
+impl Cli {
+    /// 명령줄을 읽고, 실행할 하위 명령어의 이름(clap에 등록한 이름, 예: `code-assist`)을 함께 반환합니다.
+    /// 이름은 훅 이벤트 JSON의 `command` 필드와 `[notifications.commands]`의 키로 씁니다.
+    /// 잘못된 사용법은 종료하지 않고 오류로 돌려주므로 호출하는 쪽에서 종료 코드를 정합니다
+    pub fn try_parse_with_command_name() -> Result<(Self, Option<String>), clap::Error> {
+        let matches = Self::command().try_get_matches()?;
+        let cli = Self::from_arg_matches(&matches)?;
+        let name = matches.subcommand_name().map(str::to_string);
+        Ok((cli, name))
+    }
+}
+
+impl Commands {
+    /// 사용자가 직접 끝낼 때까지 이어지는 대화형 명령어 (실행 시간 알림 대상이 아님)
+    pub fn is_interactive(&self) -> bool {
+        matches!(self, Commands::Chat { .. } | Commands::Plan { action: Some(PlanAction::Dashboard), .. })
+    }
+}
@@ -0,0 +1,111 @@
+use toml::{Table, Value};
+
+const PREFIX: &str = "RICCI_";
+
+/// 자주 쓰는 필드의 짧은 환경 변수 이름
+const ALIASES: &[(&str, &[&str])] = &[
+    ("RICCI_PROVIDER", &["model_preferences", "default_provider"]),
+    ("RICCI_MODEL", &["model_preferences", "default_model"]),
+    ("RICCI_TEMPERATURE", &["model_preferences", "temperature"]),
+    ("RICCI_MAX_TOKENS", &["model_preferences", "max_tokens"]),
+    ("RICCI_LANGUAGE", &["output_preferences", "language"]),
+    ("RICCI_THEME", &["output_preferences", "theme"]),
+    ("RICCI_SESSION_DIR", &["output_preferences", "session_dir"]),
+    ("RICCI_DATA_DIR", &["data_dir"]),
+];
+
+/// 설정에 반영하지 않는 ricci 환경 변수 (다른 용도). 훅이 실행한 셸에서 ricci를 다시 실행해도
+/// 훅에 넘긴 `RICCI_EVENT`가 설정 항목으로 읽히지 않도록 함
+const RESERVED: &[&str] = &[super::GATEWAY_TOKEN_ENV, crate::hooks::EVENT_ENV];
+
+/// 환경 변수로 덮어쓴 설정 항목
+#[derive(Debug, Clone)]
+pub struct EnvOverride {
+    pub name: String,
+    pub path: Vec<String>,
+}
+
+/// `RICCI_*` 환경 변수를 설정 테이블에 덮어씁니다. 적용한 항목을 반환합니다.
+///
+/// 우선순위: 기본값 < 설정 파일 < 환경 변수 < 명령줄 옵션.
+/// 짧은 이름(`RICCI_MODEL` 등) 외의 필드는 `RICCI_<섹션>__<필드>` 형식으로 지정합니다
+/// (예: `RICCI_OUTPUT_PREFERENCES__SHOW_STATS=true`, `RICCI_MODELS__OPENAI__MODEL=gpt-4o`)
+pub fn apply_env_overrides(table: &mut Table) -> Vec<EnvOverride> {
+    let mut vars: Vec<(String, String)> = std::env::vars()
+        .filter(|(name, _)| name.starts_with(PREFIX) && !RESERVED.contains(&name.as_str()))
+        .collect();
+    // 같은 필드를 긴 이름과 짧은 이름으로 모두 지정하면 짧은 이름이 이김
+    vars.sort_by_key(|(name, _)| ALIASES.iter().any(|(alias, _)| alias == name));
+
+    let mut applied = Vec::new();
+    for (name, raw) in vars {
+        let path: Vec<String> = match ALIASES.iter().find(|(alias, _)| *alias == name) {
+            Some((_, path)) => path.iter().map(|s| s.to_string()).collect(),
+            None => name[PREFIX.len()..].to_lowercase().split("__").map(str::to_string).collect(),
+        };
+        if path.iter().any(|segment| segment.is_empty()) {
+            continue;
+        }
+        if set_path(table, &path, &raw) {
+            applied.push(EnvOverride { name, path });
+        }
+    }
+    applied.sort_by(|a, b| a.name.cmp(&b.name));
+    applied
+}
+
+/// 저장할 때 환경 변수로 덮어쓴 항목은 파일에 있던 값으로 되돌립니다 (환경 변수 값이 파일에 남지 않도록)
+pub fn restore_overridden(table: &mut Table, file: &Table, overrides: &[EnvOverride]) {
+    for entry in overrides {
+        let original = lookup(file, &entry.path).cloned();
+        let (last, parents) = entry.path.split_last().expect("빈 경로");
+        let parent = parents.iter()
+            .try_fold(&mut *table, |current, segment| current.get_mut(segment)?.as_table_mut());
+        let Some(current) = parent else { continue };
+        match original {
+            Some(value) => {
+                current.insert(last.clone(), value);
+            }
+            None => {
+                current.remove(last);
+            }
+        }
+    }
+}
+
+fn lookup<'a>(table: &'a Table, path: &[String]) -> Option<&'a Value> {
+    let (last, parents) = path.split_last()?;
+    let mut current = table;
+    for segment in parents {
+        current = current.get(segment)?.as_table()?;
+    }
+    current.get(last)
+}
+
+/// 경로의 값을 설정합니다. 기존 값이 문자열이면 문자열로, 아니면 TOML 값으로 해석합니다
+fn set_path(table: &mut Table, path: &[String], raw: &str) -> bool {
+    let (last, parents) = path.split_last().expect("빈 경로");
+    let mut current = table;
+    for segment in parents {
+        let entry = current.entry(segment.clone())
+            .or_insert_with(|| Value::Table(Table::new()));
+        let Value::Table(next) = entry else {
+            return false;
+        };
+        current = next;
+    }
+
+    let value = match current.get(last) {
+        Some(Value::String(_)) => Value::String(raw.to_string()),
+        _ => parse_value(raw),
+    };
+    current.insert(last.clone(), value);
+    true
+}
+
+fn parse_value(raw: &str) -> Value {
+    toml::from_str::<Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut t| t.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
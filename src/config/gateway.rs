@@ -0,0 +1,167 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use crate::config::types::{ApiKeySource, Config};
+use crate::paths::data_dir;
+
+/// 게이트웨이 인증 토큰을 읽을 환경 변수 (Bearer 토큰으로 전달)
+pub const GATEWAY_TOKEN_ENV: &str = "RICCI_GATEWAY_TOKEN";
+
+/// 응답에 만료 정보가 없을 때 사용할 유효 시간
+const DEFAULT_TTL_SECS: i64 = 3600;
+/// 만료 직전 요청이 실패하지 않도록 이만큼 일찍 갱신
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+/// 게이트웨이 응답. 제공자별 키 또는 기본 제공자용 `api_key` 하나를 받습니다
+#[derive(Debug, Deserialize)]
+struct GatewayResponse {
+    #[serde(default)]
+    api_key: Option<String>,
+    #[serde(default)]
+    openai_api_key: Option<String>,
+    #[serde(default)]
+    anthropic_api_key: Option<String>,
+    #[serde(default)]
+    gemini_api_key: Option<String>,
+    /// 게이트웨이가 프록시를 제공할 때 요청을 보낼 주소
+    #[serde(default)]
+    base_url: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// 캐시된 단기 키 (데이터 디렉토리의 `gateway.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GatewayCredentials {
+    url: String,
+    openai_api_key: Option<String>,
+    anthropic_api_key: Option<String>,
+    gemini_api_key: Option<String>,
+    base_url: Option<String>,
+    expires_at: DateTime<Utc>,
+}
+
+impl GatewayCredentials {
+    fn is_fresh(&self, url: &str) -> bool {
+        self.url == url && self.expires_at - Duration::seconds(REFRESH_MARGIN_SECS) > Utc::now()
+    }
+
+    /// 받은 키는 저장하지 않는 `gateway_keys`에 두므로 `config.save()`를 해도 파일에 남지 않습니다
+    fn apply(&self, config: &mut Config) {
+        let keys = [
+            ("openai", &self.openai_api_key),
+            ("anthropic", &self.anthropic_api_key),
+            ("gemini", &self.gemini_api_key),
+        ];
+        for (provider, key) in keys {
+            if let Some(key) = key {
+                config.gateway_keys.insert(provider.to_string(), key.clone());
+            }
+        }
+        config.api_base = self.base_url.clone();
+    }
+}
+
+fn cache_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("gateway.json"))
+}
+
+/// `api_key_source = { rest_api = { url } }`이면 게이트웨이에서 단기 키를 받아 설정에 채웁니다.
+/// 받은 키는 만료 전까지 캐시하며 설정 파일에는 저장하지 않습니다
+pub async fn resolve_gateway_keys(config: &mut Config) -> Result<()> {
+    let ApiKeySource::RestApi { url } = &config.api_key_source else {
+        return Ok(());
+    };
+    let url = url.clone();
+
+    let cached = cache_path().ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|data| serde_json::from_str::<GatewayCredentials>(&data).ok())
+        .filter(|c| c.is_fresh(&url));
+
+    let credentials = match cached {
+        Some(credentials) => credentials,
+        None => {
+            let credentials = fetch_credentials(&url, &config.model_preferences.default_provider).await?;
+            save_cache(&credentials)?;
+            credentials
+        }
+    };
+
+    credentials.apply(config);
+    Ok(())
+}
+
+/// 캐시된 게이트웨이 키를 지워 다음 실행 때 새로 받도록 합니다
+pub fn clear_gateway_cache() -> Result<()> {
+    let path = cache_path()?;
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+async fn fetch_credentials(url: &str, default_provider: &str) -> Result<GatewayCredentials> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()?;
+    let mut request = client.get(url);
+    if let Ok(token) = std::env::var(GATEWAY_TOKEN_ENV) {
+        request = request.bearer_auth(token);
+    }
+
+    let response: GatewayResponse = request.send().await
+        .with_context(|| format!("키 게이트웨이 연결 실패: {}", url))?
+        .error_for_status()
+        .context("키 게이트웨이가 요청을 거부했습니다")?
+        .json().await
+        .context("키 게이트웨이 응답 형식이 올바르지 않습니다")?;
+
+    let mut credentials = GatewayCredentials {
+        url: url.to_string(),
+        openai_api_key: response.openai_api_key,
+        anthropic_api_key: response.anthropic_api_key,
+        gemini_api_key: response.gemini_api_key,
+        base_url: response.base_url,
+        expires_at: response.expires_at.unwrap_or_else(|| {
+            Utc::now() + Duration::seconds(response.expires_in.unwrap_or(DEFAULT_TTL_SECS))
+        }),
+    };
+
+    // 제공자를 지정하지 않은 키는 기본 제공자용
+    if let Some(key) = response.api_key {
+        match default_provider {
+            "anthropic" => credentials.anthropic_api_key = Some(key),
+            "gemini" => credentials.gemini_api_key = Some(key),
+            _ => credentials.openai_api_key = Some(key),
+        }
+    }
+
+    if credentials.openai_api_key.is_none()
+        && credentials.anthropic_api_key.is_none()
+        && credentials.gemini_api_key.is_none()
+    {
+        anyhow::bail!("키 게이트웨이 응답에 API 키가 없습니다");
+    }
+    Ok(credentials)
+}
+
+fn save_cache(credentials: &GatewayCredentials) -> Result<()> {
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(credentials)?)
+        .with_context(|| format!("게이트웨이 키 캐시 저장 실패: {}", path.display()))?;
+
+    // 단기 키라도 다른 사용자가 읽지 못하도록
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
@@ -2,39 +2,105 @@ use anyhow::{Result, Context};
 use std::fs;
 use std::path::PathBuf;
 use colored::*;
+use crate::renderer::plain;
 use crate::config::types::{Config, ApiKeySource};
+use crate::config::migration::{upgrade_config, CONFIG_VERSION};
+use crate::config::env::{apply_env_overrides, restore_overridden};
 
 pub fn load_config() -> Result<Config> {
     let config_path = get_config_path()?;
     
     if config_path.exists() {
-        println!("{} {}", "설정 파일 로드 중:".dimmed(), config_path.display());
+        // 표준 출력은 응답 전용 (`--output-format json`을 파이프로 읽을 수 있게)
+        eprintln!("{} {}", "설정 파일 로드 중:".dimmed(), config_path.display());
         
         let content = fs::read_to_string(&config_path)
             .context("설정 파일 읽기 실패")?;
         
-        toml::from_str(&content)
-            .context("설정 파일 파싱 실패")
-    } else {
-        println!("{}", "기본 설정 사용 중".yellow());
-        let mut config = Config::default();
-        
-        // 환경 변수에서 API 키 로드
-        if let Ok(key) = std::env::var("OPENAI_API_KEY") {
-            config.openai_api_key = Some(key);
-            config.api_key_source = ApiKeySource::Environment;
-        }
+        let (config, from_version, file_table) = parse_versioned(&content)?;
         
-        if let Ok(key) = std::env::var("ANTHROPIC_API_KEY") {
-            config.anthropic_api_key = Some(key);
-        }
-        
-        if let Ok(key) = std::env::var("GEMINI_API_KEY") {
-            config.gemini_api_key = Some(key);
+        // 이전 형식이면 원본을 백업하고 새 형식으로 저장 (환경 변수 덮어쓰기 전 값으로)
+        if from_version < CONFIG_VERSION {
+            let backup = config_path.with_extension(format!("toml.v{}.bak", from_version));
+            fs::copy(&config_path, &backup)
+                .context("설정 파일 백업 실패")?;
+            let file_config: Config = file_table.try_into().context("설정 파일 파싱 실패")?;
+            fs::write(&config_path, toml::to_string_pretty(&file_config).context("설정 직렬화 실패")?)
+                .context("설정 파일 저장 실패")?;
+            eprintln!("{} 버전 {} {} {} (백업: {})",
+                "설정 파일 형식 업그레이드:".yellow(), from_version, plain("→"), CONFIG_VERSION, backup.display());
         }
         
         Ok(config)
+    } else {
+        eprintln!("{}", "기본 설정 사용 중".yellow());
+        default_config()
+    }
+}
+
+/// 안내 문구나 형식 업그레이드 없이 설정을 읽습니다. 파일이 없거나 깨져 있으면 기본 설정.
+/// 상태 경로처럼 설정 검증보다 먼저 정해야 하는 값에 씁니다
+pub fn load_config_lenient() -> Config {
+    get_config_path().ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| parse_config(&content).ok())
+        .or_else(|| default_config().ok())
+        .unwrap_or_default()
+}
+
+/// 설정 파일이 없을 때의 설정 (환경 변수의 API 키와 `RICCI_*` 덮어쓰기 반영)
+fn default_config() -> Result<Config> {
+    let mut config = Config::default();
+    
+    // 환경 변수에서 API 키 로드
+    if let Ok(key) = std::env::var("OPENAI_API_KEY") {
+        config.openai_api_key = Some(key);
+        config.api_key_source = ApiKeySource::Environment;
+    }
+    
+    if let Ok(key) = std::env::var("ANTHROPIC_API_KEY") {
+        config.anthropic_api_key = Some(key);
+    }
+    
+    if let Ok(key) = std::env::var("GEMINI_API_KEY") {
+        config.gemini_api_key = Some(key);
     }
+    
+    let mut table = toml::Table::try_from(&config).context("기본 설정 직렬화 실패")?;
+    let overrides = apply_env_overrides(&mut table);
+    if overrides.is_empty() {
+        return Ok(config);
+    }
+    let mut config = table.try_into::<Config>()
+        .context("RICCI_* 환경 변수 값이 올바르지 않습니다")?;
+    config.env_overrides = overrides;
+    Ok(config)
+}
+
+/// 설정 파일 내용을 현재 버전으로 올려 읽습니다 (파일은 건드리지 않음)
+pub fn parse_config(content: &str) -> Result<Config> {
+    parse_versioned(content).map(|(config, _, _)| config)
+}
+
+/// 설정 파일을 읽어 (환경 변수까지 반영한 설정, 원래 버전, 환경 변수 반영 전 테이블)을 반환합니다
+fn parse_versioned(content: &str) -> Result<(Config, u32, toml::Table)> {
+    let mut table = read_file_table(content)?;
+    let from_version = upgrade_config(&mut table)?;
+    let file_table = table.clone();
+    
+    let overrides = apply_env_overrides(&mut table);
+    let mut config: Config = table.try_into()
+        .context(if overrides.is_empty() {
+            "설정 파일 파싱 실패"
+        } else {
+            "설정 파일 파싱 실패 (RICCI_* 환경 변수 값도 확인하세요)"
+        })?;
+    config.env_overrides = overrides;
+    Ok((config, from_version, file_table))
+}
+
+fn read_file_table(content: &str) -> Result<toml::Table> {
+    toml::from_str(content).context("설정 파일 파싱 실패")
 }
 
 pub fn save_config(config: &Config) -> Result<()> {
@@ -45,8 +111,19 @@ pub fn save_config(config: &Config) -> Result<()> {
             .context("설정 디렉토리 생성 실패")?;
     }
     
-    let content = toml::to_string_pretty(config)
-        .context("설정 직렬화 실패")?;
+    let content = if config.env_overrides.is_empty() {
+        toml::to_string_pretty(config)
+    } else {
+        let mut table = toml::Table::try_from(config)
+            .context("설정 직렬화 실패")?;
+        let mut file_table = fs::read_to_string(&config_path).ok()
+            .and_then(|content| read_file_table(&content).ok())
+            .unwrap_or_default();
+        let _ = upgrade_config(&mut file_table);
+        restore_overridden(&mut table, &file_table, &config.env_overrides);
+        let restored: Config = table.try_into().context("설정 직렬화 실패")?;
+        toml::to_string_pretty(&restored)
+    }.context("설정 직렬화 실패")?;
     
     fs::write(&config_path, content)
         .context("설정 파일 저장 실패")?;
@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use toml::{Table, Value};
+use crate::config::types::Config;
+
+/// 현재 설정 파일 형식 버전. `Config`의 모양이 바뀌면 올리고 `MIGRATIONS`에 단계를 추가합니다
+pub const CONFIG_VERSION: u32 = 2;
+
+/// `MIGRATIONS[n]`은 버전 n 파일을 n + 1로 올립니다
+const MIGRATIONS: &[fn(&mut Table)] = &[
+    migrate_v0_provider_models,
+    migrate_v1_session_dir,
+];
+
+/// 읽은 설정 파일을 현재 버전으로 올리고 빠진 필드를 기본값으로 채웁니다.
+/// 원래 파일의 버전을 반환합니다 (`version`이 없으면 0)
+pub fn upgrade_config(table: &mut Table) -> Result<u32> {
+    let from = match table.get("version") {
+        Some(value) => value.as_integer()
+            .and_then(|v| u32::try_from(v).ok())
+            .context("설정 파일의 version 값이 올바르지 않습니다")?,
+        None => 0,
+    };
+    if from > CONFIG_VERSION {
+        anyhow::bail!(
+            "설정 파일 버전({})이 이 ricci가 지원하는 버전({})보다 높습니다. ricci를 업데이트하세요",
+            from, CONFIG_VERSION
+        );
+    }
+
+    for migration in &MIGRATIONS[from as usize..] {
+        migration(table);
+    }
+
+    let defaults = Table::try_from(Config::default()).context("기본 설정 직렬화 실패")?;
+    fill_missing(table, &defaults);
+    table.insert("version".to_string(), Value::Integer(CONFIG_VERSION.into()));
+    Ok(from)
+}
+
+/// 파일에 없는 키를 기본값에서 재귀적으로 채웁니다 (있는 값은 그대로 둠)
+fn fill_missing(target: &mut Table, defaults: &Table) {
+    for (key, default) in defaults {
+        match (target.get_mut(key), default) {
+            (Some(Value::Table(existing)), Value::Table(default)) => fill_missing(existing, default),
+            (Some(_), _) => {}
+            (None, _) => {
+                target.insert(key.clone(), default.clone());
+            }
+        }
+    }
+}
+
+/// v0 → v1: 공용 `default_model`을 현재 제공자의 `[models.<provider>]`로 옮겨
+/// 제공자를 바꿔도 제공자마다 고른 모델이 유지되도록 합니다
+fn migrate_v0_provider_models(table: &mut Table) {
+    let Some(prefs) = table.get("model_preferences").and_then(Value::as_table) else {
+        return;
+    };
+    let (Some(provider), Some(model)) = (
+        prefs.get("default_provider").and_then(Value::as_str).map(str::to_string),
+        prefs.get("default_model").and_then(Value::as_str).map(str::to_string),
+    ) else {
+        return;
+    };
+
+    let models = table.entry("models")
+        .or_insert_with(|| Value::Table(Table::new()));
+    if let Value::Table(models) = models {
+        let entry = models.entry(provider)
+            .or_insert_with(|| Value::Table(Table::new()));
+        if let Value::Table(entry) = entry {
+            entry.entry("model").or_insert(Value::String(model));
+        }
+    }
+}
+
+/// v1 → v2: 예전 기본값으로 채워져 실제로는 쓰이지 않던 `session_dir`(`<data_local_dir>/ricci`)을 지워
+/// 세션이 데이터 디렉토리 아래 `sessions`에 저장되도록 합니다. 사용자가 바꾼 값은 그대로 둡니다
+fn migrate_v1_session_dir(table: &mut Table) {
+    let Some(prefs) = table.get_mut("output_preferences").and_then(Value::as_table_mut) else {
+        return;
+    };
+    let old_default = dirs::data_local_dir().map(|dir| dir.join("ricci"));
+    let is_old_default = prefs.get("session_dir")
+        .and_then(Value::as_str)
+        .is_some_and(|dir| old_default.as_deref() == Some(std::path::Path::new(dir)));
+    if is_old_default {
+        prefs.remove("session_dir");
+    }
+}
@@ -1,14 +1,72 @@
-use serde::{Serialize, Deserialize};
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use crate::renderer::OutputFormat;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// 설정 파일 형식 버전 (없으면 0, 로드할 때 현재 버전으로 올림)
+    #[serde(default)]
+    pub version: u32,
     pub openai_api_key: Option<String>,
     pub anthropic_api_key: Option<String>,
     pub gemini_api_key: Option<String>,
     pub api_key_source: ApiKeySource,
     pub model_preferences: ModelPreferences,
+    /// 제공자별 모델 설정 (`[models.openai]`, `[models.anthropic]` 등)
+    #[serde(default)]
+    pub models: BTreeMap<String, ProviderModelConfig>,
+    /// 대화 모드별 생성 설정 (`[modes.code]`, `[modes.planning]` 등)
+    #[serde(default)]
+    pub modes: BTreeMap<String, ModeModelConfig>,
     pub output_preferences: OutputPreferences,
+    #[serde(default)]
+    pub team: Option<TeamConfig>,
+    #[serde(default)]
+    pub shell: ShellConfig,
+    /// 내장 언어 정의에 추가하거나 덮어쓸 언어 (`[[languages]]` 섹션)
+    #[serde(default)]
+    pub languages: Vec<LanguageConfig>,
+    #[serde(default)]
+    pub budget: BudgetConfig,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    #[serde(default)]
+    pub permissions: PermissionsConfig,
+    #[serde(default)]
+    pub integrations: IntegrationsConfig,
+    /// 문서 유형별 템플릿 파일 (`[doc_templates]` 섹션, 예: `readme = "~/team/readme.md"`)
+    #[serde(default)]
+    pub doc_templates: BTreeMap<String, PathBuf>,
+    /// `code-assist --preset`으로 쓸 옵션 묶음 (`[code_assist_presets]` 섹션, 예: `audit = ["analyze", "security", "test"]`)
+    #[serde(default)]
+    pub code_assist_presets: BTreeMap<String, Vec<String>>,
+    /// 세션, 입력 기록, 캐시, 백업 등 상태를 저장할 디렉토리 (없으면 `$XDG_DATA_HOME/ricci`, `crate::paths::data_dir` 참고)
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+    /// `--record`로 지정한 요청/응답 기록 디렉토리 (실행 시에만 사용, 저장하지 않음)
+    #[serde(skip)]
+    pub record_dir: Option<PathBuf>,
+    /// `--yes`로 큰 프롬프트 전송 확인을 건너뜀 (실행 시에만 사용, 저장하지 않음)
+    #[serde(skip)]
+    pub assume_yes: bool,
+    /// API 키가 하나도 없음. 로컬 분석만으로 끝낼 수 있는 명령어는 AI 단계를 건너뜀 (실행 시에만 사용, 저장하지 않음)
+    #[serde(skip)]
+    pub offline: bool,
+    /// `--output-format`으로 고른 출력 형식 (실행 시에만 사용, 저장하지 않음)
+    #[serde(skip)]
+    pub output_format: OutputFormat,
+    /// 키 게이트웨이가 알려준 프록시 주소 (실행 시에만 사용, 저장하지 않음)
+    #[serde(skip)]
+    pub api_base: Option<String>,
+    /// 키 게이트웨이에서 받은 제공자별 단기 키 (실행 시에만 사용, 저장하지 않음)
+    #[serde(skip)]
+    pub gateway_keys: BTreeMap<String, String>,
+    /// `RICCI_*` 환경 변수로 덮어쓴 항목 (저장할 때 파일 값으로 되돌림)
+    #[serde(skip)]
+    pub env_overrides: Vec<super::env::EnvOverride>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -17,15 +75,356 @@ pub enum ApiKeySource {
     #[default]
     Environment,
     ConfigFile,
+    /// 사내 게이트웨이에서 단기 키를 받아 사용 (`RICCI_GATEWAY_TOKEN`을 Bearer 토큰으로 전달)
     RestApi { url: String },
 }
 
+/// 팀 공유 설정 저장소 (`[team]` 섹션)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamConfig {
+    /// git 저장소 주소 또는 번들 JSON을 제공하는 HTTP URL
+    pub source: String,
+    /// git 저장소일 때 사용할 브랜치
+    #[serde(default)]
+    pub branch: Option<String>,
+}
+
+/// 명령어 모드의 셸 명령어 실행 설정 (`[shell]` 섹션)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ShellConfig {
+    /// 사용할 셸 (powershell, pwsh, cmd, sh, bash, zsh 등). 없으면 Windows는 powershell, 그 외는 sh
+    #[serde(default)]
+    pub program: Option<String>,
+    /// 셸 출력 디코딩 방식
+    #[serde(default)]
+    pub encoding: ShellEncoding,
+}
+
+/// 예상 비용 기준 사용 한도 (`[budget]` 섹션). 한도를 지정하지 않으면 확인하지 않습니다
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetConfig {
+    #[serde(default)]
+    pub daily_usd: Option<f64>,
+    #[serde(default)]
+    pub monthly_usd: Option<f64>,
+    /// 한도 대비 이 비율을 넘으면 경고 (0.0~1.0)
+    #[serde(default = "default_budget_warn_at")]
+    pub warn_at: f64,
+    /// 한도를 넘었을 때 동작
+    #[serde(default)]
+    pub action: BudgetAction,
+}
+
+/// 오래 걸린 명령어가 끝났을 때 데스크톱 알림 (`[notifications]` 섹션)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 이 시간(초) 이상 걸린 명령어만 알림
+    #[serde(default = "default_notify_after_seconds")]
+    pub after_seconds: u64,
+    /// 명령어별 기준 시간 (`[notifications.commands]`, 예: `analyze = 10`). 0이면 그 명령어는 알리지 않음
+    #[serde(default)]
+    pub commands: BTreeMap<String, u64>,
+}
+
+fn default_notify_after_seconds() -> u64 {
+    30
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            after_seconds: default_notify_after_seconds(),
+            commands: BTreeMap::new(),
+        }
+    }
+}
+
+/// 파일 쓰기와 명령어 실행 권한 (`[permissions]` 섹션)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PermissionsConfig {
+    /// 항상 읽기 전용 모드로 실행 (`--read-only`와 같음)
+    #[serde(default)]
+    pub read_only: bool,
+    /// 모델이 제안한 변경을 쓸 수 있는 경로 glob (예: `["src/**", "tests/**"]`, 비어 있으면 제한 없음)
+    #[serde(default)]
+    pub writable_paths: Vec<String>,
+}
+
+/// 이벤트마다 실행할 셸 명령어 (`[hooks]` 섹션). 명령어는 이벤트 JSON을 표준 입력으로 받습니다
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HooksConfig {
+    /// 모델 응답을 받았을 때
+    #[serde(default)]
+    pub on_response: Vec<String>,
+    /// 제안된 변경을 파일에 적용했을 때
+    #[serde(default)]
+    pub on_apply: Vec<String>,
+    /// 명령어가 오류로 끝났을 때. 대화 중 오류를 출력하고 대화를 이어가는 경우는 포함하지 않음
+    #[serde(default)]
+    pub on_error: Vec<String>,
+}
+
+/// `--share`로 결과를 보낼 외부 서비스 (`[integrations]` 섹션)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntegrationsConfig {
+    #[serde(default)]
+    pub slack: Option<SlackConfig>,
+    #[serde(default)]
+    pub teams: Option<TeamsConfig>,
+    #[serde(default)]
+    pub confluence: Option<ConfluenceConfig>,
+    #[serde(default)]
+    pub notion: Option<NotionConfig>,
+}
+
+/// Slack 수신 웹훅 (`[integrations.slack]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackConfig {
+    pub webhook_url: String,
+    /// 웹훅 기본 채널 대신 보낼 채널 (`#team-dev`, 레거시 웹훅만 지원)
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// 표시할 보낸 사람 이름 (레거시 웹훅만 지원)
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+/// Microsoft Teams 수신 웹훅 (`[integrations.teams]`, 워크플로 웹훅도 가능)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamsConfig {
+    pub webhook_url: String,
+}
+
+/// Confluence 페이지 게시 (`[integrations.confluence]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfluenceConfig {
+    /// 위키 주소 (예: `https://team.atlassian.net/wiki`)
+    pub base_url: String,
+    /// 페이지를 만들 스페이스 키
+    pub space: String,
+    /// 새 페이지를 만들 상위 페이지 id (없으면 스페이스 최상위)
+    #[serde(default, deserialize_with = "page_id")]
+    pub parent_id: Option<String>,
+    /// Atlassian 계정 이메일
+    pub user: String,
+    /// API 토큰 (`RICCI_INTEGRATIONS__CONFLUENCE__API_TOKEN`으로 지정 권장)
+    pub api_token: String,
+}
+
+/// Notion 페이지 내보내기 (`[integrations.notion]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotionConfig {
+    /// 내부 통합(integration) 토큰 (`RICCI_INTEGRATIONS__NOTION__TOKEN`으로 지정 권장)
+    pub token: String,
+    /// 페이지를 만들 상위 페이지 id (통합에 이 페이지를 공유해야 함)
+    pub parent_page_id: String,
+    /// API 주소 (사내 프록시를 거칠 때만 변경)
+    #[serde(default = "default_notion_api")]
+    pub api_url: String,
+}
+
+fn default_notion_api() -> String {
+    "https://api.notion.com".to_string()
+}
+
+/// 페이지 id는 `parent_id = 123456`처럼 숫자로 써도 받음
+fn page_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Number(u64),
+        Text(String),
+    }
+    
+    Ok(Option::<Repr>::deserialize(deserializer)?.map(|id| match id {
+        Repr::Number(n) => n.to_string(),
+        Repr::Text(s) => s,
+    }))
+}
+
+impl Default for BudgetConfig {
+    fn default() -> Self {
+        Self {
+            daily_usd: None,
+            monthly_usd: None,
+            warn_at: default_budget_warn_at(),
+            action: BudgetAction::default(),
+        }
+    }
+}
+
+fn default_budget_warn_at() -> f64 {
+    0.8
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetAction {
+    /// 경고만 출력하고 요청은 보냄
+    Warn,
+    /// 요청을 거부
+    #[default]
+    Block,
+}
+
+/// 분석/리뷰/포매팅에 사용할 언어 정의. 내장 언어와 이름이 같으면 해당 언어를 대체합니다
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageConfig {
+    pub name: String,
+    pub extensions: Vec<String>,
+    /// 한 줄 주석 시작 문자열 (복잡도 계산에서 제외)
+    #[serde(default)]
+    pub line_comments: Vec<String>,
+    /// 함수 정의를 세는 데 사용할 문자열
+    #[serde(default)]
+    pub function_markers: Vec<String>,
+    /// 테스트 파일 이름 규칙. `{stem}`, `{ext}`를 치환 (예: "test_{stem}.py")
+    #[serde(default)]
+    pub test_file: Option<String>,
+    /// 표준 입력을 받아 표준 출력으로 정리된 코드를 내보내는 포매터 명령어. `{path}`를 치환
+    #[serde(default)]
+    pub formatter: Vec<String>,
+    /// 프로젝트 분석 대상 소스 언어인지 (false면 포매팅에만 사용)
+    #[serde(default = "default_true")]
+    pub source: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum ShellEncoding {
+    /// UTF-8로 읽고 실패하면 CP949로 다시 시도
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "utf-8", alias = "utf8")]
+    Utf8,
+    #[serde(rename = "cp949", alias = "euc-kr")]
+    Cp949,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelPreferences {
     pub default_provider: String,
     pub default_model: String,
     pub temperature: f32,
-    pub max_tokens: u16,
+    #[serde(default)]
+    pub max_tokens: MaxTokens,
+    #[serde(default)]
+    pub compare_models: Vec<String>,
+    /// 생성 중단 시퀀스 (최대 4개)
+    #[serde(default)]
+    pub stop: Vec<String>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    #[serde(default)]
+    pub frequency_penalty: Option<f32>,
+    #[serde(default)]
+    pub presence_penalty: Option<f32>,
+    #[serde(default)]
+    pub seed: Option<i64>,
+    /// 추론 모델의 사고 강도 (low, medium, high)
+    #[serde(default)]
+    pub reasoning_effort: Option<String>,
+    /// `mock` 제공자가 사용할 응답 픽스처 파일 (TOML 또는 JSON)
+    #[serde(default)]
+    pub mock_fixtures: Option<PathBuf>,
+    /// 입력이 이 토큰 수를 넘으면 예상 비용을 보여주고 확인 후 전송 (0이면 확인하지 않음)
+    #[serde(default = "default_confirm_prompt_tokens")]
+    pub confirm_prompt_tokens: usize,
+}
+
+fn default_confirm_prompt_tokens() -> usize {
+    20_000
+}
+
+/// 제공자 하나의 모델 설정. 비워둔 값은 `[model_preferences]`를 따릅니다
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProviderModelConfig {
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub max_tokens: Option<MaxTokens>,
+}
+
+/// 대화 모드 하나의 생성 설정. 비워둔 값은 제공자 설정과 `[model_preferences]`를 따릅니다
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModeModelConfig {
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub max_tokens: Option<MaxTokens>,
+}
+
+/// 응답 최대 토큰 수. "auto"면 모델 최대 출력과 남은 컨텍스트 중 작은 값을 사용
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MaxTokens {
+    #[default]
+    Auto,
+    Fixed(u32),
+}
+
+/// 모델 정보를 모를 때 auto가 사용하는 값
+const AUTO_FALLBACK_MAX_TOKENS: u32 = 4096;
+
+impl MaxTokens {
+    /// 프롬프트 토큰 수를 고려해 실제 요청에 보낼 값을 계산합니다
+    pub fn resolve(&self, model: &str, prompt_tokens: usize) -> u32 {
+        match self {
+            MaxTokens::Fixed(n) => *n,
+            MaxTokens::Auto => match crate::api::capabilities(model) {
+                Some(caps) => {
+                    let remaining = caps.context_window.saturating_sub(prompt_tokens as u32);
+                    caps.max_output_tokens.min(remaining).max(1)
+                }
+                None => AUTO_FALLBACK_MAX_TOKENS,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for MaxTokens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaxTokens::Auto => write!(f, "auto"),
+            MaxTokens::Fixed(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+impl Serialize for MaxTokens {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MaxTokens::Auto => serializer.serialize_str("auto"),
+            MaxTokens::Fixed(n) => serializer.serialize_u32(*n),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MaxTokens {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Number(u32),
+            Text(String),
+        }
+        
+        match Repr::deserialize(deserializer)? {
+            Repr::Number(n) => Ok(MaxTokens::Fixed(n)),
+            Repr::Text(s) if s.eq_ignore_ascii_case("auto") => Ok(MaxTokens::Auto),
+            Repr::Text(s) => s.parse().map(MaxTokens::Fixed).map_err(|_| {
+                serde::de::Error::custom(format!("max_tokens는 숫자 또는 \"auto\"여야 합니다: {s}"))
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,18 +432,98 @@ pub struct OutputPreferences {
     pub syntax_highlighting: bool,
     pub markdown_rendering: bool,
     pub auto_save_sessions: bool,
-    pub session_dir: PathBuf,
+    /// 세션 파일 디렉토리 (없으면 `<data_dir>/sessions`)
+    #[serde(default)]
+    pub session_dir: Option<PathBuf>,
+    /// 세션이 이 메시지 수를 넘으면 오래된 메시지를 보관 파일로 옮기고 요약만 남김 (0이면 제한 없음)
+    #[serde(default = "default_max_session_messages")]
+    pub max_session_messages: usize,
+    /// 세션 메시지의 예상 토큰 합이 이 값을 넘으면 보관 (0이면 제한 없음)
+    #[serde(default = "default_max_session_tokens")]
+    pub max_session_tokens: usize,
+    #[serde(default)]
+    pub show_stats: bool,
+    /// 응답이 max_tokens에서 잘리면 자동으로 이어받기
+    #[serde(default)]
+    pub auto_continue: bool,
+    /// 추론 모델의 사고 과정을 펼쳐서 표시 (기본은 한 줄로 접음)
+    #[serde(default)]
+    pub show_reasoning: bool,
+    /// 명령어 모드에서 실패한 셸 명령어를 자동으로 AI에게 진단 요청
+    #[serde(default)]
+    pub explain_failed_commands: bool,
+    /// 응답 언어 (ko, en)
+    #[serde(default = "default_language")]
+    pub language: String,
+    /// 코드 블록 구문 강조 테마 (syntect 테마 이름)
+    #[serde(default = "default_theme")]
+    pub theme: String,
+    /// REPL 프롬프트 형식. {model}, {mode}, {persona}, {branch}, {tokens}, {chat} 토큰 사용 가능
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// 이모지, 상자 그리기 문자, 스플래시 아트와 색상을 빼고 출력 (화면 낭독기, 단순 터미널용)
+    #[serde(default)]
+    pub plain: bool,
+    /// 대화형 모드 시작 시 화면을 지우고 배너와 사용법을 표시 (false면 한 줄 제목만)
+    #[serde(default = "default_true")]
+    pub show_splash: bool,
+    /// 스플래시 배너 문구 (기본 "RICCI CLI")
+    #[serde(default)]
+    pub banner: Option<String>,
+    /// 배너에 사용할 FIGlet 글꼴 파일 (.flf, 기본은 내장 standard 글꼴)
+    #[serde(default)]
+    pub banner_font: Option<PathBuf>,
+    /// 세션을 저장할 때마다 노트로도 기록할 Obsidian 보관함 폴더
+    #[serde(default)]
+    pub vault_dir: Option<PathBuf>,
+}
+
+fn default_language() -> String {
+    "ko".to_string()
+}
+
+fn default_max_session_messages() -> usize {
+    200
+}
+
+fn default_max_session_tokens() -> usize {
+    100_000
+}
+
+fn default_theme() -> String {
+    crate::renderer::DEFAULT_THEME.to_string()
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            version: super::migration::CONFIG_VERSION,
             openai_api_key: None,
             anthropic_api_key: None,
             gemini_api_key: None,
             api_key_source: ApiKeySource::Environment,
             model_preferences: ModelPreferences::default(),
+            models: BTreeMap::new(),
+            modes: BTreeMap::new(),
             output_preferences: OutputPreferences::default(),
+            team: None,
+            shell: ShellConfig::default(),
+            languages: Vec::new(),
+            budget: BudgetConfig::default(),
+            notifications: NotificationConfig::default(),
+            hooks: HooksConfig::default(),
+            permissions: PermissionsConfig::default(),
+            integrations: IntegrationsConfig::default(),
+            doc_templates: BTreeMap::new(),
+            code_assist_presets: BTreeMap::new(),
+            data_dir: None,
+            record_dir: None,
+            assume_yes: false,
+            offline: false,
+            output_format: OutputFormat::default(),
+            api_base: None,
+            gateway_keys: BTreeMap::new(),
+            env_overrides: Vec::new(),
         }
     }
 }
@@ -55,7 +534,16 @@ impl Default for ModelPreferences {
             default_provider: "openai".to_string(),
             default_model: "gpt-4".to_string(),
             temperature: 0.7,
-            max_tokens: 2048,
+            max_tokens: MaxTokens::Auto,
+            compare_models: vec!["gpt-4".to_string(), "gpt-3.5-turbo".to_string()],
+            stop: Vec::new(),
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            seed: None,
+            reasoning_effort: None,
+            mock_fixtures: None,
+            confirm_prompt_tokens: default_confirm_prompt_tokens(),
         }
     }
 }
@@ -66,9 +554,21 @@ impl Default for OutputPreferences {
             syntax_highlighting: true,
             markdown_rendering: true,
             auto_save_sessions: true,
-            session_dir: dirs::data_local_dir()
-                .unwrap_or_else(|| PathBuf::from("."))
-                .join("ricci"),
+            session_dir: None,
+            max_session_messages: default_max_session_messages(),
+            max_session_tokens: default_max_session_tokens(),
+            show_stats: false,
+            auto_continue: false,
+            show_reasoning: false,
+            explain_failed_commands: false,
+            language: default_language(),
+            theme: default_theme(),
+            prompt: None,
+            plain: false,
+            show_splash: true,
+            banner: None,
+            banner_font: None,
+            vault_dir: None,
         }
     }
 } 
\ No newline at end of file
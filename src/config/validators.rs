@@ -1,36 +1,152 @@
 use anyhow::{Result, anyhow};
-use crate::config::types::Config;
+use colored::*;
+use crate::api::{capabilities, closest_model, provider_of, suggested_models};
+use crate::assistant::ChatMode;
+use crate::config::types::{ApiKeySource, Config, MaxTokens};
+use crate::error::RicciError;
+
+const PROVIDERS: &[&str] = &["openai", "anthropic", "gemini", "mock"];
 
 pub fn validate_config(config: &Config) -> Result<()> {
-    // API 키 확인
-    let has_any_key = config.openai_api_key.is_some() 
-        || config.anthropic_api_key.is_some()
-        || config.gemini_api_key.is_some();
-    
-    if !has_any_key {
-        return Err(anyhow!(
+    if !has_api_key(config) {
+        return Err(RicciError::Config(
             "최소 하나의 API 키가 필요합니다.\n\
-            환경 변수 설정: OPENAI_API_KEY, ANTHROPIC_API_KEY, GEMINI_API_KEY"
-        ));
+            환경 변수 설정: OPENAI_API_KEY, ANTHROPIC_API_KEY, GEMINI_API_KEY".to_string()
+        ).into());
     }
-    
+    validate_settings(config)
+}
+
+/// 모델 요청에 쓸 키가 있는지 (게이트웨이 키는 설정을 읽은 뒤에 받아오므로 있는 것으로 봄)
+pub fn has_api_key(config: &Config) -> bool {
+    config.openai_api_key.is_some()
+        || config.anthropic_api_key.is_some()
+        || config.gemini_api_key.is_some()
+        || matches!(config.api_key_source, ApiKeySource::RestApi { .. })
+        || config.model_preferences.default_provider == "mock"
+}
+
+/// API 키를 제외한 모델과 제공자 설정을 확인합니다
+pub fn validate_settings(config: &Config) -> Result<()> {
+    check_settings(config).map_err(|e| RicciError::InvalidSettings(e.into()).into())
+}
+
+fn check_settings(config: &Config) -> Result<()> {
     // 모델 설정 확인
     if config.model_preferences.temperature < 0.0 || config.model_preferences.temperature > 2.0 {
         return Err(anyhow!("temperature는 0.0에서 2.0 사이여야 합니다"));
     }
     
-    if config.model_preferences.max_tokens == 0 {
+    let prefs = &config.model_preferences;
+    if prefs.top_p.is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+        return Err(anyhow!("top_p는 0.0에서 1.0 사이여야 합니다"));
+    }
+    
+    for (name, penalty) in [("frequency_penalty", prefs.frequency_penalty), ("presence_penalty", prefs.presence_penalty)] {
+        if penalty.is_some_and(|p| !(-2.0..=2.0).contains(&p)) {
+            return Err(anyhow!("{}는 -2.0에서 2.0 사이여야 합니다", name));
+        }
+    }
+    
+    if let Some(effort) = &prefs.reasoning_effort {
+        if !["low", "medium", "high"].contains(&effort.as_str()) {
+            return Err(anyhow!("reasoning_effort는 low, medium, high 중 하나여야 합니다: {}", effort));
+        }
+    }
+    
+    if prefs.stop.len() > 4 {
+        return Err(anyhow!("stop 시퀀스는 최대 4개까지 지정할 수 있습니다"));
+    }
+    
+    if config.model_preferences.max_tokens == MaxTokens::Fixed(0) {
         return Err(anyhow!("max_tokens는 0보다 커야 합니다"));
     }
     
+    validate_provider_models(config)?;
+    validate_modes(config)?;
+    validate_model_provider(&prefs.default_provider, &prefs.default_model)?;
+    
+    // 알려진 모델이면 한도와 비교
+    let model = &config.model_preferences.default_model;
+    if let (Some(caps), MaxTokens::Fixed(max_tokens)) = (capabilities(model), config.model_preferences.max_tokens) {
+        if max_tokens >= caps.context_window {
+            return Err(anyhow!(
+                "max_tokens({})가 {} 모델의 컨텍스트 크기({})보다 큽니다",
+                max_tokens, model, caps.context_window
+            ));
+        }
+        if max_tokens > caps.max_output_tokens {
+            eprintln!("{} max_tokens({})가 {} 모델의 최대 출력({})을 넘습니다. 요청이 거부될 수 있습니다.",
+                "[경고]".yellow(), max_tokens, model, caps.max_output_tokens);
+        }
+    }
+    
     Ok(())
 }
 
-pub fn get_api_key(config: &Config, provider: &str) -> Option<String> {
-    match provider {
-        "openai" => config.openai_api_key.clone(),
-        "anthropic" => config.anthropic_api_key.clone(),
-        "gemini" => config.gemini_api_key.clone(),
-        _ => None,
+/// `[models.<provider>]` 항목의 제공자 이름과 모델 이름을 확인합니다
+fn validate_provider_models(config: &Config) -> Result<()> {
+    for (provider, models) in &config.models {
+        if !PROVIDERS.contains(&provider.as_str()) {
+            return Err(anyhow!("[models.{}]: 알 수 없는 제공자입니다 ({})", provider, PROVIDERS.join(", ")));
+        }
+        if models.temperature.is_some_and(|t| !(0.0..=2.0).contains(&t)) {
+            return Err(anyhow!("[models.{}]: temperature는 0.0에서 2.0 사이여야 합니다", provider));
+        }
+        if let Some(model) = &models.model {
+            validate_model_provider(provider, model)
+                .map_err(|e| anyhow!("[models.{}]: {}", provider, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// `[modes.<mode>]` 항목의 모드 이름과 값을 확인합니다
+fn validate_modes(config: &Config) -> Result<()> {
+    for (mode, settings) in &config.modes {
+        if !ChatMode::ALL.iter().any(|m| m.label() == mode) {
+            let labels: Vec<&str> = ChatMode::ALL.iter().map(|m| m.label()).collect();
+            return Err(anyhow!("[modes.{}]: 알 수 없는 대화 모드입니다 ({})", mode, labels.join(", ")));
+        }
+        if settings.temperature.is_some_and(|t| !(0.0..=2.0).contains(&t)) {
+            return Err(anyhow!("[modes.{}]: temperature는 0.0에서 2.0 사이여야 합니다", mode));
+        }
+        if settings.max_tokens == Some(MaxTokens::Fixed(0)) {
+            return Err(anyhow!("[modes.{}]: max_tokens는 0보다 커야 합니다", mode));
+        }
+    }
+    Ok(())
+}
+
+/// 제공자와 모델 조합을 확인합니다. 다른 제공자의 모델이면 오류, 모르는 모델이면 경고합니다
+fn validate_model_provider(provider: &str, model: &str) -> Result<()> {
+    if !PROVIDERS.contains(&provider) {
+        return Err(anyhow!("알 수 없는 제공자: {} ({} 중 하나)", provider, PROVIDERS.join(", ")));
     }
+    if provider == "mock" {
+        return Ok(());
+    }
+    
+    let candidates = suggested_models(provider).join(", ");
+    if let Some(owner) = provider_of(model).filter(|owner| *owner != provider) {
+        return Err(anyhow!(
+            "{} 모델은 {} 제공자에서 사용할 수 없습니다 ({} 모델).\n\
+            {} 모델: {}\n\
+            [models.{}]에 model을 지정하거나 default_provider를 {}로 바꾸세요.",
+            model, provider, owner, provider, candidates, provider, owner
+        ));
+    }
+    if capabilities(model).is_none() {
+        let hint = closest_model(model)
+            .filter(|m| provider_of(m) == Some(provider))
+            .map(|m| format!("혹시 {}인가요? ", m))
+            .unwrap_or_default();
+        eprintln!("{} 알 수 없는 {} 모델: {} ({}추천: {})",
+            "[경고]".yellow(), provider, model, hint, candidates);
+    }
+    Ok(())
+}
+
+pub fn get_api_key(config: &Config, provider: &str) -> Option<String> {
+    config.api_key(provider).map(str::to_string)
 } 
\ No newline at end of file
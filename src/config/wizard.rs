@@ -0,0 +1,80 @@
+use anyhow::Result;
+use colored::*;
+use dialoguer::{Input, Password, Select};
+use crate::config::types::{Config, ApiKeySource};
+use crate::api::suggested_models;
+use crate::renderer::{plain, MarkdownRenderer, DEFAULT_THEME};
+
+const PROVIDERS: &[&str] = &["openai", "anthropic", "gemini"];
+const LANGUAGES: &[(&str, &str)] = &[("ko", "한국어"), ("en", "English")];
+
+/// 설정 파일과 API 키가 모두 없을 때 실행되는 최초 설정 마법사
+pub fn run_setup_wizard() -> Result<Config> {
+    println!("\n{}", plain("👋 Ricci CLI 최초 설정").bright_cyan().bold());
+    println!("{}", "설정 파일이 없어 초기 설정을 진행합니다. (Ctrl+C로 중단)".dimmed());
+
+    let mut config = Config::default();
+
+    let provider_idx = Select::new()
+        .with_prompt("사용할 AI 제공자를 선택하세요")
+        .items(PROVIDERS)
+        .default(0)
+        .interact()?;
+    let provider = PROVIDERS[provider_idx];
+
+    let key: String = Password::new()
+        .with_prompt(format!("{provider} API 키를 입력하세요"))
+        .interact()?;
+
+    let mut models: Vec<String> = suggested_models(provider).iter().map(|m| m.to_string()).collect();
+    models.push("직접 입력...".to_string());
+    let model_idx = Select::new()
+        .with_prompt("기본 모델을 선택하세요")
+        .items(&models)
+        .default(0)
+        .interact()?;
+    let model = if model_idx == models.len() - 1 {
+        Input::<String>::new().with_prompt("모델 이름").interact_text()?
+    } else {
+        models[model_idx].clone()
+    };
+
+    let language_idx = Select::new()
+        .with_prompt("응답 언어를 선택하세요")
+        .items(&LANGUAGES.iter().map(|(_, name)| *name).collect::<Vec<_>>())
+        .default(0)
+        .interact()?;
+
+    let mut themes = MarkdownRenderer::available_themes();
+    themes.sort();
+    let theme_default = themes.iter().position(|t| t == DEFAULT_THEME).unwrap_or(0);
+    let theme_idx = Select::new()
+        .with_prompt("코드 강조 테마를 선택하세요")
+        .items(&themes)
+        .default(theme_default)
+        .interact()?;
+
+    match provider {
+        "anthropic" => config.anthropic_api_key = Some(key),
+        "gemini" => config.gemini_api_key = Some(key),
+        _ => config.openai_api_key = Some(key),
+    }
+    config.api_key_source = ApiKeySource::ConfigFile;
+    config.model_preferences.default_provider = provider.to_string();
+    config.set_provider_model(provider, &model);
+    config.output_preferences.language = LANGUAGES[language_idx].0.to_string();
+    config.output_preferences.theme = themes[theme_idx].clone();
+
+    config.save()?;
+    // 키는 운영체제 키체인이 아니라 설정 파일에 평문으로 저장되므로 다른 사용자가 읽지 못하도록
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(crate::config::get_config_path()?, std::fs::Permissions::from_mode(0o600))?;
+    }
+    println!("{}", format!("API 키는 설정 파일에 평문으로 저장되었습니다. 파일에 두지 않으려면 키를 지우고 {}_API_KEY 환경 변수를 사용하세요.",
+        provider.to_uppercase()).dimmed());
+    println!("{}", plain("✓ 설정이 완료되었습니다. 'ricci config show'로 확인할 수 있습니다.\n").green());
+
+    Ok(config)
+}
@@ -0,0 +1,109 @@
+use thiserror::Error;
+
+/// 호출하는 쪽(스크립트, 테스트)이 구분해야 하는 실패 종류.
+/// 함수는 계속 `anyhow::Result`를 돌려주고, 이 값을 오류 체인에 담아 종료 코드와 안내를 정합니다
+#[derive(Debug, Error)]
+pub enum RicciError {
+    /// 설정 파일, 환경 변수, API 키 문제
+    #[error("{0}")]
+    Config(String),
+    /// 설정 값 검증 실패. 원인 오류를 그대로 담아 오류 체인으로 보여줌
+    #[error("설정 값이 올바르지 않습니다")]
+    InvalidSettings(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// 모델 제공자 호출 실패 (네트워크, 인증, 요청 한도, 예산)
+    #[error("{0}")]
+    Provider(String),
+    /// 모델 응답이나 입력 파일을 해석하지 못함
+    #[error("{0}")]
+    Parse(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// 사용자가 확인을 거절했거나, 확인을 받을 수 없어 진행하지 않음 (`--yes` 필요)
+    #[error("{0}")]
+    Declined(String),
+    /// Ctrl+C로 중단함
+    #[error("{0}")]
+    Aborted(String),
+    /// 리뷰 점수가 `--fail-under` 기준보다 낮음
+    #[error("{0}")]
+    ReviewThreshold(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Config,
+    Provider,
+    Parse,
+    Io,
+    Declined,
+    Aborted,
+    ReviewThreshold,
+    /// 잘못된 명령줄 사용법 (clap이 거부함)
+    Usage,
+    Other,
+}
+
+impl RicciError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Config(_) | Self::InvalidSettings(_) => ErrorKind::Config,
+            Self::Provider(_) => ErrorKind::Provider,
+            Self::Parse(_) => ErrorKind::Parse,
+            Self::Io(_) => ErrorKind::Io,
+            Self::Declined(_) => ErrorKind::Declined,
+            Self::Aborted(_) => ErrorKind::Aborted,
+            Self::ReviewThreshold(_) => ErrorKind::ReviewThreshold,
+        }
+    }
+}
+
+impl ErrorKind {
+    /// 오류 체인에서 실패 종류를 찾습니다. 체인에 `RicciError`가 있으면 그 분류가 우선이고,
+    /// 없으면 가장 바깥쪽 원인 오류의 타입으로 짐작합니다. 형식 해석(5)과 파일 입출력(6)을 따로 두는 것은
+    /// 스크립트가 "입력 파일을 고쳐야 함"과 "경로나 권한 문제"를 다시 시도할 만한 실패와 구분하도록 하기 위함이며,
+    /// 분류된 오류(설정, 제공자 등)가 안에 입출력 오류를 담고 있어도 바깥 분류를 따릅니다
+    pub fn of(error: &anyhow::Error) -> Self {
+        if let Some(kind) = error.chain().find_map(|e| e.downcast_ref::<RicciError>()).map(RicciError::kind) {
+            return kind;
+        }
+        error.chain()
+            .find_map(|e| {
+                if e.is::<std::io::Error>() {
+                    Some(Self::Io)
+                } else if e.is::<serde_json::Error>() || e.is::<toml::de::Error>() || e.is::<serde_yaml::Error>() {
+                    Some(Self::Parse)
+                } else if e.is::<reqwest::Error>() || e.is::<async_openai::error::OpenAIError>() {
+                    Some(Self::Provider)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(Self::Other)
+    }
+
+    /// 문서화된 종료 코드 (README의 "종료 코드" 참고)
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::Other => 1,
+            Self::Config => 2,
+            Self::Provider => 3,
+            Self::ReviewThreshold => 4,
+            Self::Parse => 5,
+            Self::Io => 6,
+            Self::Declined => 7,
+            Self::Usage => 64,
+            Self::Aborted => 130,
+        }
+    }
+
+    /// 오류 메시지 아래에 덧붙일 해결 안내
+    pub fn hint(self) -> Option<&'static str> {
+        match self {
+            Self::Config => Some("`ricci config show`로 현재 설정을 보거나 `ricci doctor`로 점검하세요."),
+            Self::Provider => Some("네트워크, API 키, 요청 한도를 확인하고 잠시 후 다시 시도하세요."),
+            Self::Parse => Some("입력 파일의 형식을 확인하세요. 모델 응답이면 다시 실행하거나 다른 모델을 사용해 보세요."),
+            Self::Declined => Some("확인 없이 진행하려면 --yes를 지정하세요."),
+            Self::Io | Self::Aborted | Self::ReviewThreshold | Self::Usage | Self::Other => None,
+        }
+    }
+}
@@ -1,46 +1,85 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::*;
+use dialoguer::MultiSelect;
+use std::io::Write;
+use crate::renderer::{plain, MarkdownRenderer, NoticeLevel};
+use std::path::Path;
+use walkdir::WalkDir;
 use crate::{
-    assistant::DevAssistant,
-    analyzer::CodeAnalyzer,
-    planner::ProjectPlanner,
+    assistant::{
+        annotate_source, extract_markdown_block, fence_file, find_doc_template, format_code, is_reviewable, load_history,
+        parse_review_target, scan_patterns, score_badge, sparkline, strip_file_blocks, unreviewed,
+        CodeReview, DevAssistant, FileChange, IssueSeverity, ReviewIssue, ReviewView, SafeFileModifier, SecurityReport,
+        KEEP_MARKER,
+    },
+    analyzer::{AnalysisSnapshot, CodeAnalyzer, IGNORED_DIRS, print_snapshot_comparison},
+    planner::{save_plan, ProjectPlanner, PLAN_FORMATS},
     config::Config,
+    handlers::{code_assistant::extract_code_block, plan::emit_plan},
+    integrations::{check_share_target, share as share_result, share_plan},
+    permissions::ensure_allowed,
+    error::RicciError,
 };
 
+/// `review --history`에서 막대로 그릴 최근 기록 수
+const HISTORY_POINTS: usize = 20;
+
 pub async fn handle_plan(
     description: &str,
     format: &str,
     detail: u8,
     estimate: bool,
+    output: Option<&Path>,
+    share: Option<&str>,
     config: &Config,
 ) -> Result<()> {
+    if !PLAN_FORMATS.contains(&format) {
+        anyhow::bail!("지원하지 않는 형식: {} ({})", format, PLAN_FORMATS.join(", "));
+    }
+    if let Some(target) = share {
+        check_share_target(target, config)?;
+    }
+    if output.is_some() {
+        ensure_allowed("파일 저장")?;
+    }
     println!("{}", "작업계획서 생성 중...".yellow());
     
     let planner = ProjectPlanner::new(config.clone())?;
-    let plan = planner.create_plan(description, detail, estimate).await?;
+    let mut plan = planner.create_plan(description, detail, estimate).await?;
+    save_plan(&mut plan)?;
     
-    match format {
-        "markdown" => {
-            println!("\n{}", plan.to_markdown());
-        }
-        "json" => {
-            println!("{}", serde_json::to_string_pretty(&plan)?);
-        }
-        "yaml" => {
-            println!("{}", serde_yaml::to_string(&plan)?);
-        }
-        _ => {
-            anyhow::bail!("지원하지 않는 형식: {}", format);
-        }
-    }
+    emit_plan(&plan.render(format)?, format, output)?;
     
+    eprintln!("{} 계획을 저장했습니다: {} (`ricci plan done {} <작업>`으로 완료 기록)",
+        plain("✓").green(), plan.id.cyan(), plan.id);
+    if let Some(factor) = plan.calibration_factor {
+        eprintln!("{}", format!("예상 시간에 보정 계수 {:.2}배를 적용했습니다.", factor).dimmed());
+    }
+    if let Some(target) = share {
+        share_plan(target, &plan, config).await?;
+    }
     Ok(())
 }
 
-pub async fn handle_analyze(path: &str, type_: &str, config: &Config) -> Result<()> {
+pub async fn handle_analyze(
+    path: &str,
+    type_: &str,
+    save_snapshot: bool,
+    compare: Option<&str>,
+    share: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    if let Some(target) = share {
+        check_share_target(target, config)?;
+    }
+    if save_snapshot {
+        ensure_allowed("스냅샷 저장")?;
+    }
     println!("{} {}", "분석 중:".yellow(), path);
     
     let analyzer = CodeAnalyzer::new(config.clone())?;
+    // `all`로 만든 전체 보고서는 스냅샷, 비교, 공유에 그대로 씀
+    let mut full_report = None;
     
     match type_ {
         "structure" => {
@@ -55,36 +94,526 @@ pub async fn handle_analyze(path: &str, type_: &str, config: &Config) -> Result<
             let complexity = analyzer.analyze_complexity(path).await?;
             analyzer.print_complexity_report(&complexity);
         }
+        "tree" => {
+            let tree = analyzer.analyze_tree(path).await?;
+            analyzer.print_tree_report(&tree);
+        }
         "all" => {
             let report = analyzer.analyze_all(path).await?;
             analyzer.print_full_report(&report);
+            full_report = Some(report);
         }
         _ => {
             anyhow::bail!("지원하지 않는 분석 유형: {}", type_);
         }
     }
     
+    if full_report.is_none() && (save_snapshot || compare.is_some() || share.is_some()) {
+        full_report = Some(analyzer.analyze_all(path).await?);
+    }
+    
+    if let (Some(report), true) = (&full_report, save_snapshot || compare.is_some()) {
+        let snapshot = AnalysisSnapshot::from_report(report);
+        
+        if let Some(previous) = compare {
+            let before = AnalysisSnapshot::load(Path::new(previous))?;
+            print_snapshot_comparison(&before, &snapshot);
+        }
+        
+        if save_snapshot {
+            let filename = format!("snapshot_{}.json", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+            snapshot.save(Path::new(&filename))?;
+            println!("\n{} 분석 스냅샷이 {} 파일로 저장되었습니다.", plain("✓").green(), filename.cyan());
+        }
+    }
+    
+    // 공유는 분석 유형과 관계없이 전체 요약을 보냄
+    if let (Some(target), Some(report)) = (share, &full_report) {
+        share_result(target, &format!("프로젝트 분석: {}", path), &report.to_markdown(), config).await?;
+    }
+    
     Ok(())
 }
 
-pub async fn handle_review(path: &str, criteria: &str, config: &Config) -> Result<()> {
-    println!("{} {}", "코드 리뷰 중:".yellow(), path);
-    
+/// `ricci review`의 리뷰 방식과 결과 처리 옵션
+#[derive(Debug, Clone, Copy)]
+pub struct ReviewOptions<'a> {
+    pub lint: bool,
+    pub no_cache: bool,
+    pub share: Option<&'a str>,
+    pub badge: Option<&'a Path>,
+    pub apply: bool,
+    pub explain: bool,
+    pub annotate: bool,
+    pub fail_under: Option<f32>,
+    pub view: &'a ReviewView,
+}
+
+pub async fn handle_review(path: &str, criteria: &str, options: ReviewOptions<'_>, config: &Config) -> Result<()> {
+    let ReviewOptions { lint, no_cache, share, badge, apply, explain, annotate, fail_under, view } = options;
+    if let Some(target) = share {
+        check_share_target(target, config)?;
+    }
+    for (enabled, flag) in [(apply, "--apply"), (explain, "--explain"), (annotate, "--annotate")] {
+        if enabled && Path::new(path).is_dir() {
+            anyhow::bail!("{}는 파일 하나를 리뷰할 때만 사용할 수 있습니다: {}", flag, path);
+        }
+    }
+    if badge.is_some() {
+        ensure_allowed("배지 저장")?;
+    }
     let assistant = DevAssistant::new(config.clone())?;
-    let review = assistant.review_code(path, criteria).await?;
+    // 안내 문구도 출력 형식을 따름 (`--output-format json`의 표준 출력은 JSON 줄만)
+    let output = config.output_sink();
+    
+    if !Path::new(path).is_dir() {
+        output.notice(NoticeLevel::Info, &format!("{} {}", "코드 리뷰 중:".yellow(), path));
+        let review = assistant.review_code(path, criteria, lint, no_cache).await?;
+        let markdown = review.format_markdown_view(view);
+        output.document(&markdown)?;
+        if let Some(badge) = badge {
+            write_badge(badge, review.overall_score)?;
+        }
+        if let Some(target) = share {
+            share_result(target, &format!("코드 리뷰: {}", path), &markdown, config).await?;
+        }
+        let shown = review.filtered(view);
+        if explain {
+            explain_issues(&assistant, path, &shown, config).await?;
+        }
+        if apply {
+            apply_review(&assistant, path, &shown, config).await?;
+        }
+        if annotate {
+            annotate_review(path, &shown, config).await?;
+        }
+        return check_fail_under(fail_under, &[(path.to_string(), review.overall_score)]);
+    }
+    
+    // 디렉토리면 소스 파일과 인프라 파일(Dockerfile, compose, Terraform, Kubernetes)을 차례로 리뷰
+    let mut files: Vec<_> = WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|e| !IGNORED_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && is_reviewable(e.path()))
+        .map(|e| e.path().display().to_string())
+        .collect();
+    files.sort();
+    
+    if files.is_empty() {
+        output.notice(NoticeLevel::Warning, &format!("{} 리뷰할 파일이 없습니다: {}", plain("⚠️"), path));
+        return Ok(());
+    }
+    output.notice(NoticeLevel::Info, &format!("{} {}개 파일", "코드 리뷰 중:".yellow(), files.len()));
+    
+    let mut shared = String::new();
+    let mut reviews = Vec::new();
+    for (index, file) in files.iter().enumerate() {
+        output.notice(NoticeLevel::Info, &format!("\n{}", plain(&format!("━━ [{}/{}] {} ━━", index + 1, files.len(), file)).bright_cyan().bold()));
+        match assistant.review_code(file, criteria, lint, no_cache).await {
+            Ok(review) => {
+                let markdown = review.format_markdown_view(view);
+                output.document(&markdown)?;
+                // 파일별 결과의 최상위 제목을 파일 이름으로 바꿔 한 문서로 합침
+                shared.push_str(&markdown.replacen("# 코드 리뷰 결과", &format!("# {}", file), 1));
+                shared.push('\n');
+                reviews.push((file.clone(), review.filtered(view)));
+            }
+            Err(e) => output.notice(NoticeLevel::Error, &format!("리뷰 실패: {}", e)),
+        }
+    }
+    
+    // 파일별 결과를 다시 모델에 보내 저장소 수준 요약을 덧붙임
+    if reviews.len() > 1 {
+        output.notice(NoticeLevel::Info, &format!("\n{}", plain("━━ 저장소 요약 ━━").bright_cyan().bold()));
+        match assistant.summarize_reviews(&reviews).await {
+            Ok(summary) => {
+                let summary = format!("# 저장소 요약 ({}개 파일)\n\n{}\n", reviews.len(), summary.trim());
+                output.document(&summary)?;
+                shared.push_str(&summary);
+            }
+            Err(e) => output.notice(NoticeLevel::Error, &format!("요약 실패: {}", e)),
+        }
+    }
+    
+    if let (Some(badge), false) = (badge, reviews.is_empty()) {
+        let average = reviews.iter().map(|(_, review)| review.overall_score).sum::<f32>() / reviews.len() as f32;
+        write_badge(badge, average)?;
+    }
     
-    println!("\n{}", review.format_markdown());
+    if let (Some(target), false) = (share, shared.is_empty()) {
+        share_result(target, &format!("코드 리뷰: {} ({}개 파일)", path, files.len()), &shared, config).await?;
+    }
+    
+    let scores: Vec<(String, f32)> = reviews.iter().map(|(file, review)| (file.clone(), review.overall_score)).collect();
+    check_fail_under(fail_under, &scores)
+}
+
+/// `--fail-under`보다 낮은 점수가 있으면 종료 코드 4로 실패합니다
+fn check_fail_under(fail_under: Option<f32>, scores: &[(String, f32)]) -> Result<()> {
+    let Some(threshold) = fail_under else {
+        return Ok(());
+    };
+    let failed: Vec<String> = scores.iter()
+        .filter(|(_, score)| *score < threshold)
+        .map(|(file, score)| format!("{} ({:.0}점)", file, score))
+        .collect();
+    if failed.is_empty() {
+        return Ok(());
+    }
+    Err(RicciError::ReviewThreshold(format!("리뷰 점수가 기준 {:.0}점보다 낮습니다: {}", threshold, failed.join(", "))).into())
+}
+
+/// 번호로 고른 문제 하나를 나머지 리뷰를 문맥 삼아 더 자세히 설명하고 수정 예시를 보여줍니다
+async fn explain_issues(assistant: &DevAssistant, target: &str, review: &CodeReview, config: &Config) -> Result<()> {
+    if review.issues.is_empty() {
+        return Ok(());
+    }
+    let (path, _) = parse_review_target(target)?;
+    let extension = Path::new(path).extension().and_then(|e| e.to_str()).unwrap_or("");
+    let file = fence_file(path, extension, &std::fs::read_to_string(path)?);
+    let context = review.format_markdown();
+    let renderer = MarkdownRenderer::with_theme(&config.output_preferences.theme);
+    
+    println!("\n{}", "문제 목록".bright_cyan().bold());
+    for (index, issue) in review.issues.iter().enumerate() {
+        println!("  {}. [{:?}] {} - {}", index + 1, issue.severity, issue.location, issue.description.lines().next().unwrap_or(""));
+    }
+    loop {
+        print!("\n{}", "자세히 볼 문제 번호 (Enter로 종료): ".yellow());
+        std::io::stdout().flush()?;
+        let mut input = String::new();
+        if std::io::stdin().read_line(&mut input)? == 0 || input.trim().is_empty() {
+            return Ok(());
+        }
+        let Some(issue) = input.trim().parse::<usize>().ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| review.issues.get(i))
+        else {
+            println!("{} 1부터 {} 사이의 번호를 입력하세요", "잘못된 번호:".red(), review.issues.len());
+            continue;
+        };
+        
+        let prompt = format!(
+            "다음은 파일 전체 리뷰 결과와 리뷰한 파일입니다.\n\n{}\n\n{}\n\n\
+            이 중 아래 문제 하나만 더 깊이 설명해주세요.\n\
+            - [{:?}/{:?}] {}: {}\n\n\
+            1. 문제가 무엇이고 왜 생기는지 (해당 코드를 인용해서)\n\
+            2. 실제로 어떤 상황에서 어떤 영향이 있는지\n\
+            3. 고친 코드 예시 (바뀌는 부분만)\n\
+            4. 고칠 때 주의할 점\n\
+            다른 문제는 이 문제와 직접 관련될 때만 언급하세요.",
+            context, file, issue.severity, issue.category, issue.location, issue.description
+        );
+        println!("{}", plain("🤖 설명 생성 중...").dimmed());
+        match assistant.query(&prompt).await {
+            Ok(answer) => renderer.render(&answer)?,
+            Err(e) => println!("{} {}", "오류:".red(), e),
+        }
+    }
+}
+
+/// 리뷰에서 고른 문제를 모델에게 고치게 하고, 결과를 변경 블록마다 확인받아 적용합니다
+async fn apply_review(assistant: &DevAssistant, target: &str, review: &CodeReview, config: &Config) -> Result<()> {
+    if review.issues.is_empty() {
+        println!("{}", "적용할 리뷰 문제가 없습니다.".yellow());
+        return Ok(());
+    }
     
+    // 기본으로 Medium 이상을 고름. --yes면 묻지 않고 기본 선택 사용
+    let defaults: Vec<bool> = review.issues.iter().map(|i| i.severity <= IssueSeverity::Medium).collect();
+    let selected: Vec<&ReviewIssue> = if config.assume_yes {
+        review.issues.iter().zip(&defaults).filter(|(_, &d)| d).map(|(i, _)| i).collect()
+    } else {
+        let labels: Vec<String> = review.issues.iter()
+            .map(|i| format!("[{:?}] {} - {}", i.severity, i.location, i.description.lines().next().unwrap_or("")))
+            .collect();
+        let chosen = MultiSelect::new()
+            .with_prompt("수정할 문제를 고르세요 (스페이스로 선택, Enter로 확인)")
+            .items(&labels)
+            .defaults(&defaults)
+            .interact()?;
+        chosen.into_iter().map(|index| &review.issues[index]).collect()
+    };
+    if selected.is_empty() {
+        println!("{}", "선택한 문제가 없습니다.".yellow());
+        return Ok(());
+    }
+    
+    let (path, _) = parse_review_target(target)?;
+    let original = std::fs::read_to_string(path)?;
+    let extension = Path::new(path).extension().and_then(|e| e.to_str()).unwrap_or("");
+    let problems: Vec<String> = selected.iter()
+        .map(|i| match &i.suggestion {
+            Some(suggestion) => format!("- [{:?}] {}: {} (제안: {})", i.severity, i.location, i.description, suggestion),
+            None => format!("- [{:?}] {}: {}", i.severity, i.location, i.description),
+        })
+        .collect();
+    println!("{} {}건", "수정안 생성 중:".cyan(), selected.len());
+    let prompt = format!(
+        "코드 리뷰에서 다음 파일의 문제가 지적되었습니다.\n\n\
+        문제:\n{}\n\n\
+        이 문제들만 고치고 나머지 코드는 그대로 둔 파일 전체를 설명 없이 코드 블록 하나로 응답해주세요.\n\n\
+        파일:\n{}",
+        problems.join("\n"), fence_file(path, extension, &original)
+    );
+    let response = strip_file_blocks(&assistant.query(&prompt).await?).into_owned();
+    let updated = format!("{}\n", format_code(&extract_code_block(&response, extension), path).trim_end());
+    if updated == original {
+        println!("{}", "모델이 제안한 변경이 없습니다.".yellow());
+        return Ok(());
+    }
+    
+    let change = FileChange {
+        path: path.to_string(),
+        original_content: original,
+        new_content: updated,
+        description: format!("리뷰 문제 {}건 수정", selected.len()),
+    };
+    assistant.propose_changes(std::slice::from_ref(&change));
+    SafeFileModifier::new(config.assume_yes).by_hunk().modify_with_backup(vec![change]).await
+}
+
+/// 리뷰 문제를 해당 줄 위에 주석으로 달아 안전한 수정 절차(확인, 백업)로 적용합니다
+async fn annotate_review(target: &str, review: &CodeReview, config: &Config) -> Result<()> {
+    if review.issues.is_empty() {
+        println!("{}", "주석으로 달 리뷰 문제가 없습니다.".yellow());
+        return Ok(());
+    }
+    
+    let (path, _) = parse_review_target(target)?;
+    let original = std::fs::read_to_string(path)?;
+    let annotation = annotate_source(Path::new(path), &original, &review.issues)?;
+    if annotation.unplaced > 0 {
+        println!("{} 줄 번호를 알 수 없는 문제 {}건은 건너뜁니다", plain("⚠️").yellow(), annotation.unplaced);
+    }
+    if annotation.existing > 0 {
+        println!("{} 이미 주석이 달린 문제 {}건은 건너뜁니다", plain("ℹ️").dimmed(), annotation.existing);
+    }
+    if annotation.added == 0 {
+        println!("{}", "추가할 주석이 없습니다.".yellow());
+        return Ok(());
+    }
+    
+    let change = FileChange {
+        path: path.to_string(),
+        original_content: original,
+        new_content: annotation.content,
+        description: format!("리뷰 주석 {}건 추가", annotation.added),
+    };
+    SafeFileModifier::new(config.assume_yes).modify_with_backup(vec![change]).await
+}
+
+/// 경로 아래 파일별 리뷰 점수 추이를 보여줍니다. 배지를 요청하면 파일별 마지막 점수의 평균을 씁니다
+pub fn handle_review_history(path: &str, badge: Option<&Path>) -> Result<()> {
+    let history = load_history(path)?;
+    if history.is_empty() {
+        println!("{} 리뷰 기록이 없습니다: {}", plain("⚠️").yellow(), path);
+        return Ok(());
+    }
+    
+    let cwd = std::env::current_dir().ok().and_then(|d| d.canonicalize().ok());
+    println!("{}", plain(&format!("📈 리뷰 점수 추이 ({}개 경로)", history.len())).bright_cyan().bold());
+    for (file, records) in &history {
+        let display = cwd.as_ref()
+            .and_then(|cwd| Path::new(file).strip_prefix(cwd).ok())
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| file.clone());
+        let scores: Vec<f32> = records.iter().map(|r| r.score).collect();
+        let recent = &scores[scores.len().saturating_sub(HISTORY_POINTS)..];
+        let latest = scores[scores.len() - 1];
+        let change = match scores.len().checked_sub(2).map(|i| latest - scores[i]) {
+            Some(delta) if delta > 0.0 => format!("▲{:.0}", delta).green(),
+            Some(delta) if delta < 0.0 => format!("▼{:.0}", -delta).red(),
+            Some(_) => "=".dimmed(),
+            None => "새 기록".dimmed(),
+        };
+        let last = records[records.len() - 1].reviewed_at.with_timezone(&chrono::Local);
+        println!(
+            "  {:>3.0}  {:<width$}  {}  {}",
+            latest,
+            sparkline(recent),
+            change,
+            display,
+            width = HISTORY_POINTS,
+        );
+        println!("{}", format!("       {}회, 마지막 {}", records.len(), last.format("%Y-%m-%d %H:%M")).dimmed());
+    }
+    
+    if let Some(badge) = badge {
+        ensure_allowed("배지 저장")?;
+        let latest: Vec<f32> = history.iter().map(|(_, records)| records[records.len() - 1].score).collect();
+        write_badge(badge, latest.iter().sum::<f32>() / latest.len() as f32)?;
+    }
+    Ok(())
+}
+
+fn write_badge(path: &Path, score: f32) -> Result<()> {
+    std::fs::write(path, score_badge("review", score))
+        .with_context(|| format!("배지 저장 실패: {}", path.display()))?;
+    eprintln!("{} {} ({:.0}/100)", "배지 저장:".green(), path.display(), score);
     Ok(())
 }
 
-pub async fn handle_doc(target: &str, type_: &str, config: &Config) -> Result<()> {
+pub async fn handle_scan(
+    path: &str,
+    security: bool,
+    offline: bool,
+    output: Option<&Path>,
+    config: &Config,
+) -> Result<()> {
+    if !security {
+        println!("{} 스캔 종류를 지정하세요 (현재 지원: --security)", "안내:".yellow());
+        return Ok(());
+    }
+    if output.is_some() {
+        ensure_allowed("보고서 저장")?;
+    }
+    
+    if config.offline && !offline {
+        eprintln!("{} API 키가 없어 AI 분류를 건너뛰고 패턴 검사 결과만 보고합니다.", "[안내]".dimmed());
+    }
+    let offline = offline || config.offline;
+    
+    let sink = config.output_sink();
+    sink.notice(NoticeLevel::Info, &format!("{} {}", plain("🔒 보안 패턴 검사 중:").yellow(), path));
+    let (findings, scanned) = scan_patterns(Path::new(path))?;
+    sink.notice(NoticeLevel::Info, &plain(&format!("  • 검사한 파일: {}, 의심 위치: {}", scanned, findings.len())));
+    
+    // 패턴 검사에 걸린 코드 조각만 모델에 보내 분류
+    let report = if offline || findings.is_empty() {
+        SecurityReport::new(findings.into_iter().map(unreviewed).collect(), scanned, offline)
+    } else {
+        let assistant = DevAssistant::new(config.clone())?;
+        let triaged = assistant.triage_security_findings(findings).await?;
+        SecurityReport::new(triaged, scanned, false)
+    };
+    
+    let markdown = report.format_markdown();
+    sink.document(&markdown)?;
+    
+    if let Some(output) = output {
+        std::fs::write(output, &markdown)?;
+        sink.notice(NoticeLevel::Info, &format!("{} {}", "보고서 저장됨:".green(), output.display()));
+    }
+    
+    Ok(())
+}
+
+pub async fn handle_doc(
+    target: &str,
+    type_: &str,
+    template: Option<&Path>,
+    output: Option<&Path>,
+    sections: &[String],
+    share: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    if let Some(target) = share {
+        check_share_target(target, config)?;
+    }
+    let title = format!("{} 문서: {}", type_, doc_subject(target));
+    
+    // 이미 README가 있으면 덮어쓰지 않고 병합
+    let readme = Path::new(target).join("README.md");
+    if type_ == "readme" && readme.is_file() {
+        let accepted = merge_existing_readme(target, output.unwrap_or(&readme), &readme, sections, config).await?;
+        if let Some(share) = share {
+            share_accepted(share, &title, accepted.as_deref(), config).await?;
+        }
+        return Ok(());
+    }
+    
     println!("{} {} 문서 생성 중...", type_.cyan(), target);
+    if let Some(path) = find_doc_template(type_, template, config) {
+        println!("{} {}", "템플릿 사용:".dimmed(), path.display());
+    }
     
     let assistant = DevAssistant::new(config.clone())?;
-    let doc = assistant.generate_documentation(target, type_).await?;
-    
-    println!("\n{doc}");
+    let doc = assistant.generate_documentation(target, type_, template).await?;
     
+    let accepted = match output {
+        None => {
+            println!("\n{doc}");
+            Some(extract_markdown_block(&doc))
+        }
+        Some(output) => {
+            // 파일로 저장할 때는 기존 내용과의 diff를 보여주고 확인받음
+            let change = FileChange {
+                path: output.to_string_lossy().to_string(),
+                original_content: std::fs::read_to_string(output).unwrap_or_default(),
+                new_content: format!("{}\n", extract_markdown_block(&doc)),
+                description: format!("{} 문서 생성", type_),
+            };
+            apply_doc_change(change, config).await?
+        }
+    };
+    if let Some(share) = share {
+        share_accepted(share, &title, accepted.as_deref(), config).await?;
+    }
     Ok(())
+}
+
+/// 문서 변경을 확인받아 적용하고, 파일에 실제로 들어간 내용을 돌려줍니다.
+/// 건너뛰거나 취소했으면(읽기 전용 모드 포함) None, 일부 블록만 고르거나 고쳐 썼으면 그 결과
+async fn apply_doc_change(change: FileChange, config: &Config) -> Result<Option<String>> {
+    let path = change.path.clone();
+    let original = change.original_content.clone();
+    SafeFileModifier::new(config.assume_yes).modify_with_backup(vec![change]).await?;
+    let written = std::fs::read_to_string(&path).unwrap_or_default();
+    Ok((written != original).then_some(written))
+}
+
+/// 사용자가 받아들인 문서만 공유합니다
+async fn share_accepted(target: &str, title: &str, accepted: Option<&str>, config: &Config) -> Result<()> {
+    match accepted {
+        Some(content) => share_result(target, title, content, config).await,
+        None => {
+            println!("{}", "적용한 변경이 없어 문서를 공유하지 않았습니다.".yellow());
+            Ok(())
+        }
+    }
+}
+
+/// 공유 제목에 쓸 대상 이름 (`.`이면 현재 디렉토리 이름)
+fn doc_subject(target: &str) -> String {
+    std::fs::canonicalize(target).ok()
+        .and_then(|path| path.file_name().map(|name| name.to_string_lossy().to_string()))
+        .unwrap_or_else(|| target.to_string())
+}
+
+/// 기존 README를 보존 구역은 그대로 둔 채 갱신하고, 변경 내용을 diff로 확인받아 적용합니다.
+/// 적용한 내용을 돌려줌 (`apply_doc_change` 참고)
+async fn merge_existing_readme(
+    target: &str,
+    output: &Path,
+    readme: &Path,
+    sections: &[String],
+    config: &Config,
+) -> Result<Option<String>> {
+    let existing = std::fs::read_to_string(readme)?;
+    println!("{} {}", "기존 README와 병합합니다:".cyan(), readme.display());
+    println!("{}", format!("배지, 라이선스, {} 섹션은 그대로 유지됩니다", KEEP_MARKER).dimmed());
+    if !sections.is_empty() {
+        println!("{} {}", "다시 작성할 섹션:".dimmed(), sections.join(", "));
+    }
+    
+    let assistant = DevAssistant::new(config.clone())?;
+    let (merged, missing) = assistant.merge_readme(Path::new(target), &existing, sections).await?;
+    if missing > 0 {
+        println!("{}", format!("모델이 보존 구역 {}개를 빠뜨려 문서 끝에 덧붙였습니다. diff를 확인하세요.", missing).yellow());
+    }
+    
+    let original_content = if output == readme {
+        existing
+    } else {
+        std::fs::read_to_string(output).unwrap_or_default()
+    };
+    let change = FileChange {
+        path: output.to_string_lossy().to_string(),
+        original_content,
+        new_content: merged,
+        description: "README 갱신 (보존 구역 유지)".to_string(),
+    };
+    apply_doc_change(change, config).await
 } 
\ No newline at end of file
@@ -1,12 +1,19 @@
 use anyhow::Result;
 use colored::*;
+use crate::renderer::plain;
 
-use std::process::{Command, Stdio};
+use std::io::Write;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
 use crate::{
-    assistant::DevAssistant,
-    config::Config,
+    assistant::{expand_activity_references, DevAssistant, ACTIVITY_REFERENCES},
+    config::{Config, ShellConfig, ShellEncoding},
+    permissions::ensure_allowed,
+    paths::history_file,
     splash::display_splash,
 };
+use super::prompt::{GitStatus, PromptState, default_prompt, render_prompt};
+use super::shell::{decode_output, shell_command, CommandRecord, ShellState};
 use rustyline::error::ReadlineError;
 use rustyline::{Editor, CompletionType, Config as RustyConfig, EditMode, Cmd, EventHandler, KeyCode, KeyEvent, Modifiers};
 use rustyline::completion::{Completer, FilenameCompleter, Pair};
@@ -21,7 +28,12 @@ enum AppMode {
     Chat,
 }
 
-pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config) -> Result<()> {
+pub async fn handle_chat(
+    context: bool,
+    save_path: Option<&str>,
+    persona: Option<&str>,
+    config: &Config,
+) -> Result<()> {
     // 자동완성 헬퍼 구조체
     struct RicciHelper {
         completer: FilenameCompleter,
@@ -39,9 +51,9 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
                 validator: MatchingBracketValidator::new(),
                 hinter: HistoryHinter {},
                 commands: vec![
-                    "/clear", "/context", "/save", "/help", "/plan", 
+                    "/clear", "/context", "/context save", "/context load", "/context list", "/save", "/help", "/plan", 
                     "/analyze", "/review", "/doc", "/new", "/cls", 
-                    "/mode", "/summary", "/chat",
+                    "/mode", "/summary", "/chat", "/stats", "/compare", "/continue", "/thinking", "/autosave", "/pin", "/pins", "/unpin", "/persona", "/explain-last", "/adr",
                 ].into_iter().map(String::from).collect(),
             }
         }
@@ -63,6 +75,19 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
                 }
                 return Ok((0, matches));
             }
+            // `#lastcmd`, `#lastdiff` 참조
+            // 전각 공백처럼 여러 바이트인 공백 뒤에서도 글자 경계에서 자름
+            let start = line[..pos].char_indices().rev()
+                .find(|(_, c)| c.is_whitespace())
+                .map_or(0, |(i, c)| i + c.len_utf8());
+            let word = &line[start..pos];
+            if word.starts_with('#') {
+                let matches = ACTIVITY_REFERENCES.iter()
+                    .filter(|(reference, _)| reference.starts_with(word))
+                    .map(|(reference, _)| Pair { display: reference.to_string(), replacement: reference.to_string() })
+                    .collect();
+                return Ok((start, matches));
+            }
             self.completer.complete(line, pos, ctx)
         }
     }
@@ -88,8 +113,8 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
 
     impl Highlighter for RicciHelper {
         fn highlight_prompt<'b, 's: 'b, 'p: 'b>(&'s self, prompt: &'p str, _default: bool) -> std::borrow::Cow<'b, str> {
-            if prompt == "ricci (chat)> " {
-                std::borrow::Cow::Owned(format!("{} {}", "ricci".bright_blue().bold(), "(chat)>".yellow()))
+            if let Some(rest) = prompt.strip_prefix("ricci (chat)") {
+                std::borrow::Cow::Owned(format!("{} {}{}", "ricci".bright_blue().bold(), "(chat)".yellow(), rest.yellow()))
             } else {
                 std::borrow::Cow::Owned(prompt.bright_blue().bold().to_string())
             }
@@ -143,37 +168,45 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
     );
     
     // 히스토리 파일 로드
-    let history_path = dirs::data_dir()
-        .map(|p| p.join("ricci").join("history.txt"));
+    let history_path = history_file().ok();
     
     if let Some(ref path) = history_path {
         let _ = rl.load_history(path);
     }
     
     // Splash 화면 표시
-    display_splash()?;
+    display_splash(&config.output_preferences)?;
     
     let mut assistant = DevAssistant::new(config.clone())?;
     
     // 이전 세션 로드 시도
     assistant.load_session().await.ok();
     
+    if let Some(name) = persona {
+        assistant.set_persona(Some(name))?;
+        println!("{} {}", "페르소나 적용:".green(), name.cyan());
+    }
+    
     // 컨텍스트 파일 로드
     if context {
         println!("{}", "프로젝트 컨텍스트 로딩 중...".yellow());
         assistant.load_project_context(".").await?;
-        println!("{}", "✓ 프로젝트 컨텍스트 로드 완료\n".green());
+        println!("{}", plain("✓ 프로젝트 컨텍스트 로드 완료\n").green());
     }
     
     let mut mode = AppMode::Command;
+    // git 상태는 셸 명령어를 실행한 뒤에만 다시 확인
+    let mut shell = ShellState::default();
+    let mut git_status = GitStatus::detect(shell.cwd());
 
     loop {
-        let prompt = match mode {
-            AppMode::Command => "ricci> ",
-            AppMode::Chat => "ricci (chat)> ",
+        let state = PromptState::from_assistant(&assistant, mode == AppMode::Chat, git_status.clone());
+        let prompt = match &config.output_preferences.prompt {
+            Some(template) => render_prompt(template, &state),
+            None => default_prompt(&state),
         };
 
-        let readline = rl.readline(prompt);
+        let readline = rl.readline(&prompt);
         
         match readline {
             Ok(line) => {
@@ -202,12 +235,12 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
                             }
                             // 한글 명령어 처리
                             "폴더분석" | "폴더 분석" | "구조분석" | "구조 분석" => {
-                                println!("{}", "📁 현재 폴더 구조를 분석합니다...".green());
-                                super::handle_analyze(".", "structure", config).await?;
+                                println!("{}", plain("📁 현재 폴더 구조를 분석합니다...").green());
+                                super::handle_analyze(".", "structure", false, None, None, config).await?;
                                 continue;
                             }
                             "파일분석" | "파일 분석" | "코드분석" | "코드 분석" => {
-                                println!("{}", "📝 파일 경로를 입력하세요 (예: src/main.rs 또는 . 전체):".cyan());
+                                println!("{}", plain("📝 파일 경로를 입력하세요 (예: src/main.rs 또는 . 전체):").cyan());
                                 if let Ok(file_path) = rl.readline("파일 경로> ") {
                                     let file_path = file_path.trim();
                                     if !file_path.is_empty() {
@@ -217,15 +250,25 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
                                 continue;
                             }
                             "하위폴더 코드분석" | "하위폴더 분석" | "전체 코드분석" | "전체 코드 분석" => {
-                                println!("{}", "📂 하위 폴더의 모든 코드를 분석합니다...".green());
+                                println!("{}", plain("📂 하위 폴더의 모든 코드를 분석합니다...").green());
                                 super::handle_folder_code_analysis(".", &mut assistant, config).await?;
                                 continue;
                             }
                             "작업계획서" | "계획서" | "작업정리" | "작업 정리" => {
-                                println!("{}", "📋 대화 내용을 작업계획서로 정리합니다...".green());
+                                println!("{}", plain("📋 대화 내용을 작업계획서로 정리합니다...").green());
                                 super::command::handle_special_command("/summary", &mut assistant).await?;
                                 continue;
                             }
+                            "/explain-last" => {
+                                match shell.last_command() {
+                                    Some(record) => {
+                                        println!("{} {}", plain("🔍 마지막 명령어를 분석합니다:").cyan(), record.command);
+                                        assistant.stream_response(&record.explain_prompt()).await?;
+                                    }
+                                    None => println!("{}", "설명할 셸 명령어 실행 기록이 없습니다.".yellow()),
+                                }
+                                continue;
+                            }
                             cmd if cmd.starts_with('/') => {
                                 super::command::handle_special_command(cmd, &mut assistant).await?;
                                 continue;
@@ -234,7 +277,7 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
                                 // 한글 명령어를 직접 처리
                                 match input {
                                     "안녕" | "하이" | "헬로" => {
-                                        println!("안녕하세요! 무엇을 도와드릴까요? 🙂");
+                                        println!("{}", plain("안녕하세요! 무엇을 도와드릴까요? 🙂"));
                                         continue;
                                     }
                                     _ => {
@@ -242,10 +285,21 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
                                         if input.starts_with('?') || input.starts_with('@') {
                                             let query = input.trim_start_matches(['?', '@']).trim();
                                             if !query.is_empty() {
-                                                assistant.stream_response(query).await?;
+                                                assistant.stream_response(&expand_references(query)).await?;
                                             }
                                         } else {
-                                            execute_shell_command(input)?
+                                            if !shell.try_builtin(input)? {
+                                                if let Err(e) = ensure_allowed("셸 명령어 실행") {
+                                                    println!("{} {}", "차단됨:".red(), e);
+                                                } else if let Some(record) = execute_shell_command(input, &shell, &config.shell).await? {
+                                                    if !record.success() && config.output_preferences.explain_failed_commands {
+                                                        println!("{}", plain("🔍 실패 원인을 분석합니다...").cyan());
+                                                        assistant.stream_response(&record.explain_prompt()).await?;
+                                                    }
+                                                    shell.set_last_command(record);
+                                                }
+                                            }
+                                            git_status = GitStatus::detect(shell.cwd());
                                         }
                                     }
                                 }
@@ -258,7 +312,11 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
                             println!("{}", "명령어 모드로 돌아갑니다.".yellow());
                             continue;
                         }
-                        assistant.stream_response(input).await?;
+                        if super::command::is_special_command(input) {
+                            super::command::handle_special_command(input, &mut assistant).await?;
+                            continue;
+                        }
+                        assistant.stream_response(&expand_references(input)).await?;
                     }
                 }
             }
@@ -285,8 +343,10 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
         let _ = rl.save_history(path);
     }
     
-    // 세션 자동 저장
-    assistant.save_session().await?;
+    // 세션 자동 저장 (`auto_save_sessions`, `/autosave`)
+    if !assistant.auto_save_session().await? {
+        println!("{}", "세션 자동 저장이 꺼져 있어 세션을 저장하지 않았습니다. (/save 로 직접 저장)".dimmed());
+    }
     
     if let Some(path) = save_path {
         assistant.save_conversation(path)?;
@@ -296,89 +356,131 @@ pub async fn handle_chat(context: bool, save_path: Option<&str>, config: &Config
     Ok(())
 }
 
-fn execute_shell_command(input: &str) -> Result<()> {
+/// 입력의 `#lastcmd`, `#lastdiff`를 최근 작업 내용으로 펼칩니다
+fn expand_references(input: &str) -> String {
+    let result = expand_activity_references(input);
+    if !result.expanded.is_empty() {
+        println!("{} {}", plain("📎 최근 작업을 함께 보냅니다:").dimmed(), result.expanded.join(", ").dimmed());
+    }
+    for reference in &result.missing {
+        println!("{}", format!("{}: 참조할 최근 기록이 없어 그대로 보냅니다.", reference).yellow());
+    }
+    result.text
+}
+
+async fn execute_shell_command(
+    input: &str,
+    shell: &ShellState,
+    shell_config: &ShellConfig,
+) -> Result<Option<CommandRecord>> {
 
     
     // 한글 명령어 처리
     let processed_input = match input {
         "해당 하위 폴더구조 분석좀" | "폴더 분석" | "구조 분석" => {
-            println!("{}", "📁 현재 폴더 구조를 분석합니다...".green());
+            println!("{}", plain("📁 현재 폴더 구조를 분석합니다...").green());
             "ricci analyze ."
         }
         "파일 분석" | "코드 분석" => {
-            println!("{}", "📝 코드 분석 모드로 전환합니다. 파일 경로를 입력하세요...".green());
-            return Ok(());
+            println!("{}", plain("📝 코드 분석 모드로 전환합니다. 파일 경로를 입력하세요...").green());
+            return Ok(None);
         }
         "작업계획서" | "계획서 작성" | "작업 정리" => {
-            println!("{}", "📋 대화 내용을 작업계획서로 정리합니다...".green());
+            println!("{}", plain("📋 대화 내용을 작업계획서로 정리합니다...").green());
             "ricci plan \"현재 대화 내용 정리\""
         }
         _ => input,
     };
 
-    println!("{} {}", "❯ Executing:".dimmed(), processed_input);
+    println!("{} {}", plain("❯ Executing:").dimmed(), processed_input);
     
-    // Windows에서는 PowerShell을 사용하여 UTF-8 처리 개선
-    let mut command = if cfg!(target_os = "windows") {
-        let mut com = Command::new("powershell");
-        com.arg("-NoProfile")
-            .arg("-Command")
-            .arg(&format!("[Console]::OutputEncoding = [System.Text.Encoding]::UTF8; {}", processed_input));
-        com
-    } else {
-        let mut com = Command::new("sh");
-        com.arg("-c").arg(processed_input);
-        com
-    };
+    let mut command = shell_command(shell_config, processed_input);
 
     // 표준 입출력 설정
-    command.stdout(Stdio::piped())
+    command.envs(shell.env())
+        .current_dir(shell.cwd())
+        .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .stdin(Stdio::null());
-
-    match command.output() {
-        Ok(output) => {
-            // stdout 출력
-            if !output.stdout.is_empty() {
-                match String::from_utf8(output.stdout.clone()) {
-                    Ok(text) => print!("{}", text),
-                    Err(_) => {
-                        // UTF-8 실패 시 Windows 기본 인코딩 시도
-                        if cfg!(target_os = "windows") {
-                            // CP949 (Korean Windows) 디코딩 시도
-                            let (text, _, _) = encoding_rs::EUC_KR.decode(&output.stdout);
-                            print!("{}", text);
-                        } else {
-                            println!("{}", "출력을 디코딩할 수 없습니다".yellow());
-                        }
-                    }
-                }
-            }
-            
-            // stderr 출력
-            if !output.stderr.is_empty() {
-                match String::from_utf8(output.stderr.clone()) {
-                    Ok(text) => eprint!("{}", text.yellow()),
-                    Err(_) => {
-                        if cfg!(target_os = "windows") {
-                            let (text, _, _) = encoding_rs::EUC_KR.decode(&output.stderr);
-                            eprint!("{}", text.yellow());
-                        }
-                    }
-                }
-            }
-            
-            // 종료 코드 확인
-            if !output.status.success() {
-                if let Some(code) = output.status.code() {
-                    eprintln!("{} {}", "명령어 실행 실패. 종료 코드:".red(), code);
-                }
-            }
-        }
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
         Err(e) => {
             eprintln!("{} {}", "명령어 실행 오류:".red(), e);
+            return Ok(None);
+        }
+    };
+
+    // 출력은 버퍼링하지 않고 줄 단위로 바로 표시
+    let encoding = shell_config.encoding;
+    let stdout_task = child.stdout.take().map(|out| tokio::spawn(stream_lines(out, encoding, false)));
+    let stderr_task = child.stderr.take().map(|err| tokio::spawn(stream_lines(err, encoding, true)));
+
+    let status = tokio::select! {
+        status = child.wait() => Some(status),
+        _ = tokio::signal::ctrl_c() => {
+            let _ = child.kill().await;
+            None
+        }
+    };
+
+    let mut captured = [String::new(), String::new()];
+    for (task, output) in [stdout_task, stderr_task].into_iter().zip(captured.iter_mut()) {
+        let Some(task) = task else { continue };
+        if status.is_some() {
+            *output = task.await.unwrap_or_default();
+        } else {
+            // 중단한 경우 자식의 하위 프로세스가 파이프를 잡고 있을 수 있으므로 기다리지 않음
+            task.abort();
         }
     }
+    let [stdout, stderr] = captured;
+    let exit_code = status.as_ref().and_then(|s| s.as_ref().ok()).and_then(|s| s.code());
+
+    // 종료 상태 표시
+    match status {
+        Some(Ok(status)) if status.success() => {
+            println!("{}", plain("✓ 완료 (종료 코드 0)").dimmed());
+        }
+        Some(Ok(status)) => match status.code() {
+            Some(code) => eprintln!("{} {}", "명령어 실행 실패. 종료 코드:".red(), code),
+            None => eprintln!("{}", "명령어가 시그널로 종료되었습니다.".red()),
+        },
+        Some(Err(e)) => eprintln!("{} {}", "명령어 실행 오류:".red(), e),
+        None => println!("\n{}", "명령어를 중단했습니다 (Ctrl+C).".yellow()),
+    }
     
-    Ok(())
-} 
\ No newline at end of file
+    Ok(Some(CommandRecord {
+        command: processed_input.to_string(),
+        stdout,
+        stderr,
+        exit_code,
+    }))
+}
+
+/// 자식 프로세스 출력을 줄 단위로 읽어 바로 출력하고, 전체 출력을 반환합니다
+async fn stream_lines<R: AsyncRead + Unpin>(reader: R, encoding: ShellEncoding, is_stderr: bool) -> String {
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    let mut captured = String::new();
+
+    loop {
+        line.clear();
+        match reader.read_until(b'\n', &mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let text = decode_output(&line, encoding);
+                if is_stderr {
+                    eprint!("{}", text.yellow());
+                } else {
+                    print!("{}", text);
+                    let _ = std::io::stdout().flush();
+                }
+                captured.push_str(&text);
+            }
+        }
+    }
+
+    captured
+}
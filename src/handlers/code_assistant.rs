@@ -1,10 +1,16 @@
 use anyhow::Result;
 use colored::*;
+use crate::renderer::plain;
 use std::path::Path;
 use std::fs;
 use walkdir;
 use crate::{
-    assistant::{DevAssistant, SafeFileModifier, FileChange},
+    analyzer::{detect_project, language_registry},
+    assistant::{
+        DevAssistant, SafeFileModifier, FileChange, AnnotatedFile, format_code,
+        extract_annotated_files, annotated_files_to_changes, run_apply_hooks,
+        fence_file, strip_file_blocks,
+    },
     config::Config,
 };
 
@@ -33,12 +39,70 @@ impl Default for CodeAssistantOptions {
     }
 }
 
+/// `--options`와 `[code_assist_presets]`에 쓰는 옵션 이름
+pub const OPTION_NAMES: [&str; 7] = ["analyze", "refactor", "optimize", "security", "test", "docs", "fix"];
+
+/// 내장 프리셋. 설정의 `[code_assist_presets]`에 같은 이름이 있으면 그쪽을 사용
+const BUILTIN_PRESETS: [(&str, &[&str]); 5] = [
+    ("default", &["analyze", "refactor", "optimize", "security"]),
+    ("security", &["analyze", "security"]),
+    ("quality", &["analyze", "refactor"]),
+    ("performance", &["optimize"]),
+    ("full", &["analyze", "refactor", "optimize", "security", "test", "docs"]),
+];
+
+impl CodeAssistantOptions {
+    /// 옵션 이름 목록으로 만듭니다. 목록에 없는 옵션은 꺼집니다
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Result<Self> {
+        let mut options = Self {
+            analyze: false,
+            refactor: false,
+            optimize: false,
+            security: false,
+            test: false,
+            docs: false,
+            fix_all: false,
+        };
+        for name in names {
+            let flag = match name.as_ref().trim() {
+                "analyze" => &mut options.analyze,
+                "refactor" => &mut options.refactor,
+                "optimize" => &mut options.optimize,
+                "security" => &mut options.security,
+                "test" => &mut options.test,
+                "docs" => &mut options.docs,
+                "fix" => &mut options.fix_all,
+                other => anyhow::bail!("알 수 없는 옵션입니다: {} (사용 가능: {})", other, OPTION_NAMES.join(", ")),
+            };
+            *flag = true;
+        }
+        Ok(options)
+    }
+    
+    /// 이름으로 프리셋을 찾습니다. 설정의 프리셋이 같은 이름의 내장 프리셋보다 우선합니다
+    pub fn from_preset(name: &str, config: &Config) -> Result<Self> {
+        if let Some(names) = config.code_assist_presets.get(name) {
+            return Self::from_names(names);
+        }
+        match BUILTIN_PRESETS.iter().find(|(preset, _)| *preset == name) {
+            Some((_, names)) => Self::from_names(names),
+            None => {
+                let mut available: Vec<&str> = BUILTIN_PRESETS.iter().map(|(preset, _)| *preset).collect();
+                available.extend(config.code_assist_presets.keys().map(String::as_str));
+                available.sort();
+                available.dedup();
+                anyhow::bail!("알 수 없는 프리셋입니다: {} (사용 가능: {})", name, available.join(", "))
+            }
+        }
+    }
+}
+
 pub async fn run_code_assistant_interactive(
     path: &str,
     assistant: &mut DevAssistant,
     _config: &Config,
 ) -> Result<()> {
-    println!("{}", "🚀 고급 코드 어시스턴트".bright_cyan().bold());
+    println!("{}", plain("🚀 고급 코드 어시스턴트").bright_cyan().bold());
     println!("{}", "=".repeat(50).dimmed());
     
     // 옵션 선택
@@ -64,10 +128,10 @@ fn select_options() -> Result<CodeAssistantOptions> {
     use std::io::{self, Write};
     
     println!("\n{}", "분석 옵션을 선택하세요:".yellow());
-    println!("1. [✓] 코드 분석 (품질, 스타일, 복잡도)");
-    println!("2. [✓] 리팩토링 제안");
-    println!("3. [✓] 성능 최적화 제안");
-    println!("4. [✓] 보안 취약점 검사");
+    println!("{}", plain("1. [✓] 코드 분석 (품질, 스타일, 복잡도)"));
+    println!("{}", plain("2. [✓] 리팩토링 제안"));
+    println!("{}", plain("3. [✓] 성능 최적화 제안"));
+    println!("{}", plain("4. [✓] 보안 취약점 검사"));
     println!("5. [ ] 테스트 코드 생성");
     println!("6. [ ] 문서화 생성");
     println!("7. [ ] 모든 문제 자동 수정 (위험!)");
@@ -100,13 +164,13 @@ fn select_options() -> Result<CodeAssistantOptions> {
         
         // 현재 상태 표시
         println!("\n현재 선택:");
-        println!("1. [{}] 코드 분석", if options.analyze { "✓" } else { " " });
-        println!("2. [{}] 리팩토링 제안", if options.refactor { "✓" } else { " " });
-        println!("3. [{}] 성능 최적화", if options.optimize { "✓" } else { " " });
-        println!("4. [{}] 보안 검사", if options.security { "✓" } else { " " });
-        println!("5. [{}] 테스트 생성", if options.test { "✓" } else { " " });
-        println!("6. [{}] 문서화", if options.docs { "✓" } else { " " });
-        println!("7. [{}] 자동 수정", if options.fix_all { "✓" } else { " " });
+        println!("{}", plain(&format!("1. [{}] 코드 분석", if options.analyze { "✓" } else { " " })));
+        println!("{}", plain(&format!("2. [{}] 리팩토링 제안", if options.refactor { "✓" } else { " " })));
+        println!("{}", plain(&format!("3. [{}] 성능 최적화", if options.optimize { "✓" } else { " " })));
+        println!("{}", plain(&format!("4. [{}] 보안 검사", if options.security { "✓" } else { " " })));
+        println!("{}", plain(&format!("5. [{}] 테스트 생성", if options.test { "✓" } else { " " })));
+        println!("{}", plain(&format!("6. [{}] 문서화", if options.docs { "✓" } else { " " })));
+        println!("{}", plain(&format!("7. [{}] 자동 수정", if options.fix_all { "✓" } else { " " })));
     }
     
     Ok(options)
@@ -122,7 +186,7 @@ pub async fn analyze_file_interactive(
         .and_then(|s| s.to_str())
         .unwrap_or("");
     
-    println!("\n{} {}", "📄 파일 분석:".cyan(), file_path);
+    println!("\n{} {}", plain("📄 파일 분석:").cyan(), file_path);
     println!("{}", "=".repeat(50).dimmed());
     
     let mut analysis_results = Vec::new();
@@ -130,31 +194,24 @@ pub async fn analyze_file_interactive(
     
     // 1. 코드 분석
     if options.analyze {
-        println!("\n{}", "🔍 코드 품질 분석 중...".yellow());
-        let analysis = analyze_code_quality(assistant, &content, extension).await?;
+        println!("\n{}", plain("🔍 코드 품질 분석 중...").yellow());
+        let analysis = analyze_code_quality(assistant, &content, extension, file_path).await?;
         println!("{}", analysis);
         analysis_results.push(("코드 품질", analysis));
     }
     
     // 2. 리팩토링 제안
     if options.refactor {
-        println!("\n{}", "🔧 리팩토링 기회 찾는 중...".yellow());
-        let (suggestions, code) = suggest_refactoring(assistant, &content, extension).await?;
+        println!("\n{}", plain("🔧 리팩토링 기회 찾는 중...").yellow());
+        let (suggestions, files) = suggest_refactoring(assistant, &content, extension, file_path).await?;
         println!("{}", suggestions);
-        if !code.is_empty() {
-            suggested_changes.push(FileChange {
-                path: file_path.to_string(),
-                original_content: content.clone(),
-                new_content: code,
-                description: "리팩토링 제안".to_string(),
-            });
-        }
+        suggested_changes.extend(annotated_files_to_changes(files, "리팩토링 제안"));
     }
     
     // 3. 성능 최적화
     if options.optimize {
-        println!("\n{}", "⚡ 성능 최적화 분석 중...".yellow());
-        let (optimization, code) = analyze_performance(assistant, &content, extension).await?;
+        println!("\n{}", plain("⚡ 성능 최적화 분석 중...").yellow());
+        let (optimization, code) = analyze_performance(assistant, &content, extension, file_path).await?;
         println!("{}", optimization);
         if !code.is_empty() {
             suggested_changes.push(FileChange {
@@ -168,21 +225,18 @@ pub async fn analyze_file_interactive(
     
     // 4. 보안 검사
     if options.security {
-        println!("\n{}", "🔒 보안 취약점 검사 중...".yellow());
-        let security = check_security(assistant, &content, extension).await?;
+        println!("\n{}", plain("🔒 보안 취약점 검사 중...").yellow());
+        let security = check_security(assistant, &content, extension, file_path).await?;
         println!("{}", security);
         analysis_results.push(("보안 검사", security));
     }
     
     // 5. 테스트 코드 생성
     if options.test {
-        println!("\n{}", "🧪 테스트 코드 생성 중...".yellow());
+        println!("\n{}", plain("🧪 테스트 코드 생성 중...").yellow());
         let test_code = generate_tests(assistant, &content, extension, file_path).await?;
         if !test_code.is_empty() {
-            let test_file = format!("{}_test.{}", 
-                file_path.trim_end_matches(&format!(".{}", extension)), 
-                extension
-            );
+            let test_file = language_registry().test_file_path(file_path);
             suggested_changes.push(FileChange {
                 path: test_file,
                 original_content: String::new(),
@@ -194,18 +248,24 @@ pub async fn analyze_file_interactive(
     
     // 6. 문서화 생성
     if options.docs {
-        println!("\n{}", "📚 문서 생성 중...".yellow());
-        let docs = generate_documentation(assistant, &content, extension).await?;
+        println!("\n{}", plain("📚 문서 생성 중...").yellow());
+        let docs = generate_documentation(assistant, &content, extension, file_path).await?;
         println!("{}", docs);
         analysis_results.push(("문서화", docs));
     }
     
+    // 포매팅 차이로 diff가 지저분해지지 않도록 적용 전에 정리
+    for change in &mut suggested_changes {
+        change.new_content = format_code(&change.new_content, &change.path);
+    }
+    
     // 변경사항 적용
     if !suggested_changes.is_empty() {
-        println!("\n{}", "💡 제안된 변경사항:".green().bold());
+        println!("\n{}", plain("💡 제안된 변경사항:").green().bold());
         for (idx, change) in suggested_changes.iter().enumerate() {
             println!("{}. {} - {}", idx + 1, change.path, change.description);
         }
+        assistant.propose_changes(&suggested_changes);
         
         if options.fix_all {
             println!("\n{}", "자동 수정 모드가 활성화되어 있습니다.".yellow());
@@ -231,7 +291,8 @@ pub async fn analyze_file_interactive(
 async fn analyze_code_quality(
     assistant: &DevAssistant,
     content: &str,
-    extension: &str
+    extension: &str,
+    file_path: &str,
 ) -> Result<String> {
     let prompt = format!(
         "다음 {} 코드의 품질을 분석해주세요. 다음 관점에서 평가해주세요:\n\
@@ -241,8 +302,8 @@ async fn analyze_code_quality(
         4. 복잡도\n\
         5. 주석의 적절성\n\
         6. 에러 처리\n\n\
-        코드:\n```{}\n{}\n```",
-        extension, extension, content
+        코드:\n{}",
+        extension, fence_file(file_path, extension, content)
     );
     
     assistant.query(&prompt).await
@@ -251,8 +312,9 @@ async fn analyze_code_quality(
 async fn suggest_refactoring(
     assistant: &DevAssistant,
     content: &str,
-    extension: &str
-) -> Result<(String, String)> {
+    extension: &str,
+    file_path: &str,
+) -> Result<(String, Vec<AnnotatedFile>)> {
     let prompt = format!(
         "다음 {} 코드를 리팩토링해주세요. 다음을 개선해주세요:\n\
         1. 중복 코드 제거\n\
@@ -260,27 +322,38 @@ async fn suggest_refactoring(
         3. 더 나은 추상화\n\
         4. SOLID 원칙 적용\n\
         5. 디자인 패턴 적용\n\n\
-        먼저 개선점을 설명하고, 그 다음 전체 리팩토링된 코드를 제공해주세요.\n\n\
-        코드:\n```{}\n{}\n```",
-        extension, extension, content
+        먼저 개선점을 설명하고, 그 다음 전체 리팩토링된 코드를 제공해주세요.\n\
+        여러 파일로 나누는 경우 각 코드 블록 첫 줄에 `// 파일경로` 주석을 달아주세요.\n\n\
+        코드:\n{}",
+        extension, fence_file(file_path, extension, content)
     );
     
-    let response = assistant.query(&prompt).await?;
+    // 파일 내용을 그대로 옮겨 적은 부분은 변경 제안으로 쓰지 않음
+    let response = strip_file_blocks(&assistant.query(&prompt).await?).into_owned();
     
     // 응답에서 설명과 코드 분리
-    if let Some(code_start) = response.find("```") {
-        let explanation = response[..code_start].trim().to_string();
+    let Some(code_start) = response.find("```") else {
+        return Ok((response, Vec::new()));
+    };
+    let explanation = response[..code_start].trim().to_string();
+    
+    // 경로가 표시된 블록이 있으면 여러 파일로, 없으면 원본 파일 하나로 취급
+    let mut files = extract_annotated_files(&response[code_start..]);
+    if files.is_empty() {
         let code = extract_code_block(&response[code_start..], extension);
-        Ok((explanation, code))
-    } else {
-        Ok((response, String::new()))
+        if !code.is_empty() {
+            files.push(AnnotatedFile { path: file_path.to_string(), content: code });
+        }
     }
+    
+    Ok((explanation, files))
 }
 
 async fn analyze_performance(
     assistant: &DevAssistant,
     content: &str,
-    extension: &str
+    extension: &str,
+    file_path: &str,
 ) -> Result<(String, String)> {
     let prompt = format!(
         "다음 {} 코드의 성능을 분석하고 최적화해주세요:\n\
@@ -291,11 +364,11 @@ async fn analyze_performance(
         5. 병렬 처리 가능성\n\
         6. 메모리 사용 최적화\n\n\
         분석 결과와 최적화된 코드를 제공해주세요.\n\n\
-        코드:\n```{}\n{}\n```",
-        extension, extension, content
+        코드:\n{}",
+        extension, fence_file(file_path, extension, content)
     );
     
-    let response = assistant.query(&prompt).await?;
+    let response = strip_file_blocks(&assistant.query(&prompt).await?).into_owned();
     
     if let Some(code_start) = response.find("```") {
         let explanation = response[..code_start].trim().to_string();
@@ -309,7 +382,8 @@ async fn analyze_performance(
 async fn check_security(
     assistant: &DevAssistant,
     content: &str,
-    extension: &str
+    extension: &str,
+    file_path: &str,
 ) -> Result<String> {
     let prompt = format!(
         "다음 {} 코드의 보안 취약점을 검사해주세요:\n\
@@ -321,8 +395,8 @@ async fn check_security(
         6. 입력 검증 부족\n\
         7. 암호화 문제\n\n\
         발견된 취약점과 수정 방법을 설명해주세요.\n\n\
-        코드:\n```{}\n{}\n```",
-        extension, extension, content
+        코드:\n{}",
+        extension, fence_file(file_path, extension, content)
     );
     
     assistant.query(&prompt).await
@@ -340,20 +414,20 @@ async fn generate_tests(
         2. 엣지 케이스 테스트\n\
         3. 에러 케이스 테스트\n\
         4. 성능 테스트 (필요시)\n\n\
-        파일명: {}\n\
-        코드:\n```{}\n{}\n```\n\n\
+        코드:\n{}\n\n\
         테스트 코드만 제공해주세요.",
-        extension, file_path, extension, content
+        extension, fence_file(file_path, extension, content)
     );
     
     let response = assistant.query(&prompt).await?;
-    Ok(extract_code_block(&response, extension))
+    Ok(extract_code_block(&strip_file_blocks(&response), extension))
 }
 
 async fn generate_documentation(
     assistant: &DevAssistant,
     content: &str,
-    extension: &str
+    extension: &str,
+    file_path: &str,
 ) -> Result<String> {
     let prompt = format!(
         "다음 {} 코드에 대한 문서를 생성해주세요:\n\
@@ -362,8 +436,8 @@ async fn generate_documentation(
         3. 파라미터와 반환값\n\
         4. 사용 예제\n\
         5. 주의사항\n\n\
-        코드:\n```{}\n{}\n```",
-        extension, extension, content
+        코드:\n{}",
+        extension, fence_file(file_path, extension, content)
     );
     
     assistant.query(&prompt).await
@@ -373,20 +447,21 @@ pub async fn analyze_project_interactive(
     assistant: &mut DevAssistant,
     _options: &CodeAssistantOptions,
 ) -> Result<()> {
-    println!("\n{}", "🏗️ 프로젝트 전체 분석".bright_cyan().bold());
+    println!("\n{}", plain("🏗️ 프로젝트 전체 분석").bright_cyan().bold());
     println!("{}", "=".repeat(50).dimmed());
     
     // 현재 디렉토리의 프로젝트 구조 분석
     let current_dir = std::env::current_dir()?;
-    println!("📁 분석 대상: {}", current_dir.display());
+    println!("{}", plain(&format!("📁 분석 대상: {}", current_dir.display())));
     
     // 프로젝트 타입 감지
-    let project_type = detect_project_type(&current_dir)?;
-    println!("🔍 프로젝트 타입: {}", project_type.bright_green());
+    let project = detect_project(&current_dir);
+    let project_type = project.as_ref().map_or_else(|| "Unknown".to_string(), |p| p.to_string());
+    println!("{}", plain(&format!("🔍 프로젝트 타입: {}", project_type.bright_green())));
     
     // 프로젝트 메타데이터 읽기
     let mut project_metadata = String::new();
-    if project_type == "Rust" {
+    if project.as_ref().is_some_and(|p| p.language == "Rust") {
         if let Ok(cargo_toml) = fs::read_to_string(current_dir.join("Cargo.toml")) {
             // Cargo.toml에서 프로젝트 정보 추출
             if let Ok(toml) = cargo_toml.parse::<toml::Value>() {
@@ -445,7 +520,7 @@ pub async fn analyze_project_interactive(
                 // 코드 샘플 추출
                 if let Some(ext) = path.extension() {
                     let ext_str = ext.to_str().unwrap_or("");
-                    if matches!(ext_str, "rs" | "js" | "ts" | "py" | "go" | "java") {
+                    if language_registry().is_source(ext_str) {
                         // 언어별 통계
                         *language_stats.entry(ext_str.to_string()).or_insert(0) += 1;
                         
@@ -455,8 +530,7 @@ pub async fn analyze_project_interactive(
                                 .take(20)
                                 .collect::<Vec<_>>()
                                 .join("\n");
-                            code_samples.push(format!("파일: {}\n```{}\n{}\n```", 
-                                relative_path, ext_str, preview));
+                            code_samples.push(fence_file(&relative_path, ext_str, &preview));
                         }
                     }
                 }
@@ -465,23 +539,23 @@ pub async fn analyze_project_interactive(
     }
     
     // 프로젝트 통계 출력
-    println!("\n📊 프로젝트 통계:");
+    println!("{}", plain("\n📊 프로젝트 통계:"));
     if !project_metadata.is_empty() {
         print!("{}", project_metadata);
     }
-    println!("  • 총 파일 수: {}", file_count);
-    println!("  • 총 코드 라인: {}", total_lines.to_string().bright_yellow());
+    println!("{}", plain(&format!("  • 총 파일 수: {}", file_count)));
+    println!("{}", plain(&format!("  • 총 코드 라인: {}", total_lines.to_string().bright_yellow())));
     
     // 언어별 통계
     if !language_stats.is_empty() {
-        println!("\n📈 언어별 파일 수:");
+        println!("{}", plain("\n📈 언어별 파일 수:"));
         for (lang, count) in &language_stats {
-            println!("  • {}: {} 파일", lang, count);
+            println!("{}", plain(&format!("  • {}: {} 파일", lang, count)));
         }
     }
     
     // 주요 파일 목록 (상위 10개)
-    println!("\n📄 주요 파일:");
+    println!("{}", plain("\n📄 주요 파일:"));
     for (i, file) in files_info.iter().take(10).enumerate() {
         println!("  {}. {}", i + 1, file);
     }
@@ -538,7 +612,7 @@ pub async fn analyze_project_interactive(
         }
     }
     
-    println!("\n🤖 AI가 프로젝트를 분석하고 있습니다...");
+    println!("{}", plain("\n🤖 AI가 프로젝트를 분석하고 있습니다..."));
     
     let analysis_prompt = format!(
         "다음 {} 프로젝트의 실제 구조와 코드를 분석하고 구체적인 개선점을 제안해주세요:\n\n{}\n\n\
@@ -555,7 +629,7 @@ pub async fn analyze_project_interactive(
         project_type, project_summary
     );
     
-    let _analysis = assistant.stream_response(&analysis_prompt).await?;
+    assistant.stream_response(&analysis_prompt).await?;
     
     // 추가 분석 옵션
     println!("\n\n추가 분석을 원하시나요?");
@@ -595,24 +669,6 @@ pub async fn analyze_project_interactive(
     Ok(())
 }
 
-fn detect_project_type(path: &Path) -> Result<String> {
-    if path.join("Cargo.toml").exists() {
-        Ok("Rust".to_string())
-    } else if path.join("package.json").exists() {
-        Ok("Node.js/JavaScript".to_string())
-    } else if path.join("requirements.txt").exists() || path.join("setup.py").exists() {
-        Ok("Python".to_string())
-    } else if path.join("go.mod").exists() {
-        Ok("Go".to_string())
-    } else if path.join("pom.xml").exists() {
-        Ok("Java (Maven)".to_string())
-    } else if path.join("build.gradle").exists() {
-        Ok("Java (Gradle)".to_string())
-    } else {
-        Ok("Unknown".to_string())
-    }
-}
-
 fn should_ignore_path(path: &Path) -> bool {
     let ignore_dirs = vec![
         ".git", "target", "node_modules", ".venv", "venv", 
@@ -629,7 +685,7 @@ fn should_ignore_path(path: &Path) -> bool {
 }
 
 async fn analyze_dependencies(path: &Path, assistant: &mut DevAssistant) -> Result<()> {
-    println!("\n📦 의존성 분석 중...");
+    println!("{}", plain("\n📦 의존성 분석 중..."));
     
     let mut deps_info = String::new();
     
@@ -670,7 +726,7 @@ async fn analyze_dependencies(path: &Path, assistant: &mut DevAssistant) -> Resu
 }
 
 async fn analyze_code_complexity(path: &Path, assistant: &mut DevAssistant) -> Result<()> {
-    println!("\n🔬 코드 복잡도 분석 중...");
+    println!("{}", plain("\n🔬 코드 복잡도 분석 중..."));
     
     let mut complex_files = Vec::new();
     
@@ -687,10 +743,10 @@ async fn analyze_code_complexity(path: &Path, assistant: &mut DevAssistant) -> R
         
         if let Some(ext) = file_path.extension() {
             let ext_str = ext.to_str().unwrap_or("");
-            if matches!(ext_str, "rs" | "js" | "ts" | "py" | "go" | "java") {
+            if language_registry().is_source(ext_str) {
                 if let Ok(content) = fs::read_to_string(file_path) {
                     let lines = content.lines().count();
-                    let functions = count_functions(&content, ext_str);
+                    let functions = language_registry().count_functions(&content, ext_str);
                     
                     if lines > 300 || functions > 10 {
                         complex_files.push(format!(
@@ -708,7 +764,7 @@ async fn analyze_code_complexity(path: &Path, assistant: &mut DevAssistant) -> R
     if !complex_files.is_empty() {
         println!("\n복잡한 파일들:");
         for file in &complex_files {
-            println!("  • {}", file);
+            println!("{}", plain(&format!("  • {}", file)));
         }
         
         let prompt = format!(
@@ -728,23 +784,12 @@ async fn analyze_code_complexity(path: &Path, assistant: &mut DevAssistant) -> R
     Ok(())
 }
 
-fn count_functions(content: &str, extension: &str) -> usize {
-    match extension {
-        "rs" => content.matches("fn ").count(),
-        "js" | "ts" => content.matches("function").count() + content.matches("=>").count(),
-        "py" => content.matches("def ").count(),
-        "go" => content.matches("func ").count(),
-        "java" => content.matches("public ").count() + content.matches("private ").count(),
-        _ => 0,
-    }
-}
-
 pub async fn analyze_directory_interactive(
     path: &str,
     assistant: &mut DevAssistant,
-    options: &CodeAssistantOptions,
+    _options: &CodeAssistantOptions,
 ) -> Result<()> {
-    println!("\n{} {}", "📁 디렉토리 분석:".cyan(), path);
+    println!("\n{} {}", plain("📁 디렉토리 분석:").cyan(), path);
     
     // 디렉토리 내 파일들 분석
     super::handle_folder_code_analysis(path, assistant, &Config::default()).await?;
@@ -754,16 +799,22 @@ pub async fn analyze_directory_interactive(
 
 async fn apply_all_changes(
     changes: Vec<FileChange>,
-    assistant: &DevAssistant,
+    _assistant: &DevAssistant,
 ) -> Result<()> {
-    println!("\n{}", "🔄 모든 변경사항을 적용하는 중...".yellow());
+    crate::permissions::ensure_allowed("파일 수정")?;
+    println!("\n{}", plain("🔄 모든 변경사항을 적용하는 중...").yellow());
     
     for change in changes {
-        println!("  • {} 수정 중...", change.path);
+        if let Err(e) = crate::permissions::ensure_writable(std::path::Path::new(&change.path)) {
+            println!("  {} {}", "건너뜀:".red(), e);
+            continue;
+        }
+        println!("{}", plain(&format!("  • {} 수정 중...", change.path)));
         fs::write(&change.path, &change.new_content)?;
+        run_apply_hooks(&change);
     }
     
-    println!("{}", "✓ 모든 변경사항이 적용되었습니다!".green());
+    println!("{}", plain("✓ 모든 변경사항이 적용되었습니다!").green());
     Ok(())
 }
 
@@ -772,30 +823,40 @@ fn print_analysis_summary(results: &[(&str, String)]) {
         return;
     }
     
-    println!("\n{}", "📊 분석 요약".green().bold());
+    println!("\n{}", plain("📊 분석 요약").green().bold());
     println!("{}", "=".repeat(50).dimmed());
     
     for (category, _) in results {
-        println!("  ✓ {} 완료", category);
+        println!("{}", plain(&format!("  ✓ {} 완료", category)));
     }
 }
 
-fn extract_code_block(text: &str, language: &str) -> String {
-    let patterns = vec![
-        format!("```{}\n", language),
-        "```\n".to_string(),
-        format!("```{}", language),
-        "```".to_string(),
-    ];
-    
-    for pattern in patterns {
-        if let Some(start) = text.find(&pattern) {
-            let code_start = start + pattern.len();
-            if let Some(end) = text[code_start..].find("```") {
-                return text[code_start..code_start + end].trim().to_string();
+/// 응답의 코드 블록 본문을 꺼냅니다. 여는 줄(```` ```언어 ````)의 언어 표시가 `language`인 블록을 먼저 고르고,
+/// 없으면 첫 블록을 씁니다. 닫는 줄은 ```` ``` ````만 있는 줄이며, 코드 블록이 없으면 응답 전체를 반환합니다
+pub(crate) fn extract_code_block(text: &str, language: &str) -> String {
+    let mut blocks: Vec<(&str, String)> = Vec::new();
+    let mut open: Option<(&str, String)> = None;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        match open.as_mut() {
+            Some(_) if trimmed == "```" => blocks.extend(open.take()),
+            Some((_, body)) => {
+                body.push_str(line);
+                body.push('\n');
+            }
+            None => {
+                if let Some(info) = trimmed.strip_prefix("```") {
+                    open = Some((info.split_whitespace().next().unwrap_or(""), String::new()));
+                }
             }
         }
     }
+    // 닫히지 않은 블록은 응답 끝까지
+    blocks.extend(open);
     
-    text.trim().to_string()
-} 
\ No newline at end of file
+    blocks.iter()
+        .find(|(info, _)| info.eq_ignore_ascii_case(language))
+        .or(blocks.first())
+        .map(|(_, body)| body.trim().to_string())
+        .unwrap_or_else(|| text.trim().to_string())
+}
\ No newline at end of file
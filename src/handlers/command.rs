@@ -1,11 +1,25 @@
 use anyhow::Result;
 use colored::*;
+use crate::renderer::plain;
 use std::io::Write;
 use crate::{
-    assistant::{DevAssistant, ChatMode},
+    assistant::{DevAssistant, ChatMode, Message, ACTIVITY_REFERENCES},
     analyzer::CodeAnalyzer,
+    integrations::{check_share_target, share, SHARE_TARGETS},
+    permissions::ensure_allowed,
 };
 
+/// `handle_special_command`가 처리하는 명령어 이름
+const SPECIAL_COMMANDS: &[&str] = &[
+    "/clear", "/cls", "/new", "/context", "/save", "/help", "/plan", "/analyze", "/review", "/summary", "/adr",
+    "/continue", "/stats", "/thinking", "/autosave", "/compare", "/mode", "/persona", "/pin", "/pins", "/unpin", "/doc",
+];
+
+/// 입력의 첫 단어가 알려진 `/` 명령어인지 (대화 모드에서 `/etc/hosts 설명해줘` 같은 질문은 모델로 보냄)
+pub fn is_special_command(input: &str) -> bool {
+    input.split_whitespace().next().is_some_and(|name| SPECIAL_COMMANDS.contains(&name))
+}
+
 pub async fn handle_special_command(command: &str, assistant: &mut DevAssistant) -> Result<()> {
     match command {
         "/clear" => {
@@ -16,7 +30,7 @@ pub async fn handle_special_command(command: &str, assistant: &mut DevAssistant)
             // 화면 초기화
             print!("\x1B[2J\x1B[1;1H");
             std::io::stdout().flush()?;
-            crate::splash::display_mini_splash();
+            crate::splash::display_mini_splash(&assistant.get_config().output_preferences);
             if command == "/new" {
                 assistant.clear_context();
                 println!("{}", "새 대화를 시작합니다.".green());
@@ -26,6 +40,9 @@ pub async fn handle_special_command(command: &str, assistant: &mut DevAssistant)
             let context = assistant.get_context_summary();
             println!("{}\n{}", "현재 컨텍스트:".bright_blue(), context);
         }
+        cmd if cmd.starts_with("/context ") => {
+            super::context::handle_context_command(cmd.trim_start_matches("/context "), assistant);
+        }
         "/save" => {
             assistant.save_session().await?;
             println!("{}", "세션이 저장되었습니다.".green());
@@ -46,15 +63,143 @@ pub async fn handle_special_command(command: &str, assistant: &mut DevAssistant)
         cmd if cmd.starts_with("/review ") => {
             let path = cmd.trim_start_matches("/review ").trim();
             println!("{} {}", "코드 리뷰 중:".yellow(), path);
-            let review = assistant.review_code(path, "all").await?;
-            println!("\n{}", review.format_markdown());
+            let review = assistant.review_code(path, "all", false, false).await?;
+            println!("\n{}", plain(&review.format_markdown()));
         }
         "/summary" => {
+            if let Err(e) = ensure_allowed("파일 저장") {
+                println!("{} {}", "오류:".red(), e);
+                println!("{}", "공유는 /summary <대상>으로 할 수 있습니다.".dimmed());
+                return Ok(());
+            }
             println!("{}", "작업 계획서를 생성하고 저장하는 중...".yellow());
             let plan = assistant.export_as_plan("markdown").await?;
             let filename = format!("plan_{}.md", chrono::Local::now().format("%Y%m%d_%H%M%S"));
             std::fs::write(&filename, &plan)?;
-            println!("{} 작업 계획서가 {} 파일로 저장되었습니다.", "✓".green(), filename.cyan());
+            println!("{} 작업 계획서가 {} 파일로 저장되었습니다.", plain("✓").green(), filename.cyan());
+        }
+        cmd if cmd.starts_with("/summary ") => {
+            // 세션 요약을 저장하지 않고 바로 공유 (예: /summary notion)
+            let target = cmd.trim_start_matches("/summary ").trim();
+            if let Err(e) = check_share_target(target, assistant.get_config()) {
+                println!("{} {}", "오류:".red(), e);
+                return Ok(());
+            }
+            println!("{}", "세션 요약을 만드는 중...".yellow());
+            let summary = assistant.export_as_plan("markdown").await?;
+            let title = format!("세션 요약: {}", chrono::Local::now().format("%Y-%m-%d %H:%M"));
+            if let Err(e) = share(target, &title, &summary, assistant.get_config()).await {
+                println!("{} {}", "공유 실패:".red(), e);
+            }
+        }
+        "/adr" => {
+            println!("{}", "사용법: /adr <제목> (예: /adr 세션 저장소를 SQLite로 변경)".yellow());
+        }
+        cmd if cmd.starts_with("/adr ") => {
+            let title = cmd.trim_start_matches("/adr ").trim();
+            println!("{}", "대화 내용을 ADR로 정리하는 중...".yellow());
+            let path = assistant.export_adr(title).await?;
+            println!("{} ADR이 {} 파일로 저장되었습니다.", plain("✓").green(), path.display().to_string().cyan());
+        }
+        "/continue" => {
+            if !assistant.continue_response().await? {
+                println!("{}", "이어받을 잘린 응답이 없습니다.".yellow());
+            }
+        }
+        "/stats" => {
+            let enabled = assistant.toggle_stats();
+            println!("{} 응답 통계 표시: {}", plain("✓").green(), if enabled { "켜짐".green() } else { "꺼짐".red() });
+        }
+        "/thinking" => {
+            match assistant.last_reasoning() {
+                Some(reasoning) => {
+                    println!("{}", plain("💭 마지막 응답의 추론 과정").bright_cyan().bold());
+                    println!("{}", reasoning.trim().dimmed());
+                }
+                None => println!("{}", "표시할 추론 과정이 없습니다. (`<think>` 블록을 응답에 넣는 모델만 지원)".yellow()),
+            }
+        }
+        "/autosave" => {
+            println!("세션 자동 저장: {} (/autosave on|off 로 변경)", if assistant.auto_save() { "켜짐".green() } else { "꺼짐".red() });
+        }
+        "/autosave on" | "/autosave off" => {
+            let enabled = command == "/autosave on";
+            assistant.set_auto_save(enabled);
+            println!("{} 세션 자동 저장: {}", plain("✓").green(), if enabled { "켜짐".green() } else { "꺼짐".red() });
+        }
+        "/thinking toggle" => {
+            let enabled = assistant.toggle_reasoning();
+            println!("{} 추론 과정 펼쳐 보기: {}", plain("✓").green(), if enabled { "켜짐".green() } else { "꺼짐".red() });
+        }
+        cmd if cmd.starts_with("/compare ") => {
+            let prompt = cmd.trim_start_matches("/compare ").trim();
+            if !prompt.is_empty() {
+                super::compare::handle_compare(prompt, &[], assistant.get_config()).await?;
+            }
+        }
+        "/mode" => {
+            println!("{} {}", "현재 모드:".bright_blue(), assistant.get_mode().label().cyan());
+            println!("  1. normal  2. concise  3. detailed  4. code  5. planning");
+            println!("{}", "변경하려면 /mode <번호>를 입력하세요.".dimmed());
+        }
+        "/persona" => {
+            match assistant.persona() {
+                Some(name) => println!("{} {}", "현재 페르소나:".bright_blue(), name.cyan()),
+                None => println!("{}", "선택된 페르소나가 없습니다.".dimmed()),
+            }
+            let personas = crate::assistant::list_personas();
+            if personas.is_empty() {
+                println!("{}", "사용 가능한 페르소나가 없습니다. `ricci sync`로 팀 페르소나를 가져오세요.".yellow());
+            } else {
+                println!("사용 가능: {}", personas.join(", "));
+            }
+        }
+        "/persona off" => {
+            assistant.set_persona(None)?;
+            println!("{} 페르소나를 해제했습니다.", plain("✓").green());
+        }
+        "/pin" => {
+            // 번호를 고를 수 있게 최근 메시지를 보여줌
+            let messages = &assistant.context().messages;
+            if messages.is_empty() {
+                println!("{}", "고정할 메시지가 없습니다.".yellow());
+                return Ok(());
+            }
+            println!("{}", "최근 메시지 (/pin <번호>로 고정):".bright_blue());
+            let start = messages.len().saturating_sub(10);
+            for (index, message) in messages.iter().enumerate().skip(start) {
+                print_message_line(index + 1, message);
+            }
+        }
+        "/pins" => {
+            let pinned = assistant.pinned_messages();
+            if pinned.is_empty() {
+                println!("{}", "고정한 메시지가 없습니다. /pin <번호>로 고정하세요.".yellow());
+            } else {
+                println!("{}", "고정한 메시지 (세션을 보관/요약해도 남음):".bright_blue());
+                for (number, message) in pinned {
+                    print_message_line(number, message);
+                }
+            }
+        }
+        cmd if cmd.starts_with("/pin ") || cmd.starts_with("/unpin ") => {
+            let pin = cmd.starts_with("/pin ");
+            let Ok(number) = cmd.split_whitespace().nth(1).unwrap_or_default().parse::<usize>() else {
+                println!("{}", "메시지 번호를 입력하세요 (예: /pin 3, 번호는 /pin 으로 확인)".red());
+                return Ok(());
+            };
+            match assistant.set_pinned(number, pin) {
+                Ok(_) if pin => println!("{} {}번 메시지를 고정했습니다.", plain("✓").green(), number),
+                Ok(_) => println!("{} {}번 메시지 고정을 해제했습니다.", plain("✓").green(), number),
+                Err(e) => println!("{} {}", "오류:".red(), e),
+            }
+        }
+        cmd if cmd.starts_with("/persona ") => {
+            let name = cmd.trim_start_matches("/persona ").trim();
+            match assistant.set_persona(Some(name)) {
+                Ok(()) => println!("{} 페르소나가 {}(으)로 변경되었습니다.", plain("✓").green(), name.cyan()),
+                Err(e) => println!("{} {}", "오류:".red(), e),
+            }
         }
         cmd if cmd.starts_with("/mode ") => {
             let mode_str = cmd.trim_start_matches("/mode ").trim();
@@ -70,7 +215,7 @@ pub async fn handle_special_command(command: &str, assistant: &mut DevAssistant)
                 }
             };
             assistant.set_mode(mode);
-            println!("{} 모드가 {:?}로 변경되었습니다.", "✓".green(), mode);
+            println!("{} 모드가 {}(으)로 변경되었습니다.", plain("✓").green(), mode.label().cyan());
         }
         cmd if cmd.starts_with("/doc ") => {
             let parts: Vec<&str> = cmd.split_whitespace().collect();
@@ -78,7 +223,7 @@ pub async fn handle_special_command(command: &str, assistant: &mut DevAssistant)
                 let target = parts[1];
                 let doc_type = parts.get(2).unwrap_or(&"readme");
                 println!("{} {} 문서 생성 중...", doc_type.cyan(), target);
-                let doc = assistant.generate_documentation(target, doc_type).await?;
+                let doc = assistant.generate_documentation(target, doc_type, None).await?;
                 println!("\n{doc}");
             }
         }
@@ -96,19 +241,34 @@ pub fn print_special_commands() {
     println!("  {} ({})        - 현재 대화 내용으로 작업 계획서를 생성하고 파일로 저장합니다.", "/summary".cyan(), "p".green());
 
     println!("{}", "\n자동완성:".bright_green().bold());
-    println!("  {} 또는 {}    - 입력 중 회색으로 표시되는 명령어를 완성합니다.", "Tab".bright_yellow(), "→".bright_yellow());
+    println!("  {} 또는 {}    - 입력 중 회색으로 표시되는 명령어를 완성합니다.", "Tab".bright_yellow(), plain("→").bright_yellow());
     println!("  {}         - 가능한 명령어 목록을 확인합니다.", "Ctrl+I".bright_yellow());
 
     println!("{}", "\n모든 특수 명령어:".bright_blue().bold());
     println!("  {}       - 새 대화 시작 (컨텍스트 초기화)", "/new, /clear".cyan());
     println!("  {}           - 화면을 지웁니다.", "/cls".cyan());
     println!("  {}         - 현재 대화 모드를 확인하고 변경합니다.", "/mode".cyan());
+    println!("  {} <이름> - 공유 페르소나를 선택합니다. (off: 해제)", "/persona".cyan());
     println!("  {}       - 현재 세션을 파일로 저장합니다.", "/save".cyan());
+    println!("  {}      - 응답 후 속도/토큰 통계 표시를 켜고 끕니다.", "/stats".cyan());
+    println!("  {}   - 길이 제한으로 잘린 마지막 응답을 이어서 받습니다.", "/continue".cyan());
+    println!("  {}   - 추론 모델의 마지막 사고 과정을 봅니다. (toggle: 항상 펼치기)", "/thinking".cyan());
+    println!("  {} - 종료할 때 세션을 자동 저장할지 정합니다. (이번 실행에만 적용)", "/autosave on|off".cyan());
+    println!("  {} <번호> - 메시지를 고정해 세션을 보관/요약해도 원문을 남깁니다. (번호 없이: 최근 메시지 목록)", "/pin, /unpin".cyan());
+    println!("  {}          - 고정한 메시지 목록을 봅니다.", "/pins".cyan());
+    println!("  {} - 마지막 셸 명령어와 출력을 AI에게 보내 설명/실패 진단을 받습니다.", "/explain-last".cyan());
+    for (reference, description) in ACTIVITY_REFERENCES {
+        println!("  {:<13} - {} (질문에 쓰면 펼쳐서 함께 보냅니다)", reference.cyan(), description);
+    }
     println!("  {}     - 현재 프로젝트 구조를 분석합니다.", "/analyze".cyan());
     println!("  {} <file>   - 지정된 파일의 코드를 리뷰합니다.", "/review".cyan());
     println!("  {} <target> - 지정된 대상에 대한 문서를 생성합니다.", "/doc".cyan());
+    println!("  {} <prompt> - 여러 모델의 답변을 동시에 받아 비교합니다.", "/compare".cyan());
     println!("  {}   - 대화 내용 기반으로 작업계획서를 생성합니다.", "/plan, /summary".cyan());
+    println!("  {} <대상> - 세션 요약을 공유합니다 ({}).", "/summary".cyan(), SHARE_TARGETS.join(", "));
+    println!("  {} <제목> - 대화에서 내린 결정을 docs/adr에 ADR 문서로 저장합니다.", "/adr".cyan());
     println!("  {}         - 현재 대화의 컨텍스트 정보를 봅니다.", "/context".cyan());
+    println!("  {} <이름> - 첨부 파일, 페르소나, 모드, 기억을 문맥 묶음으로 저장하거나 불러옵니다. ({}: 목록)", "/context save|load".cyan(), "/context list".cyan());
     
     println!("{}", "\n한글 명령어:".bright_blue().bold());
     println!("  {} - 현재 폴더의 구조를 분석합니다.", "폴더 분석, 구조 분석".cyan());
@@ -144,4 +304,11 @@ fn get_plan_templates() -> String {
    ricci plan "Kubernetes 기반 CI/CD 파이프라인 구축"
    ricci plan "모니터링 및 로깅 시스템 구현"
 "#.to_string()
-} 
\ No newline at end of file
+} 
+
+/// `/pin`, `/pins` 목록의 한 줄: 번호, 역할, 본문 첫 줄
+fn print_message_line(number: usize, message: &Message) {
+    let first_line: String = message.content.lines().next().unwrap_or_default().chars().take(70).collect();
+    let marker = if message.pinned { plain("📌") } else { "  ".into() };
+    println!("  {:>3} {} {:<9} {}", number, marker, message.role.dimmed(), first_line);
+}
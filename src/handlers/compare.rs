@@ -0,0 +1,69 @@
+use anyhow::Result;
+use colored::*;
+use futures::future::join_all;
+use std::time::Instant;
+use crate::{
+    api::OpenAIClient,
+    config::Config,
+    renderer::{plain, MarkdownRenderer},
+};
+
+const MAX_COMPARE_MODELS: usize = 3;
+
+pub async fn handle_compare(prompt: &str, models: &[String], config: &Config) -> Result<()> {
+    let models = resolve_models(models, config);
+
+    if models.len() < 2 {
+        anyhow::bail!(
+            "비교하려면 모델이 2개 이상 필요합니다. --models 또는 model_preferences.compare_models를 설정하세요"
+        );
+    }
+
+    println!("{} {}", "모델 비교 중:".yellow(), models.join(", ").cyan());
+
+    let clients = models.iter()
+        .map(|model| OpenAIClient::with_model(config, model))
+        .collect::<Result<Vec<_>>>()?;
+
+    // 모든 모델에 동시에 요청
+    let results = join_all(clients.iter().map(|client| async move {
+        let started = Instant::now();
+        let answer = client.query(prompt).await;
+        (client.model(), answer, started.elapsed())
+    })).await;
+
+    let renderer = MarkdownRenderer::with_theme(&config.output_preferences.theme);
+    for (model, answer, elapsed) in results {
+        println!("\n{}", "=".repeat(50).dimmed());
+        println!("{} {} {}",
+            plain("▸").bright_yellow(),
+            model.bright_cyan().bold(),
+            format!("({:.2}s)", elapsed.as_secs_f64()).dimmed()
+        );
+        println!("{}", "=".repeat(50).dimmed());
+
+        match answer {
+            Ok(text) => renderer.render(&text)?,
+            Err(e) => println!("{} {}", "오류:".red(), e),
+        }
+    }
+
+    Ok(())
+}
+
+fn resolve_models(models: &[String], config: &Config) -> Vec<String> {
+    let source = if models.is_empty() {
+        &config.model_preferences.compare_models
+    } else {
+        models
+    };
+
+    let mut resolved: Vec<String> = Vec::new();
+    for model in source {
+        if !resolved.contains(model) {
+            resolved.push(model.clone());
+        }
+    }
+    resolved.truncate(MAX_COMPARE_MODELS);
+    resolved
+}
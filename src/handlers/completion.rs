@@ -2,10 +2,146 @@ use anyhow::{Result, Context};
 use clap::{CommandFactory};
 use clap_complete::{generate, Shell};
 use colored::*;
-use std::io::Write;
-use crate::Cli;
+use crate::renderer::plain;
+use std::path::{Path, PathBuf};
+use crate::{
+    api::known_models,
+    assistant::list_personas,
+    paths::sessions_dir,
+    config::{get_config_path, parse_config},
+    Cli,
+};
 
-pub fn install_completions(shell: Option<Shell>) -> Result<()> {
+/// rc 파일에 추가한 줄을 감싸는 표시 주석 (재설치/제거 시 이 구간만 교체)
+const RC_BLOCK_START: &str = "# >>> ricci completion >>>";
+const RC_BLOCK_END: &str = "# <<< ricci completion <<<";
+/// 표시 주석을 쓰기 전 버전이 추가하던 머리 주석
+const LEGACY_RC_HEADER: &str = "# Ricci CLI 자동완성";
+
+const SUPPORTED_SHELLS: [Shell; 4] = [Shell::Bash, Shell::Zsh, Shell::PowerShell, Shell::Fish];
+
+/// `ricci complete-values <kind>`로 출력하는 동적 자동완성 후보 종류
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum CompletionKind {
+    Models,
+    Sessions,
+    Personas,
+}
+
+/// 동적 후보를 한 줄에 하나씩 출력합니다 (쉘 스크립트에서 호출되므로 오류는 조용히 무시)
+pub fn print_completion_values(kind: CompletionKind) {
+    let values: Vec<String> = match kind {
+        CompletionKind::Models => {
+            // load_config는 안내 문구를 출력하므로 설정 파일을 직접 읽음
+            let mut models = Vec::new();
+            let config = get_config_path().ok()
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .and_then(|content| parse_config(&content).ok());
+            if let Some(config) = config {
+                models.push(config.model_preferences.default_model);
+                models.extend(config.model_preferences.compare_models);
+            }
+            models.extend(known_models().map(String::from));
+            let mut seen = std::collections::HashSet::new();
+            models.retain(|m| seen.insert(m.clone()));
+            models
+        }
+        CompletionKind::Sessions => sessions_dir().ok()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|e| e == "json"))
+            .map(|p| p.display().to_string())
+            .collect(),
+        CompletionKind::Personas => list_personas(),
+    };
+    
+    for value in values {
+        println!("{value}");
+    }
+}
+
+/// 정적 완성 스크립트에 모델/세션/페르소나 동적 후보 연결을 덧붙입니다
+pub fn completion_script(shell: Shell) -> Result<String> {
+    let mut cmd = Cli::command();
+    let mut script = Vec::new();
+    generate(shell, &mut cmd, "ricci", &mut script);
+    let script = String::from_utf8(script)?;
+    
+    Ok(match shell {
+        Shell::Bash => format!("{script}\n{BASH_DYNAMIC}"),
+        Shell::Zsh => {
+            let script = script
+                .replace(":MODEL:_default'", ":MODEL:_ricci_dynamic_models'")
+                .replace(":SESSION:_files'", ":SESSION:_ricci_dynamic_sessions'")
+                .replace(":PERSONA:_default'", ":PERSONA:_ricci_dynamic_personas'");
+            // 자동 로드 시 첫 호출 전에 정의되도록 마지막 실행 구문 앞에 넣음
+            match script.rfind("if [ \"$funcstack[1]\" = \"_ricci\" ]") {
+                Some(index) => format!("{}{ZSH_DYNAMIC}\n{}", &script[..index], &script[index..]),
+                None => format!("{script}\n{ZSH_DYNAMIC}"),
+            }
+        }
+        Shell::Fish => format!("{script}\n{FISH_DYNAMIC}"),
+        _ => script,
+    })
+}
+
+const BASH_DYNAMIC: &str = r#"# ricci 동적 후보 (모델, 세션, 페르소나)
+_ricci_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "$prev" in
+        --models|-m)
+            local prefix=""
+            [[ "$cur" == *,* ]] && prefix="${cur%,*},"
+            COMPREPLY=($(compgen -P "$prefix" -W "$(ricci complete-values models 2>/dev/null)" -- "${cur##*,}"))
+            return 0
+            ;;
+        --input|-i)
+            COMPREPLY=($(compgen -W "$(ricci complete-values sessions 2>/dev/null)" -- "$cur"))
+            return 0
+            ;;
+        --persona)
+            COMPREPLY=($(compgen -W "$(ricci complete-values personas 2>/dev/null)" -- "$cur"))
+            return 0
+            ;;
+    esac
+    _ricci "$@"
+}
+complete -F _ricci_dynamic -o bashdefault -o default ricci
+"#;
+
+const ZSH_DYNAMIC: &str = r#"# ricci 동적 후보 (모델, 세션, 페르소나)
+_ricci_dynamic_models() {
+    local -a models
+    models=(${(f)"$(ricci complete-values models 2>/dev/null)"})
+    _values -s , 'model' $models
+}
+_ricci_dynamic_sessions() {
+    local -a sessions
+    sessions=(${(f)"$(ricci complete-values sessions 2>/dev/null)"})
+    compadd -a sessions
+}
+_ricci_dynamic_personas() {
+    local -a personas
+    personas=(${(f)"$(ricci complete-values personas 2>/dev/null)"})
+    compadd -a personas
+}
+"#;
+
+const FISH_DYNAMIC: &str = r#"# ricci 동적 후보 (모델, 세션, 페르소나)
+complete -c ricci -n "__fish_ricci_using_subcommand compare" -s m -l models -r -f -a "(ricci complete-values models)"
+complete -c ricci -n "__fish_ricci_using_subcommand session; and __fish_seen_subcommand_from export" -s i -l input -r -a "(ricci complete-values sessions)"
+complete -c ricci -n "__fish_ricci_using_subcommand chat" -l persona -r -f -a "(ricci complete-values personas)"
+"#;
+
+pub fn install_completions(shell: Option<Shell>, uninstall: bool) -> Result<()> {
+    crate::permissions::ensure_allowed(if uninstall { "자동완성 제거" } else { "자동완성 설치" })?;
+    if uninstall {
+        return uninstall_completions(shell);
+    }
+    
     // 쉘 자동 감지
     let detected_shell = if let Some(shell) = shell {
         shell
@@ -19,21 +155,27 @@ pub fn install_completions(shell: Option<Shell>) -> Result<()> {
     );
     
     // 완성 스크립트 생성
-    let mut cmd = Cli::command();
-    let mut script = Vec::new();
-    generate(detected_shell, &mut cmd, "ricci", &mut script);
-    let script_content = String::from_utf8(script)?;
+    let script_content = completion_script(detected_shell)?;
     
-    // 설치 경로 결정
-    match detected_shell {
-        Shell::Bash => install_bash_completion(&script_content)?,
-        Shell::Zsh => install_zsh_completion(&script_content)?,
-        Shell::PowerShell => install_powershell_completion(&script_content)?,
-        Shell::Fish => install_fish_completion(&script_content)?,
-        _ => anyhow::bail!("지원하지 않는 쉘입니다: {detected_shell:?}"),
+    // 완성 스크립트는 덮어쓰고, rc 파일 구간은 교체하므로 여러 번 실행해도 결과가 같음
+    let paths = completion_paths(detected_shell)?;
+    if let Some(parent) = paths.script.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&paths.script, script_content)?;
+    
+    if let Some((rc_file, lines)) = &paths.rc {
+        if detected_shell == Shell::PowerShell {
+            if let Some(parent) = rc_file.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        if rc_file.exists() {
+            write_rc_block(rc_file, lines)?;
+        }
     }
     
-    println!("{}", "✓ 자동완성 설치 완료!".green().bold());
+    println!("{}", plain("✓ 자동완성 설치 완료!").green().bold());
     println!("\n다음 중 하나를 실행하여 적용하세요:");
     
     match detected_shell {
@@ -72,98 +214,163 @@ fn detect_shell() -> Result<Shell> {
     Ok(Shell::Bash)
 }
 
-fn install_bash_completion(script: &str) -> Result<()> {
-    let home = dirs::home_dir().context("홈 디렉토리를 찾을 수 없습니다")?;
-    let completion_dir = home.join(".local").join("share").join("bash-completion").join("completions");
-    std::fs::create_dir_all(&completion_dir)?;
-    
-    let completion_file = completion_dir.join("ricci");
-    std::fs::write(&completion_file, script)?;
-    
-    // .bashrc에 추가
-    let bashrc = home.join(".bashrc");
-    if bashrc.exists() {
-        let content = std::fs::read_to_string(&bashrc)?;
-        if !content.contains("bash-completion/completions") {
-            let mut file = std::fs::OpenOptions::new()
-                .append(true)
-                .open(&bashrc)?;
-            writeln!(file, "\n# Ricci CLI 자동완성")?;
-            writeln!(file, "[ -f ~/.local/share/bash-completion/completions/ricci ] && source ~/.local/share/bash-completion/completions/ricci")?;
+/// 완성 스크립트와 rc 파일 줄을 제거합니다. 쉘을 지정하지 않으면 지원하는 모든 쉘에서 제거
+fn uninstall_completions(shell: Option<Shell>) -> Result<()> {
+    let shells = match shell {
+        Some(shell) => vec![shell],
+        None => SUPPORTED_SHELLS.to_vec(),
+    };
+    
+    let mut removed = 0;
+    for shell in shells {
+        let paths = completion_paths(shell)?;
+        
+        if paths.script.exists() {
+            std::fs::remove_file(&paths.script)
+                .with_context(|| format!("삭제 실패: {}", paths.script.display()))?;
+            println!("  {} {}", "삭제:".yellow(), paths.script.display());
+            removed += 1;
+        }
+        
+        if let Some((rc_file, _)) = &paths.rc {
+            if rc_file.exists() && remove_rc_block(rc_file)? {
+                println!("  {} {}", "rc 파일 정리:".yellow(), rc_file.display());
+                removed += 1;
+            }
         }
     }
     
+    if removed == 0 {
+        println!("{}", "제거할 자동완성 설치가 없습니다.".dimmed());
+    } else {
+        println!("{}", plain("✓ 자동완성 제거 완료!").green().bold());
+    }
     Ok(())
 }
 
-fn install_zsh_completion(script: &str) -> Result<()> {
+/// 쉘별 완성 스크립트 경로와 rc 파일에 추가할 줄
+struct CompletionPaths {
+    script: PathBuf,
+    rc: Option<(PathBuf, Vec<String>)>,
+}
+
+fn completion_paths(shell: Shell) -> Result<CompletionPaths> {
     let home = dirs::home_dir().context("홈 디렉토리를 찾을 수 없습니다")?;
-    let completion_dir = home.join(".local").join("share").join("zsh").join("completions");
-    std::fs::create_dir_all(&completion_dir)?;
-    
-    let completion_file = completion_dir.join("_ricci");
-    std::fs::write(&completion_file, script)?;
-    
-    // .zshrc에 fpath 추가
-    let zshrc = home.join(".zshrc");
-    if zshrc.exists() {
-        let content = std::fs::read_to_string(&zshrc)?;
-        if !content.contains(".local/share/zsh/completions") {
-            let mut file = std::fs::OpenOptions::new()
-                .append(true)
-                .open(&zshrc)?;
-            writeln!(file, "\n# Ricci CLI 자동완성")?;
-            writeln!(file, "fpath=(~/.local/share/zsh/completions $fpath)")?;
-            writeln!(file, "autoload -Uz compinit && compinit")?;
+    
+    let paths = match shell {
+        Shell::Bash => CompletionPaths {
+            script: home.join(".local").join("share").join("bash-completion").join("completions").join("ricci"),
+            rc: Some((home.join(".bashrc"), vec![
+                "[ -f ~/.local/share/bash-completion/completions/ricci ] && source ~/.local/share/bash-completion/completions/ricci".to_string(),
+            ])),
+        },
+        Shell::Zsh => CompletionPaths {
+            script: home.join(".local").join("share").join("zsh").join("completions").join("_ricci"),
+            rc: Some((home.join(".zshrc"), vec![
+                "fpath=(~/.local/share/zsh/completions $fpath)".to_string(),
+                "autoload -Uz compinit && compinit".to_string(),
+            ])),
+        },
+        Shell::PowerShell => {
+            let script = dirs::config_dir()
+                .context("설정 디렉토리를 찾을 수 없습니다")?
+                .join("ricci")
+                .join("ricci-completion.ps1");
+            let import_line = format!(". \"{}\"", script.display());
+            CompletionPaths {
+                rc: std::env::var("PROFILE").ok().map(|profile| (PathBuf::from(profile), vec![import_line])),
+                script,
+            }
         }
+        Shell::Fish => CompletionPaths {
+            script: dirs::config_dir()
+                .context("설정 디렉토리를 찾을 수 없습니다")?
+                .join("fish")
+                .join("completions")
+                .join("ricci.fish"),
+            rc: None,
+        },
+        _ => anyhow::bail!("지원하지 않는 쉘입니다: {shell:?}"),
+    };
+    Ok(paths)
+}
+
+/// 기존 구간을 지우고 표시 주석으로 감싼 줄을 파일 끝에 추가합니다
+fn write_rc_block(rc_file: &Path, lines: &[String]) -> Result<()> {
+    let content = std::fs::read_to_string(rc_file).unwrap_or_default();
+    let mut content = strip_rc_block(&content);
+    
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
     }
+    content.push('\n');
+    content.push_str(RC_BLOCK_START);
+    content.push('\n');
+    for line in lines {
+        content.push_str(line);
+        content.push('\n');
+    }
+    content.push_str(RC_BLOCK_END);
+    content.push('\n');
     
-    Ok(())
+    std::fs::write(rc_file, content)
+        .with_context(|| format!("rc 파일 쓰기 실패: {}", rc_file.display()))
+}
+
+/// 추가했던 구간이 있으면 지우고 true를 반환합니다
+fn remove_rc_block(rc_file: &Path) -> Result<bool> {
+    let content = std::fs::read_to_string(rc_file)?;
+    let stripped = strip_rc_block(&content);
+    if stripped == content {
+        return Ok(false);
+    }
+    std::fs::write(rc_file, stripped)
+        .with_context(|| format!("rc 파일 쓰기 실패: {}", rc_file.display()))?;
+    Ok(true)
 }
 
-fn install_powershell_completion(script: &str) -> Result<()> {
-    let config_dir = dirs::config_dir()
-        .context("설정 디렉토리를 찾을 수 없습니다")?
-        .join("ricci");
-    std::fs::create_dir_all(&config_dir)?;
+/// 표시 주석 구간과, 이전 버전이 머리 주석과 함께 추가한 줄을 제거합니다
+fn strip_rc_block(content: &str) -> String {
+    let legacy_lines = [
+        "[ -f ~/.local/share/bash-completion/completions/ricci ] && source ~/.local/share/bash-completion/completions/ricci",
+        "fpath=(~/.local/share/zsh/completions $fpath)",
+        "autoload -Uz compinit && compinit",
+    ];
     
-    let completion_file = config_dir.join("ricci-completion.ps1");
-    std::fs::write(&completion_file, script)?;
+    let mut result: Vec<&str> = Vec::new();
+    let mut in_block = false;
+    let mut in_legacy = false;
     
-    // PowerShell 프로필에 추가
-    if let Ok(profile) = std::env::var("PROFILE") {
-        let profile_path = std::path::Path::new(&profile);
-        if let Some(parent) = profile_path.parent() {
-            std::fs::create_dir_all(parent)?;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed == RC_BLOCK_START {
+            in_block = true;
+        } else if in_block {
+            in_block = trimmed != RC_BLOCK_END;
+            continue;
+        } else if trimmed == LEGACY_RC_HEADER {
+            in_legacy = true;
+        } else if in_legacy && (legacy_lines.contains(&trimmed) || is_legacy_import(trimmed)) {
+            continue;
+        } else {
+            in_legacy = false;
+            result.push(line);
+            continue;
         }
         
-        if profile_path.exists() {
-            let content = std::fs::read_to_string(profile_path)?;
-            let import_line = format!(". \"{}\"", completion_file.display());
-            
-            if !content.contains(&import_line) {
-                let mut file = std::fs::OpenOptions::new()
-                    .append(true)
-                    .create(true)
-                    .open(profile_path)?;
-                writeln!(file, "\n# Ricci CLI 자동완성")?;
-                writeln!(file, "{import_line}")?;
-            }
+        // 구간 앞에 넣었던 빈 줄도 함께 제거
+        if result.last().is_some_and(|l| l.trim().is_empty()) {
+            result.pop();
         }
     }
     
-    Ok(())
+    let mut stripped = result.join("\n");
+    if !stripped.is_empty() && content.ends_with('\n') {
+        stripped.push('\n');
+    }
+    stripped
 }
 
-fn install_fish_completion(script: &str) -> Result<()> {
-    let config_dir = dirs::config_dir()
-        .context("설정 디렉토리를 찾을 수 없습니다")?
-        .join("fish")
-        .join("completions");
-    std::fs::create_dir_all(&config_dir)?;
-    
-    let completion_file = config_dir.join("ricci.fish");
-    std::fs::write(&completion_file, script)?;
-    
-    Ok(())
-} 
\ No newline at end of file
+fn is_legacy_import(line: &str) -> bool {
+    line.starts_with(". \"") && line.ends_with("ricci-completion.ps1\"")
+}
@@ -1,6 +1,11 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::*;
-use crate::config::Config;
+use crate::renderer::plain;
+use dialoguer::Select;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+use crate::config::{Config, get_config_path, parse_config, validate_settings};
 
 #[derive(clap::Subcommand)]
 pub enum ConfigAction {
@@ -15,6 +20,8 @@ pub enum ConfigAction {
     Show,
     /// 설정 초기화
     Reset,
+    /// 설정 파일을 $VISUAL/$EDITOR로 열고 저장 후 검증
+    Edit,
 }
 
 pub fn handle_config(action: ConfigAction) -> Result<()> {
@@ -31,6 +38,115 @@ pub fn handle_config(action: ConfigAction) -> Result<()> {
             Config::reset()?;
             println!("{}", "설정이 초기화되었습니다.".yellow());
         }
+        ConfigAction::Edit => edit_config()?,
     }
     Ok(())
+}
+
+/// 설정 파일을 편집기로 열고, 저장된 내용에 오류가 있으면 줄 번호와 함께 보여준 뒤
+/// 다시 편집하거나 이전 내용으로 되돌릴 수 있게 합니다 (설정이 깨져 있어도 실행 가능)
+pub fn edit_config() -> Result<()> {
+    crate::permissions::ensure_allowed("설정 편집")?;
+    let path = get_config_path()?;
+    if !path.exists() {
+        Config::default().save()?;
+    }
+    let original = fs::read_to_string(&path).context("설정 파일 읽기 실패")?;
+
+    loop {
+        open_in_editor(&path)?;
+        let content = fs::read_to_string(&path).context("설정 파일 읽기 실패")?;
+
+        let Err(message) = check_config(&content) else {
+            if content == original {
+                println!("{}", "변경사항이 없습니다.".dimmed());
+            } else {
+                println!("{} {}", plain("✓ 설정을 저장했습니다:").green(), path.display());
+            }
+            return Ok(());
+        };
+
+        println!("\n{}\n{}", "설정 파일에 오류가 있습니다:".red().bold(), message);
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("설정 파일 검증 실패");
+        }
+
+        let choice = Select::new()
+            .with_prompt("어떻게 할까요?")
+            .items(&["다시 편집", "이전 내용으로 되돌리기", "오류가 있는 채로 저장"])
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => continue,
+            1 => {
+                fs::write(&path, &original).context("설정 파일 복원 실패")?;
+                println!("{}", "이전 설정으로 되돌렸습니다.".yellow());
+                return Ok(());
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
+fn open_in_editor(path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+    // "code --wait"처럼 인자가 붙은 편집기 지원
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("편집기가 지정되지 않았습니다")?;
+
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("편집기 실행 실패: {}", editor))?;
+    if !status.success() {
+        anyhow::bail!("편집기가 오류로 종료되었습니다 ({})", status);
+    }
+    Ok(())
+}
+
+/// 설정 파일 내용을 파싱/검증하고, 실패하면 줄 번호가 포함된 메시지를 돌려줍니다
+fn check_config(content: &str) -> std::result::Result<(), String> {
+    let mut config = match parse_config(content) {
+        Ok(config) => config,
+        Err(e) => {
+            // 마이그레이션을 거친 값에는 위치 정보가 없으므로 원문을 직접 읽어 위치를 찾음
+            return Err(match toml::from_str::<Config>(content) {
+                Err(direct) if direct.span().is_some() => direct.to_string(),
+                _ => format!("{:#}", e),
+            });
+        }
+    };
+
+    config.apply_provider_models();
+    // API 키는 보통 환경 변수로 지정하므로 파일에 없다는 이유로 거부하지 않음
+    validate_settings(&config).map_err(|e| {
+        let message = format!("{:#}", e);
+        match locate_line(content, &message) {
+            Some((line, text)) => format!("{}\n  --> {}번째 줄: {}", message, line, text.trim()),
+            None => message,
+        }
+    })
+}
+
+/// 검증 오류 메시지에 나오는 키 이름이나 값이 있는 줄을 찾습니다
+fn locate_line<'a>(content: &'a str, message: &str) -> Option<(usize, &'a str)> {
+    let mentions = |word: &str| {
+        !word.is_empty() && message.match_indices(word).any(|(i, _)| {
+            let after = message[i + word.len()..].chars().next();
+            !after.is_some_and(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        })
+    };
+
+    content.lines().enumerate().find_map(|(i, line)| {
+        let trimmed = line.trim();
+        if let Some(section) = trimmed.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            return mentions(&format!("[{}]", section)).then_some((i + 1, line));
+        }
+        let (key, value) = trimmed.split_once('=')?;
+        let value = value.trim().trim_matches('"');
+        (mentions(key.trim()) || mentions(value)).then_some((i + 1, line))
+    })
 } 
\ No newline at end of file
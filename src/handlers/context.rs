@@ -0,0 +1,138 @@
+use anyhow::Result;
+use colored::*;
+use crate::renderer::plain;
+use std::path::{Path, PathBuf};
+use crate::assistant::{list_personas, ContextBundle, ContextStore, DevAssistant, FileContextStore};
+
+#[derive(clap::Subcommand)]
+pub enum ContextAction {
+    /// 현재 세션의 첨부 파일, 페르소나, 대화 모드, 기억(보관된 요약, 고정한 메시지)을 이름 붙여 저장
+    Save {
+        /// 묶음 이름 (예: billing-service)
+        name: String,
+        /// 함께 첨부할 파일 (여러 번 지정 가능)
+        #[clap(short, long = "file", value_name = "FILE")]
+        files: Vec<PathBuf>,
+        /// 세션의 페르소나 대신 저장할 페르소나
+        #[clap(long)]
+        persona: Option<String>,
+    },
+    /// 저장된 문맥 묶음 목록
+    List,
+    /// 문맥 묶음 내용 보기
+    Show {
+        name: String,
+    },
+    /// 문맥 묶음 삭제
+    Delete {
+        name: String,
+    },
+}
+
+pub fn handle_context(action: ContextAction) -> Result<()> {
+    match action {
+        ContextAction::Save { name, files, persona } => {
+            let session = FileContextStore::current_session()?.load()?.unwrap_or_default();
+            let mut bundle = ContextBundle::from_context(&name, &session)?;
+            for file in &files {
+                bundle.add_file(file)?;
+            }
+            if let Some(persona) = persona {
+                anyhow::ensure!(list_personas().contains(&persona),
+                    "페르소나를 찾을 수 없습니다: {} (`ricci sync`로 공유 페르소나를 받으세요)", persona);
+                bundle.persona = Some(persona);
+            }
+            save_bundle(&bundle)?;
+        }
+        ContextAction::List => list_bundles()?,
+        ContextAction::Show { name } => print_bundle(&ContextBundle::load(&name)?),
+        ContextAction::Delete { name } => {
+            ContextBundle::delete(&name)?;
+            println!("{} 문맥 묶음 '{}'을(를) 삭제했습니다.", plain("✓").green(), name);
+        }
+    }
+    Ok(())
+}
+
+/// 대화 중 `/context save|load|list` 처리. 오류는 출력하고 대화를 이어감
+pub fn handle_context_command(args: &str, assistant: &mut DevAssistant) {
+    if let Err(e) = run_context_command(args, assistant) {
+        println!("{} {}", "오류:".red(), e);
+    }
+}
+
+fn run_context_command(args: &str, assistant: &mut DevAssistant) -> Result<()> {
+    match args.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["save", name] => save_bundle(&ContextBundle::from_context(name, assistant.context())?)?,
+        ["load", name] => {
+            let bundle = ContextBundle::load(name)?;
+            if let Some(location) = assistant.load_bundle(&bundle)? {
+                println!("{} {}", "지금 대화 보관:".dimmed(), location);
+            }
+            println!("{} 문맥 묶음 '{}'을(를) 불러왔습니다.", plain("✓").green(), bundle.name.cyan());
+            print_summary(&bundle);
+        }
+        ["list"] => list_bundles()?,
+        _ => println!("{} /context [save <이름> | load <이름> | list]", "사용법:".yellow()),
+    }
+    Ok(())
+}
+
+fn save_bundle(bundle: &ContextBundle) -> Result<()> {
+    let path = bundle.save()?;
+    println!("{} 문맥 묶음 '{}'을(를) 저장했습니다: {}", plain("✓").green(), bundle.name.cyan(), path.display().to_string().dimmed());
+    print_summary(bundle);
+    if bundle.files.is_empty() && bundle.persona.is_none() && bundle.summary.is_none() && bundle.pinned.is_empty() {
+        println!("{}", "저장할 첨부 파일이나 기억이 없어 대화 모드만 저장했습니다. (--file, --persona, /pin 참고)".yellow());
+    }
+    Ok(())
+}
+
+fn list_bundles() -> Result<()> {
+    let bundles = ContextBundle::list()?;
+    if bundles.is_empty() {
+        println!("저장된 문맥 묶음이 없습니다. `ricci context save <이름>`으로 만드세요.");
+        return Ok(());
+    }
+    for bundle in &bundles {
+        println!("{}  {}  파일 {}개 · 고정 메시지 {}개{}",
+            bundle.name.cyan(),
+            bundle.saved_at.format("%Y-%m-%d"),
+            bundle.files.len(),
+            bundle.pinned.len(),
+            bundle.persona.as_deref().map(|p| format!(" · 페르소나 {p}")).unwrap_or_default());
+    }
+    Ok(())
+}
+
+/// 한 줄 요약: 모드, 페르소나, 파일 수, 기억
+fn print_summary(bundle: &ContextBundle) {
+    println!("  대화 모드 {} · 페르소나 {} · 첨부 파일 {}개 · 고정 메시지 {}개 · 요약 {}",
+        bundle.chat_mode.label(),
+        bundle.persona.as_deref().unwrap_or("없음"),
+        bundle.files.len(),
+        bundle.pinned.len(),
+        if bundle.summary.is_some() { "있음" } else { "없음" });
+}
+
+fn print_bundle(bundle: &ContextBundle) {
+    println!("{} ({})", bundle.name.cyan().bold(), bundle.saved_at.format("%Y-%m-%d %H:%M"));
+    print_summary(bundle);
+    if !bundle.files.is_empty() {
+        println!("\n{}", "첨부 파일:".bright_blue());
+        for file in &bundle.files {
+            let missing = if Path::new(file).exists() { "" } else { " (없음)" };
+            println!("  {} {}{}", plain("•"), file, missing.red());
+        }
+    }
+    if let Some(summary) = &bundle.summary {
+        println!("\n{}\n{}", "보관된 대화 요약:".bright_blue(), summary);
+    }
+    if !bundle.pinned.is_empty() {
+        println!("\n{}", "고정한 메시지:".bright_blue());
+        for message in &bundle.pinned {
+            let first_line: String = message.content.lines().next().unwrap_or_default().chars().take(70).collect();
+            println!("  {:<9} {}", message.role.dimmed(), first_line);
+        }
+    }
+}
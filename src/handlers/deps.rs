@@ -0,0 +1,223 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::*;
+use serde_json::Value;
+use std::path::Path;
+use std::time::Duration;
+use crate::renderer::{plain, MarkdownRenderer};
+use crate::{
+    assistant::{fence_file, DevAssistant},
+    config::Config,
+};
+
+#[derive(clap::Subcommand)]
+pub enum DepsAction {
+    /// 레지스트리 정보(다운로드 수, 마지막 릴리스, 라이선스, 저장소)와 AI 추천, 대안 보기
+    Info {
+        /// 크레이트 또는 패키지 이름
+        name: String,
+        /// 조회할 레지스트리 (비워두면 현재 디렉토리의 매니페스트로 판단, 없으면 crates.io)
+        #[clap(long, value_enum)]
+        registry: Option<Registry>,
+        /// AI 추천 없이 레지스트리 정보만 보기
+        #[clap(long)]
+        no_ai: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Registry {
+    Crates,
+    Npm,
+    Pypi,
+}
+
+impl Registry {
+    fn detect() -> Self {
+        if Path::new("package.json").exists() {
+            Self::Npm
+        } else if Path::new("pyproject.toml").exists() || Path::new("requirements.txt").exists() {
+            Self::Pypi
+        } else {
+            Self::Crates
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Crates => "crates.io",
+            Self::Npm => "npm",
+            Self::Pypi => "PyPI",
+        }
+    }
+}
+
+/// 레지스트리에서 모은 패키지 정보
+#[derive(Debug, Default)]
+struct PackageInfo {
+    version: String,
+    description: Option<String>,
+    license: Option<String>,
+    repository: Option<String>,
+    homepage: Option<String>,
+    /// (기간 설명, 다운로드 수)
+    downloads: Vec<(&'static str, u64)>,
+    last_release: Option<DateTime<Utc>>,
+    versions: Option<usize>,
+}
+
+pub async fn handle_deps(action: DepsAction, config: &Config) -> Result<()> {
+    match action {
+        DepsAction::Info { name, registry, no_ai } => {
+            let registry = registry.unwrap_or_else(Registry::detect);
+            println!("{} {} ({})", "레지스트리 조회 중:".dimmed(), name, registry.label());
+            let info = fetch_info(registry, &name).await?;
+            print_info(registry, &name, &info);
+            if !no_ai {
+                recommend(registry, &name, &info, config).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn fetch_info(registry: Registry, name: &str) -> Result<PackageInfo> {
+    // crates.io는 User-Agent 없는 요청을 거부함
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .user_agent(concat!("ricci/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    let get = |url: String| {
+        let client = client.clone();
+        async move {
+            let response = client.get(&url).send().await.with_context(|| format!("요청 실패: {}", url))?;
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                anyhow::bail!("{}에서 {}을(를) 찾을 수 없습니다", registry.label(), name);
+            }
+            Ok::<Value, anyhow::Error>(response.error_for_status()?.json().await?)
+        }
+    };
+    let text = |value: &Value| value.as_str().filter(|s| !s.is_empty()).map(str::to_string);
+    let time = |value: &Value| value.as_str().and_then(|s| s.parse::<DateTime<Utc>>().ok());
+
+    Ok(match registry {
+        Registry::Crates => {
+            let json = get(format!("https://crates.io/api/v1/crates/{}", name)).await?;
+            let krate = &json["crate"];
+            let versions = json["versions"].as_array();
+            let latest = versions.and_then(|v| v.iter().find(|v| v["yanked"] != true));
+            PackageInfo {
+                version: text(&krate["max_stable_version"]).or_else(|| text(&krate["newest_version"])).unwrap_or_default(),
+                description: text(&krate["description"]),
+                license: latest.and_then(|v| text(&v["license"])),
+                repository: text(&krate["repository"]),
+                homepage: text(&krate["homepage"]),
+                downloads: [("전체", &krate["downloads"]), ("최근 90일", &krate["recent_downloads"])]
+                    .into_iter()
+                    .filter_map(|(label, value)| value.as_u64().map(|n| (label, n)))
+                    .collect(),
+                last_release: latest.and_then(|v| time(&v["created_at"])),
+                versions: versions.map(Vec::len),
+            }
+        }
+        Registry::Npm => {
+            let json = get(format!("https://registry.npmjs.org/{}", name)).await?;
+            let version = text(&json["dist-tags"]["latest"]).unwrap_or_default();
+            let repository = text(&json["repository"]["url"]).or_else(|| text(&json["repository"]))
+                .map(|url| url.trim_start_matches("git+").trim_end_matches(".git").to_string());
+            // 다운로드 수는 별도 API. 실패해도 나머지 정보는 보여줌
+            let monthly = get(format!("https://api.npmjs.org/downloads/point/last-month/{}", name)).await.ok()
+                .and_then(|json| json["downloads"].as_u64());
+            PackageInfo {
+                description: text(&json["description"]),
+                license: text(&json["license"]).or_else(|| text(&json["license"]["type"])),
+                repository,
+                homepage: text(&json["homepage"]),
+                downloads: monthly.map(|n| ("최근 30일", n)).into_iter().collect(),
+                last_release: time(&json["time"][&version]),
+                versions: json["versions"].as_object().map(|v| v.len()),
+                version,
+            }
+        }
+        Registry::Pypi => {
+            let json = get(format!("https://pypi.org/pypi/{}/json", name)).await?;
+            let info = &json["info"];
+            let version = text(&info["version"]).unwrap_or_default();
+            let urls = &info["project_urls"];
+            PackageInfo {
+                description: text(&info["summary"]),
+                license: text(&info["license"]).filter(|l| l.len() < 100),
+                repository: ["Source", "Repository", "Source Code", "GitHub"].iter().find_map(|key| text(&urls[*key])),
+                homepage: text(&info["home_page"]).or_else(|| text(&urls["Homepage"])),
+                downloads: Vec::new(),
+                last_release: json["releases"][&version].as_array()
+                    .and_then(|files| files.first())
+                    .and_then(|file| time(&file["upload_time_iso_8601"])),
+                versions: json["releases"].as_object().map(|v| v.len()),
+                version,
+            }
+        }
+    })
+}
+
+fn print_info(registry: Registry, name: &str, info: &PackageInfo) {
+    println!("\n{}", plain(&format!("📦 {} {} ({})", name, info.version, registry.label())).bright_cyan().bold());
+    if let Some(description) = &info.description {
+        println!("  {}", description);
+    }
+    let row = |label: &str, value: String| println!("  {:<10} {}", label.dimmed(), value);
+    for (period, count) in &info.downloads {
+        row(&format!("다운로드({})", period), group_digits(*count));
+    }
+    if let Some(released) = info.last_release {
+        let days = (Utc::now() - released).num_days();
+        row("마지막 릴리스", format!("{} ({}일 전)", released.format("%Y-%m-%d"), days));
+    }
+    if let Some(versions) = info.versions {
+        row("릴리스 수", versions.to_string());
+    }
+    row("라이선스", info.license.clone().unwrap_or_else(|| "알 수 없음".to_string()));
+    if let Some(repository) = &info.repository {
+        row("저장소", repository.clone());
+    }
+    if let Some(homepage) = info.homepage.as_ref().filter(|h| Some(*h) != info.repository.as_ref()) {
+        row("홈페이지", homepage.clone());
+    }
+}
+
+async fn recommend(registry: Registry, name: &str, info: &PackageInfo, config: &Config) -> Result<()> {
+    let mut facts = vec![format!("이름: {}", name), format!("최신 버전: {}", info.version)];
+    facts.extend(info.description.as_ref().map(|d| format!("설명: {}", d)));
+    facts.extend(info.downloads.iter().map(|(period, count)| format!("다운로드({}): {}", period, count)));
+    facts.extend(info.last_release.map(|r| format!("마지막 릴리스: {}", r.format("%Y-%m-%d"))));
+    facts.extend(info.versions.map(|v| format!("릴리스 수: {}", v)));
+    facts.extend(info.license.as_ref().map(|l| format!("라이선스: {}", l)));
+    facts.extend(info.repository.as_ref().map(|r| format!("저장소: {}", r)));
+
+    let prompt = format!(
+        "다음은 {} 패키지의 레지스트리 정보입니다 (오늘: {}). 프로젝트에 의존성으로 추가할지 판단할 수 있도록 \
+        짧게 답해주세요:\n\
+        1. 한 줄 요약\n\
+        2. 추천 여부(추천/주의/비추천)와 이유 (유지보수 활동, 사용량, 라이선스, 알려진 문제)\n\
+        3. 대안 2~3개와 각각의 차이\n\n{}",
+        registry.label(),
+        Utc::now().format("%Y-%m-%d"),
+        fence_file(&format!("{}/{}", registry.label(), name), "", &facts.join("\n"))
+    );
+    println!("\n{}", plain("🤖 AI 추천 생성 중...").yellow());
+    let answer = DevAssistant::new(config.clone())?.query(&prompt).await?;
+    MarkdownRenderer::with_theme(&config.output_preferences.theme).render(&answer)
+}
+
+/// 1234567 → "1,234,567"
+fn group_digits(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::new();
+    for (index, c) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
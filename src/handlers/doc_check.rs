@@ -0,0 +1,79 @@
+use anyhow::Result;
+use colored::*;
+use crate::renderer::plain;
+use std::collections::BTreeMap;
+use std::path::Path;
+use crate::{
+    analyzer::{check_doc_freshness, StaleDoc},
+    assistant::{extract_markdown_block, fence_file, strip_file_blocks, DevAssistant, FileChange, SafeFileModifier},
+    config::Config,
+    handlers::code_assistant::extract_code_block,
+};
+
+/// 문서 주석과 README가 현재 코드와 맞는지 확인합니다.
+/// `fix`면 파일마다 수정안을 받아 diff로 확인한 뒤 적용하고, 아니면 오래된 문서가 있을 때 실패합니다
+pub async fn handle_doc_check(target: &str, fix: bool, config: &Config) -> Result<()> {
+    let root = Path::new(target);
+    println!("{} {}", "문서 최신 상태 확인 중:".cyan(), root.display());
+
+    let stale = check_doc_freshness(root);
+    if stale.is_empty() {
+        println!("{}", plain("✓ 코드와 맞지 않는 문서를 찾지 못했습니다.").green());
+        return Ok(());
+    }
+
+    let mut by_file: BTreeMap<&str, Vec<&StaleDoc>> = BTreeMap::new();
+    for item in &stale {
+        by_file.entry(item.file.as_str()).or_default().push(item);
+    }
+    for (file, items) in &by_file {
+        println!("\n{}", file.bright_blue().bold());
+        for item in items {
+            println!("  {} {}", format!("{}:", item.line).dimmed(), item.message.yellow());
+        }
+    }
+    println!();
+
+    if !fix {
+        anyhow::bail!("코드와 맞지 않는 문서 {}건 (`--fix`로 수정안을 받을 수 있습니다)", stale.len());
+    }
+
+    let assistant = DevAssistant::new(config.clone())?;
+    let mut changes = Vec::new();
+    for (file, items) in &by_file {
+        let path = if root.is_dir() { root.join(file) } else { root.to_path_buf() };
+        let original = std::fs::read_to_string(&path)?;
+        println!("{} {}", "수정안 생성 중:".cyan(), file);
+
+        let problems: Vec<String> = items.iter()
+            .map(|item| format!("- {}번째 줄: {}", item.line, item.message))
+            .collect();
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let prompt = format!(
+            "다음 파일의 문서(문서 주석, docstring, README 설명)가 현재 코드와 맞지 않습니다.\n\n\
+            문제:\n{}\n\n\
+            코드는 바꾸지 말고 문서 부분만 현재 코드에 맞게 고친 파일 전체를 코드 블록 하나로 응답해주세요.\n\n\
+            파일:\n{}",
+            problems.join("\n"), fence_file(file, extension, &original)
+        );
+        let response = strip_file_blocks(&assistant.query(&prompt).await?).into_owned();
+        let updated = if extension.eq_ignore_ascii_case("md") {
+            extract_markdown_block(&response)
+        } else {
+            extract_code_block(&response, extension)
+        };
+
+        let updated = format!("{}\n", updated.trim_end());
+        if updated != original {
+            changes.push(FileChange {
+                path: path.to_string_lossy().to_string(),
+                original_content: original,
+                new_content: updated,
+                description: format!("오래된 문서 {}건 수정", items.len()),
+            });
+        }
+    }
+
+    assistant.propose_changes(&changes);
+    SafeFileModifier::new(config.assume_yes).modify_with_backup(changes).await
+}
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use clap::{Arg, Command, CommandFactory};
+use colored::*;
+use crate::renderer::plain;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::Cli;
+
+#[derive(clap::Subcommand)]
+pub enum DocsAction {
+    /// 모든 서브커맨드의 man 페이지와 마크다운 명령어 레퍼런스 생성
+    GenerateMan {
+        /// 출력 디렉토리 (man/*.1 과 commands.md 생성)
+        #[clap(short, long, default_value = "docs")]
+        out_dir: PathBuf,
+    },
+}
+
+pub fn handle_docs(action: DocsAction) -> Result<()> {
+    match action {
+        DocsAction::GenerateMan { out_dir } => {
+            crate::permissions::ensure_allowed("문서 파일 생성")?;
+            let mut cmd = Cli::command();
+            cmd.build();
+
+            let man_dir = out_dir.join("man");
+            fs::create_dir_all(&man_dir)
+                .with_context(|| format!("디렉토리 생성 실패: {}", man_dir.display()))?;
+
+            let pages = write_man_pages(&cmd, "ricci", &man_dir)?;
+            println!("{}", plain(&format!("{} {}개 → {}", plain("✓ man 페이지").green(), pages, man_dir.display())));
+
+            let reference = out_dir.join("commands.md");
+            fs::write(&reference, markdown_reference(&cmd))
+                .with_context(|| format!("파일 쓰기 실패: {}", reference.display()))?;
+            println!("{} {}", plain("✓ 명령어 레퍼런스").green(), reference.display());
+        }
+    }
+    Ok(())
+}
+
+/// `ricci.1`, `ricci-chat.1`, `ricci-config-set.1`처럼 하위 명령어마다 페이지를 만듭니다
+fn write_man_pages(cmd: &Command, name: &str, dir: &Path) -> Result<usize> {
+    let page = cmd.clone().name(name.to_string());
+    let mut buffer = Vec::new();
+    clap_mangen::Man::new(page).render(&mut buffer)?;
+    fs::write(dir.join(format!("{name}.1")), buffer)?;
+
+    let mut count = 1;
+    for sub in visible_subcommands(cmd) {
+        count += write_man_pages(sub, &format!("{name}-{}", sub.get_name()), dir)?;
+    }
+    Ok(count)
+}
+
+fn markdown_reference(cmd: &Command) -> String {
+    let mut out = String::from("# ricci 명령어 레퍼런스\n\n");
+    out.push_str("> `ricci docs generate-man`으로 생성된 문서입니다. 직접 수정하지 마세요.\n");
+    write_command_markdown(&mut out, cmd, "ricci", 2);
+    out
+}
+
+fn write_command_markdown(out: &mut String, cmd: &Command, path: &str, level: usize) {
+    let _ = writeln!(out, "\n{} `{}`\n", "#".repeat(level), path);
+    if let Some(about) = cmd.get_long_about().or(cmd.get_about()) {
+        let _ = writeln!(out, "{about}\n");
+    }
+
+    let usage = cmd.clone().bin_name(path).render_usage().to_string();
+    let _ = writeln!(out, "```\n{}\n```", usage.trim_start_matches("Usage: "));
+
+    // 전역 옵션은 최상위 명령어에만 표시
+    let args: Vec<&Arg> = cmd.get_arguments()
+        .filter(|a| !a.is_hide_set() && !matches!(a.get_id().as_str(), "help" | "version"))
+        .filter(|a| level == 2 || !a.is_global_set())
+        .collect();
+    if !args.is_empty() {
+        out.push_str("\n| 인자 | 설명 |\n|---|---|\n");
+        for arg in args {
+            let _ = writeln!(out, "| `{}` | {} |", arg_label(arg), arg_help(arg));
+        }
+    }
+
+    for sub in visible_subcommands(cmd) {
+        write_command_markdown(out, sub, &format!("{path} {}", sub.get_name()), (level + 1).min(6));
+    }
+}
+
+fn visible_subcommands(cmd: &Command) -> impl Iterator<Item = &Command> {
+    cmd.get_subcommands().filter(|s| !s.is_hide_set() && s.get_name() != "help")
+}
+
+fn arg_label(arg: &Arg) -> String {
+    match (arg.get_short(), arg.get_long()) {
+        (Some(short), Some(long)) => format!("-{short}, --{long}"),
+        (None, Some(long)) => format!("--{long}"),
+        (Some(short), None) => format!("-{short}"),
+        (None, None) => format!("<{}>", arg.get_id().as_str().to_uppercase()),
+    }
+}
+
+fn arg_help(arg: &Arg) -> String {
+    let mut help = arg.get_help().map(|h| h.to_string()).unwrap_or_default();
+    if !arg.get_action().takes_values() {
+        return help.replace('|', "\\|").replace('\n', " ");
+    }
+    let defaults: Vec<String> = arg.get_default_values().iter()
+        .map(|v| v.to_string_lossy().into_owned())
+        .collect();
+    if !defaults.is_empty() {
+        let _ = write!(help, " (기본값: `{}`)", defaults.join(", "));
+    }
+    help.replace('|', "\\|").replace('\n', " ")
+}
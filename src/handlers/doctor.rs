@@ -0,0 +1,228 @@
+use anyhow::Result;
+use colored::*;
+use crate::renderer::plain;
+use std::process::Command;
+use std::time::Duration;
+use crate::config::{Config, get_config_path, load_config, validate_config};
+use crate::paths::data_dir;
+
+enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+struct CheckResult {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl CheckResult {
+    fn ok(name: &str, detail: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status: CheckStatus::Ok, detail: detail.into(), fix: None }
+    }
+
+    fn warn(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status: CheckStatus::Warn, detail: detail.into(), fix: Some(fix.into()) }
+    }
+
+    fn fail(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status: CheckStatus::Fail, detail: detail.into(), fix: Some(fix.into()) }
+    }
+
+    fn print(&self) {
+        let icon = match self.status {
+            CheckStatus::Ok => plain("✓").green(),
+            CheckStatus::Warn => plain("⚠").yellow(),
+            CheckStatus::Fail => plain("✗").red(),
+        };
+        println!("  {} {}: {}", icon, self.name.bold(), self.detail);
+        if let Some(fix) = &self.fix {
+            println!("      {} {}", plain("→").bright_blue(), fix.dimmed());
+        }
+    }
+}
+
+/// 설정을 로드하지 못하는 상황에서도 실행되어야 하므로 Config를 직접 받지 않습니다
+pub async fn handle_doctor() -> Result<()> {
+    println!("{}", plain("🩺 Ricci 환경 진단").bright_cyan().bold());
+    println!("{}", "=".repeat(50).dimmed());
+
+    let mut results = Vec::new();
+
+    let config = check_config(&mut results);
+    if let Some(config) = &config {
+        check_api_keys(config, &mut results).await;
+    }
+    check_terminal(&mut results);
+    check_git(&mut results);
+    check_ricci_dir(&mut results);
+
+    for result in &results {
+        result.print();
+    }
+
+    let failures = results.iter().filter(|r| matches!(r.status, CheckStatus::Fail)).count();
+    let warnings = results.iter().filter(|r| matches!(r.status, CheckStatus::Warn)).count();
+
+    println!("{}", "=".repeat(50).dimmed());
+    if failures == 0 && warnings == 0 {
+        println!("{}", "모든 항목이 정상입니다.".green().bold());
+    } else {
+        println!("실패 {} | 경고 {}", failures.to_string().red(), warnings.to_string().yellow());
+    }
+
+    Ok(())
+}
+
+fn check_config(results: &mut Vec<CheckResult>) -> Option<Config> {
+    let path = match get_config_path() {
+        Ok(path) => path,
+        Err(e) => {
+            results.push(CheckResult::fail("설정 경로", e.to_string(), "HOME 또는 XDG_CONFIG_HOME 환경 변수를 확인하세요"));
+            return None;
+        }
+    };
+
+    let mut config = match load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            results.push(CheckResult::fail(
+                "설정 파일",
+                format!("{} ({})", e, path.display()),
+                "ricci config reset 후 다시 설정하거나 파일의 TOML 문법을 확인하세요",
+            ));
+            return None;
+        }
+    };
+
+    config.apply_provider_models();
+    match validate_config(&config) {
+        Ok(()) => results.push(CheckResult::ok("설정 검증", "유효함")),
+        Err(e) => results.push(CheckResult::fail(
+            "설정 검증",
+            format!("{:#}", e).lines().next().unwrap_or_default().to_string(),
+            "설정 파일의 제공자/모델 값을 확인하거나, 키가 없으면 ricci config set-key <provider> <key>로 설정하세요",
+        )),
+    }
+
+    Some(config)
+}
+
+async fn check_api_keys(config: &Config, results: &mut Vec<CheckResult>) {
+    let client = match reqwest::Client::builder().timeout(Duration::from_secs(10)).build() {
+        Ok(client) => client,
+        Err(e) => {
+            results.push(CheckResult::fail("HTTP 클라이언트", e.to_string(), "TLS/네트워크 설정을 확인하세요"));
+            return;
+        }
+    };
+
+    for provider in ["openai", "anthropic", "gemini"] {
+        let key = config.api_key(provider);
+        let name = format!("API 키 ({provider})");
+        let Some(key) = key else {
+            if provider == config.model_preferences.default_provider {
+                results.push(CheckResult::fail(&name, "기본 제공자의 키가 없습니다", format!("ricci config set-key {provider} <key>")));
+            }
+            continue;
+        };
+
+        // 토큰을 소모하지 않는 모델 목록 엔드포인트로 확인
+        let request = match provider {
+            "openai" => client.get("https://api.openai.com/v1/models").bearer_auth(key),
+            "anthropic" => client.get("https://api.anthropic.com/v1/models")
+                .header("x-api-key", key)
+                .header("anthropic-version", "2023-06-01"),
+            _ => client.get("https://generativelanguage.googleapis.com/v1beta/models")
+                .query(&[("key", key)]),
+        };
+
+        let result = match request.send().await {
+            Ok(resp) if resp.status().is_success() => CheckResult::ok(&name, "연결 확인됨"),
+            Ok(resp) if resp.status().as_u16() == 401 || resp.status().as_u16() == 403 => CheckResult::fail(
+                &name,
+                format!("인증 실패 (HTTP {})", resp.status().as_u16()),
+                format!("키가 만료되었거나 잘못되었습니다. ricci config set-key {provider} <key>"),
+            ),
+            Ok(resp) => CheckResult::warn(
+                &name,
+                format!("예상치 못한 응답 (HTTP {})", resp.status().as_u16()),
+                "제공자 상태 페이지를 확인하세요",
+            ),
+            Err(e) => CheckResult::fail(&name, format!("연결 실패: {e}"), "네트워크/프록시 설정(HTTPS_PROXY)을 확인하세요"),
+        };
+        results.push(result);
+    }
+}
+
+fn check_terminal(results: &mut Vec<CheckResult>) {
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    if colorterm.contains("truecolor") || colorterm.contains("24bit") {
+        results.push(CheckResult::ok("트루컬러", colorterm));
+    } else {
+        results.push(CheckResult::warn(
+            "트루컬러",
+            "감지되지 않음 (구문 강조 색상이 부정확할 수 있음)",
+            "트루컬러 지원 터미널을 사용하거나 COLORTERM=truecolor 를 설정하세요",
+        ));
+    }
+
+    if cfg!(windows) {
+        results.push(CheckResult::ok("UTF-8", "Windows에서는 실행 시 UTF-8 출력을 사용합니다"));
+        return;
+    }
+
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"].iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.is_empty())
+        .unwrap_or_default();
+    let upper = locale.to_uppercase();
+    if upper.contains("UTF-8") || upper.contains("UTF8") {
+        results.push(CheckResult::ok("UTF-8", locale));
+    } else {
+        results.push(CheckResult::warn(
+            "UTF-8",
+            format!("로케일이 UTF-8이 아닙니다 ({})", if locale.is_empty() { "미설정" } else { &locale }),
+            "export LANG=ko_KR.UTF-8 (또는 en_US.UTF-8)",
+        ));
+    }
+}
+
+fn check_git(results: &mut Vec<CheckResult>) {
+    match Command::new("git").arg("--version").output() {
+        Ok(output) if output.status.success() => {
+            results.push(CheckResult::ok("git", String::from_utf8_lossy(&output.stdout).trim().to_string()));
+        }
+        _ => results.push(CheckResult::warn(
+            "git",
+            "git을 찾을 수 없습니다",
+            "git을 설치하고 PATH에 추가하세요 (sync 등 일부 기능에 필요)",
+        )),
+    }
+}
+
+fn check_ricci_dir(results: &mut Vec<CheckResult>) {
+    let dir = match data_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            results.push(CheckResult::fail("데이터 디렉토리", e.to_string(), "HOME 환경 변수나 설정의 data_dir을 확인하세요"));
+            return;
+        }
+    };
+    let probe = dir.join(".doctor_probe");
+    let writable = std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+
+    match writable {
+        Ok(()) => results.push(CheckResult::ok("데이터 디렉토리", format!("쓰기 가능 ({})", dir.display()))),
+        Err(e) => results.push(CheckResult::fail(
+            "데이터 디렉토리",
+            format!("쓰기 불가: {e}"),
+            format!("권한을 확인하세요: chmod u+rwx {}", dir.display()),
+        )),
+    }
+}
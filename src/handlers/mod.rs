@@ -4,20 +4,60 @@ pub mod analysis;
 pub mod completion;
 pub mod config;
 pub mod code_assistant;
+pub mod compare;
+pub mod session;
+pub mod context;
+pub mod sync;
+pub mod doctor;
+pub mod models;
+pub mod replay;
+pub mod prompt;
+pub mod shell;
+pub mod docs;
+pub mod usage;
+pub mod openapi;
+pub mod doc_check;
+pub mod standup;
+pub mod plan;
+pub mod plan_dashboard;
+pub mod notify;
+pub mod symbol_refactor;
+pub mod rename;
+pub mod upgrade;
+pub mod deps;
 
 // Re-export main handler functions
 pub use chat::handle_chat;
 pub use command::handle_special_command;
-pub use analysis::{handle_analyze, handle_review, handle_doc, handle_plan};
+pub use analysis::{handle_analyze, handle_review, handle_review_history, ReviewOptions, handle_scan, handle_doc, handle_plan};
 pub use completion::install_completions;
 pub use config::handle_config;
 pub use code_assistant::run_code_assistant_interactive;
+pub use symbol_refactor::handle_symbol_refactor;
+pub use rename::handle_rename;
+pub use upgrade::handle_upgrade;
+pub use deps::handle_deps;
+pub use compare::handle_compare;
+pub use session::handle_session;
+pub use context::handle_context;
+pub use sync::handle_sync;
+pub use doctor::handle_doctor;
+pub use models::handle_models;
+pub use replay::handle_replay;
+pub use docs::handle_docs;
+pub use usage::handle_usage;
+pub use openapi::handle_openapi;
+pub use doc_check::handle_doc_check;
+pub use standup::handle_standup;
+pub use plan::handle_plan_action;
 
 
 
 use anyhow::Result;
 use colored::*;
+use crate::renderer::plain;
 use crate::{
+    analyzer::language_registry,
     assistant::DevAssistant,
     config::Config,
 };
@@ -25,9 +65,7 @@ use crate::{
 // 공통 유틸리티 함수들
 pub async fn handle_direct_query(query: &str, config: &Config) -> Result<()> {
     let mut assistant = DevAssistant::new(config.clone())?;
-    assistant.stream_response(query).await?;
-    println!();
-    Ok(())
+    assistant.stream_response(query).await
 }
 
 // Export functions from submodules
@@ -36,20 +74,29 @@ pub async fn handle_code_assist(
     fix: bool, 
     test: bool, 
     docs: bool, 
+    preset: Option<&str>,
+    option_names: &[String],
     config: &Config
 ) -> Result<()> {
+    // 프리셋이나 옵션 목록이 있으면 옵션 선택 메뉴 없이 바로 실행하고, 플래그는 그 위에 켬
+    let selected = match preset {
+        Some(name) => Some(code_assistant::CodeAssistantOptions::from_preset(name, config)?),
+        None if !option_names.is_empty() => Some(code_assistant::CodeAssistantOptions::from_names(option_names)?),
+        None if fix || test || docs => Some(code_assistant::CodeAssistantOptions::default()),
+        None => None,
+    };
+    
     let mut assistant = crate::assistant::DevAssistant::new(config.clone())?;
     
     // 세션 로드
     assistant.load_session().await.ok();
     
-    if fix || test || docs {
+    if let Some(mut options) = selected {
         // 직접 실행 모드
-        println!("{}", "🚀 코드 어시스턴트 직접 모드".bright_cyan().bold());
-        let mut options = code_assistant::CodeAssistantOptions::default();
-        options.fix_all = fix;
-        options.test = test;
-        options.docs = docs;
+        println!("{}", plain("🚀 코드 어시스턴트 직접 모드").bright_cyan().bold());
+        options.fix_all |= fix;
+        options.test |= test;
+        options.docs |= docs;
         
         if path == "." {
             code_assistant::analyze_project_interactive(&mut assistant, &options).await?;
@@ -63,34 +110,12 @@ pub async fn handle_code_assist(
         run_code_assistant_interactive(path, &mut assistant, config).await?;
     }
     
-    // 세션 저장
-    assistant.save_session().await.ok();
+    // 세션 저장 (`auto_save_sessions`가 켜져 있을 때만)
+    assistant.auto_save_session().await.ok();
     
     Ok(())
 }
 
-fn extract_code_block(text: &str, language: &str) -> String {
-    // 코드 블록 추출 (```언어 ... ``` 형식)
-    let patterns = vec![
-        format!("```{}\n", language),
-        "```\n".to_string(),
-        format!("```{}", language),
-        "```".to_string(),
-    ];
-    
-    for pattern in patterns {
-        if let Some(start) = text.find(&pattern) {
-            let code_start = start + pattern.len();
-            if let Some(end) = text[code_start..].find("```") {
-                return text[code_start..code_start + end].trim().to_string();
-            }
-        }
-    }
-    
-    // 코드 블록을 찾지 못하면 전체 텍스트 반환
-    text.trim().to_string()
-} 
-
 pub async fn handle_folder_code_analysis(
     folder_path: &str,
     assistant: &mut DevAssistant,
@@ -112,15 +137,9 @@ pub async fn handle_folder_code_analysis(
         return Ok(());
     }
     
-    println!("{} {}", "📂 폴더 분석 시작:".cyan(), folder_path);
+    println!("{} {}", plain("📂 폴더 분석 시작:").cyan(), folder_path);
     println!("{}", "=".repeat(50).dimmed());
     
-    // 소스 파일 확장자 목록
-    let code_extensions = vec![
-        "rs", "py", "js", "ts", "jsx", "tsx", "java", "cpp", "c", "h", "hpp",
-        "cs", "go", "rb", "php", "swift", "kt", "scala", "r", "dart", "vue"
-    ];
-    
     let mut files_analyzed = 0;
     let mut total_issues = Vec::new();
     
@@ -150,21 +169,21 @@ pub async fn handle_folder_code_analysis(
         // 코드 파일인지 확인
         if let Some(ext) = entry_path.extension() {
             if let Some(ext_str) = ext.to_str() {
-                if code_extensions.contains(&ext_str.to_lowercase().as_str()) {
+                if language_registry().is_source(ext_str) {
                     // 파일 크기 확인 (너무 큰 파일은 건너뛰기)
                     if let Ok(metadata) = entry_path.metadata() {
                         if metadata.len() > 1_000_000 { // 1MB 이상
-                            println!("{} {} (너무 큼)", "⏩ 건너뛰기:".yellow(), path_str);
+                            println!("{} {} (너무 큼)", plain("⏩ 건너뛰기:").yellow(), path_str);
                             continue;
                         }
                     }
                     
-                    println!("\n{} {}", "🔍 분석 중:".blue(), path_str);
+                    println!("\n{} {}", plain("🔍 분석 중:").blue(), path_str);
                     
                     // 파일 읽기
                     if let Ok(content) = std::fs::read_to_string(entry_path) {
                         let lines = content.lines().count();
-                        println!("  • 줄 수: {}", lines);
+                        println!("{}", plain(&format!("  • 줄 수: {}", lines)));
                         
                         // 간단한 코드 품질 체크
                         let mut issues = Vec::new();
@@ -198,13 +217,13 @@ pub async fn handle_folder_code_analysis(
                         }
                         
                         if !issues.is_empty() {
-                            println!("  • 발견된 이슈:");
+                            println!("{}", plain("  • 발견된 이슈:"));
                             for issue in &issues {
                                 println!("    - {}", issue.yellow());
                             }
                             total_issues.push((path_str.to_string(), issues));
                         } else {
-                            println!("  • {}", "이슈 없음 ✓".green());
+                            println!("{}", plain(&format!("  • {}", plain("이슈 없음 ✓").green())));
                         }
                         
                         files_analyzed += 1;
@@ -216,12 +235,12 @@ pub async fn handle_folder_code_analysis(
     
     // 전체 요약
     println!("\n{}", "=".repeat(50).dimmed());
-    println!("{}", "📊 분석 요약".green().bold());
-    println!("  • 분석된 파일 수: {}", files_analyzed);
-    println!("  • 이슈가 있는 파일 수: {}", total_issues.len());
+    println!("{}", plain("📊 분석 요약").green().bold());
+    println!("{}", plain(&format!("  • 분석된 파일 수: {}", files_analyzed)));
+    println!("{}", plain(&format!("  • 이슈가 있는 파일 수: {}", total_issues.len())));
     
     if !total_issues.is_empty() {
-        println!("\n{}", "📋 이슈 요약:".yellow().bold());
+        println!("\n{}", plain("📋 이슈 요약:").yellow().bold());
         for (file, issues) in &total_issues {
             println!("\n  {}:", file.cyan());
             for issue in issues {
@@ -237,7 +256,7 @@ pub async fn handle_folder_code_analysis(
         std::io::stdin().read_line(&mut input)?;
         
         if input.trim().to_lowercase() == "y" {
-            println!("{}", "🤖 AI가 전체 코드베이스를 분석하고 있습니다...".yellow());
+            println!("{}", plain("🤖 AI가 전체 코드베이스를 분석하고 있습니다...").yellow());
             
             let prompt = format!(
                 "다음은 프로젝트의 코드 분석 결과입니다:\n\n\
@@ -255,7 +274,7 @@ pub async fn handle_folder_code_analysis(
             
             let analysis = assistant.query(&prompt).await?;
             
-            println!("\n{}", "📋 AI 분석 결과:".green().bold());
+            println!("\n{}", plain("📋 AI 분석 결과:").green().bold());
             println!("{}", "=".repeat(50).dimmed());
             println!("{}", analysis);
             println!("{}", "=".repeat(50).dimmed());
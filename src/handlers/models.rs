@@ -0,0 +1,81 @@
+use anyhow::Result;
+use colored::*;
+use crate::renderer::plain;
+use dialoguer::Select;
+use std::io::IsTerminal;
+use crate::{
+    api::{list_models, ModelInfo},
+    config::{Config, get_api_key},
+};
+
+const PROVIDERS: &[&str] = &["openai", "anthropic", "gemini"];
+
+pub async fn handle_models(provider: Option<&str>, config: &Config) -> Result<()> {
+    let providers: Vec<&str> = match provider {
+        Some(p) if PROVIDERS.contains(&p) => vec![p],
+        Some(p) => anyhow::bail!("알 수 없는 제공자: {}", p),
+        None => PROVIDERS.to_vec(),
+    };
+
+    let mut all_models: Vec<ModelInfo> = Vec::new();
+
+    let explicit = provider.is_some();
+    for provider in providers {
+        let Some(key) = get_api_key(config, provider) else {
+            if explicit || provider == config.model_preferences.default_provider {
+                println!("{} {} API 키가 설정되지 않았습니다.", plain("⏩").yellow(), provider);
+            }
+            continue;
+        };
+
+        println!("\n{}", format!("{provider} 모델").bright_cyan().bold());
+        println!("{}", "=".repeat(50).dimmed());
+
+        match list_models(provider, &key).await {
+            Ok(models) => {
+                for model in &models {
+                    let window = model.context_window
+                        .map(format_tokens)
+                        .unwrap_or_else(|| "-".to_string());
+                    let marker = if model.id == config.model_preferences.default_model { plain("★") } else { " ".normal() };
+                    println!("  {} {:<45} {}", marker.yellow(), model.id, window.dimmed());
+                }
+                all_models.extend(models);
+            }
+            Err(e) => println!("  {} {}", "오류:".red(), e),
+        }
+    }
+
+    if all_models.is_empty() || !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec!["변경하지 않음".to_string()];
+    items.extend(all_models.iter().map(|m| format!("{} ({})", m.id, m.provider)));
+
+    let selection = Select::new()
+        .with_prompt("기본 모델로 설정할 모델을 선택하세요")
+        .items(&items)
+        .default(0)
+        .max_length(15)
+        .interact()?;
+
+    if selection > 0 {
+        let chosen = &all_models[selection - 1];
+        let mut config = config.clone();
+        config.model_preferences.default_provider = chosen.provider.clone();
+        config.set_provider_model(&chosen.provider, &chosen.id);
+        config.save()?;
+        println!("{} 기본 모델: {} ({})", plain("✓").green(), chosen.id.cyan(), chosen.provider);
+    }
+
+    Ok(())
+}
+
+fn format_tokens(tokens: u32) -> String {
+    if tokens >= 1_000_000 {
+        format!("{:.1}M 토큰", tokens as f64 / 1_000_000.0)
+    } else {
+        format!("{}K 토큰", tokens / 1000)
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+use colored::*;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use crate::config::NotificationConfig;
+
+/// 명령어가 기준 시간보다 오래 걸렸고 터미널 창이 포커스를 잃은 상태면 완료 알림을 보냅니다.
+/// 포커스를 알 수 없는 환경(tmux, SSH, Wayland 등)에서는 항상 알립니다
+pub fn notify_if_slow(command: &str, elapsed: Duration, succeeded: bool, config: &NotificationConfig) {
+    if !config.enabled {
+        return;
+    }
+    let threshold = config.commands.get(command).copied().unwrap_or(config.after_seconds);
+    if threshold == 0 || elapsed.as_secs() < threshold || terminal_focused() == Some(true) {
+        return;
+    }
+
+    let title = if succeeded {
+        format!("ricci {} 완료", command)
+    } else {
+        format!("ricci {} 실패", command)
+    };
+    let directory = std::env::current_dir().ok()
+        .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default();
+    let body = format!("{} 걸렸습니다 ({})", format_elapsed(elapsed), directory);
+    if let Err(e) = send_notification(&title, &body) {
+        eprintln!("{} {}", "데스크톱 알림을 보내지 못했습니다:".yellow(), e);
+    }
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{}초", secs)
+    } else {
+        format!("{}분 {}초", secs / 60, secs % 60)
+    }
+}
+
+/// 운영체제 알림 도구로 알림을 띄웁니다 (macOS osascript, Windows PowerShell, 그 외 notify-send).
+/// notify-rust 같은 알림 crate는 오프라인 빌드에서 받을 수 없어 의존성을 늘리지 않고 도구를 직접 실행합니다.
+/// 알림이 떠 있는 동안 명령어 종료가 늦어지지 않도록 기다리지 않고 띄워만 둡니다
+fn send_notification(title: &str, body: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!("display notification {} with title {}", apple_quote(body), apple_quote(title));
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    } else if cfg!(windows) {
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             $n = New-Object System.Windows.Forms.NotifyIcon; \
+             $n.Icon = [System.Drawing.SystemIcons]::Information; $n.Visible = $true; \
+             $n.ShowBalloonTip(5000, '{}', '{}', 'Info'); Start-Sleep -Seconds 5; $n.Dispose()",
+            title.replace('\'', "''"),
+            body.replace('\'', "''"),
+        );
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command", &script]);
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args(["--app-name=ricci", title, body]);
+        command
+    };
+    command.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("알림 명령어를 실행할 수 없습니다: {}", e))?;
+    Ok(())
+}
+
+fn apple_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// 터미널 창이 지금 포커스를 갖고 있는지 (확인할 수 없으면 None)
+fn terminal_focused() -> Option<bool> {
+    if cfg!(target_os = "macos") {
+        let front = command_output("osascript", &[
+            "-e",
+            "tell application \"System Events\" to get name of first application process whose frontmost is true",
+        ])?;
+        let app = match std::env::var("TERM_PROGRAM").ok()?.as_str() {
+            "Apple_Terminal" => "Terminal".to_string(),
+            "iTerm.app" => "iTerm2".to_string(),
+            "vscode" => "Code".to_string(),
+            other => other.to_string(),
+        };
+        Some(front.eq_ignore_ascii_case(&app))
+    } else if cfg!(target_os = "linux") {
+        // X11 터미널은 자기 창 id를 WINDOWID로 알려줌
+        let window = std::env::var("WINDOWID").ok()?;
+        let active = command_output("xdotool", &["getactivewindow"])?;
+        Some(active == window)
+    } else {
+        None
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
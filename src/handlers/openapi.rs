@@ -0,0 +1,134 @@
+use anyhow::{Context, Result};
+use colored::*;
+use crate::renderer::plain;
+use serde_yaml::Value;
+use std::path::Path;
+use crate::{
+    analyzer::{detect_routes, RouteFile},
+    assistant::{fence_file, DevAssistant},
+    config::Config,
+    handlers::code_assistant::extract_code_block,
+};
+
+/// 프롬프트에 넣을 라우트 파일 내용의 최대 길이 (문자 수)
+const MAX_SOURCE_CHARS: usize = 60_000;
+
+/// 백엔드 코드의 라우트를 찾아 OpenAPI 3 YAML 명세를 생성합니다.
+/// 모델이 만든 YAML이 올바르지 않으면 오류를 알려 한 번 다시 요청하고, 그래도 실패하면 저장하지 않습니다
+pub async fn handle_openapi(dir: &str, output: Option<&Path>, config: &Config) -> Result<()> {
+    crate::permissions::ensure_allowed("명세 저장")?;
+    let root = Path::new(dir);
+    let files = detect_routes(root);
+    if files.is_empty() {
+        anyhow::bail!("{}에서 라우트를 찾지 못했습니다 (지원: axum, actix-web, express, FastAPI)", dir);
+    }
+
+    let route_count: usize = files.iter().map(|f| f.routes.len()).sum();
+    println!("{} {}개 라우트 발견", plain("✓").green(), route_count);
+    for route in files.iter().flat_map(|f| &f.routes) {
+        println!("  {:7} {}  {}",
+            route.method.cyan(),
+            route.path,
+            format!("{}:{} ({})", route.file, route.line, route.framework).dimmed());
+    }
+
+    let assistant = DevAssistant::new(config.clone())?;
+    let prompt = build_openapi_prompt(root, &files);
+    println!("\n{}", "OpenAPI 명세 생성 중...".cyan());
+    let mut yaml = extract_code_block(&assistant.query(&prompt).await?, "yaml");
+
+    if let Err(e) = validate_openapi(&yaml) {
+        println!("{} {} — 다시 요청합니다", "생성된 명세가 올바르지 않습니다:".yellow(), e);
+        let retry = format!(
+            "{}\n\n이전에 생성한 명세는 다음 오류가 있었습니다: {}\n\n이전 명세:\n```yaml\n{}\n```\n\n\
+            오류를 고친 전체 명세를 다시 작성해주세요.",
+            prompt, e, yaml
+        );
+        yaml = extract_code_block(&assistant.query(&retry).await?, "yaml");
+        validate_openapi(&yaml).context("생성된 OpenAPI 명세가 올바르지 않아 저장하지 않았습니다")?;
+    }
+
+    let spec: Value = serde_yaml::from_str(&yaml)?;
+    let missing = missing_routes(&spec, &files);
+    if !missing.is_empty() {
+        println!("{} {}", "명세에 빠진 라우트:".yellow(), missing.join(", "));
+    }
+
+    let output = output.map(Path::to_path_buf).unwrap_or_else(|| root.join("openapi.yaml"));
+    std::fs::write(&output, format!("{}\n", yaml.trim_end()))
+        .with_context(|| format!("명세 저장 실패: {}", output.display()))?;
+    println!("{} {}", "OpenAPI 명세 저장됨:".green(), output.display());
+    Ok(())
+}
+
+fn build_openapi_prompt(root: &Path, files: &[RouteFile]) -> String {
+    let routes: Vec<String> = files.iter()
+        .flat_map(|f| &f.routes)
+        .map(|r| format!("- {} {} ({}:{}, {})", r.method, r.path, r.file, r.line, r.framework))
+        .collect();
+
+    let mut sources = String::new();
+    for file in files {
+        if sources.len() + file.content.len() > MAX_SOURCE_CHARS {
+            sources.push_str(&format!("\n(이하 {} 등 생략)\n", file.path));
+            break;
+        }
+        sources.push_str(&format!("\n{}\n", fence_file(&file.path, "", &file.content)));
+    }
+
+    let project = root.canonicalize().ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "API".to_string());
+
+    format!(
+        "다음 백엔드 코드의 HTTP API에 대한 OpenAPI 3.0 명세를 YAML로 작성해주세요.\n\n\
+        프로젝트: {}\n\n발견된 라우트:\n{}\n\n라우트 정의 코드:\n{}\n\n\
+        요구사항:\n\
+        1. `openapi: 3.0.3`, `info`(title, version), `paths`를 포함\n\
+        2. 위의 모든 라우트를 포함하고 경로 매개변수는 `{{id}}` 형식으로 표기\n\
+        3. 핸들러 코드에서 요청 본문, 쿼리/경로 매개변수, 응답 형식을 추론해 `components.schemas`로 정리\n\
+        4. 코드에서 알 수 없는 내용은 지어내지 말고 description에 추정임을 표시\n\n\
+        설명 없이 ```yaml 코드 블록 하나로만 응답해주세요.",
+        project, routes.join("\n"), sources
+    )
+}
+
+/// YAML 문법과 OpenAPI 3 필수 항목을 확인합니다
+fn validate_openapi(yaml: &str) -> Result<()> {
+    let spec: Value = serde_yaml::from_str(yaml).context("YAML 파싱 실패")?;
+    let version = spec.get("openapi").and_then(Value::as_str)
+        .context("`openapi` 버전 항목이 없습니다")?;
+    if !version.starts_with("3.") {
+        anyhow::bail!("OpenAPI 3 명세가 아닙니다 (openapi: {})", version);
+    }
+    let info = spec.get("info").context("`info` 항목이 없습니다")?;
+    if info.get("title").is_none() || info.get("version").is_none() {
+        anyhow::bail!("`info`에 title과 version이 필요합니다");
+    }
+    let paths = spec.get("paths").and_then(Value::as_mapping)
+        .context("`paths` 항목이 없거나 올바르지 않습니다")?;
+    if let Some(invalid) = paths.keys().filter_map(Value::as_str).find(|p| !p.starts_with('/')) {
+        anyhow::bail!("경로는 /로 시작해야 합니다: {}", invalid);
+    }
+    Ok(())
+}
+
+/// 코드에서 찾았지만 명세의 `paths`에 없는 라우트
+fn missing_routes(spec: &Value, files: &[RouteFile]) -> Vec<String> {
+    let Some(paths) = spec.get("paths").and_then(Value::as_mapping) else {
+        return Vec::new();
+    };
+    let mut missing: Vec<String> = files.iter()
+        .flat_map(|f| &f.routes)
+        .filter(|route| {
+            let path = route.openapi_path();
+            let Some(item) = paths.get(path.as_str()) else {
+                return true;
+            };
+            route.method != "ANY" && item.get(route.method.to_lowercase().as_str()).is_none()
+        })
+        .map(|route| format!("{} {}", route.method, route.path))
+        .collect();
+    missing.dedup();
+    missing
+}
@@ -0,0 +1,370 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::*;
+use crate::renderer::plain;
+use std::path::{Path, PathBuf};
+use crate::{
+    assistant::{language_instruction, DevAssistant},
+    config::Config,
+    handlers::plan_dashboard::run_dashboard,
+    integrations::{check_share_target, share_plan, SHARE_TARGETS},
+    permissions::ensure_allowed,
+    planner::{
+        import_plan, list_plans, load_plan, save_plan, Calibration, ProjectPlan, Risk, RiskLevel, RiskStatus,
+    },
+};
+
+/// 보정 계수를 믿을 만하다고 볼 최소 완료 작업 수
+const MIN_CALIBRATION_SAMPLES: usize = 5;
+
+#[derive(clap::Subcommand)]
+pub enum PlanAction {
+    /// 저장된 계획 목록과 진행 상황
+    List,
+    /// 저장된 계획을 원하는 형식으로 출력
+    Show {
+        /// 계획 id (앞부분만 써도 됨)
+        plan: String,
+        /// 출력 형식 (markdown, json, yaml, table, mermaid, csv)
+        #[clap(short, long, default_value = "markdown")]
+        format: String,
+        /// 출력을 파일로 저장
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+        /// 계획을 팀 채널, Confluence 또는 Notion(작업 데이터베이스)으로 공유
+        #[clap(long, value_name = "TARGET", value_parser = clap::builder::PossibleValuesParser::new(SHARE_TARGETS))]
+        share: Option<String>,
+    },
+    /// 저장된 계획의 진행률, 다가오는 마일스톤, 지연된 작업을 보는 대화형 대시보드
+    Dashboard,
+    /// 손으로 고친 YAML(또는 JSON) 계획을 검증해 가져오기 (같은 id가 있으면 갱신)
+    Import {
+        /// 계획 파일 (`ricci plan -f yaml` 출력 형식)
+        file: PathBuf,
+    },
+    /// 작업을 완료로 표시하고 실제 소요 시간 기록
+    Done {
+        /// 계획 id (앞부분만 써도 됨)
+        plan: String,
+        /// 작업 id(예: 2.1) 또는 작업 이름 일부
+        task: String,
+        /// 실제 소요 시간 (보정 계산에 사용)
+        #[clap(long)]
+        hours: Option<f32>,
+    },
+    /// 완료한 작업의 추정치와 실제 시간을 비교해 이후 추정에 쓸 보정 계수 계산
+    Calibrate {
+        /// 저장된 보정 계수 삭제
+        #[clap(long)]
+        reset: bool,
+    },
+    /// 저장된 모든 계획의 위험 요소 대장
+    #[clap(args_conflicts_with_subcommands = true)]
+    Risks {
+        #[clap(subcommand)]
+        action: Option<RiskAction>,
+        /// 출력 형식 (table, markdown, json)
+        #[clap(short, long, default_value = "table")]
+        format: String,
+        /// 완화됨/발생함으로 처리한 위험도 표시
+        #[clap(long)]
+        all: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+pub enum RiskAction {
+    /// 위험을 완화됨으로 표시
+    Mitigated {
+        /// 계획 id (앞부분만 써도 됨)
+        plan: String,
+        /// 위험 번호 (`ricci plan risks`의 # 열)
+        risk: usize,
+    },
+    /// 위험이 실제로 발생했다고 표시
+    Occurred {
+        plan: String,
+        risk: usize,
+    },
+    /// 위험을 다시 열림으로 표시
+    Reopen {
+        plan: String,
+        risk: usize,
+    },
+    /// 확률이 높은 열린 위험의 대응 방안을 모델로 다시 작성
+    Mitigate {
+        /// 이 계획의 위험만 처리
+        #[clap(long)]
+        plan: Option<String>,
+    },
+}
+
+pub async fn handle_plan_action(action: PlanAction, config: &Config) -> Result<()> {
+    match action {
+        PlanAction::List => list(),
+        PlanAction::Dashboard => run_dashboard(),
+        PlanAction::Show { plan, format, output, share } => {
+            let plan = load_plan(&plan)?;
+            if let Some(target) = &share {
+                check_share_target(target, config)?;
+            }
+            emit_plan(&plan.render(&format)?, &format, output.as_deref())?;
+            match share {
+                Some(target) => share_plan(&target, &plan, config).await,
+                None => Ok(()),
+            }
+        }
+        PlanAction::Import { file } => {
+            let (plan, updated) = import_plan(&file)?;
+            let verb = if updated { "갱신했습니다" } else { "새 계획으로 저장했습니다" };
+            println!("{}", plain(&format!("{} {} → {} ({})", plain("✓").green(), file.display(), plan.id.cyan(), verb)));
+            println!("  단계 {}개 · 작업 {}개 · 총 예상 {}",
+                plan.phases.len(), plan.tasks().count(), plan.total_duration);
+            Ok(())
+        }
+        PlanAction::Done { plan, task, hours } => mark_done(&plan, &task, hours),
+        PlanAction::Calibrate { reset } => {
+            if reset {
+                Calibration::reset()?;
+                println!("{} 보정 계수를 삭제했습니다. 이후 추정치는 모델 값을 그대로 사용합니다.", plain("✓").green());
+                Ok(())
+            } else {
+                calibrate()
+            }
+        }
+        PlanAction::Risks { action: None, format, all } => list_risks(&format, all),
+        PlanAction::Risks { action: Some(action), .. } => match action {
+            RiskAction::Mitigated { plan, risk } => set_risk_status(&plan, risk, RiskStatus::Mitigated),
+            RiskAction::Occurred { plan, risk } => set_risk_status(&plan, risk, RiskStatus::Occurred),
+            RiskAction::Reopen { plan, risk } => set_risk_status(&plan, risk, RiskStatus::Open),
+            RiskAction::Mitigate { plan } => regenerate_mitigations(plan.as_deref(), config).await,
+        },
+    }
+}
+
+/// 출력한 계획을 파일에 쓰거나 화면에 표시합니다
+pub(crate) fn emit_plan(rendered: &str, format: &str, output: Option<&Path>) -> Result<()> {
+    match output {
+        Some(path) => {
+            ensure_allowed("파일 저장")?;
+            std::fs::write(path, rendered).with_context(|| format!("계획 저장 실패: {}", path.display()))?;
+            println!("{} {} 형식으로 저장했습니다: {}", plain("✓").green(), format, path.display());
+        }
+        None if format == "markdown" || format == "table" => println!("\n{}", rendered),
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+fn list() -> Result<()> {
+    let plans = list_plans()?;
+    if plans.is_empty() {
+        println!("저장된 계획이 없습니다. `ricci plan \"설명\"`으로 만들면 자동으로 저장됩니다.");
+        return Ok(());
+    }
+    for plan in &plans {
+        let total = plan.tasks().count();
+        let done = plan.tasks().filter(|t| t.completed_at.is_some()).count();
+        println!("{}  {}  {}  작업 {}/{} 완료",
+            plan.id.cyan(),
+            plan.created_at.format("%Y-%m-%d"),
+            plan.title.bold(),
+            done, total);
+    }
+    Ok(())
+}
+
+fn mark_done(plan_id: &str, task_query: &str, hours: Option<f32>) -> Result<()> {
+    if hours.is_some_and(|h| h <= 0.0) {
+        anyhow::bail!("--hours는 0보다 커야 합니다");
+    }
+    let mut plan = load_plan(plan_id)?;
+    let task = plan.find_task_mut(task_query)?;
+    task.completed_at = Some(Utc::now());
+    task.actual_hours = hours.or(task.actual_hours);
+    let (id, name, estimate, actual) = (task.id.clone(), task.name.clone(), task.duration.clone(), task.actual_hours);
+    save_plan(&mut plan)?;
+
+    println!("{} [{}] {} 완료", plain("✓").green(), id, name);
+    match actual {
+        Some(actual) if !estimate.is_zero() => {
+            println!("  추정 {} · 실제 {:.1}시간", estimate, actual);
+        }
+        Some(actual) => println!("  실제 {:.1}시간 (추정치 없음, 보정에는 쓰이지 않음)", actual),
+        None => println!("  {}", "--hours를 지정하지 않아 보정 계산에서 제외됩니다.".dimmed()),
+    }
+    Ok(())
+}
+
+fn calibrate() -> Result<()> {
+    let plans = list_plans()?;
+    let mut estimated_total = 0.0f32;
+    let mut actual_total = 0.0f32;
+    let mut samples = 0usize;
+    let mut within_range = 0usize;
+
+    println!("{}", "추정 대비 실제 시간".bright_cyan().bold());
+    println!("{}", "=".repeat(50).dimmed());
+    for plan in &plans {
+        let completed: Vec<_> = plan.tasks()
+            .filter_map(|task| {
+                let actual = task.actual_hours?;
+                let estimate = plan.raw_estimate(task);
+                (!estimate.is_zero()).then_some((estimate, actual))
+            })
+            .collect();
+        if completed.is_empty() {
+            continue;
+        }
+
+        let estimated: f32 = completed.iter().map(|(e, _)| e.likely_hours).sum();
+        let actual: f32 = completed.iter().map(|(_, a)| a).sum();
+        within_range += completed.iter()
+            .filter(|(e, a)| (e.min_hours..=e.max_hours).contains(a))
+            .count();
+        println!("{}  {}  작업 {}개 · 추정 {:.1}시간 · 실제 {:.1}시간 ({:.2}배)",
+            plan.id.cyan(), plan.title, completed.len(), estimated, actual, actual / estimated);
+
+        estimated_total += estimated;
+        actual_total += actual;
+        samples += completed.len();
+    }
+
+    if samples == 0 {
+        anyhow::bail!("실제 시간이 기록된 완료 작업이 없습니다 (`ricci plan done <계획> <작업> --hours N`)");
+    }
+
+    let factor = actual_total / estimated_total;
+    println!("{}", "=".repeat(50).dimmed());
+    println!("완료 작업 {}개 · 추정 범위 안에 끝난 작업 {}%",
+        samples, within_range * 100 / samples);
+    println!("{} {:.2} (실제 {:.1}시간 / 추정 {:.1}시간)",
+        "보정 계수:".bold(), factor, actual_total, estimated_total);
+    if samples < MIN_CALIBRATION_SAMPLES {
+        println!("{}", format!("완료 작업이 {}개 미만이라 보정 계수가 불안정할 수 있습니다.", MIN_CALIBRATION_SAMPLES).yellow());
+    }
+
+    Calibration { factor, samples, updated_at: Utc::now() }.save()?;
+    println!("{} 이후 `ricci plan`의 예상 시간에 {:.2}배를 적용합니다.", plain("✓").green(), factor);
+    Ok(())
+}
+
+/// 계획별 위험 (번호는 계획 안에서 1부터)
+fn risk_register(plans: &[ProjectPlan], all: bool) -> Vec<(&ProjectPlan, usize, &Risk)> {
+    plans.iter()
+        .flat_map(|plan| plan.risks.iter().enumerate().map(move |(i, risk)| (plan, i + 1, risk)))
+        .filter(|(_, _, risk)| all || risk.status == RiskStatus::Open)
+        .collect()
+}
+
+fn list_risks(format: &str, all: bool) -> Result<()> {
+    let plans = list_plans()?;
+    let register = risk_register(&plans, all);
+
+    match format {
+        "table" => {
+            if register.is_empty() {
+                println!("{}", if all { "저장된 계획에 위험 요소가 없습니다." } else { "열린 위험이 없습니다." });
+                return Ok(());
+            }
+            let mut table = prettytable::Table::new();
+            table.add_row(prettytable::row!["계획", "#", "상태", "확률", "영향", "위험", "대응"]);
+            for (plan, number, risk) in &register {
+                table.add_row(prettytable::row![
+                    plan.id, number, risk.status.label(), risk.probability.label(), risk.impact.label(),
+                    risk.description, risk.mitigation
+                ]);
+            }
+            table.printstd();
+        }
+        "markdown" => {
+            println!("| 계획 | # | 상태 | 확률 | 영향 | 위험 | 대응 |");
+            println!("|---|---|---|---|---|---|---|");
+            for (plan, number, risk) in &register {
+                let cell = |text: &str| text.replace('|', "\\|").replace('\n', "<br>");
+                println!("| {} | {} | {} | {} | {} | {} | {} |",
+                    plan.id, number, risk.status.label(), risk.probability.label(), risk.impact.label(),
+                    cell(&risk.description), cell(&risk.mitigation));
+            }
+        }
+        "json" => {
+            let entries: Vec<serde_json::Value> = register.iter()
+                .map(|(plan, number, risk)| serde_json::json!({
+                    "plan": plan.id,
+                    "plan_title": plan.title,
+                    "number": number,
+                    "risk": risk,
+                }))
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        _ => anyhow::bail!("지원하지 않는 형식: {} (table, markdown, json)", format),
+    }
+    Ok(())
+}
+
+fn set_risk_status(plan_id: &str, number: usize, status: RiskStatus) -> Result<()> {
+    let mut plan = load_plan(plan_id)?;
+    let count = plan.risks.len();
+    let risk = number.checked_sub(1)
+        .and_then(|i| plan.risks.get_mut(i))
+        .ok_or_else(|| anyhow::anyhow!("계획 {}에는 위험이 {}개 있습니다: {}", plan.id, count, number))?;
+    risk.status = status;
+    let description = risk.description.clone();
+    save_plan(&mut plan)?;
+    println!("{}", plain(&format!("{} [{} #{}] {} → {}", plain("✓").green(), plan.id, number, description, status)));
+    Ok(())
+}
+
+/// 확률이 높은 열린 위험마다 계획 맥락을 주고 대응 방안을 다시 받아 저장합니다
+async fn regenerate_mitigations(plan_filter: Option<&str>, config: &Config) -> Result<()> {
+    let mut plans = match plan_filter {
+        Some(id) => vec![load_plan(id)?],
+        None => list_plans()?,
+    };
+    let targets: usize = plans.iter()
+        .flat_map(|p| &p.risks)
+        .filter(|r| needs_mitigation(r))
+        .count();
+    if targets == 0 {
+        println!("확률이 높은 열린 위험이 없습니다.");
+        return Ok(());
+    }
+
+    let assistant = DevAssistant::new(config.clone())?;
+    println!("{} 위험 {}개의 대응 방안 작성 중...", plain("⟳").cyan(), targets);
+    for plan in &mut plans {
+        let mut changed = false;
+        let context = format!("계획: {}\n{}", plan.title, plan.description);
+        let others: Vec<String> = plan.risks.iter()
+            .filter(|r| r.status == RiskStatus::Occurred)
+            .map(|r| format!("- {}", r.description))
+            .collect();
+        for (i, risk) in plan.risks.iter_mut().enumerate() {
+            if !needs_mitigation(risk) {
+                continue;
+            }
+            let prompt = format!(
+                "다음 프로젝트 계획의 위험 요소에 대한 대응 방안을 다시 작성해주세요.\n\n\
+                {}\n\n위험: {}\n확률/영향: {}/{}\n현재 대응 방안: {}\n{}\n\
+                예방 조치, 조기 경보 신호, 발생 시 대응을 구체적인 행동으로 3~5줄 이내로 쓰고 다른 설명은 하지 마세요. {}",
+                context, risk.description, risk.probability.label(), risk.impact.label(),
+                if risk.mitigation.is_empty() { "없음" } else { &risk.mitigation },
+                if others.is_empty() { String::new() } else { format!("이미 발생한 위험:\n{}\n", others.join("\n")) },
+                language_instruction(&config.output_preferences.language)
+            );
+            risk.mitigation = assistant.query(&prompt).await?.trim().to_string();
+            changed = true;
+            println!("\n{} {}", format!("[{} #{}]", plan.id, i + 1).cyan(), risk.description.bold());
+            println!("{}", risk.mitigation);
+        }
+        if changed {
+            save_plan(plan)?;
+        }
+    }
+    Ok(())
+}
+
+fn needs_mitigation(risk: &Risk) -> bool {
+    risk.status == RiskStatus::Open && matches!(risk.probability, RiskLevel::High)
+}
@@ -0,0 +1,226 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use colored::*;
+use crate::renderer::plain;
+use std::io::{IsTerminal, Write};
+use termimad::crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use crate::planner::{list_plans, save_plan, ProjectPlan, Schedule};
+
+const BAR_WIDTH: usize = 20;
+/// 예정 종료일 계산에 쓰는 하루 작업 시간
+const HOURS_PER_DAY: f32 = 8.0;
+const MAX_LISTED: usize = 5;
+
+/// 대시보드가 떠 있는 동안 원시 모드와 대체 화면을 유지하고, 끝나면(오류 포함) 터미널을 되돌립니다
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(std::io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(std::io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+struct Dashboard {
+    plans: Vec<ProjectPlan>,
+    plan_index: usize,
+    task_index: usize,
+    message: String,
+}
+
+/// 저장된 계획의 단계별 진행률, 다가오는 마일스톤, 지연된 작업을 보여주고 작업 완료를 표시합니다.
+/// 화면은 ratatui 없이 termimad에 포함된 crossterm으로 직접 그립니다 (오프라인 빌드에서 ratatui를 받을 수 없음)
+pub fn run_dashboard() -> Result<()> {
+    let plans = list_plans()?;
+    if plans.is_empty() {
+        println!("저장된 계획이 없습니다. `ricci plan \"설명\"`으로 만들면 자동으로 저장됩니다.");
+        return Ok(());
+    }
+    if !std::io::stdout().is_terminal() {
+        anyhow::bail!("대시보드는 터미널에서만 실행할 수 있습니다 (`ricci plan list`를 사용하세요)");
+    }
+
+    let mut dashboard = Dashboard {
+        plan_index: plans.len() - 1,
+        plans,
+        task_index: 0,
+        message: String::new(),
+    };
+    let _guard = TerminalGuard::enter()?;
+    loop {
+        dashboard.draw()?;
+        let Event::Key(KeyEvent { code, modifiers }) = event::read()? else {
+            continue;
+        };
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Up | KeyCode::Char('k') => dashboard.move_task(-1),
+            KeyCode::Down | KeyCode::Char('j') => dashboard.move_task(1),
+            KeyCode::Left | KeyCode::Char('h') => dashboard.move_plan(-1),
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => dashboard.move_plan(1),
+            KeyCode::Char(' ') | KeyCode::Enter => dashboard.toggle_done()?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+impl Dashboard {
+    fn plan(&self) -> &ProjectPlan {
+        &self.plans[self.plan_index]
+    }
+
+    fn move_plan(&mut self, delta: isize) {
+        let count = self.plans.len() as isize;
+        self.plan_index = (self.plan_index as isize + delta).rem_euclid(count) as usize;
+        self.task_index = 0;
+        self.message.clear();
+    }
+
+    fn move_task(&mut self, delta: isize) {
+        let count = self.plan().tasks().count();
+        if count > 0 {
+            self.task_index = (self.task_index as isize + delta).clamp(0, count as isize - 1) as usize;
+        }
+    }
+
+    /// 선택한 작업의 완료 여부를 바꾸고 바로 저장합니다
+    fn toggle_done(&mut self) -> Result<()> {
+        let plan = &mut self.plans[self.plan_index];
+        let Some(task) = plan.phases.iter_mut().flat_map(|p| p.tasks.iter_mut()).nth(self.task_index) else {
+            return Ok(());
+        };
+        task.completed_at = match task.completed_at {
+            Some(_) => None,
+            None => Some(Utc::now()),
+        };
+        self.message = match task.completed_at {
+            Some(_) => format!("{} 완료 (실제 시간은 `ricci plan done {} {} --hours N`으로 기록)", task.id, plan.id, task.id),
+            None => format!("{} 완료 취소", task.id),
+        };
+        save_plan(plan)?;
+        Ok(())
+    }
+
+    fn draw(&self) -> Result<()> {
+        let (cols, rows) = terminal::size()?;
+        let (cols, rows) = (cols as usize, rows as usize);
+        let plan = self.plan();
+        let schedule = plan.schedule().ok();
+        let mut lines: Vec<String> = Vec::new();
+
+        lines.push(format!("{}  {}",
+            "ricci 계획 대시보드".bright_cyan().bold(),
+            plain("←/→ 계획  ↑/↓ 작업  Space 완료 표시  q 종료").dimmed()));
+        let total = plan.tasks().count();
+        let done = plan.tasks().filter(|t| t.completed_at.is_some()).count();
+        lines.push(format!("계획 {}/{}: {} ({})  {} {}/{}",
+            self.plan_index + 1, self.plans.len(), plan.title.bold(), plan.id.dimmed(),
+            progress_bar(done, total), done, total));
+        lines.push(String::new());
+
+        lines.push("단계별 진행".yellow().bold().to_string());
+        for phase in &plan.phases {
+            let phase_done = phase.tasks.iter().filter(|t| t.completed_at.is_some()).count();
+            lines.push(format!("  {} {}  {} {}/{}",
+                phase.id, fit(&phase.name, 24), progress_bar(phase_done, phase.tasks.len()), phase_done, phase.tasks.len()));
+        }
+        lines.push(String::new());
+
+        let now = Utc::now();
+        let mut milestones: Vec<_> = plan.milestones.iter()
+            .filter_map(|m| m.date.filter(|d| *d >= now).map(|d| (d, m)))
+            .collect();
+        milestones.sort_by_key(|(date, _)| *date);
+        lines.push("다가오는 마일스톤".yellow().bold().to_string());
+        if milestones.is_empty() {
+            lines.push("  (날짜가 정해진 마일스톤 없음)".dimmed().to_string());
+        }
+        for (date, milestone) in milestones.iter().take(MAX_LISTED) {
+            lines.push(format!("  {}  {} ({}일 남음)", date.format("%Y-%m-%d"), milestone.name, (*date - now).num_days()));
+        }
+        lines.push(String::new());
+
+        let overdue: Vec<_> = plan.tasks()
+            .filter(|t| t.completed_at.is_none())
+            .filter_map(|t| planned_end(plan, schedule.as_ref(), &t.id).filter(|end| *end < now).map(|end| (t, end)))
+            .collect();
+        lines.push("지연된 작업".yellow().bold().to_string());
+        if overdue.is_empty() {
+            lines.push("  (없음)".dimmed().to_string());
+        }
+        for (task, end) in overdue.iter().take(MAX_LISTED) {
+            lines.push(format!("  {} {} (예정 종료 {}, {}일 지남)",
+                task.id, task.name, end.format("%m-%d"), (now - *end).num_days()).red().to_string());
+        }
+        lines.push(String::new());
+
+        lines.push("작업".yellow().bold().to_string());
+        let header_len = lines.len();
+        // 작업 목록이 화면보다 길면 선택한 작업이 보이도록 스크롤
+        let room = rows.saturating_sub(header_len + 2).max(1);
+        let skip = self.task_index.saturating_sub(room - 1);
+        for (i, task) in plan.tasks().enumerate().skip(skip).take(room) {
+            let check = if task.completed_at.is_some() { "[x]".green() } else { "[ ]".normal() };
+            let estimate = if task.duration.is_zero() { String::new() } else { task.duration.to_string() };
+            let line = format!("{} {} {}  {}", check, task.id, fit(&task.name, 40), estimate.dimmed());
+            lines.push(if i == self.task_index { format!("{} {}", ">".cyan().bold(), line) } else { format!("  {}", line) });
+        }
+
+        let mut out = std::io::stdout();
+        queue!(out, Clear(ClearType::All))?;
+        for (y, line) in lines.iter().take(rows.saturating_sub(1)).enumerate() {
+            queue!(out, MoveTo(0, y as u16), Print(line))?;
+        }
+        queue!(out, MoveTo(0, rows.saturating_sub(1) as u16), Print(fit(&self.message, cols).dimmed()))?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// 계획 생성 시각에 최소 일정의 종료 시각(하루 8시간 기준)을 더한 예정 종료일
+fn planned_end(plan: &ProjectPlan, schedule: Option<&Schedule>, task_id: &str) -> Option<DateTime<Utc>> {
+    let (_, finish) = schedule?.times.get(task_id).copied()?;
+    if finish <= 0.0 {
+        return None;
+    }
+    let days = (finish / HOURS_PER_DAY).ceil() as i64;
+    Some(plan.created_at + Duration::days(days))
+}
+
+fn progress_bar(done: usize, total: usize) -> String {
+    let ratio = if total == 0 { 0.0 } else { done as f32 / total as f32 };
+    let filled = (ratio * BAR_WIDTH as f32).round() as usize;
+    format!("{}{} {:>3}%", plain("█").repeat(filled).green(), plain("░").repeat(BAR_WIDTH - filled).dimmed(), (ratio * 100.0).round())
+}
+
+/// 화면 폭에 맞게 자릅니다 (한글 등 비ASCII 문자는 두 칸으로 계산)
+fn fit(text: &str, width: usize) -> String {
+    let mut used = 0;
+    let mut result = String::new();
+    for c in text.chars() {
+        let w = if c.is_ascii() { 1 } else { 2 };
+        if used + w > width {
+            result.push('…');
+            break;
+        }
+        used += w;
+        result.push(c);
+    }
+    result
+}
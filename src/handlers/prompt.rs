@@ -0,0 +1,107 @@
+use std::path::Path;
+use std::process::Command;
+use crate::assistant::{ChatMode, DevAssistant};
+
+/// REPL 프롬프트에 표시할 현재 상태
+pub struct PromptState {
+    pub chat: bool,
+    pub model: String,
+    pub mode: ChatMode,
+    pub persona: Option<String>,
+    pub tokens: usize,
+    pub git: Option<GitStatus>,
+}
+
+/// 셸 작업 디렉토리의 git 저장소 상태
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitStatus {
+    pub branch: String,
+    pub dirty: bool,
+}
+
+impl GitStatus {
+    /// `dir`의 git 상태. git 저장소가 아니거나 git이 없으면 None
+    pub fn detect(dir: &Path) -> Option<Self> {
+        let output = Command::new("git")
+            .args(["status", "--porcelain", "--branch"])
+            .current_dir(dir)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        let mut lines = text.lines();
+        let header = lines.next()?.strip_prefix("## ")?;
+        let branch = parse_branch(header);
+        let dirty = lines.any(|l| !l.trim().is_empty());
+        Some(Self { branch, dirty })
+    }
+
+    /// `main` 또는 변경 사항이 있으면 `main*`
+    pub fn label(&self) -> String {
+        if self.dirty {
+            format!("{}*", self.branch)
+        } else {
+            self.branch.clone()
+        }
+    }
+}
+
+impl PromptState {
+    pub fn from_assistant(assistant: &DevAssistant, chat: bool, git: Option<GitStatus>) -> Self {
+        Self {
+            chat,
+            model: assistant.get_config().model_preferences.default_model.clone(),
+            mode: assistant.get_mode(),
+            persona: assistant.persona().map(String::from),
+            tokens: assistant.context_tokens(),
+            git,
+        }
+    }
+}
+
+/// `output_preferences.prompt` 형식 문자열을 채웁니다.
+/// 지원 토큰: {model}, {mode}, {persona}, {branch}, {tokens}, {chat}
+pub fn render_prompt(template: &str, state: &PromptState) -> String {
+    template
+        .replace("{model}", &state.model)
+        .replace("{mode}", state.mode.label())
+        .replace("{persona}", state.persona.as_deref().unwrap_or(""))
+        .replace("{tokens}", &state.tokens.to_string())
+        .replace("{chat}", if state.chat { "(chat)" } else { "" })
+        .replace("{branch}", &state.git.as_ref().map(GitStatus::label).unwrap_or_default())
+}
+
+/// 기본 프롬프트: 명령어 모드의 git 브랜치와 기본값이 아닌 대화 모드/페르소나를 표시
+pub fn default_prompt(state: &PromptState) -> String {
+    let mut tags = Vec::new();
+    if state.mode != ChatMode::Normal {
+        tags.push(state.mode.label().to_string());
+    }
+    if let Some(persona) = &state.persona {
+        tags.push(persona.clone());
+    }
+    let tag = if tags.is_empty() { String::new() } else { format!(" [{}]", tags.join("·")) };
+
+    if state.chat {
+        format!("ricci (chat){tag}> ")
+    } else {
+        let branch = state.git.as_ref().map(|g| format!(" ({})", g.label())).unwrap_or_default();
+        format!("ricci{branch}{tag}> ")
+    }
+}
+
+/// `## main...origin/main [ahead 1]` 형태의 헤더에서 브랜치 이름을 꺼냅니다
+fn parse_branch(header: &str) -> String {
+    if let Some(rest) = header.strip_prefix("No commits yet on ") {
+        return rest.to_string();
+    }
+    if header.starts_with("HEAD (no branch)") {
+        return "HEAD".to_string();
+    }
+    header.split("...").next().unwrap_or(header)
+        .split_whitespace().next().unwrap_or(header)
+        .to_string()
+}
@@ -0,0 +1,241 @@
+use anyhow::Result;
+use colored::*;
+use serde::Deserialize;
+use std::path::Path;
+use crate::renderer::plain;
+use crate::{
+    analyzer::language_registry,
+    assistant::{fence_file, DevAssistant, FileChange, SafeFileModifier},
+    config::Config,
+    handlers::symbol_refactor::{find_symbol_sites, word_positions},
+};
+
+/// 이름이 나온 자리의 종류. 코드와 주석은 바로 바꾸고, 문자열 리터럴은 모델에게 판단을 맡깁니다
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Place {
+    Code,
+    Comment,
+    Text,
+}
+
+/// 파일 안의 이름 한 곳
+struct Occurrence {
+    path: String,
+    /// 0부터 시작하는 줄 번호와 줄 안의 바이트 위치
+    line: usize,
+    column: usize,
+    place: Place,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct Decision {
+    id: usize,
+    rename: bool,
+    #[serde(default)]
+    reason: String,
+}
+
+/// `path` 아래 소스 파일에서 `old`를 단어 단위로 찾아 `new`로 바꿉니다. 모든 변경 블록을 보여준 뒤
+/// 한 번에 적용하며, 문자열 리터럴 안의 이름만 모델에게 바꿀지 묻습니다 (`no_ai`면 그대로 둠)
+pub async fn handle_rename(old: &str, new: &str, path: &str, no_ai: bool, config: &Config) -> Result<()> {
+    let is_name = |name: &str| !name.is_empty() && !name.contains(char::is_whitespace);
+    if !is_name(old) || !is_name(new) {
+        anyhow::bail!("공백 없는 이름을 지정하세요: `{}` → `{}`", old, new);
+    }
+    if old == new {
+        anyhow::bail!("바꿀 이름이 같습니다: {}", old);
+    }
+
+    let sites = find_symbol_sites(path, old);
+    if sites.is_empty() {
+        println!("{} `{}`을(를) 찾지 못했습니다: {}", plain("⚠️").yellow(), old, path);
+        return Ok(());
+    }
+    let existing_new = count_words(path, new);
+    if existing_new > 0 {
+        println!("{} `{}`이(가) 이미 {}곳에 있습니다. 이름이 겹치지 않는지 확인하세요", "경고:".yellow(), new, existing_new);
+    }
+
+    let mut occurrences = Vec::new();
+    for site in &sites {
+        occurrences.extend(find_occurrences(&site.path, &site.content, old));
+    }
+    let texts: Vec<usize> = (0..occurrences.len()).filter(|&i| occurrences[i].place == Place::Text).collect();
+    let code = occurrences.iter().filter(|o| o.place == Place::Code).count();
+    let comments = occurrences.iter().filter(|o| o.place == Place::Comment).count();
+    println!(
+        "{} `{}` {}곳 (코드 {}, 주석 {}, 문자열 {}), {}개 파일",
+        plain("🔎 찾은 위치:").cyan(), old, occurrences.len(), code, comments, texts.len(), sites.len()
+    );
+
+    // 문자열 안의 이름은 같은 심볼을 가리킬 때만 바꿈 (모델 판단, 실패하면 그대로 둠)
+    let mut accepted = vec![true; occurrences.len()];
+    for &index in &texts {
+        accepted[index] = false;
+    }
+    if !texts.is_empty() {
+        if no_ai {
+            println!("{}", format!("  문자열 안 {}곳은 바꾸지 않습니다 (--no-ai)", texts.len()).dimmed());
+        } else {
+            match judge_strings(old, new, &occurrences, &texts, config).await {
+                Ok(decisions) => {
+                    for decision in decisions {
+                        let Some(&index) = texts.get(decision.id.wrapping_sub(1)) else {
+                            continue;
+                        };
+                        accepted[index] = decision.rename;
+                        let occurrence = &occurrences[index];
+                        let verdict = if decision.rename { "변경".green() } else { "유지".yellow() };
+                        println!("  {} {}:{} {}", verdict, occurrence.path, occurrence.line + 1, decision.reason.dimmed());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{} 문자열 판단 실패, 문자열 안의 이름은 바꾸지 않습니다: {}", "경고:".yellow(), e);
+                }
+            }
+        }
+    }
+
+    let mut changes = Vec::new();
+    for site in &sites {
+        let selected: Vec<&Occurrence> = occurrences.iter().zip(&accepted)
+            .filter(|(o, &a)| a && o.path == site.path)
+            .map(|(o, _)| o)
+            .collect();
+        if selected.is_empty() {
+            continue;
+        }
+        changes.push(FileChange {
+            path: site.path.clone(),
+            original_content: site.content.clone(),
+            new_content: replace_occurrences(&site.content, &selected, old, new),
+            description: format!("`{}` → `{}`", old, new),
+        });
+    }
+    if changes.is_empty() {
+        println!("{}", "바꿀 위치가 없습니다.".yellow());
+        return Ok(());
+    }
+
+    let replaced = accepted.iter().filter(|&&a| a).count();
+    if !SafeFileModifier::new(config.assume_yes).apply_all_or_nothing(&changes)? {
+        return Ok(());
+    }
+
+    // 적용 결과 검증: 남은 이름은 유지하기로 한 문자열뿐이어야 하고, 새 이름은 바꾼 만큼 늘어야 함
+    let remaining = count_words(path, old);
+    let expected_remaining = occurrences.len() - replaced;
+    let renamed = count_words(path, new);
+    if remaining == expected_remaining && renamed == existing_new + replaced {
+        println!("{} {}곳 변경, 남은 `{}` {}곳(유지한 문자열)", plain("✓ 검증:").green(), replaced, old, remaining);
+    } else {
+        println!(
+            "{} 남은 `{}` {}곳(예상 {}), `{}` {}곳(예상 {}). 변경 내용을 확인하세요",
+            "검증 불일치:".red(), old, remaining, expected_remaining, new, renamed, existing_new + replaced
+        );
+    }
+    Ok(())
+}
+
+/// `path` 아래 소스 파일에서 단어 단위로 나온 횟수
+fn count_words(path: &str, word: &str) -> usize {
+    find_symbol_sites(path, word).iter()
+        .flat_map(|site| site.lines.iter())
+        .map(|(_, line)| word_positions(line, word).len())
+        .sum()
+}
+
+fn find_occurrences(path: &str, content: &str, old: &str) -> Vec<Occurrence> {
+    let registry = language_registry();
+    let language = registry.for_path(Path::new(path));
+    let comments: Vec<&str> = language.map(|l| l.line_comments.iter().map(String::as_str).collect()).unwrap_or_default();
+    // Rust의 `'`는 수명 표시라 문자열로 보지 않음
+    let quotes: &[char] = if language.is_some_and(|l| l.name == "Rust") { &['"'] } else { &['"', '\'', '`'] };
+
+    let mut occurrences = Vec::new();
+    for (line_index, line) in content.lines().enumerate() {
+        let places = line_places(line, &comments, quotes);
+        for column in word_positions(line, old) {
+            occurrences.push(Occurrence {
+                path: path.to_string(),
+                line: line_index,
+                column,
+                place: places[column],
+                text: line.to_string(),
+            });
+        }
+    }
+    occurrences
+}
+
+/// 줄의 바이트마다 코드/주석/문자열 중 어디에 속하는지 (줄 단위로 판단, 여러 줄 문자열은 코드로 봄)
+fn line_places(line: &str, comments: &[&str], quotes: &[char]) -> Vec<Place> {
+    let mut places = vec![Place::Code; line.len()];
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some(q) => {
+                places[index..index + c.len_utf8()].fill(Place::Text);
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if comments.iter().any(|prefix| line[index..].starts_with(prefix)) => {
+                places[index..].fill(Place::Comment);
+                break;
+            }
+            None if quotes.contains(&c) => {
+                quote = Some(c);
+                places[index] = Place::Text;
+            }
+            None => {}
+        }
+    }
+    places
+}
+
+/// 모델에게 문자열 안의 이름이 같은 심볼을 가리키는지 묻습니다
+async fn judge_strings(
+    old: &str,
+    new: &str,
+    occurrences: &[Occurrence],
+    texts: &[usize],
+    config: &Config,
+) -> Result<Vec<Decision>> {
+    let listing: Vec<String> = texts.iter().enumerate()
+        .map(|(id, &index)| {
+            let o = &occurrences[index];
+            format!("{}. {}:{}: {}", id + 1, o.path, o.line + 1, o.text.trim())
+        })
+        .collect();
+    let prompt = format!(
+        "심볼 `{}`을(를) `{}`(으)로 이름을 바꾸는 중입니다. 아래 줄의 문자열 리터럴 안에 있는 `{}`이(가) \
+        같은 심볼을 가리켜 함께 바꿔야 하는지 판단해주세요. 리플렉션, 직렬화 키, 동적 호출, 오류/로그 메시지의 \
+        함수 이름처럼 심볼을 가리키면 바꾸고, 사용자에게 보이는 일반 단어나 외부 API 이름이면 유지합니다.\n\n\
+        다음 형식의 JSON 배열로만 응답해주세요:\n\
+        [{{\"id\": 1, \"rename\": true, \"reason\": \"짧은 이유\"}}]\n\n{}",
+        old, new, old, fence_file("string-literals.txt", "", &listing.join("\n"))
+    );
+    let assistant = DevAssistant::new(config.clone())?;
+    let response = assistant.query(&prompt).await?;
+    let (start, end) = response.find('[').zip(response.rfind(']'))
+        .filter(|(start, end)| start < end)
+        .ok_or_else(|| anyhow::anyhow!("응답에서 JSON 배열을 찾을 수 없습니다"))?;
+    Ok(serde_json::from_str(&response[start..=end])?)
+}
+
+/// 고른 위치만 뒤에서부터 바꿔 앞쪽 위치가 밀리지 않게 합니다
+fn replace_occurrences(content: &str, selected: &[&Occurrence], old: &str, new: &str) -> String {
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(str::to_string).collect();
+    for occurrence in selected.iter().rev() {
+        let line = &mut lines[occurrence.line];
+        line.replace_range(occurrence.column..occurrence.column + old.len(), new);
+    }
+    lines.concat()
+}
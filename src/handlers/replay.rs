@@ -0,0 +1,116 @@
+use anyhow::{Context, Result};
+use colored::*;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::{
+    api::{Segment, ThinkSplitter},
+    config::load_config,
+    renderer::{plain, MarkdownRenderer, DEFAULT_THEME},
+};
+
+/// `--record`로 기록한 요청/응답을 네트워크 없이 다시 렌더링합니다
+pub fn handle_replay(dir: &Path) -> Result<()> {
+    let mut requests: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("기록 디렉토리를 읽을 수 없습니다: {}", dir.display()))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.to_string_lossy().ends_with("-request.json"))
+        .collect();
+    requests.sort();
+
+    if requests.is_empty() {
+        println!("{} 재생할 기록이 없습니다: {}", plain("⚠️").yellow(), dir.display());
+        return Ok(());
+    }
+
+    let theme = load_config()
+        .map(|c| c.output_preferences.theme)
+        .unwrap_or_else(|_| DEFAULT_THEME.to_string());
+    let renderer = MarkdownRenderer::with_theme(&theme);
+
+    let total = requests.len();
+    for (index, request_path) in requests.iter().enumerate() {
+        let request: Value = serde_json::from_str(&fs::read_to_string(request_path)?)
+            .with_context(|| format!("요청 기록 파싱 실패: {}", request_path.display()))?;
+
+        println!("\n{}", plain(&format!(
+            "━━ [{}/{}] {} ━━",
+            index + 1,
+            total,
+            request["model"].as_str().unwrap_or("-")
+        )).bright_cyan().bold());
+
+        if let Some(prompt) = last_user_message(&request) {
+            println!("{} {}", "You:".green().bold(), prompt);
+        }
+
+        println!("\n{} ", "Assistant:".blue().bold());
+        let response_path = PathBuf::from(
+            request_path.to_string_lossy().replace("-request.json", "-response.jsonl")
+        );
+        match fs::read_to_string(&response_path) {
+            Ok(content) => replay_response(&content, &renderer)?,
+            Err(_) => println!("{}", "(응답 기록 없음)".dimmed()),
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+fn last_user_message(request: &Value) -> Option<String> {
+    let message = request["messages"].as_array()?
+        .iter()
+        .rev()
+        .find(|m| m["role"] == "user")?;
+
+    match &message["content"] {
+        Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// 응답 기록의 각 줄(스트림 청크 또는 단일 응답)을 순서대로 렌더링합니다
+fn replay_response(content: &str, renderer: &MarkdownRenderer) -> Result<()> {
+    let mut splitter = ThinkSplitter::default();
+
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let chunk: Value = serde_json::from_str(line).context("응답 기록 파싱 실패")?;
+        let choice = &chunk["choices"][0];
+
+        // 스트림 청크는 delta, 단일 응답은 message에 본문이 있음
+        let text = choice["delta"]["content"].as_str()
+            .or_else(|| choice["message"]["content"].as_str());
+        if let Some(text) = text {
+            render_segments(splitter.push(text), renderer)?;
+        }
+
+        if choice["finish_reason"] == "length" {
+            render_segments(splitter.finish(), renderer)?;
+            println!("\n{}", "[경고] 응답이 max_tokens 한도에서 잘렸습니다.".yellow());
+        }
+
+        let usage = &chunk["usage"];
+        if usage.is_object() {
+            render_segments(splitter.finish(), renderer)?;
+            println!("\n{}", format!(
+                "[사용량] 입력 {} | 출력 {} | 캐시 {}",
+                usage["prompt_tokens"].as_u64().unwrap_or(0),
+                usage["completion_tokens"].as_u64().unwrap_or(0),
+                usage["prompt_tokens_details"]["cached_tokens"].as_u64().unwrap_or(0)
+            ).dimmed());
+        }
+    }
+
+    render_segments(splitter.finish(), renderer)
+}
+
+fn render_segments(segments: Vec<Segment>, renderer: &MarkdownRenderer) -> Result<()> {
+    for segment in segments {
+        match segment {
+            Segment::Content(text) => renderer.render_chunk(&text)?,
+            Segment::Reasoning(text) => print!("{}", text.dimmed()),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use anyhow::{Result, Context};
+use colored::*;
+use crate::renderer::plain;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::config::Config;
+use crate::permissions::ensure_allowed;
+use crate::paths::sessions_dir;
+use crate::error::RicciError;
+use crate::assistant::{
+    AssistantContext, ChatMode, ContextStore, ExportFormat, FileContextStore, ImportFormat, ImportedConversation,
+    export_finetune_jsonl, import_conversations, language_instruction, write_vault_note,
+};
+
+#[derive(clap::Subcommand)]
+pub enum SessionAction {
+    /// 저장된 세션을 파인튜닝용 JSONL 또는 Obsidian 보관함 노트로 내보내기
+    Export {
+        /// 내보내기 형식
+        #[clap(short, long, value_enum, default_value = "openai-jsonl")]
+        format: ExportFormat,
+        /// 내보낼 세션 파일 (비워두면 세션 디렉토리의 모든 세션)
+        #[clap(short, long, value_name = "SESSION")]
+        input: Vec<PathBuf>,
+        /// 출력 파일 (비워두면 표준 출력)
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+        /// 각 대화에 넣을 시스템 프롬프트 (기본값: Normal 모드 프롬프트)
+        #[clap(long)]
+        system: Option<String>,
+        /// JSONL 대신 세션마다 머리말(날짜, 태그, 모델)이 있는 노트로 이 보관함 폴더에 저장
+        #[clap(long, value_name = "DIR", conflicts_with_all = ["output", "system"])]
+        vault: Option<PathBuf>,
+    },
+    /// 다른 도구의 대화 기록(ChatGPT/Claude 내보내기 conversations.json, aider 기록)을 세션으로 가져오기
+    Import {
+        /// 가져올 파일
+        file: PathBuf,
+        /// 파일 형식 (비워두면 내용으로 판단)
+        #[clap(long, value_enum)]
+        format: Option<ImportFormat>,
+        /// 가져온 대화 하나를 현재 세션으로 만들어 `ricci chat`에서 이어가기 (번호 또는 제목 일부, 값이 없으면 가장 최근 대화)
+        #[clap(long, value_name = "N|TITLE", num_args = 0..=1, default_missing_value = "")]
+        resume: Option<String>,
+    },
+}
+
+pub fn handle_session(action: SessionAction, config: &Config) -> Result<()> {
+    match action {
+        SessionAction::Export { input, vault: Some(vault), .. } => {
+            ensure_allowed("노트 저장")?;
+            let files = if input.is_empty() { list_session_files()? } else { input };
+            let mut written = 0;
+            for file in &files {
+                let context = read_session(file)?;
+                if let Some(path) = write_vault_note(&context, &vault, &config.model_preferences.default_model)? {
+                    println!("  {} {}", plain("•"), path.display());
+                    written += 1;
+                }
+            }
+            eprintln!("{} {}개 세션을 {}에 노트로 저장했습니다.",
+                plain("✓").green(), written, vault.display().to_string().cyan());
+        }
+        SessionAction::Export { format, input, output, system, vault: None } => {
+            let files = if input.is_empty() { list_session_files()? } else { input };
+            let system_prompt = system.unwrap_or_else(|| {
+                format!("{} {}", ChatMode::Normal.system_prompt(), language_instruction(&config.output_preferences.language))
+            });
+
+            let mut lines = Vec::new();
+            for file in &files {
+                let context = read_session(file)?;
+                if let Some(line) = export_finetune_jsonl(&context, format, &system_prompt)? {
+                    lines.push(line);
+                }
+            }
+
+            let mut content = lines.join("\n");
+            if !content.is_empty() {
+                content.push('\n');
+            }
+
+            match output {
+                Some(path) => {
+                    ensure_allowed("파일 저장")?;
+                    fs::write(&path, content).context("내보내기 파일 저장 실패")?;
+                    eprintln!("{} {}개 대화를 {}에 저장했습니다.",
+                        plain("✓").green(), lines.len(), path.display().to_string().cyan());
+                }
+                None => print!("{content}"),
+            }
+        }
+        SessionAction::Import { file, format, resume } => import_sessions(&file, format, resume.as_deref())?,
+    }
+    Ok(())
+}
+
+fn import_sessions(file: &Path, format: Option<ImportFormat>, resume: Option<&str>) -> Result<()> {
+    let content = fs::read_to_string(file)
+        .with_context(|| format!("가져올 파일 읽기 실패: {}", file.display()))?;
+    let format = match format.or_else(|| ImportFormat::detect(file, &content)) {
+        Some(format) => format,
+        None => return Err(RicciError::Parse(format!(
+            "대화 기록 형식을 알 수 없습니다: {} (--format chatgpt|claude|aider로 지정하세요)", file.display()
+        )).into()),
+    };
+    let conversations = import_conversations(&content, format)?;
+    if conversations.is_empty() {
+        println!("{} 가져올 대화가 없습니다: {}", plain("⚠️").yellow(), file.display());
+        return Ok(());
+    }
+
+    // 같은 파일을 다시 가져오면 같은 세션 파일을 덮어씀
+    let dir = sessions_dir()?;
+    fs::create_dir_all(&dir)?;
+    for (index, conversation) in conversations.iter().enumerate() {
+        let path = dir.join(format!("import_{}_{:03}_{}.json", format.label(), index + 1, slug(&conversation.title)));
+        fs::write(&path, serde_json::to_string_pretty(&conversation.context)?)?;
+        println!("  {} [{}] {} (메시지 {}개) {} {}",
+            plain("•"), index + 1, conversation.title, conversation.context.messages.len(),
+            plain("→"), path.display().to_string().dimmed());
+    }
+    println!("{} {}개 대화를 {} 형식으로 가져왔습니다.", plain("✓").green(), conversations.len(), format.label().cyan());
+
+    if let Some(selector) = resume {
+        let chosen = pick_conversation(&conversations, selector)?;
+        let store = FileContextStore::current_session()?;
+        // 지금 세션은 덮어쓰기 전에 보관
+        if let Some(previous) = store.load()?.filter(|c| !c.messages.is_empty()) {
+            if let Some(location) = store.archive(&previous)? {
+                println!("{} {}", "기존 세션 보관:".dimmed(), location);
+            }
+        }
+        store.save(&chosen.context)?;
+        println!("{} '{}'을(를) 현재 세션으로 설정했습니다. `ricci chat`에서 이어서 대화할 수 있습니다.",
+            plain("✓").green(), chosen.title.cyan());
+    }
+    Ok(())
+}
+
+/// 번호(1부터), 제목 일부, 또는 빈 값(가장 최근 대화)으로 대화를 고릅니다
+fn pick_conversation<'a>(conversations: &'a [ImportedConversation], selector: &str) -> Result<&'a ImportedConversation> {
+    if selector.is_empty() {
+        return conversations.iter().max_by_key(|c| c.last_activity())
+            .context("가져온 대화가 없습니다");
+    }
+    if let Ok(number) = selector.parse::<usize>() {
+        return conversations.get(number.wrapping_sub(1))
+            .with_context(|| format!("대화 번호는 1부터 {}까지입니다: {}", conversations.len(), number));
+    }
+    let needle = selector.to_lowercase();
+    conversations.iter().find(|c| c.title.to_lowercase().contains(&needle))
+        .with_context(|| format!("제목에 '{}'이(가) 들어간 대화가 없습니다", selector))
+}
+
+/// 파일 이름에 쓸 수 있게 제목을 줄입니다 (글자와 숫자만 남기고 나머지는 '-')
+fn slug(title: &str) -> String {
+    let slug: String = title.chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug: String = slug.chars().take(40).collect();
+    if slug.is_empty() { "untitled".to_string() } else { slug }
+}
+
+fn read_session(file: &Path) -> Result<AssistantContext> {
+    let data = fs::read_to_string(file)
+        .with_context(|| format!("세션 파일 읽기 실패: {}", file.display()))?;
+    serde_json::from_str(&data)
+        .with_context(|| format!("세션 파일 파싱 실패: {}", file.display()))
+}
+
+fn list_session_files() -> Result<Vec<PathBuf>> {
+    let dir = sessions_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
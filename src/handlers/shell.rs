@@ -0,0 +1,260 @@
+use anyhow::{Context, Result};
+use colored::*;
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use crate::assistant::record_command;
+use crate::config::{ShellConfig, ShellEncoding};
+
+/// 명령어 모드에서 셸 명령어 사이에 유지되는 상태.
+/// 셸 명령어는 매번 새 프로세스로 실행되므로 `cd`/`export`는 여기서 직접 처리합니다.
+/// ricci 프로세스의 작업 디렉토리는 그대로 두고, 이동한 디렉토리는 셸 명령어를 실행할 때만 적용합니다
+#[derive(Debug)]
+pub struct ShellState {
+    cwd: PathBuf,
+    previous_dir: Option<PathBuf>,
+    env: HashMap<String, String>,
+    last_command: Option<CommandRecord>,
+}
+
+/// AI 컨텍스트로 보낼 출력의 최대 길이 (뒤쪽을 남김)
+const MAX_CAPTURED_CHARS: usize = 8000;
+
+/// 실행한 셸 명령어와 캡처한 출력
+#[derive(Debug, Clone)]
+pub struct CommandRecord {
+    pub command: String,
+    pub stdout: String,
+    pub stderr: String,
+    /// 시그널 종료나 Ctrl+C 중단이면 None
+    pub exit_code: Option<i32>,
+}
+
+impl CommandRecord {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// 명령어, 결과, 출력을 정리한 마크다운 (`#lastcmd`로 대화에 넣는 내용)
+    pub fn context_block(&self) -> String {
+        let status = match self.exit_code {
+            Some(code) => format!("종료 코드 {code}"),
+            None => "중단됨 (종료 코드 없음)".to_string(),
+        };
+        format!(
+            "명령어: `{}`\n\
+            결과: {}\n\n\
+            표준 출력:\n```\n{}\n```\n\n\
+            표준 에러:\n```\n{}\n```",
+            self.command,
+            status,
+            tail(&self.stdout),
+            tail(&self.stderr),
+        )
+    }
+
+    /// 명령어 설명/실패 진단 요청 프롬프트
+    pub fn explain_prompt(&self) -> String {
+        let request = if self.success() {
+            "이 명령어가 무엇을 했는지, 출력이 무엇을 의미하는지 설명해주세요."
+        } else {
+            "이 명령어가 왜 실패했는지 진단하고 해결 방법을 제시해주세요."
+        };
+
+        format!("터미널에서 다음 명령어를 실행했습니다.\n\n{}\n\n{}", self.context_block(), request)
+    }
+}
+
+/// 긴 출력은 끝부분만 남깁니다 (오류 메시지는 대개 마지막에 있음)
+fn tail(text: &str) -> &str {
+    let text = text.trim_end();
+    if text.len() <= MAX_CAPTURED_CHARS {
+        return text;
+    }
+    let mut start = text.len() - MAX_CAPTURED_CHARS;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    &text[start..]
+}
+
+/// ricci를 실행한 디렉토리에서 시작
+impl Default for ShellState {
+    fn default() -> Self {
+        Self {
+            cwd: env::current_dir().unwrap_or_default(),
+            previous_dir: None,
+            env: HashMap::new(),
+            last_command: None,
+        }
+    }
+}
+
+impl ShellState {
+    /// `cd`로 이동한 디렉토리 (셸 명령어 실행 시 적용)
+    pub fn cwd(&self) -> &Path {
+        &self.cwd
+    }
+
+    /// `export`로 설정한 환경 변수 (셸 명령어 실행 시 적용)
+    pub fn env(&self) -> &HashMap<String, String> {
+        &self.env
+    }
+
+    /// 마지막으로 실행한 셸 명령어 (`/explain-last`에서 사용)
+    pub fn last_command(&self) -> Option<&CommandRecord> {
+        self.last_command.as_ref()
+    }
+
+    /// 마지막 명령어로 기록하고 대화의 `#lastcmd` 참조에도 남깁니다
+    pub fn set_last_command(&mut self, record: CommandRecord) {
+        record_command(record.context_block());
+        self.last_command = Some(record);
+    }
+
+    /// 내장 명령어(`cd`, `pwd`, `export`, `unset`)면 처리하고 true를 반환합니다.
+    /// `cd a && make`처럼 셸 연산자가 섞인 입력은 셸에 그대로 넘깁니다
+    pub fn try_builtin(&mut self, input: &str) -> Result<bool> {
+        let Some(words) = split_words(input) else {
+            return Ok(false);
+        };
+        let Some((command, args)) = words.split_first() else {
+            return Ok(false);
+        };
+
+        match command.as_str() {
+            "cd" => self.cd(args.first().map(String::as_str).unwrap_or(""))?,
+            "pwd" => println!("{}", self.cwd.display()),
+            "export" if args.is_empty() => {
+                let mut vars: Vec<_> = self.env.iter().collect();
+                vars.sort();
+                for (key, value) in vars {
+                    println!("{}={}", key.cyan(), value);
+                }
+            }
+            "export" => {
+                for assignment in args {
+                    match assignment.split_once('=') {
+                        Some((key, value)) if !key.is_empty() => {
+                            self.env.insert(key.to_string(), value.to_string());
+                        }
+                        _ => println!("{} 형식: export 이름=값", "사용법:".yellow()),
+                    }
+                }
+            }
+            "unset" => {
+                for key in args {
+                    self.env.remove(key);
+                }
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn cd(&mut self, target: &str) -> Result<()> {
+        let destination = match target {
+            "" | "~" => dirs::home_dir().context("홈 디렉토리를 찾을 수 없습니다")?,
+            "-" => match &self.previous_dir {
+                Some(dir) => dir.clone(),
+                None => {
+                    println!("{}", "이전 디렉토리가 없습니다.".yellow());
+                    return Ok(());
+                }
+            },
+            _ => match target.strip_prefix("~/") {
+                Some(rest) => dirs::home_dir().context("홈 디렉토리를 찾을 수 없습니다")?.join(rest),
+                None => self.cwd.join(target),
+            },
+        };
+
+        // `..`을 풀고 존재하는 디렉토리인지 확인
+        match destination.canonicalize() {
+            Ok(dir) if dir.is_dir() => {
+                self.previous_dir = Some(std::mem::replace(&mut self.cwd, dir));
+                if target == "-" {
+                    println!("{}", self.cwd.display());
+                }
+            }
+            Ok(_) => eprintln!("{} {}: 디렉토리가 아닙니다", "cd 실패:".red(), destination.display()),
+            Err(e) => eprintln!("{} {}: {}", "cd 실패:".red(), destination.display(), e),
+        }
+        Ok(())
+    }
+}
+
+/// 입력을 따옴표를 벗긴 단어로 나눕니다 (`export A="x y"` -> `export`, `A=x y`).
+/// 따옴표 밖에 셸 연산자(`;`, `|`, `&`, `<`, `>`, `` ` ``, `$(`)가 있거나 따옴표가 닫히지 않으면 None
+fn split_words(input: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.get_or_insert_with(String::new).push(c),
+            None => match c {
+                '"' | '\'' => {
+                    quote = Some(c);
+                    current.get_or_insert_with(String::new);
+                }
+                ';' | '|' | '&' | '<' | '>' | '`' | '\n' => return None,
+                '$' if chars.peek() == Some(&'(') => return None,
+                c if c.is_whitespace() => words.extend(current.take()),
+                c => current.get_or_insert_with(String::new).push(c),
+            },
+        }
+    }
+    if quote.is_some() {
+        return None;
+    }
+    words.extend(current);
+    Some(words)
+}
+
+/// 설정한 셸로 명령어를 실행하는 Command를 만듭니다
+pub fn shell_command(config: &ShellConfig, input: &str) -> Command {
+    let default_program = if cfg!(target_os = "windows") { "powershell" } else { "sh" };
+    let program = config.program.as_deref().unwrap_or(default_program);
+
+    let mut command = Command::new(program);
+    match program_name(program).as_str() {
+        // PowerShell은 출력 인코딩을 UTF-8로 맞춘 뒤 실행
+        "powershell" | "pwsh" => {
+            command.arg("-NoProfile")
+                .arg("-Command")
+                .arg(format!("[Console]::OutputEncoding = [System.Text.Encoding]::UTF8; {}", input));
+        }
+        "cmd" => {
+            command.arg("/C").arg(input);
+        }
+        _ => {
+            command.arg("-c").arg(input);
+        }
+    }
+    command
+}
+
+/// 경로와 확장자를 뗀 셸 이름 (`C:\...\pwsh.exe` -> `pwsh`)
+fn program_name(program: &str) -> String {
+    std::path::Path::new(program)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// 셸 출력 바이트를 설정한 인코딩으로 디코딩합니다
+pub fn decode_output(bytes: &[u8], encoding: ShellEncoding) -> String {
+    match encoding {
+        ShellEncoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        ShellEncoding::Cp949 => encoding_rs::EUC_KR.decode(bytes).0.into_owned(),
+        ShellEncoding::Auto => match std::str::from_utf8(bytes) {
+            Ok(text) => text.to_string(),
+            // 한국어 Windows 콘솔 프로그램은 CP949로 출력하는 경우가 많음
+            Err(_) => encoding_rs::EUC_KR.decode(bytes).0.into_owned(),
+        },
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use colored::*;
+use std::process::Command;
+use crate::assistant::{language_instruction, AssistantContext, DevAssistant};
+use crate::config::Config;
+
+/// 프롬프트에 넣을 커밋 수, 세션 질문 수와 질문당 최대 길이
+const MAX_COMMITS: usize = 60;
+const MAX_SESSION_PROMPTS: usize = 40;
+const MAX_PROMPT_CHARS: usize = 200;
+
+/// 최근 git 커밋과 세션 대화를 모아 "한 일 / 할 일 / 블로커" 스탠드업 요약을 만듭니다
+pub async fn handle_standup(since: &str, config: &Config) -> Result<()> {
+    let since_time = parse_since(since)?;
+    println!("{} {} 이후", "활동 수집 중:".cyan(), since_time.with_timezone(&Local).format("%Y-%m-%d %H:%M"));
+
+    let commits = git_commits(since_time);
+    let changes = git_uncommitted();
+    let prompts = session_prompts(since_time);
+    println!("  커밋 {}개, 세션 질문 {}개, 커밋하지 않은 변경 {}개",
+        commits.len(), prompts.len(), changes.len());
+
+    if commits.is_empty() && prompts.is_empty() && changes.is_empty() {
+        anyhow::bail!("{} 이후 활동 기록이 없습니다 (git 커밋, ricci 세션)", since);
+    }
+
+    let section = |title: &str, items: &[String]| {
+        if items.is_empty() {
+            String::new()
+        } else {
+            format!("{}:\n{}\n\n", title, items.iter().map(|i| format!("- {}", i)).collect::<Vec<_>>().join("\n"))
+        }
+    };
+    let prompt = format!(
+        "다음은 개발자의 최근 활동 기록입니다. 데일리 스탠드업에서 말할 짧은 요약을 작성해주세요.\n\n\
+        {}{}{}\
+        `## 한 일`, `## 할 일`, `## 블로커` 세 섹션에 각각 3~5개 이하의 짧은 항목으로 정리하세요. \
+        비슷한 커밋은 하나로 묶고, 할 일은 커밋하지 않은 변경과 대화 흐름에서 추론하되 근거가 없으면 쓰지 마세요. \
+        블로커가 보이지 않으면 \"없음\"이라고 쓰세요. {}",
+        section("git 커밋", &commits),
+        section("커밋하지 않은 변경", &changes),
+        section("ricci에 한 질문", &prompts),
+        language_instruction(&config.output_preferences.language)
+    );
+
+    let assistant = DevAssistant::new(config.clone())?;
+    let summary = assistant.query(&prompt).await?;
+    println!("\n{}", summary.trim());
+    Ok(())
+}
+
+/// `yesterday`, `today`, `3d`, `12h`, `1w`, `2024-05-01` 형식을 시각으로 바꿉니다
+fn parse_since(since: &str) -> Result<DateTime<Utc>> {
+    let today = Local::now().date_naive();
+    let start_of = |date: NaiveDate| {
+        Local.from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default())
+            .earliest()
+            .map(|t| t.with_timezone(&Utc))
+            .context("시각 변환 실패")
+    };
+
+    match since {
+        "today" => return start_of(today),
+        "yesterday" => return start_of(today - Duration::days(1)),
+        _ => {}
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        return start_of(date);
+    }
+
+    let invalid = || anyhow::anyhow!("--since 형식이 올바르지 않습니다: {} (예: yesterday, today, 3d, 12h, 1w, 2024-05-01)", since);
+    // 단위 앞까지만 숫자로 읽음 (`어제`처럼 여러 바이트 글자가 와도 글자 경계에서 자름)
+    let unit = since.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = since.strip_suffix(unit)
+        .and_then(|amount| amount.parse().ok())
+        .ok_or_else(invalid)?;
+    let duration = match unit {
+        'h' => Duration::hours(amount),
+        'd' => Duration::days(amount),
+        'w' => Duration::weeks(amount),
+        _ => anyhow::bail!("--since 단위는 h, d, w 중 하나여야 합니다: {}", since),
+    };
+    Ok(Utc::now() - duration)
+}
+
+/// 현재 사용자(`git config user.email`)의 커밋. git 저장소가 아니면 빈 목록
+fn git_commits(since: DateTime<Utc>) -> Vec<String> {
+    let email = git_output(&["config", "user.email"]).unwrap_or_default();
+    let since = format!("--since={}", since.to_rfc3339());
+    let mut args = vec!["log", "--all", "--no-merges", since.as_str(), "--date=short", "--pretty=format:%ad %s"];
+    let author = format!("--author={}", email.trim());
+    if !email.trim().is_empty() {
+        args.push(author.as_str());
+    }
+    git_output(&args)
+        .map(|out| out.lines().take(MAX_COMMITS).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn git_uncommitted() -> Vec<String> {
+    git_output(&["status", "--short"])
+        .map(|out| out.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+fn git_output(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 저장된 세션에서 기간 안에 한 질문 (많으면 최근 것만, 시간순)
+fn session_prompts(since: DateTime<Utc>) -> Vec<String> {
+    let Ok(dir) = crate::paths::sessions_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut messages: Vec<(DateTime<Utc>, String)> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .filter_map(|data| serde_json::from_str::<AssistantContext>(&data).ok())
+        .flat_map(|context| context.messages)
+        .filter(|m| m.role == "user" && m.timestamp >= since)
+        .map(|m| {
+            let text: String = m.content.split_whitespace().collect::<Vec<_>>().join(" ");
+            (m.timestamp, text.chars().take(MAX_PROMPT_CHARS).collect())
+        })
+        .collect();
+    messages.sort_by_key(|(timestamp, _)| *timestamp);
+    let skip = messages.len().saturating_sub(MAX_SESSION_PROMPTS);
+    messages.into_iter().skip(skip).map(|(_, text)| text).collect()
+}
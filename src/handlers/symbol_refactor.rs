@@ -0,0 +1,162 @@
+use anyhow::Result;
+use colored::*;
+use std::path::Path;
+use walkdir::WalkDir;
+use crate::renderer::plain;
+use crate::{
+    analyzer::{language_registry, IGNORED_DIRS},
+    assistant::{
+        annotated_files_to_changes, extract_annotated_files, fence_file, format_code, strip_file_blocks,
+        DevAssistant, SafeFileModifier,
+    },
+    config::Config,
+};
+
+/// 한 번의 요청에 함께 보낼 파일 수와 전체 내용 길이 한도
+const MAX_SYMBOL_FILES: usize = 20;
+const MAX_SYMBOL_CHARS: usize = 60_000;
+
+/// 심볼이 나오는 파일 하나와 그 줄들
+pub(crate) struct SymbolSites {
+    pub(crate) path: String,
+    pub(crate) content: String,
+    pub(crate) lines: Vec<(usize, String)>,
+}
+
+/// `path` 아래 소스 파일에서 `symbol`을 찾아, 정의와 사용 위치를 함께 보내 여러 파일에 걸친 변경을 한 번에 받아 적용합니다
+pub async fn handle_symbol_refactor(path: &str, symbol: &str, goal: &str, config: &Config) -> Result<()> {
+    if symbol.trim().is_empty() {
+        anyhow::bail!("심볼 이름이 비어 있습니다");
+    }
+    let sites = find_symbol_sites(path, symbol);
+    if sites.is_empty() {
+        println!("{} `{}`을(를) 찾지 못했습니다: {}", plain("⚠️").yellow(), symbol, path);
+        return Ok(());
+    }
+    let total_chars: usize = sites.iter().map(|s| s.content.len()).sum();
+    if sites.len() > MAX_SYMBOL_FILES || total_chars > MAX_SYMBOL_CHARS {
+        anyhow::bail!(
+            "`{}`이(가) {}개 파일({}자)에 있어 한 번에 보내기에 너무 많습니다. 더 좁은 경로를 지정하세요",
+            symbol, sites.len(), total_chars
+        );
+    }
+
+    let count: usize = sites.iter().map(|s| s.lines.len()).sum();
+    println!("{} `{}` {}곳, {}개 파일", plain("🔎 사용 위치:").cyan(), symbol, count, sites.len());
+    for site in &sites {
+        let numbers: Vec<String> = site.lines.iter().map(|(n, _)| n.to_string()).collect();
+        println!("  {} ({})", site.path, numbers.join(", ").dimmed());
+    }
+
+    let listing: Vec<String> = sites.iter()
+        .flat_map(|site| site.lines.iter().map(move |(n, line)| format!("- {}:{}: {}", site.path, n, line.trim())))
+        .collect();
+    let files: Vec<String> = sites.iter()
+        .map(|site| {
+            let extension = Path::new(&site.path).extension().and_then(|e| e.to_str()).unwrap_or("");
+            fence_file(&site.path, extension, &site.content)
+        })
+        .collect();
+    let prompt = format!(
+        "다음 목표에 맞게 여러 파일을 함께 수정해주세요.\n\n\
+        목표: {}\n\n\
+        대상 심볼 `{}`의 정의와 사용 위치:\n{}\n\n\
+        정의와 모든 사용 위치가 서로 맞도록 바꿔주세요. 먼저 변경 계획을 간단히 설명하고, \
+        바뀌는 파일마다 전체 내용을 ` ```언어:파일경로 ` 형식의 코드 블록 하나로 제공해주세요. \
+        파일경로는 아래에 적힌 경로를 그대로 쓰고, 바뀌지 않는 파일은 생략해주세요.\n\n\
+        파일:\n{}",
+        goal, symbol, listing.join("\n"), files.join("\n\n")
+    );
+
+    println!("\n{}", plain("🔧 여러 파일 변경안 생성 중...").yellow());
+    let assistant = DevAssistant::new(config.clone())?;
+    let response = strip_file_blocks(&assistant.query(&prompt).await?).into_owned();
+    let explanation = response.find("```").map(|start| &response[..start]).unwrap_or(&response).trim();
+    if !explanation.is_empty() {
+        println!("\n{}", explanation);
+    }
+
+    // 검색한 파일과 검색 범위 안의 새 파일만 받아들임
+    let root = Path::new(path);
+    let mut proposed = extract_annotated_files(&response);
+    proposed.retain_mut(|file| {
+        file.path = normalize(&file.path).to_string();
+        let known = sites.iter().any(|site| site.path == file.path);
+        let inside = root.is_dir() && !Path::new(&file.path).exists() && is_inside(root, &file.path);
+        if !known && !inside {
+            println!("  {} 검색 범위 밖의 파일은 건너뜁니다: {}", "건너뜀:".red(), file.path);
+        }
+        known || inside
+    });
+
+    let mut changes = annotated_files_to_changes(proposed, &format!("`{}` 변경: {}", symbol, goal));
+    for change in &mut changes {
+        change.new_content = format_code(&change.new_content, &change.path);
+    }
+    changes.retain(|change| change.new_content != change.original_content);
+    if changes.is_empty() {
+        println!("{}", "모델이 제안한 변경이 없습니다.".yellow());
+        return Ok(());
+    }
+
+    println!("\n{}", plain("💡 제안된 변경사항:").green().bold());
+    for (index, change) in changes.iter().enumerate() {
+        let state = if change.original_content.is_empty() { "새 파일" } else { "수정" };
+        println!("{}. {} ({})", index + 1, change.path, state);
+    }
+    assistant.propose_changes(&changes);
+    SafeFileModifier::new(config.assume_yes).modify_with_backup(changes).await
+}
+
+/// 소스 파일에서 `symbol`이 단어 단위로 나오는 줄을 찾습니다 (경로순)
+pub(crate) fn find_symbol_sites(path: &str, symbol: &str) -> Vec<SymbolSites> {
+    let registry = language_registry();
+    let mut sites: Vec<SymbolSites> = WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|e| !IGNORED_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && registry.is_source_path(e.path()))
+        .filter_map(|e| {
+            let content = std::fs::read_to_string(e.path()).ok()?;
+            let lines: Vec<(usize, String)> = content.lines()
+                .enumerate()
+                .filter(|(_, line)| contains_word(line, symbol))
+                .map(|(index, line)| (index + 1, line.to_string()))
+                .collect();
+            let path = normalize(&e.path().to_string_lossy()).to_string();
+            (!lines.is_empty()).then_some(SymbolSites { path, content, lines })
+        })
+        .collect();
+    sites.sort_by(|a, b| a.path.cmp(&b.path));
+    sites
+}
+
+fn contains_word(line: &str, word: &str) -> bool {
+    !word_positions(line, word).is_empty()
+}
+
+/// 앞뒤가 식별자 문자가 아닌 `word`의 시작 위치들
+pub(crate) fn word_positions(line: &str, word: &str) -> Vec<usize> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    line.match_indices(word)
+        .map(|(start, _)| start)
+        .filter(|&start| {
+            let before = line[..start].chars().next_back();
+            let after = line[start + word.len()..].chars().next();
+            !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+        })
+        .collect()
+}
+
+fn normalize(path: &str) -> &str {
+    path.strip_prefix("./").unwrap_or(path)
+}
+
+/// 상대 경로가 `..` 없이 `root` 아래에 있는지
+fn is_inside(root: &Path, path: &str) -> bool {
+    let path = Path::new(path);
+    let root = Path::new(normalize(&root.to_string_lossy())).to_path_buf();
+    !path.is_absolute()
+        && !path.components().any(|c| matches!(c, std::path::Component::ParentDir))
+        && (root == Path::new(".") || path.starts_with(&root))
+}
@@ -0,0 +1,175 @@
+use anyhow::{Result, Context};
+use colored::*;
+use crate::renderer::plain;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use crate::config::{Config, TeamConfig};
+use crate::paths::shared_dir;
+
+/// 팀 저장소에서 가져오는 공유 항목 디렉토리
+const SHARED_CATEGORIES: &[&str] = &["personas", "templates", "review_rules"];
+const VERSION_FILE: &str = "VERSION";
+
+/// HTTP 소스가 제공하는 번들 형식
+#[derive(Debug, Deserialize)]
+struct SharedBundle {
+    version: String,
+    files: HashMap<String, String>,
+}
+
+pub async fn handle_sync(force: bool, config: &Config) -> Result<()> {
+    let team = config.team.as_ref()
+        .context("팀 설정이 없습니다. config.toml에 [team] source = \"...\" 를 추가하세요")?;
+
+    let shared_dir = shared_dir()?;
+    fs::create_dir_all(&shared_dir).context("공유 디렉토리 생성 실패")?;
+
+    println!("{} {}", "팀 설정 동기화 중:".yellow(), team.source.cyan());
+
+    let local_version = fs::read_to_string(shared_dir.join(VERSION_FILE))
+        .ok()
+        .map(|v| v.trim().to_string());
+
+    let (remote_version, files) = if is_git_source(&team.source) {
+        fetch_git(team, &shared_dir)?
+    } else {
+        fetch_http(&team.source).await?
+    };
+
+    if !force && local_version.as_deref() == Some(remote_version.as_str()) {
+        println!("{} 이미 최신 버전입니다 ({})", plain("✓").green(), remote_version);
+        return Ok(());
+    }
+
+    let mut written = 0;
+    for (relative, content) in &files {
+        let target = shared_dir.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, content)
+            .with_context(|| format!("공유 파일 저장 실패: {}", target.display()))?;
+        written += 1;
+    }
+    let removed = prune_stale(&shared_dir, &files)?;
+    fs::write(shared_dir.join(VERSION_FILE), &remote_version)?;
+
+    println!("{} {} {} {} ({}개 파일, 삭제 {}개)",
+        plain("✓ 동기화 완료:").green(),
+        local_version.as_deref().unwrap_or("없음"),
+        plain("→"),
+        remote_version.bright_white(),
+        written,
+        removed
+    );
+    println!("{}", format!("저장 위치: {}", shared_dir.display()).dimmed());
+
+    Ok(())
+}
+
+/// 팀 저장소에서 사라진 공유 파일을 지웁니다 (지운 규칙이나 페르소나가 계속 적용되지 않도록)
+fn prune_stale(shared_dir: &Path, files: &[(PathBuf, String)]) -> Result<usize> {
+    let keep: HashSet<PathBuf> = files.iter().map(|(relative, _)| shared_dir.join(relative)).collect();
+    let mut removed = 0;
+    for category in SHARED_CATEGORIES {
+        for entry in walkdir::WalkDir::new(shared_dir.join(category)).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_file() && !keep.contains(entry.path()) {
+                fs::remove_file(entry.path())
+                    .with_context(|| format!("오래된 공유 파일 삭제 실패: {}", entry.path().display()))?;
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+fn is_git_source(source: &str) -> bool {
+    source.ends_with(".git") || source.starts_with("git@") || source.starts_with("git+")
+}
+
+fn fetch_git(team: &TeamConfig, shared_dir: &Path) -> Result<(String, Vec<(PathBuf, String)>)> {
+    let repo_dir = shared_dir.join(".repo");
+    let url = team.source.trim_start_matches("git+");
+
+    if repo_dir.join(".git").exists() {
+        run_git(&repo_dir, &["fetch", "--depth", "1", "origin"])?;
+        let target = match &team.branch {
+            Some(branch) => format!("origin/{branch}"),
+            None => "FETCH_HEAD".to_string(),
+        };
+        run_git(&repo_dir, &["reset", "--hard", &target])?;
+    } else {
+        let mut args = vec!["clone", "--depth", "1"];
+        if let Some(branch) = &team.branch {
+            args.extend(["--branch", branch.as_str()]);
+        }
+        let repo_str = repo_dir.to_string_lossy().to_string();
+        args.extend([url, repo_str.as_str()]);
+        run_git(shared_dir, &args)?;
+    }
+
+    let version = match fs::read_to_string(repo_dir.join(VERSION_FILE)) {
+        Ok(v) => v.trim().to_string(),
+        Err(_) => run_git(&repo_dir, &["rev-parse", "--short", "HEAD"])?,
+    };
+
+    let mut files = Vec::new();
+    for category in SHARED_CATEGORIES {
+        let dir = repo_dir.join(category);
+        for entry in walkdir::WalkDir::new(&dir).into_iter().filter_map(|e| e.ok()) {
+            if entry.path().is_file() {
+                let relative = entry.path().strip_prefix(&repo_dir)?.to_path_buf();
+                files.push((relative, fs::read_to_string(entry.path())?));
+            }
+        }
+    }
+
+    Ok((version, files))
+}
+
+async fn fetch_http(url: &str) -> Result<(String, Vec<(PathBuf, String)>)> {
+    let bundle: SharedBundle = reqwest::get(url).await
+        .context("공유 번들 다운로드 실패")?
+        .error_for_status()?
+        .json().await
+        .context("공유 번들 파싱 실패")?;
+
+    let mut files = Vec::new();
+    for (relative, content) in bundle.files {
+        let path = PathBuf::from(&relative);
+        if !is_allowed_shared_path(&path) {
+            println!("{} {}", plain("⏩ 허용되지 않은 경로 건너뛰기:").yellow(), relative);
+            continue;
+        }
+        files.push((path, content));
+    }
+
+    Ok((bundle.version, files))
+}
+
+/// personas/, templates/, review_rules/ 하위의 상대 경로만 허용합니다
+fn is_allowed_shared_path(path: &Path) -> bool {
+    let mut components = path.components();
+    let in_category = matches!(
+        components.next(),
+        Some(Component::Normal(first)) if SHARED_CATEGORIES.iter().any(|c| first == *c)
+    );
+    in_category && components.all(|c| matches!(c, Component::Normal(_)))
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("git 실행 실패 (git이 설치되어 있는지 확인하세요)")?;
+
+    if !output.status.success() {
+        anyhow::bail!("git {} 실패: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,328 @@
+use anyhow::{Context, Result};
+use colored::*;
+use regex::Regex;
+use std::path::Path;
+use std::process::Command;
+use crate::renderer::plain;
+use crate::{
+    assistant::{
+        extract_annotated_files, fence_file, format_code, strip_file_blocks, DevAssistant, FileChange,
+        SafeFileModifier,
+    },
+    config::Config,
+    permissions::ensure_allowed,
+};
+
+/// 한 번의 수정 요청에 보낼 빌드 오류 길이, 파일 수, 파일 내용 길이 한도
+const MAX_BUILD_OUTPUT: usize = 8_000;
+const MAX_UPGRADE_FILES: usize = 8;
+const MAX_UPGRADE_CHARS: usize = 60_000;
+
+/// 업그레이드를 지원하는 매니페스트 종류
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Manifest {
+    Cargo,
+    Npm,
+}
+
+impl Manifest {
+    fn detect(root: &Path) -> Option<Self> {
+        if root.join("Cargo.toml").exists() {
+            Some(Self::Cargo)
+        } else if root.join("package.json").exists() {
+            Some(Self::Npm)
+        } else {
+            None
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo.toml",
+            Self::Npm => "package.json",
+        }
+    }
+
+    /// 레지스트리에서 최신 버전을 찾습니다 (`cargo search`, `npm view`)
+    fn latest_version(self, root: &Path, dependency: &str) -> Option<String> {
+        let output = match self {
+            Self::Cargo => Command::new("cargo").args(["search", dependency, "--limit", "1"]).current_dir(root).output(),
+            Self::Npm => Command::new("npm").args(["view", dependency, "version"]).current_dir(root).output(),
+        }.ok().filter(|o| o.status.success())?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        match self {
+            Self::Cargo => stdout.lines()
+                .find_map(|line| line.strip_prefix(&format!("{} = \"", dependency)))
+                .and_then(|rest| rest.split('"').next())
+                .map(str::to_string),
+            Self::Npm => Some(stdout.trim().to_string()).filter(|v| !v.is_empty()),
+        }
+    }
+
+    /// 빌드 명령어. npm은 새 버전을 받은 뒤 `build` 스크립트나 `tsc`로 확인
+    fn build_commands(self, root: &Path) -> Vec<Vec<&'static str>> {
+        match self {
+            Self::Cargo => vec![vec!["cargo", "build", "--message-format=short"]],
+            Self::Npm => {
+                let has_build = std::fs::read_to_string(root.join("package.json")).ok()
+                    .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+                    .is_some_and(|json| json["scripts"]["build"].is_string());
+                let check = if has_build {
+                    vec!["npm", "run", "build"]
+                } else {
+                    vec!["npx", "tsc", "--noEmit"]
+                };
+                vec![vec!["npm", "install"], check]
+            }
+        }
+    }
+}
+
+/// 의존성 버전을 올리고 빌드해서, 깨진 호출 지점을 모델이 고치게 하는 과정을 빌드가 성공할 때까지 반복합니다
+pub async fn handle_upgrade(
+    dependency: &str,
+    to: Option<&str>,
+    path: &str,
+    max_rounds: usize,
+    config: &Config,
+) -> Result<()> {
+    ensure_allowed("의존성 업그레이드")?;
+    let root = Path::new(path);
+    let manifest = Manifest::detect(root)
+        .with_context(|| format!("지원하는 매니페스트(Cargo.toml, package.json)가 없습니다: {}", path))?;
+    let manifest_path = root.join(manifest.file_name());
+    let original = std::fs::read_to_string(&manifest_path)?;
+
+    let target = match to {
+        Some(version) => version.to_string(),
+        None => {
+            println!("{} {}", "최신 버전 확인 중:".dimmed(), dependency);
+            manifest.latest_version(root, dependency)
+                .with_context(|| format!("{}의 최신 버전을 찾지 못했습니다. --to로 버전을 지정하세요", dependency))?
+        }
+    };
+    let (current, bumped) = match manifest {
+        Manifest::Cargo => bump_cargo(&original, dependency, &target),
+        Manifest::Npm => bump_package_json(&original, dependency, &target),
+    }
+    .with_context(|| format!("{}에서 {}을(를) 찾지 못했습니다", manifest.file_name(), dependency))?;
+    if bumped == original {
+        println!("{} {}은(는) 이미 {}입니다", plain("ℹ️").dimmed(), dependency, current);
+        return Ok(());
+    }
+
+    println!("{} {} {} → {}", plain("⬆️ 업그레이드:").cyan().bold(), dependency, current, target);
+    let change = FileChange {
+        path: manifest_path.to_string_lossy().trim_start_matches("./").to_string(),
+        original_content: original,
+        new_content: bumped,
+        description: format!("{} {} → {}", dependency, current, target),
+    };
+    let modifier = SafeFileModifier::new(config.assume_yes);
+    if !modifier.apply_all_or_nothing(&[change])? {
+        return Ok(());
+    }
+
+    let assistant = DevAssistant::new(config.clone())?;
+    let mut previous_errors = String::new();
+    for round in 0..=max_rounds {
+        println!("\n{}", plain(&format!("🔨 빌드 중... ({}/{})", round + 1, max_rounds + 1)).yellow());
+        let Some(errors) = run_build(manifest, root)? else {
+            println!("{}", plain(&format!("✓ 빌드 성공: {} {} ({}회 수정)", dependency, target, round)).green().bold());
+            return Ok(());
+        };
+        println!("{}", errors.dimmed());
+        if round == max_rounds {
+            break;
+        }
+        if errors == previous_errors {
+            println!("{}", "같은 빌드 오류가 반복되어 중단합니다.".yellow());
+            break;
+        }
+
+        let files = error_files(&errors, root);
+        if files.is_empty() {
+            println!("{}", "오류가 난 프로젝트 파일을 찾지 못해 중단합니다.".yellow());
+            break;
+        }
+        println!("{} {}", "수정안 생성 중:".cyan(), files.join(", "));
+        let changes = request_patch(&assistant, dependency, &current, &target, &errors, &files).await?;
+        if changes.is_empty() {
+            println!("{}", "모델이 제안한 변경이 없어 중단합니다.".yellow());
+            break;
+        }
+        if !modifier.apply_all_or_nothing(&changes)? {
+            return Ok(());
+        }
+        previous_errors = errors;
+    }
+
+    println!(
+        "\n{} 빌드가 아직 실패합니다. 남은 오류를 직접 고치거나 데이터 디렉토리 backups의 백업으로 되돌리세요",
+        plain("⚠️").yellow()
+    );
+    Ok(())
+}
+
+/// 빌드를 실행해 실패하면 오류 줄을, 성공하면 None을 돌려줍니다
+fn run_build(manifest: Manifest, root: &Path) -> Result<Option<String>> {
+    for command in manifest.build_commands(root) {
+        let output = Command::new(command[0])
+            .args(&command[1..])
+            .current_dir(root)
+            .output()
+            .with_context(|| format!("{} 실행 실패", command.join(" ")))?;
+        if output.status.success() {
+            continue;
+        }
+        let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+        let error_lines: Vec<&str> = text.lines().filter(|line| line.to_lowercase().contains("error")).collect();
+        // 오류 표시 줄이 없으면 출력의 끝부분을 사용
+        let lines = if error_lines.is_empty() {
+            text.lines().rev().take(60).collect::<Vec<_>>().into_iter().rev().collect()
+        } else {
+            error_lines
+        };
+        let mut errors = lines.join("\n");
+        if errors.len() > MAX_BUILD_OUTPUT {
+            let cut = (0..=MAX_BUILD_OUTPUT).rev().find(|&i| errors.is_char_boundary(i)).unwrap_or(0);
+            errors.truncate(cut);
+            errors.push_str("\n... (생략)");
+        }
+        return Ok(Some(errors));
+    }
+    Ok(None)
+}
+
+/// 빌드 오류에 나온 프로젝트 안의 소스 파일 경로 (레지스트리, node_modules 등 밖의 경로는 제외)
+fn error_files(errors: &str, root: &Path) -> Vec<String> {
+    let pattern = Regex::new(r"([A-Za-z0-9_./\\-]+\.(?:rs|ts|tsx|js|jsx|mjs|cjs))[:(]\d+").expect("valid regex");
+    let mut files: Vec<String> = Vec::new();
+    let mut total = 0;
+    for captures in pattern.captures_iter(errors) {
+        let relative = Path::new(&captures[1]);
+        if relative.is_absolute()
+            || relative.components().any(|c| matches!(c, std::path::Component::ParentDir))
+            || captures[1].contains("node_modules/")
+        {
+            continue;
+        }
+        // 빌드 오류의 경로는 프로젝트 루트 기준
+        let joined = root.join(relative);
+        let file = joined.to_string_lossy().trim_start_matches("./").to_string();
+        if files.contains(&file) {
+            continue;
+        }
+        let Ok(size) = std::fs::metadata(&file).map(|m| m.len() as usize) else {
+            continue;
+        };
+        if files.len() == MAX_UPGRADE_FILES || total + size > MAX_UPGRADE_CHARS {
+            break;
+        }
+        total += size;
+        files.push(file);
+    }
+    files
+}
+
+/// 빌드 오류와 관련 파일을 보내 새 버전 API에 맞춘 파일을 받습니다 (보낸 파일만 받아들임)
+async fn request_patch(
+    assistant: &DevAssistant,
+    dependency: &str,
+    current: &str,
+    target: &str,
+    errors: &str,
+    files: &[String],
+) -> Result<Vec<FileChange>> {
+    let mut contents = Vec::new();
+    for file in files {
+        let content = std::fs::read_to_string(file)?;
+        let extension = Path::new(file).extension().and_then(|e| e.to_str()).unwrap_or("");
+        contents.push((file.clone(), fence_file(file, extension, &content), content));
+    }
+    let prompt = format!(
+        "{}을(를) {}에서 {}(으)로 올린 뒤 빌드가 실패했습니다. 새 버전의 바뀐 API에 맞게 호출 지점을 고쳐주세요.\n\n\
+        빌드 오류:\n```\n{}\n```\n\n\
+        먼저 무엇이 바뀌었는지 짧게 설명하고, 고친 파일마다 전체 내용을 ` ```언어:파일경로 ` 형식의 코드 블록 하나로 \
+        제공해주세요. 파일경로는 아래 경로를 그대로 쓰고, 바뀌지 않는 파일은 생략해주세요.\n\n파일:\n{}",
+        dependency, current, target, errors,
+        contents.iter().map(|(_, fenced, _)| fenced.as_str()).collect::<Vec<_>>().join("\n\n")
+    );
+    let response = strip_file_blocks(&assistant.query(&prompt).await?).into_owned();
+    let explanation = response.find("```").map(|start| &response[..start]).unwrap_or(&response).trim();
+    if !explanation.is_empty() {
+        println!("\n{}", explanation);
+    }
+
+    let mut changes = Vec::new();
+    for file in extract_annotated_files(&response) {
+        let path = file.path.trim_start_matches("./");
+        let Some((_, _, original)) = contents.iter().find(|(sent, _, _)| sent == path) else {
+            println!("  {} 보내지 않은 파일은 건너뜁니다: {}", "건너뜀:".red(), file.path);
+            continue;
+        };
+        let new_content = format_code(&file.content, path);
+        if new_content != *original {
+            changes.push(FileChange {
+                path: path.to_string(),
+                original_content: original.clone(),
+                new_content,
+                description: format!("{} {} 대응", dependency, target),
+            });
+        }
+    }
+    Ok(changes)
+}
+
+/// 기존 버전 앞의 비교 연산자(`^`, `~`, `=` 등)를 유지한 새 버전 문자열
+fn with_operator(old: &str, version: &str) -> String {
+    let operator: String = old.chars().take_while(|c| "^~=<> ".contains(*c)).collect();
+    format!("{}{}", operator.trim(), version)
+}
+
+/// Cargo.toml의 `[*dependencies]` 섹션에서 의존성 버전을 바꿉니다 (`이름 = "1.0"`, `이름 = { version = "1.0", .. }`,
+/// `[dependencies.이름]` 형식). 서식은 그대로 두고 (이전 버전, 새 내용)을 돌려줍니다
+fn bump_cargo(content: &str, dependency: &str, version: &str) -> Option<(String, String)> {
+    let inline = Regex::new(&format!(r#"^(\s*"?{}"?\s*=\s*")([^"]*)(")"#, regex::escape(dependency))).ok()?;
+    let table = Regex::new(&format!(r#"^(\s*"?{}"?\s*=\s*\{{.*?\bversion\s*=\s*")([^"]*)(")"#, regex::escape(dependency))).ok()?;
+    let version_key = Regex::new(r#"^(\s*version\s*=\s*")([^"]*)(")"#).ok()?;
+
+    let mut section = String::new();
+    let mut current = None;
+    let mut lines = Vec::new();
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            section = trimmed.trim_matches(|c| c == '[' || c == ']').to_string();
+        }
+        let in_dependencies = section.ends_with("dependencies");
+        let in_own_table = section.ends_with(&format!("dependencies.{}", dependency));
+        let pattern = if in_dependencies {
+            [&inline, &table].into_iter().find(|p| p.is_match(line))
+        } else if in_own_table {
+            Some(&version_key).filter(|p| p.is_match(line))
+        } else {
+            None
+        };
+        match pattern.and_then(|p| p.captures(line)) {
+            Some(captures) => {
+                let old = captures[2].to_string();
+                let replaced = format!("{}{}{}", &captures[1], with_operator(&old, version), &captures[3]);
+                lines.push(line.replacen(&captures[0], &replaced, 1));
+                current.get_or_insert(old);
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+    current.map(|current| (current, lines.concat()))
+}
+
+/// package.json의 `"이름": "버전"` 항목을 서식을 유지한 채 바꿉니다
+fn bump_package_json(content: &str, dependency: &str, version: &str) -> Option<(String, String)> {
+    let pattern = Regex::new(&format!(r#"("{}"\s*:\s*")([^"]*)(")"#, regex::escape(dependency))).ok()?;
+    let current = pattern.captures(content)?[2].to_string();
+    let bumped = pattern.replace_all(content, |captures: &regex::Captures| {
+        format!("{}{}{}", &captures[1], with_operator(&captures[2], version), &captures[3])
+    });
+    Some((current, bumped.into_owned()))
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use colored::*;
+use crate::renderer::plain;
+use crate::{
+    api::{UsageLedger, usage_path},
+    config::Config,
+};
+
+#[derive(clap::Subcommand)]
+pub enum UsageAction {
+    /// 누적 사용량 기록 초기화
+    Reset,
+}
+
+pub fn handle_usage(action: Option<UsageAction>, config: &Config) -> Result<()> {
+    match action {
+        Some(UsageAction::Reset) => {
+            let path = usage_path()?;
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+            println!("{} 사용량 기록을 초기화했습니다.", plain("✓").green());
+        }
+        None => print_usage(&UsageLedger::load(), config),
+    }
+    Ok(())
+}
+
+fn print_usage(ledger: &UsageLedger, config: &Config) {
+    let budget = &config.budget;
+    println!("{}", "사용량 (예상 비용)".bright_cyan().bold());
+    println!("{}", "=".repeat(50).dimmed());
+    println!(
+        "오늘 ({}): ${:.4} / {} · 요청 {}회",
+        ledger.day, ledger.daily_cost, limit_label(budget.daily_usd), ledger.daily_requests
+    );
+    println!(
+        "이번 달 ({}): ${:.4} / {} · 요청 {}회",
+        ledger.month, ledger.monthly_cost, limit_label(budget.monthly_usd), ledger.monthly_requests
+    );
+    println!(
+        "이번 달 토큰: 입력 {} · 출력 {}",
+        ledger.monthly_prompt_tokens, ledger.monthly_completion_tokens
+    );
+    println!("{}", "가격 정보가 없는 모델의 요청은 비용에 포함되지 않습니다.".dimmed());
+}
+
+fn limit_label(limit: Option<f64>) -> String {
+    limit.map(|l| format!("${:.2}", l)).unwrap_or_else(|| "한도 없음".to_string())
+}
@@ -0,0 +1,111 @@
+use chrono::Utc;
+use colored::*;
+use serde_json::{json, Value};
+use std::io::Write;
+use std::process::Stdio;
+use std::sync::OnceLock;
+use crate::config::{HooksConfig, ShellConfig};
+use crate::handlers::shell::shell_command;
+use crate::permissions::is_read_only;
+
+/// 훅에 이벤트 이름을 넘기는 환경 변수
+pub const EVENT_ENV: &str = "RICCI_EVENT";
+
+static HOOKS: OnceLock<Hooks> = OnceLock::new();
+
+struct Hooks {
+    config: HooksConfig,
+    shell: ShellConfig,
+    /// 실행 중인 서브커맨드 이름 (이벤트 JSON의 `command`)
+    command: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum HookEvent {
+    Response,
+    Apply,
+    Error,
+}
+
+impl HookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            HookEvent::Response => "response",
+            HookEvent::Apply => "apply",
+            HookEvent::Error => "error",
+        }
+    }
+}
+
+/// 설정의 훅으로 전역 상태를 초기화합니다. 처음 호출만 적용됩니다
+pub fn init_hooks(config: &HooksConfig, shell: &ShellConfig, command: &str) {
+    let _ = HOOKS.set(Hooks {
+        config: config.clone(),
+        shell: shell.clone(),
+        command: command.to_string(),
+    });
+}
+
+/// 이벤트에 등록된 셸 명령어를 차례로 실행합니다. 이벤트 JSON은 표준 입력으로, 이벤트 이름은
+/// `RICCI_EVENT` 환경 변수로 전달합니다. 훅이 실패해도 경고만 출력하고 작업은 계속합니다
+pub fn run_hooks(event: HookEvent, data: Value) {
+    let Some(hooks) = HOOKS.get() else { return };
+    let commands = match event {
+        HookEvent::Response => &hooks.config.on_response,
+        HookEvent::Apply => &hooks.config.on_apply,
+        HookEvent::Error => &hooks.config.on_error,
+    };
+    if commands.is_empty() {
+        return;
+    }
+    if is_read_only() {
+        eprintln!("{}", format!("읽기 전용 모드라 on_{} 훅 {}개를 실행하지 않았습니다", event.name(), commands.len()).yellow());
+        return;
+    }
+
+    let mut payload = json!({
+        "event": event.name(),
+        "command": hooks.command,
+        "timestamp": Utc::now().to_rfc3339(),
+        "cwd": std::env::current_dir().map(|d| d.display().to_string()).unwrap_or_default(),
+    });
+    if let (Some(payload), Value::Object(data)) = (payload.as_object_mut(), data) {
+        payload.extend(data);
+    }
+    let input = payload.to_string();
+
+    let run_all = || {
+        for command in commands {
+            if let Err(e) = run_hook(command, event, &input, &hooks.shell) {
+                eprintln!("{} {} ({})", format!("on_{} 훅 실패:", event.name()).yellow(), command, e);
+            }
+        }
+    };
+    // 훅은 비동기 코드 안에서 불리므로 기다리는 동안 런타임이 이 작업자 스레드의 다른 작업(스트리밍,
+    // 스피너)을 옮겨 실행하게 함. 단일 스레드 런타임에서는 옮길 곳이 없어 그대로 기다림
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(run_all)
+        }
+        _ => run_all(),
+    }
+}
+
+fn run_hook(command: &str, event: HookEvent, input: &str, shell: &ShellConfig) -> anyhow::Result<()> {
+    // 셸 명령어 모드와 같은 셸을 쓰되, 훅은 끝날 때까지 기다림
+    let mut child = shell_command(shell, command)
+        .into_std()
+        .env(EVENT_ENV, event.name())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // 입력을 읽지 않는 훅이면 파이프가 닫혀도 무시
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        anyhow::bail!("종료 코드 {}", status.code().unwrap_or(-1));
+    }
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+use crate::config::ConfluenceConfig;
+
+/// 설정한 스페이스에 같은 제목의 페이지가 있으면 새 버전으로 갱신하고, 없으면 상위 페이지 아래에
+/// 새로 만듭니다. 게시한 페이지 주소를 돌려줍니다
+pub async fn publish(config: &ConfluenceConfig, title: &str, markdown: &str) -> Result<String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+    let api = format!("{}/rest/api/content", config.base_url.trim_end_matches('/'));
+
+    let existing: Value = checked(
+        client.get(&api)
+            .basic_auth(&config.user, Some(&config.api_token))
+            .query(&[("spaceKey", config.space.as_str()), ("title", title), ("expand", "version")])
+            .send().await
+            .context("Confluence 연결 실패")?,
+    ).await?;
+
+    let mut page = json!({
+        "type": "page",
+        "title": title,
+        "space": { "key": config.space },
+        "body": { "storage": { "value": to_storage(markdown), "representation": "storage" } },
+    });
+    let request = match existing["results"].get(0) {
+        Some(current) => {
+            let id = current["id"].as_str().context("Confluence 응답에 페이지 id가 없습니다")?;
+            let version = current["version"]["number"].as_u64().unwrap_or(1);
+            page["id"] = json!(id);
+            page["version"] = json!({ "number": version + 1 });
+            client.put(format!("{}/{}", api, id))
+        }
+        None => {
+            if let Some(parent) = &config.parent_id {
+                page["ancestors"] = json!([{ "id": parent }]);
+            }
+            client.post(&api)
+        }
+    };
+    let published = checked(
+        request.basic_auth(&config.user, Some(&config.api_token))
+            .json(&page)
+            .send().await
+            .context("Confluence 연결 실패")?,
+    ).await?;
+
+    let links = &published["_links"];
+    Ok(match (links["base"].as_str(), links["webui"].as_str()) {
+        (Some(base), Some(webui)) => format!("{}{}", base, webui),
+        _ => format!("{}/spaces/{}", config.base_url.trim_end_matches('/'), config.space),
+    })
+}
+
+async fn checked(response: reqwest::Response) -> Result<Value> {
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<Value>(&body).ok()
+            .and_then(|v| v["message"].as_str().map(str::to_string))
+            .unwrap_or(body);
+        anyhow::bail!("Confluence가 요청을 거부했습니다 ({}): {}", status, message.trim());
+    }
+    Ok(response.json().await?)
+}
+
+/// 마크다운을 Confluence 저장 형식(XHTML)으로 바꿉니다. 제목, 문단, 중첩 목록과 체크박스,
+/// 표, 인용, 구분선과 코드 블록(code 매크로)을 지원합니다
+fn to_storage(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    // 열린 목록의 (들여쓰기, 태그) 스택
+    let mut lists: Vec<(usize, &str)> = Vec::new();
+    let mut table: Vec<Vec<String>> = Vec::new();
+    let mut code: Option<(String, Vec<&str>)> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+
+        if let Some(fence) = trimmed.strip_prefix("```") {
+            match code.take() {
+                Some((language, lines)) => html.push_str(&code_macro(&language, &lines.join("\n"))),
+                None => {
+                    close_blocks(&mut html, &mut paragraph, &mut lists, &mut table);
+                    code = Some((fence.trim().to_string(), Vec::new()));
+                }
+            }
+            continue;
+        }
+        if let Some((_, lines)) = code.as_mut() {
+            lines.push(line);
+            continue;
+        }
+
+        if trimmed.starts_with('|') {
+            if !table.is_empty() || paragraph.is_empty() {
+                close_list(&mut html, &mut lists);
+                table.push(trimmed.trim_matches('|').split('|').map(|cell| cell.trim().to_string()).collect());
+                continue;
+            }
+        } else if !table.is_empty() {
+            close_table(&mut html, &mut table);
+        }
+
+        let indent = line.len() - trimmed.len();
+        if let Some((tag, item)) = list_item(trimmed) {
+            flush_paragraph(&mut html, &mut paragraph);
+            while lists.last().is_some_and(|&(level, _)| level > indent) {
+                let (_, tag) = lists.pop().unwrap_or_default();
+                html.push_str(&format!("</li></{}>", tag));
+            }
+            match lists.last() {
+                Some(&(level, open)) if level == indent && open == tag => html.push_str("</li>"),
+                Some(&(level, open)) if level == indent => {
+                    lists.pop();
+                    html.push_str(&format!("</li></{}><{}>", open, tag));
+                    lists.push((indent, tag));
+                }
+                _ => {
+                    html.push_str(&format!("<{}>", tag));
+                    lists.push((indent, tag));
+                }
+            }
+            html.push_str(&format!("<li>{}", checkbox_item(item)));
+            continue;
+        }
+
+        let heading = trimmed.trim_start_matches('#');
+        let level = trimmed.len() - heading.len();
+        if (1..=6).contains(&level) && heading.starts_with(' ') {
+            close_blocks(&mut html, &mut paragraph, &mut lists, &mut table);
+            html.push_str(&format!("<h{0}>{1}</h{0}>", level, inline(heading.trim())));
+        } else if trimmed.is_empty() {
+            flush_paragraph(&mut html, &mut paragraph);
+        } else if matches!(trimmed, "---" | "***" | "___") {
+            close_blocks(&mut html, &mut paragraph, &mut lists, &mut table);
+            html.push_str("<hr />");
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            close_blocks(&mut html, &mut paragraph, &mut lists, &mut table);
+            html.push_str(&format!("<blockquote><p>{}</p></blockquote>", inline(quote.trim())));
+        } else if !lists.is_empty() && indent > 0 {
+            // 목록 항목의 이어지는 줄
+            html.push_str(&format!("<br />{}", inline(trimmed)));
+        } else {
+            close_list(&mut html, &mut lists);
+            paragraph.push(trimmed);
+        }
+    }
+
+    if let Some((language, lines)) = code {
+        html.push_str(&code_macro(&language, &lines.join("\n")));
+    }
+    close_blocks(&mut html, &mut paragraph, &mut lists, &mut table);
+    html
+}
+
+fn list_item(line: &str) -> Option<(&'static str, &str)> {
+    if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")).or_else(|| line.strip_prefix("+ ")) {
+        return Some(("ul", item));
+    }
+    let digits = line.find(|c: char| !c.is_ascii_digit())?;
+    let item = line[digits..].strip_prefix(". ").filter(|_| digits > 0)?;
+    Some(("ol", item))
+}
+
+fn checkbox_item(item: &str) -> String {
+    match item.get(..4) {
+        Some("[ ] ") => format!("☐ {}", inline(&item[4..])),
+        Some("[x] ") | Some("[X] ") => format!("☑ {}", inline(&item[4..])),
+        _ => inline(item),
+    }
+}
+
+fn close_blocks(html: &mut String, paragraph: &mut Vec<&str>, lists: &mut Vec<(usize, &str)>, table: &mut Vec<Vec<String>>) {
+    flush_paragraph(html, paragraph);
+    close_list(html, lists);
+    close_table(html, table);
+}
+
+fn flush_paragraph(html: &mut String, paragraph: &mut Vec<&str>) {
+    if !paragraph.is_empty() {
+        html.push_str(&format!("<p>{}</p>", inline(&paragraph.join(" "))));
+        paragraph.clear();
+    }
+}
+
+fn close_list(html: &mut String, lists: &mut Vec<(usize, &str)>) {
+    while let Some((_, tag)) = lists.pop() {
+        html.push_str(&format!("</li></{}>", tag));
+    }
+}
+
+/// 첫 행은 머리글, `---` 구분 행은 건너뜀
+fn close_table(html: &mut String, table: &mut Vec<Vec<String>>) {
+    if table.is_empty() {
+        return;
+    }
+    html.push_str("<table><tbody>");
+    for (index, row) in table.iter().enumerate() {
+        if row.iter().all(|cell| !cell.is_empty() && cell.chars().all(|c| matches!(c, '-' | ':' | ' '))) {
+            continue;
+        }
+        let tag = if index == 0 { "th" } else { "td" };
+        html.push_str("<tr>");
+        for cell in row {
+            html.push_str(&format!("<{0}>{1}</{0}>", tag, inline(cell)));
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</tbody></table>");
+    table.clear();
+}
+
+fn code_macro(language: &str, code: &str) -> String {
+    let language = if language.is_empty() {
+        String::new()
+    } else {
+        format!("<ac:parameter ac:name=\"language\">{}</ac:parameter>", escape(language))
+    };
+    // CDATA 안에서는 `]]>`만 끊어서 이어 붙임
+    format!(
+        "<ac:structured-macro ac:name=\"code\">{}<ac:plain-text-body><![CDATA[{}]]></ac:plain-text-body></ac:structured-macro>",
+        language,
+        code.replace("]]>", "]]]]><![CDATA[>"),
+    )
+}
+
+/// 인라인 코드, 굵게, 기울임, 링크를 태그로 바꿉니다 (코드 안은 그대로)
+fn inline(text: &str) -> String {
+    static BOLD: OnceLock<Regex> = OnceLock::new();
+    static ITALIC: OnceLock<Regex> = OnceLock::new();
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let bold = BOLD.get_or_init(|| Regex::new(r"\*\*(.+?)\*\*").expect("정규식 오류"));
+    let italic = ITALIC.get_or_init(|| Regex::new(r"(^|[^\w*])[*_]([^*_\s][^*_]*?)[*_]($|[^\w*])").expect("정규식 오류"));
+    let link = LINK.get_or_init(|| Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").expect("정규식 오류"));
+
+    text.split('`')
+        .enumerate()
+        .map(|(index, part)| {
+            let part = escape(part);
+            if index % 2 == 1 {
+                return format!("<code>{}</code>", part);
+            }
+            let part = link.replace_all(&part, "<a href=\"$2\">$1</a>");
+            let part = bold.replace_all(&part, "<strong>$1</strong>");
+            italic.replace_all(&part, "$1<em>$2</em>$3").into_owned()
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,85 @@
+mod confluence;
+mod notion;
+mod slack;
+mod teams;
+
+use anyhow::{Context, Result};
+use colored::*;
+use crate::config::{Config, ConfluenceConfig, NotionConfig, SlackConfig, TeamsConfig};
+use crate::planner::ProjectPlan;
+use crate::renderer::plain;
+
+/// `--share`로 결과를 보낼 수 있는 대상
+pub const SHARE_TARGETS: &[&str] = &["slack", "teams", "confluence", "notion"];
+
+/// 공유 대상 설정이 있는지 확인합니다. 모델을 호출하기 전에 불러 설정 누락을 먼저 알립니다
+pub fn check_share_target(target: &str, config: &Config) -> Result<()> {
+    match target {
+        "slack" => slack_config(config).map(|_| ()),
+        "teams" => teams_config(config).map(|_| ()),
+        "confluence" => confluence_config(config).map(|_| ()),
+        "notion" => notion_config(config).map(|_| ()),
+        _ => anyhow::bail!("지원하지 않는 공유 대상: {} ({})", target, SHARE_TARGETS.join(", ")),
+    }
+}
+
+/// 렌더링한 마크다운 결과를 팀 채널로 보내거나 위키 페이지로 게시합니다
+pub async fn share(target: &str, title: &str, markdown: &str, config: &Config) -> Result<()> {
+    match target {
+        "slack" => slack::post(slack_config(config)?, title, markdown).await?,
+        "teams" => teams::post(teams_config(config)?, title, markdown).await?,
+        "confluence" => {
+            let url = confluence::publish(confluence_config(config)?, title, markdown).await?;
+            println!("{} Confluence에 게시했습니다: {}", plain("✓").green(), url.cyan());
+            return Ok(());
+        }
+        "notion" => {
+            let url = notion::publish(notion_config(config)?, title, markdown).await?;
+            println!("{} Notion 페이지를 만들었습니다: {}", plain("✓").green(), url.cyan());
+            return Ok(());
+        }
+        _ => anyhow::bail!("지원하지 않는 공유 대상: {} ({})", target, SHARE_TARGETS.join(", ")),
+    }
+    println!("{} {}에 공유했습니다: {}", plain("✓").green(), target, title);
+    Ok(())
+}
+
+/// 계획을 공유합니다. Notion에는 작업을 행으로 담은 데이터베이스로, 그 외 대상에는 마크다운으로 보냄
+pub async fn share_plan(target: &str, plan: &ProjectPlan, config: &Config) -> Result<()> {
+    let title = format!("작업계획서: {}", plan.title);
+    if target != "notion" {
+        return share(target, &title, &plan.to_markdown(), config).await;
+    }
+    let url = notion::publish_plan(notion_config(config)?, &title, plan).await?;
+    println!("{} Notion에 계획과 작업 {}개를 내보냈습니다: {}", plain("✓").green(), plan.tasks().count(), url.cyan());
+    Ok(())
+}
+
+fn slack_config(config: &Config) -> Result<&SlackConfig> {
+    config.integrations.slack.as_ref()
+        .context("Slack 공유 설정이 없습니다. 설정 파일에 [integrations.slack] webhook_url을 추가하세요")
+}
+
+fn teams_config(config: &Config) -> Result<&TeamsConfig> {
+    config.integrations.teams.as_ref()
+        .context("Teams 공유 설정이 없습니다. 설정 파일에 [integrations.teams] webhook_url을 추가하세요")
+}
+
+fn confluence_config(config: &Config) -> Result<&ConfluenceConfig> {
+    config.integrations.confluence.as_ref()
+        .context("Confluence 게시 설정이 없습니다. 설정 파일에 [integrations.confluence] base_url, space, user, api_token을 추가하세요")
+}
+
+fn notion_config(config: &Config) -> Result<&NotionConfig> {
+    config.integrations.notion.as_ref()
+        .context("Notion 내보내기 설정이 없습니다. 설정 파일에 [integrations.notion] token, parent_page_id를 추가하세요")
+}
+
+fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(limit - 1).collect();
+    cut.push('…');
+    cut
+}
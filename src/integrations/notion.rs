@@ -0,0 +1,343 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use reqwest::Method;
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+use std::time::Duration;
+use crate::config::NotionConfig;
+use crate::planner::{Priority, ProjectPlan};
+
+const NOTION_VERSION: &str = "2022-06-28";
+/// 요청 하나에 넣을 수 있는 블록 수
+const MAX_CHILDREN: usize = 100;
+/// rich text 조각 하나의 글자 수 한도
+const TEXT_LIMIT: usize = 2000;
+/// Notion 코드 블록이 받는 언어 (그 외는 plain text)
+const CODE_LANGUAGES: &[&str] = &[
+    "bash", "c", "c#", "c++", "css", "dart", "diff", "docker", "go", "graphql", "html", "java",
+    "javascript", "json", "kotlin", "markdown", "php", "python", "ruby", "rust", "scala", "shell",
+    "sql", "swift", "typescript", "yaml",
+];
+
+struct Client<'a> {
+    http: reqwest::Client,
+    config: &'a NotionConfig,
+}
+
+impl<'a> Client<'a> {
+    fn new(config: &'a NotionConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+        Ok(Self { http, config })
+    }
+
+    /// API를 호출합니다. 요청 한도(429)에 걸리면 Retry-After만큼 기다렸다가 다시 보냄
+    async fn call(&self, method: Method, path: &str, body: &Value) -> Result<Value> {
+        let url = format!("{}/v1/{}", self.config.api_url.trim_end_matches('/'), path);
+        for _ in 0..3 {
+            let response = self.http.request(method.clone(), &url)
+                .bearer_auth(&self.config.token)
+                .header("Notion-Version", NOTION_VERSION)
+                .json(body)
+                .send().await
+                .context("Notion 연결 실패")?;
+            let status = response.status();
+            if status.as_u16() == 429 {
+                let wait = response.headers().get("retry-after")
+                    .and_then(|v| v.to_str().ok()?.parse().ok())
+                    .unwrap_or(1);
+                tokio::time::sleep(Duration::from_secs(wait)).await;
+                continue;
+            }
+            let value: Value = response.json().await.unwrap_or_default();
+            if !status.is_success() {
+                let message = value["message"].as_str().unwrap_or_default();
+                anyhow::bail!("Notion이 요청을 거부했습니다 ({}): {}", status, message);
+            }
+            return Ok(value);
+        }
+        anyhow::bail!("Notion 요청 한도를 초과했습니다. 잠시 후 다시 시도하세요")
+    }
+
+    /// 설정한 상위 페이지 아래에 페이지를 만들고 블록을 채웁니다. 페이지 id와 주소를 돌려줍니다
+    async fn create_page(&self, title: &str, blocks: Vec<Value>) -> Result<(String, String)> {
+        let mut chunks = blocks.chunks(MAX_CHILDREN);
+        let page = self.call(Method::POST, "pages", &json!({
+            "parent": { "page_id": self.config.parent_page_id },
+            "properties": { "title": { "title": rich_text(title) } },
+            "children": chunks.next().unwrap_or_default(),
+        })).await?;
+        let id = page["id"].as_str().context("Notion 응답에 페이지 id가 없습니다")?.to_string();
+        for chunk in chunks {
+            self.call(Method::PATCH, &format!("blocks/{}/children", id), &json!({ "children": chunk })).await?;
+        }
+        let url = page["url"].as_str().unwrap_or_default().to_string();
+        Ok((id, url))
+    }
+}
+
+/// 마크다운 결과를 Notion 페이지로 만들고 주소를 돌려줍니다
+pub async fn publish(config: &NotionConfig, title: &str, markdown: &str) -> Result<String> {
+    let client = Client::new(config)?;
+    let (_, url) = client.create_page(title, to_blocks(markdown)).await?;
+    Ok(url)
+}
+
+/// 계획을 개요 페이지와 그 안의 작업 데이터베이스(작업마다 한 행, 단계/우선순위/예상 시간 속성)로 만듭니다
+pub async fn publish_plan(config: &NotionConfig, title: &str, plan: &ProjectPlan) -> Result<String> {
+    let client = Client::new(config)?;
+    let (page_id, url) = client.create_page(title, plan_blocks(plan)).await?;
+
+    let database = client.call(Method::POST, "databases", &json!({
+        "parent": { "type": "page_id", "page_id": page_id },
+        "title": rich_text("작업"),
+        "is_inline": true,
+        "properties": {
+            "이름": { "title": {} },
+            "ID": { "rich_text": {} },
+            "단계": { "select": {} },
+            "우선순위": { "select": { "options": [
+                { "name": Priority::Critical.label(), "color": "red" },
+                { "name": Priority::High.label(), "color": "orange" },
+                { "name": Priority::Medium.label(), "color": "yellow" },
+                { "name": Priority::Low.label(), "color": "gray" },
+            ] } },
+            "예상 시간": { "number": { "format": "number" } },
+            "실제 시간": { "number": { "format": "number" } },
+            "담당": { "rich_text": {} },
+            "선행 작업": { "rich_text": {} },
+            "완료": { "checkbox": {} },
+        },
+    })).await?;
+    let database_id = database["id"].as_str().context("Notion 응답에 데이터베이스 id가 없습니다")?;
+
+    for phase in &plan.phases {
+        for task in &phase.tasks {
+            let mut properties = json!({
+                "이름": { "title": rich_text(&task.name) },
+                "ID": { "rich_text": rich_text(&task.id) },
+                "우선순위": { "select": { "name": task.priority.label() } },
+                "담당": { "rich_text": rich_text(task.assignee.as_deref().unwrap_or_default()) },
+                "선행 작업": { "rich_text": rich_text(&task.depends_on.join(", ")) },
+                "완료": { "checkbox": task.completed_at.is_some() },
+            });
+            // select 값에는 쉼표를 쓸 수 없음
+            let phase_name = phase.name.replace(',', "");
+            if !phase_name.trim().is_empty() {
+                properties["단계"] = json!({ "select": { "name": truncate_chars(phase_name.trim(), 100) } });
+            }
+            if !task.duration.is_zero() {
+                properties["예상 시간"] = json!({ "number": round(task.duration.likely_hours) });
+            }
+            if let Some(hours) = task.actual_hours {
+                properties["실제 시간"] = json!({ "number": round(hours) });
+            }
+
+            let mut children = Vec::new();
+            if !task.description.is_empty() {
+                children.push(block("paragraph", &task.description));
+            }
+            children.extend(task.acceptance_criteria.iter().map(|c| block("bulleted_list_item", c)));
+            children.extend(task.subtasks.iter().map(|s| to_do(&s.name, s.completed)));
+
+            client.call(Method::POST, "pages", &json!({
+                "parent": { "database_id": database_id },
+                "properties": properties,
+                "children": children,
+            })).await?;
+        }
+    }
+    Ok(url)
+}
+
+/// 작업 데이터베이스 위에 놓을 계획 개요 (설명, 목표, 마일스톤, 위험 요소)
+fn plan_blocks(plan: &ProjectPlan) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    if !plan.description.is_empty() {
+        blocks.push(block("paragraph", &plan.description));
+    }
+    blocks.push(block("paragraph", &format!(
+        "생성일: {} · 단계 {}개 · 작업 {}개 · 총 예상 {}",
+        plan.created_at.format("%Y-%m-%d %H:%M"), plan.phases.len(), plan.tasks().count(), plan.total_duration,
+    )));
+    if !plan.objectives.is_empty() {
+        blocks.push(block("heading_2", "목표"));
+        blocks.extend(plan.objectives.iter().map(|o| block("bulleted_list_item", o)));
+    }
+    if !plan.milestones.is_empty() {
+        blocks.push(block("heading_2", "마일스톤"));
+        for milestone in &plan.milestones {
+            let date = milestone.date.map(|d| format!(" ({})", d.format("%Y-%m-%d"))).unwrap_or_default();
+            let text = if milestone.description.is_empty() {
+                format!("{}{}", milestone.name, date)
+            } else {
+                format!("{}{}: {}", milestone.name, date, milestone.description)
+            };
+            blocks.push(block("bulleted_list_item", &text));
+        }
+    }
+    if !plan.risks.is_empty() {
+        blocks.push(block("heading_2", "위험 요소"));
+        for risk in &plan.risks {
+            blocks.push(block("bulleted_list_item", &format!(
+                "{} ({}/{}, {}) — 대응: {}",
+                risk.description, risk.probability, risk.impact, risk.status.label(), risk.mitigation,
+            )));
+        }
+    }
+    blocks.push(block("heading_2", "작업"));
+    blocks
+}
+
+/// 마크다운을 Notion 블록으로 바꿉니다 (제목, 문단, 목록, 체크박스, 인용, 구분선, 코드).
+/// 중첩 목록은 한 단계로 펼칩니다
+fn to_blocks(markdown: &str) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<(String, Vec<&str>)> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if let Some(fence) = trimmed.strip_prefix("```") {
+            match code.take() {
+                Some((language, lines)) => blocks.push(code_block(&language, &lines.join("\n"))),
+                None => {
+                    flush(&mut paragraph, &mut blocks);
+                    code = Some((fence.trim().to_lowercase(), Vec::new()));
+                }
+            }
+            continue;
+        }
+        if let Some((_, lines)) = code.as_mut() {
+            lines.push(line);
+            continue;
+        }
+
+        let heading = trimmed.trim_start_matches('#');
+        let level = trimmed.len() - heading.len();
+        let is_heading = (1..=6).contains(&level) && heading.starts_with(' ');
+        let item = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")).or_else(|| trimmed.strip_prefix("+ "));
+        let numbered = trimmed.split_once(". ")
+            .filter(|(number, _)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+            .map(|(_, rest)| rest);
+        let divider = matches!(trimmed, "---" | "***" | "___");
+
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+            continue;
+        }
+        if !is_heading && item.is_none() && numbered.is_none() && !divider && !trimmed.starts_with('>') {
+            paragraph.push(trimmed);
+            continue;
+        }
+        flush(&mut paragraph, &mut blocks);
+
+        if is_heading {
+            blocks.push(block(&format!("heading_{}", level.min(3)), heading.trim()));
+        } else if let Some(item) = item {
+            match item.get(..4) {
+                Some("[ ] ") => blocks.push(to_do(&item[4..], false)),
+                Some("[x] ") | Some("[X] ") => blocks.push(to_do(&item[4..], true)),
+                _ => blocks.push(block("bulleted_list_item", item)),
+            }
+        } else if let Some(item) = numbered {
+            blocks.push(block("numbered_list_item", item));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            blocks.push(block("quote", quote.trim()));
+        } else {
+            blocks.push(json!({ "object": "block", "type": "divider", "divider": {} }));
+        }
+    }
+    if let Some((language, lines)) = code {
+        blocks.push(code_block(&language, &lines.join("\n")));
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+fn flush(paragraph: &mut Vec<&str>, blocks: &mut Vec<Value>) {
+    if !paragraph.is_empty() {
+        blocks.push(block("paragraph", &paragraph.join("\n")));
+        paragraph.clear();
+    }
+}
+
+fn block(kind: &str, text: &str) -> Value {
+    json!({ "object": "block", "type": kind, kind: { "rich_text": rich_text(text) } })
+}
+
+fn to_do(text: &str, checked: bool) -> Value {
+    json!({ "object": "block", "type": "to_do", "to_do": { "rich_text": rich_text(text), "checked": checked } })
+}
+
+fn code_block(language: &str, code: &str) -> Value {
+    let language = match language {
+        "rs" => "rust",
+        "py" => "python",
+        "js" => "javascript",
+        "ts" => "typescript",
+        "sh" | "zsh" => "shell",
+        "yml" => "yaml",
+        "md" => "markdown",
+        other if CODE_LANGUAGES.contains(&other) => other,
+        _ => "plain text",
+    };
+    let text: Vec<Value> = chunks(code).into_iter()
+        .map(|chunk| json!({ "type": "text", "text": { "content": chunk } }))
+        .collect();
+    json!({ "object": "block", "type": "code", "code": { "rich_text": text, "language": language } })
+}
+
+/// 굵게, 인라인 코드, 링크를 주석(annotation)이 붙은 rich text 조각으로 바꿉니다
+fn rich_text(text: &str) -> Vec<Value> {
+    static INLINE: OnceLock<Regex> = OnceLock::new();
+    let inline = INLINE.get_or_init(|| {
+        Regex::new(r"\*\*(.+?)\*\*|`([^`]+)`|\[([^\]]+)\]\(([^)\s]+)\)").expect("정규식 오류")
+    });
+
+    let mut parts = Vec::new();
+    let mut push = |content: &str, bold: bool, code: bool, link: Option<&str>| {
+        for chunk in chunks(content) {
+            let mut part = json!({
+                "type": "text",
+                "text": { "content": chunk },
+                "annotations": { "bold": bold, "code": code },
+            });
+            if let Some(url) = link.filter(|url| url.starts_with("http")) {
+                part["text"]["link"] = json!({ "url": url });
+            }
+            parts.push(part);
+        }
+    };
+
+    let mut last = 0;
+    for caps in inline.captures_iter(text) {
+        let whole = caps.get(0).expect("전체 일치");
+        push(&text[last..whole.start()], false, false, None);
+        if let Some(bold) = caps.get(1) {
+            push(bold.as_str(), true, false, None);
+        } else if let Some(code) = caps.get(2) {
+            push(code.as_str(), false, true, None);
+        } else {
+            push(&caps[3], false, false, caps.get(4).map(|m| m.as_str()));
+        }
+        last = whole.end();
+    }
+    push(&text[last..], false, false, None);
+    parts
+}
+
+/// rich text 한도에 맞게 글자 단위로 나눕니다 (빈 문자열은 조각 없음)
+fn chunks(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    chars.chunks(TEXT_LIMIT).map(|chunk| chunk.iter().collect()).collect()
+}
+
+fn truncate_chars(text: &str, limit: usize) -> String {
+    text.chars().take(limit).collect()
+}
+
+fn round(hours: f32) -> f64 {
+    (hours as f64 * 10.0).round() / 10.0
+}
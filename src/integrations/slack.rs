@@ -0,0 +1,137 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+use crate::config::SlackConfig;
+use super::truncate;
+
+/// Slack section 블록 텍스트 한도
+const SECTION_LIMIT: usize = 3000;
+/// 메시지 하나에 넣을 수 있는 블록 수 (헤더 포함 50개)
+const MAX_SECTIONS: usize = 48;
+const HEADER_LIMIT: usize = 150;
+
+/// 수신 웹훅으로 제목 헤더와 본문 섹션 블록을 보냅니다
+pub async fn post(config: &SlackConfig, title: &str, markdown: &str) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()?;
+    let response = client.post(&config.webhook_url)
+        .json(&payload(config, title, markdown))
+        .send().await
+        .context("Slack 웹훅 연결 실패")?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("Slack 웹훅이 요청을 거부했습니다 ({}): {}", status, body.trim());
+    }
+    Ok(())
+}
+
+fn payload(config: &SlackConfig, title: &str, markdown: &str) -> Value {
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": truncate(title, HEADER_LIMIT), "emoji": true },
+    })];
+    let sections = split_sections(&to_mrkdwn(markdown));
+    let total = sections.len();
+    for (index, text) in sections.into_iter().enumerate() {
+        if index == MAX_SECTIONS {
+            blocks.push(json!({
+                "type": "context",
+                "elements": [{ "type": "mrkdwn", "text": format!("_나머지 {}개 구간은 생략했습니다_", total - index) }],
+            }));
+            break;
+        }
+        blocks.push(json!({ "type": "section", "text": { "type": "mrkdwn", "text": text } }));
+    }
+
+    let mut payload = json!({ "text": title, "blocks": blocks });
+    if let Some(channel) = &config.channel {
+        payload["channel"] = json!(channel);
+    }
+    if let Some(username) = &config.username {
+        payload["username"] = json!(username);
+    }
+    payload
+}
+
+/// 마크다운을 Slack mrkdwn으로 바꿉니다 (제목과 굵게는 `*굵게*`, 링크는 `<url|텍스트>`, 목록은 `•`)
+fn to_mrkdwn(markdown: &str) -> String {
+    static BOLD: OnceLock<Regex> = OnceLock::new();
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let bold = BOLD.get_or_init(|| Regex::new(r"\*\*(.+?)\*\*").expect("정규식 오류"));
+    let link = LINK.get_or_init(|| Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").expect("정규식 오류"));
+
+    let mut in_code = false;
+    let mut lines = Vec::new();
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            lines.push("```".to_string());
+            continue;
+        }
+        if in_code {
+            lines.push(line.to_string());
+            continue;
+        }
+        let trimmed = line.trim_start();
+        let heading = trimmed.trim_start_matches('#');
+        let converted = if trimmed.starts_with('#') && heading.starts_with(' ') {
+            format!("*{}*", heading.trim().replace("**", ""))
+        } else {
+            let indent = &line[..line.len() - trimmed.len()];
+            let body = match trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+                Some(item) if !item.starts_with("[ ]") && !item.starts_with("[x]") => format!("{}• {}", indent, item),
+                _ => line.to_string(),
+            };
+            let body = bold.replace_all(&body, "*$1*");
+            link.replace_all(&body, "<$2|$1>").into_owned()
+        };
+        lines.push(converted);
+    }
+    lines.join("\n")
+}
+
+/// 섹션 한도에 맞게 빈 줄(문단) 단위로 나눕니다. 한 문단이 한도를 넘으면 줄 단위로 자릅니다
+fn split_sections(text: &str) -> Vec<String> {
+    let mut sections = Vec::new();
+    let mut current = String::new();
+    for paragraph in text.split("\n\n").filter(|p| !p.trim().is_empty()) {
+        for piece in pieces(paragraph) {
+            if !current.is_empty() && current.chars().count() + piece.chars().count() + 2 > SECTION_LIMIT {
+                sections.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(&piece);
+        }
+    }
+    if !current.is_empty() {
+        sections.push(current);
+    }
+    sections
+}
+
+fn pieces(paragraph: &str) -> Vec<String> {
+    if paragraph.chars().count() <= SECTION_LIMIT {
+        return vec![paragraph.to_string()];
+    }
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for line in paragraph.lines() {
+        let line = truncate(line, SECTION_LIMIT);
+        if !current.is_empty() && current.chars().count() + line.chars().count() + 1 > SECTION_LIMIT {
+            pieces.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(&line);
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
@@ -0,0 +1,134 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use crate::config::TeamsConfig;
+use super::truncate;
+
+/// Teams 웹훅 메시지 한도(28KB)보다 조금 작게 잡은 카드 본문 크기
+const CARD_LIMIT: usize = 24_000;
+const BLOCK_LIMIT: usize = 4_000;
+
+/// 수신 웹훅으로 제목과 본문을 담은 Adaptive Card 메시지를 보냅니다
+pub async fn post(config: &TeamsConfig, title: &str, markdown: &str) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()?;
+    let response = client.post(&config.webhook_url)
+        .json(&payload(title, markdown))
+        .send().await
+        .context("Teams 웹훅 연결 실패")?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    // 커넥터 웹훅은 실패해도 200과 함께 오류 문장을 돌려줌
+    if !status.is_success() || body.contains("failed") {
+        anyhow::bail!("Teams 웹훅이 요청을 거부했습니다 ({}): {}", status, body.trim());
+    }
+    Ok(())
+}
+
+fn payload(title: &str, markdown: &str) -> Value {
+    let mut body = vec![json!({
+        "type": "TextBlock",
+        "text": title,
+        "size": "Large",
+        "weight": "Bolder",
+        "wrap": true,
+    })];
+    let blocks = to_blocks(markdown);
+    let total = blocks.len();
+    let mut size = 0;
+    for (index, block) in blocks.into_iter().enumerate() {
+        size += block.to_string().len();
+        if size > CARD_LIMIT {
+            body.push(json!({
+                "type": "TextBlock",
+                "text": format!("_나머지 {}개 구간은 생략했습니다_", total - index),
+                "isSubtle": true,
+                "wrap": true,
+            }));
+            break;
+        }
+        body.push(block);
+    }
+
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "contentUrl": null,
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "msteams": { "width": "Full" },
+                "body": body,
+            },
+        }],
+    })
+}
+
+/// 마크다운을 TextBlock 목록으로 바꿉니다. TextBlock은 굵게, 기울임, 목록, 링크만 지원하므로
+/// 제목은 굵은 글씨 블록으로, 코드 블록은 고정폭 블록으로 따로 만듭니다
+fn to_blocks(markdown: &str) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match code.take() {
+                Some(lines) => blocks.push(json!({
+                    "type": "TextBlock",
+                    "text": truncate(&lines.join("\n"), BLOCK_LIMIT),
+                    "fontType": "Monospace",
+                    "wrap": true,
+                })),
+                None => {
+                    flush(&mut paragraph, &mut blocks);
+                    code = Some(Vec::new());
+                }
+            }
+            continue;
+        }
+        if let Some(lines) = code.as_mut() {
+            lines.push(line);
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let heading = trimmed.trim_start_matches('#');
+        if trimmed.starts_with('#') && heading.starts_with(' ') {
+            flush(&mut paragraph, &mut blocks);
+            let level = trimmed.len() - heading.len();
+            blocks.push(json!({
+                "type": "TextBlock",
+                "text": heading.trim().replace("**", ""),
+                "size": if level <= 2 { "Medium" } else { "Default" },
+                "weight": "Bolder",
+                "spacing": "Medium",
+                "wrap": true,
+            }));
+        } else if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    // 닫히지 않은 코드 블록도 내용은 보냄
+    if let Some(lines) = code {
+        paragraph.extend(lines);
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+fn flush(paragraph: &mut Vec<&str>, blocks: &mut Vec<Value>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    blocks.push(json!({
+        "type": "TextBlock",
+        "text": truncate(&paragraph.join("\n"), BLOCK_LIMIT),
+        "wrap": true,
+    }));
+    paragraph.clear();
+}
@@ -5,12 +5,18 @@ pub mod analyzer;
 pub mod config;
 pub mod renderer;
 pub mod splash;
+pub mod hooks;
+pub mod permissions;
+pub mod paths;
+pub mod integrations;
 pub mod handlers;
 pub mod cli;
+pub mod error;
 
 // Re-export commonly used types
 pub use assistant::DevAssistant;
 pub use planner::{ProjectPlanner, ProjectPlan};
 pub use analyzer::CodeAnalyzer;
 pub use config::Config;
-pub use cli::Cli; 
\ No newline at end of file
+pub use cli::Cli;
+pub use error::{ErrorKind, RicciError}; 
\ No newline at end of file
@@ -1,70 +1,268 @@
-use clap::{CommandFactory, Parser};
-use clap_complete::{generate, Generator};
 use anyhow::Result;
-use std::io;
+use colored::*;
+use std::process::ExitCode;
+use std::time::Instant;
 use ricci_cli::{
-    config::Config,
+    error::{ErrorKind, RicciError},
+    config::{Config, load_config_lenient, has_api_key, validate_settings, resolve_gateway_keys},
+    analyzer::init_languages,
+    assistant::ReviewView,
     cli::{Cli, Commands},
+    renderer::{dumb_terminal, enable_plain_mode},
+    permissions::{enable_read_only, init_permissions},
+    paths::init_paths,
+    hooks::{init_hooks, run_hooks, HookEvent},
     handlers::{
-        handle_chat, handle_analyze, handle_review, handle_doc, 
+        handle_chat, handle_analyze, handle_review, handle_review_history, ReviewOptions, handle_scan, handle_doc, 
         handle_plan, handle_config, handle_direct_query, install_completions,
-        handle_code_assist
+        handle_code_assist, handle_symbol_refactor, handle_rename, handle_upgrade, handle_deps, handle_compare, handle_session, handle_context, handle_sync, handle_doctor,
+        handle_models, handle_replay, handle_docs, handle_usage, handle_openapi, handle_doc_check, handle_standup, handle_plan_action,
+        completion::{completion_script, print_completion_values},
+        config::{ConfigAction, edit_config},
+        notify::notify_if_slow,
     },
 };
-use colored::*;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // .env 파일 로드
+async fn main() -> ExitCode {
+    // .env 파일 로드 (명령줄 옵션의 환경 변수 기본값에도 쓰이므로 파싱 전에)
     dotenv::dotenv().ok();
     
-    let cli = Cli::parse();
+    let (cli, subcommand) = match Cli::try_parse_with_command_name() {
+        Ok(parsed) => parsed,
+        // --help, --version은 표준 출력으로 내보내고 성공으로 끝남
+        Err(e) if !e.use_stderr() => e.exit(),
+        // 잘못된 사용법은 clap 기본값(2)이 설정 오류와 겹치지 않도록 따로 정한 코드로 끝냄
+        Err(e) => {
+            let _ = e.print();
+            return ExitCode::from(ErrorKind::Usage.exit_code());
+        }
+    };
     
-    // 설정 로드
-    let config = Config::load()?;
+    match run(cli, subcommand).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            let kind = ErrorKind::of(&e);
+            eprintln!("{} {:#}", "오류:".red(), e);
+            if let Some(hint) = kind.hint() {
+                eprintln!("{}", hint.dimmed());
+            }
+            ExitCode::from(kind.exit_code())
+        }
+    }
+}
+
+async fn run(cli: Cli, subcommand: Option<String>) -> Result<()> {
+    if cli.plain || dumb_terminal() {
+        enable_plain_mode();
+    }
+    if cli.read_only {
+        enable_read_only();
+    }
+    
+    // 상태 경로는 진단, 자동완성 후보(저장된 세션), 게이트웨이 키 캐시도 쓰므로 설정 검증보다 먼저 정함.
+    // 설정의 읽기 전용 모드도 설정 편집, 자동완성 설치처럼 검증 전에 끝나는 명령어에 적용되도록 여기서 켬
+    let early_config = load_config_lenient();
+    init_paths(&early_config);
+    if early_config.permissions.read_only {
+        enable_read_only();
+    }
+    
+    // 진단은 설정이 깨져 있어도 실행되어야 함
+    if let Some(Commands::Doctor) = cli.command {
+        return handle_doctor().await;
+    }
     
+    // 재생은 오프라인 동작이므로 API 키가 필요 없음
+    if let Some(Commands::Replay { dir }) = &cli.command {
+        return handle_replay(dir);
+    }
+    
+    // 쉘 자동완성 스크립트/후보 출력은 설정 검증이나 안내 문구 없이 실행 (표준 출력이 그대로 스크립트가 됨)
     match cli.command {
-        Some(Commands::Chat { context, save }) => {
-            handle_chat(context, save.as_deref(), &config).await?;
+        Some(Commands::Complete { kind }) => {
+            print_completion_values(kind);
+            return Ok(());
         }
-        Some(Commands::Plan { description, format, detail, estimate }) => {
-            handle_plan(&description, &format, detail, estimate, &config).await?;
+        Some(Commands::Completion { shell }) => {
+            print!("{}", completion_script(shell)?);
+            return Ok(());
         }
-        Some(Commands::CodeAssist { path, fix, test, docs }) => {
-            handle_code_assist(&path, fix, test, docs, &config).await?;
+        _ => {}
+    }
+    
+    // 설정 편집은 설정 파일이 깨져 있을 때도 실행되어야 함
+    if let Some(Commands::Config { action: ConfigAction::Edit }) = cli.command {
+        return edit_config();
+    }
+    
+    // 문서 생성은 명령어 정의만 사용
+    if let Some(Commands::Docs { action }) = cli.command {
+        return handle_docs(action);
+    }
+    
+    // 설정 로드
+    let mut config = Config::load()?;
+    if config.output_preferences.plain {
+        enable_plain_mode();
+    }
+    init_permissions(&config.permissions);
+    resolve_gateway_keys(&mut config).await?;
+    cli.generation.apply(&mut config.model_preferences);
+    config.record_dir = cli.record.clone();
+    config.assume_yes = cli.yes;
+    config.output_format = cli.output_format;
+    // API 키는 제공자 클라이언트를 만들 때 확인 (키가 필요 없는 명령어는 그대로 실행)
+    config.offline = !has_api_key(&config);
+    validate_settings(&config)?;
+    init_languages(&config.languages);
+    
+    let command_name = match (subcommand, &cli.query) {
+        (Some(name), _) => name,
+        (None, Some(_)) => "query".to_string(),
+        (None, None) => "chat".to_string(),
+    };
+    init_hooks(&config.hooks, &config.shell, &command_name);
+    
+    // 오래 걸린 명령어는 끝났을 때 데스크톱 알림 (대화형 명령어 제외)
+    let interactive = cli.command.as_ref().map_or(cli.query.is_none(), Commands::is_interactive);
+    let started = Instant::now();
+    // 대화형 명령어는 Ctrl+C를 직접 처리하고, 그 밖의 명령어는 중단 시 종료 코드 130으로 끝냄.
+    // 실행 중인 작업을 버리고 여기로 돌아오므로 오류 훅과 알림은 그대로 실행됨
+    let result = if interactive {
+        run_command(cli.command, cli.query, &config).await
+    } else {
+        tokio::select! {
+            result = run_command(cli.command, cli.query, &config) => result,
+            Ok(()) = tokio::signal::ctrl_c() => Err(RicciError::Aborted("Ctrl+C로 중단되었습니다".to_string()).into()),
         }
-        Some(Commands::Analyze { path, type_ }) => {
-            handle_analyze(&path, &type_, &config).await?;
+    };
+    if let Err(e) = &result {
+        run_hooks(HookEvent::Error, serde_json::json!({ "message": format!("{:#}", e) }));
+    }
+    if !interactive {
+        notify_if_slow(&command_name, started.elapsed(), result.is_ok(), &config.notifications);
+    }
+    result
+}
+
+async fn run_command(command: Option<Commands>, query: Option<String>, config: &Config) -> Result<()> {
+    match command {
+        Some(Commands::Chat { context, save, persona }) => {
+            handle_chat(context, save.as_deref(), persona.as_deref(), config).await?;
+        }
+        Some(Commands::Plan { action: Some(action), .. }) => {
+            handle_plan_action(action, config).await?;
+        }
+        Some(Commands::Plan { action: None, description, format, detail, estimate, output, share }) => {
+            handle_plan(&description.unwrap_or_default(), &format, detail, estimate, output.as_deref(), share.as_deref(), config).await?;
+        }
+        Some(Commands::CodeAssist { path, symbol: Some(symbol), goal, .. }) => {
+            handle_symbol_refactor(&path, &symbol, goal.as_deref().unwrap_or_default(), config).await?;
+        }
+        Some(Commands::CodeAssist { path, fix, test, docs, preset, options, .. }) => {
+            handle_code_assist(&path, fix, test, docs, preset.as_deref(), &options, config).await?;
+        }
+        Some(Commands::Analyze { path, type_, save_snapshot, compare, share }) => {
+            handle_analyze(&path, &type_, save_snapshot, compare.as_deref(), share.as_deref(), config).await?;
+        }
+        Some(Commands::Compare { prompt, models }) => {
+            handle_compare(&prompt, &models, config).await?;
+        }
+        Some(Commands::Rename { old, new, path, no_ai }) => {
+            handle_rename(&old, &new, &path, no_ai, config).await?;
+        }
+        Some(Commands::Deps { action }) => {
+            handle_deps(action, config).await?;
+        }
+        Some(Commands::Upgrade { dependency, to, path, max_rounds }) => {
+            handle_upgrade(&dependency, to.as_deref(), &path, max_rounds, config).await?;
+        }
+        Some(Commands::Review { path, history: true, badge, .. }) => {
+            handle_review_history(&path, badge.as_deref())?;
+        }
+        Some(Commands::Review {
+            path, criteria, lint, no_cache, share, badge, apply, explain, annotate, only, min_severity, group_by, fail_under, ..
+        }) => {
+            let view = ReviewView { only, min_severity, group_by };
+            let options = ReviewOptions {
+                lint,
+                no_cache,
+                share: share.as_deref(),
+                badge: badge.as_deref(),
+                apply,
+                explain,
+                annotate,
+                fail_under,
+                view: &view,
+            };
+            handle_review(&path, &criteria, options, config).await?;
         }
-        Some(Commands::Review { path, criteria }) => {
-            handle_review(&path, &criteria, &config).await?;
+        Some(Commands::Scan { path, security, offline, output }) => {
+            handle_scan(&path, security, offline, output.as_deref(), config).await?;
         }
-        Some(Commands::Doc { target, type_ }) => {
-            handle_doc(&target, &type_, &config).await?;
+        Some(Commands::Doc { target, type_, template, output, sections, check, fix, share }) => {
+            if check {
+                handle_doc_check(&target, fix, config).await?;
+            } else if type_ == "openapi" {
+                if share.is_some() {
+                    anyhow::bail!("openapi 명세는 --share로 공유할 수 없습니다");
+                }
+                handle_openapi(&target, output.as_deref(), config).await?;
+            } else {
+                handle_doc(&target, &type_, template.as_deref(), output.as_deref(), &sections, share.as_deref(), config).await?;
+            }
         }
         Some(Commands::Config { action }) => {
             handle_config(action)?;
         }
+        Some(Commands::Session { action }) => {
+            handle_session(action, config)?;
+        }
+        Some(Commands::Context { action }) => {
+            handle_context(action)?;
+        }
+        Some(Commands::Sync { force }) => {
+            handle_sync(force, config).await?;
+        }
+        Some(Commands::Models { provider }) => {
+            handle_models(provider.as_deref(), config).await?;
+        }
+        Some(Commands::Usage { action }) => {
+            handle_usage(action, config)?;
+        }
+        Some(Commands::Standup { since }) => {
+            handle_standup(&since, config).await?;
+        }
+        // 보통은 `run`에서 설정 검증 전에 처리하지만, 여기까지 와도 설정 없이 그대로 실행
+        Some(Commands::Doctor) => {
+            handle_doctor().await?;
+        }
+        Some(Commands::Replay { dir }) => {
+            handle_replay(&dir)?;
+        }
+        Some(Commands::Docs { action }) => {
+            handle_docs(action)?;
+        }
+        Some(Commands::Complete { kind }) => {
+            print_completion_values(kind);
+        }
         Some(Commands::Completion { shell }) => {
-            print_completions(shell, &mut Cli::command());
+            print!("{}", completion_script(shell)?);
         }
-        Some(Commands::Install { shell }) => {
-            install_completions(shell)?;
+        Some(Commands::Install { shell, uninstall }) => {
+            install_completions(shell, uninstall)?;
         }
         None => {
             // 직접 질문 모드
-            if let Some(query) = cli.query {
-                handle_direct_query(&query, &config).await?;
+            if let Some(query) = query {
+                handle_direct_query(&query, config).await?;
             } else {
                 // 기본 대화형 모드
-                handle_chat(false, None, &config).await?;
+                handle_chat(false, None, None, config).await?;
             }
         }
     }
     
     Ok(())
 }
-
-pub fn print_completions<G: Generator>(gen: G, cmd: &mut clap::Command) {
-    generate(gen, cmd, cmd.get_name().to_string(), &mut io::stdout());
-}
@@ -0,0 +1,59 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use crate::assistant::expand_home;
+use crate::config::Config;
+
+/// 설정에서 지정한 상태 디렉토리
+#[derive(Default)]
+struct StateDirs {
+    data_dir: Option<PathBuf>,
+    session_dir: Option<PathBuf>,
+}
+
+static DIRS: OnceLock<StateDirs> = OnceLock::new();
+
+/// 설정의 `data_dir`와 `[output_preferences] session_dir`로 상태 경로를 정합니다. 처음 호출만 적용됩니다
+pub fn init_paths(config: &Config) {
+    let _ = DIRS.set(StateDirs {
+        data_dir: config.data_dir.as_deref().map(expand_home),
+        session_dir: config.output_preferences.session_dir.as_deref().map(expand_home),
+    });
+}
+
+/// ricci 상태(세션, 입력 기록, 리뷰 캐시, 백업, 계획, 사용량, 공유 설정)를 저장하는 디렉토리.
+/// 우선순위: 설정의 `data_dir`(`RICCI_DATA_DIR`) > 이전 버전이 쓰던 `~/.ricci`가 있으면 그대로 >
+/// `$XDG_DATA_HOME/ricci` (기본 `~/.local/share/ricci`, macOS는 `~/Library/Application Support/ricci`)
+pub fn data_dir() -> Result<PathBuf> {
+    if let Some(dir) = DIRS.get().and_then(|dirs| dirs.data_dir.clone()) {
+        return Ok(dir);
+    }
+    let legacy = dirs::home_dir().map(|home| home.join(".ricci"));
+    if let Some(legacy) = legacy.filter(|dir| dir.is_dir()) {
+        return Ok(legacy);
+    }
+    Ok(dirs::data_dir().context("데이터 디렉토리를 찾을 수 없습니다 (HOME 또는 XDG_DATA_HOME을 확인하세요)")?.join("ricci"))
+}
+
+/// 세션 파일 디렉토리. `session_dir`을 지정하지 않으면 `<data_dir>/sessions`
+pub fn sessions_dir() -> Result<PathBuf> {
+    match DIRS.get().and_then(|dirs| dirs.session_dir.clone()) {
+        Some(dir) => Ok(dir),
+        None => Ok(data_dir()?.join("sessions")),
+    }
+}
+
+/// `ricci sync`로 받은 공유 설정(페르소나, 템플릿, 리뷰 규칙)이 병합되는 디렉토리 (`<data_dir>/shared`)
+pub fn shared_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("shared"))
+}
+
+/// 대화 입력 기록 (`<data_dir>/history.txt`)
+pub fn history_file() -> Result<PathBuf> {
+    Ok(data_dir()?.join("history.txt"))
+}
+
+/// 파일을 고치기 전 원본 백업 (`<data_dir>/backups`)
+pub fn backups_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("backups"))
+}
@@ -0,0 +1,234 @@
+use anyhow::Result;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use crate::assistant::expand_home;
+use crate::config::PermissionsConfig;
+
+/// 대상이 없는 심볼릭 링크를 따라갈 최대 횟수 (순환 링크 방지)
+const MAX_LINK_DEPTH: usize = 8;
+
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+static WRITABLE: OnceLock<Vec<Regex>> = OnceLock::new();
+
+/// 설정의 권한으로 전역 상태를 초기화합니다. 쓰기 허용 경로는 처음 호출만 적용됩니다
+pub fn init_permissions(config: &PermissionsConfig) {
+    if config.read_only {
+        enable_read_only();
+    }
+    let _ = WRITABLE.set(config.writable_paths.iter().map(|pattern| glob_regex(pattern)).collect());
+}
+
+/// 읽기 전용 모드를 켭니다. 프로젝트 파일과 셸 설정 파일 쓰기, 셸 명령어/린터/훅/포매터/편집기 실행을 모두 막고,
+/// ricci 자체 상태(데이터 디렉토리의 세션, 계획, 사용량과 설정 파일)만 저장합니다
+pub fn enable_read_only() {
+    READ_ONLY.store(true, Ordering::Relaxed);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+}
+
+/// 읽기 전용 모드면 `action`(예: "파일 수정", "셸 명령어 실행")을 막는 오류를 돌려줍니다
+pub fn ensure_allowed(action: &str) -> Result<()> {
+    if is_read_only() {
+        anyhow::bail!(
+            "읽기 전용 모드에서는 허용되지 않는 작업입니다: {} (--read-only 또는 [permissions] read_only 설정)",
+            action
+        );
+    }
+    Ok(())
+}
+
+/// 모델이 제안한 변경을 쓸 수 있는 경로인지 확인합니다. `writable_paths`가 비어 있으면 모든 경로를
+/// 허용하고, 있으면 심볼릭 링크와 `..`를 푼 실제 경로가 패턴 중 하나에 맞아야 합니다
+pub fn ensure_writable(path: &Path) -> Result<()> {
+    ensure_allowed("파일 수정")?;
+    let patterns = WRITABLE.get().map(Vec::as_slice).unwrap_or_default();
+    if patterns.is_empty() || is_writable(path, patterns) {
+        return Ok(());
+    }
+    anyhow::bail!("쓰기 허용 경로([permissions] writable_paths) 밖의 파일입니다: {}", path.display())
+}
+
+fn is_writable(path: &Path, patterns: &[Regex]) -> bool {
+    let (Some(resolved), Ok(cwd)) = (resolve(path), std::env::current_dir().and_then(|d| d.canonicalize())) else {
+        return false;
+    };
+    let absolute = slash_path(&resolved);
+    // 상대 패턴은 현재 디렉토리 안의 경로에만 맞춤
+    let relative = resolved.strip_prefix(&cwd).ok().map(slash_path);
+    patterns.iter().any(|pattern| {
+        pattern.is_match(&absolute) || relative.as_deref().is_some_and(|relative| pattern.is_match(relative))
+    })
+}
+
+/// 아직 없는 파일은 존재하는 가장 가까운 상위 디렉토리까지 실제 경로로 바꾼 뒤 나머지를 붙입니다.
+/// 대상이 없는 심볼릭 링크도 쓰면 링크를 따라가므로 링크 대상 경로로 판단합니다.
+/// 남은 부분에 `..`가 있거나 링크가 너무 깊으면 판단할 수 없으므로 None
+fn resolve(path: &Path) -> Option<PathBuf> {
+    resolve_links(path, 0)
+}
+
+fn resolve_links(path: &Path, depth: usize) -> Option<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().ok()?.join(path)
+    };
+    let mut existing = absolute.as_path();
+    let mut rest = Vec::new();
+    // `exists()`는 링크를 따라가 대상이 없는 링크를 없는 파일로 보므로 링크 자체의 존재를 확인
+    while existing.symlink_metadata().is_err() {
+        rest.push(existing.file_name()?);
+        existing = existing.parent()?;
+    }
+    let mut resolved = match existing.canonicalize() {
+        Ok(resolved) => resolved,
+        Err(_) if depth < MAX_LINK_DEPTH => {
+            let target = std::fs::read_link(existing).ok()?;
+            resolve_links(&existing.parent()?.join(target), depth + 1)?
+        }
+        Err(_) => return None,
+    };
+    resolved.extend(rest.iter().rev());
+    Some(resolved)
+}
+
+fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// `src/**`, `*.md`, `{src,tests}/**` 형식의 glob을 정규식으로 바꿉니다.
+/// 디렉토리 패턴(`docs`)은 그 아래 모든 파일에도 맞습니다
+fn glob_regex(pattern: &str) -> Regex {
+    let pattern = slash_path(&expand_home(Path::new(pattern.trim())));
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut regex = String::from("^");
+    let mut in_brace = false;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    regex.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    regex.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '{' if !in_brace => {
+                regex.push_str("(?:");
+                in_brace = true;
+            }
+            ',' if in_brace => regex.push('|'),
+            '}' if in_brace => {
+                regex.push(')');
+                in_brace = false;
+            }
+            c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+        i += 1;
+    }
+    if in_brace {
+        regex.push(')');
+    }
+    regex.push_str("(?:/.*)?$");
+    Regex::new(&regex).expect("glob 변환 오류")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        glob_regex(pattern).is_match(path)
+    }
+
+    #[test]
+    fn double_star_matches_any_depth() {
+        assert!(matches("src/**", "src/main.rs"));
+        assert!(matches("src/**", "src/handlers/chat.rs"));
+        assert!(!matches("src/**", "srcx/main.rs"));
+        assert!(matches("**/*.rs", "main.rs"));
+        assert!(matches("**/*.rs", "src/handlers/chat.rs"));
+    }
+
+    #[test]
+    fn single_star_stays_in_one_directory() {
+        assert!(matches("*.md", "README.md"));
+        assert!(!matches("*.md", "docs/guide.md"));
+        assert!(matches("src/*.rs", "src/main.rs"));
+        assert!(!matches("src/*.rs", "src/handlers/chat.rs"));
+    }
+
+    #[test]
+    fn braces_match_each_alternative() {
+        assert!(matches("{src,tests}/**", "src/main.rs"));
+        assert!(matches("{src,tests}/**", "tests/repl.rs"));
+        assert!(!matches("{src,tests}/**", "benches/bench.rs"));
+    }
+
+    #[test]
+    fn directory_pattern_covers_its_contents() {
+        assert!(matches("docs", "docs"));
+        assert!(matches("docs/", "docs/a/b.md"));
+        assert!(matches("./docs", "docs/guide.md"));
+        assert!(!matches("docs", "docsx/guide.md"));
+    }
+
+    #[test]
+    fn relative_patterns_apply_inside_the_current_directory() {
+        let patterns = [glob_regex("src/**")];
+        let cwd = std::env::current_dir().unwrap();
+        assert!(is_writable(Path::new("src/new_file.rs"), &patterns));
+        assert!(is_writable(&cwd.join("src/main.rs"), &patterns));
+        assert!(!is_writable(Path::new("tests/repl.rs"), &patterns));
+        assert!(!is_writable(Path::new("/src/main.rs"), &patterns));
+    }
+
+    #[test]
+    fn parent_components_cannot_escape() {
+        let patterns = [glob_regex("src/**")];
+        assert!(!is_writable(Path::new("src/../Cargo.toml"), &patterns));
+        assert!(!is_writable(Path::new("src/../../outside.rs"), &patterns));
+        assert!(!is_writable(Path::new("src/missing/../../Cargo.toml"), &patterns));
+    }
+
+    #[test]
+    fn absolute_patterns_match_absolute_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let patterns = [glob_regex(&format!("{}/allowed/**", root.display()))];
+        assert!(is_writable(&root.join("allowed/new.txt"), &patterns));
+        assert!(!is_writable(&root.join("other/new.txt"), &patterns));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_judged_by_their_target() {
+        use std::os::unix::fs::symlink;
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("allowed")).unwrap();
+        std::fs::create_dir_all(root.join("outside")).unwrap();
+        std::fs::write(root.join("outside/existing"), "").unwrap();
+        std::fs::write(root.join("allowed/inside"), "").unwrap();
+        symlink(root.join("outside/missing"), root.join("allowed/dangling")).unwrap();
+        symlink(root.join("outside/existing"), root.join("allowed/escape")).unwrap();
+        symlink(root.join("allowed/inside"), root.join("allowed/local")).unwrap();
+        symlink(root.join("allowed/loop"), root.join("allowed/loop")).unwrap();
+
+        let patterns = [glob_regex(&format!("{}/allowed/**", root.display()))];
+        assert!(!is_writable(&root.join("allowed/dangling"), &patterns));
+        assert!(!is_writable(&root.join("allowed/escape"), &patterns));
+        assert!(is_writable(&root.join("allowed/local"), &patterns));
+        assert!(!is_writable(&root.join("allowed/loop"), &patterns));
+    }
+}
@@ -1,8 +1,31 @@
+use anyhow::Result;
 use crate::planner::types::*;
 use prettytable::{Table, row};
 use colored::*;
+use crate::renderer::plain_text;
+
+/// YAML 출력 머리말. 스키마는 README의 "계획 YAML 스키마"에 설명되어 있습니다
+const YAML_HEADER: &str = "# ricci 계획 — 수정한 뒤 `ricci plan import <파일>`로 다시 가져올 수 있습니다\n\
+# 기간은 `4-8시간`, `2일`, `6`(시간) 또는 min_hours/max_hours로, 우선순위는 긴급/높음/보통/낮음으로 쓸 수 있습니다\n";
+
+/// `ricci plan --format`에서 고를 수 있는 출력 형식
+pub const PLAN_FORMATS: &[&str] = &["markdown", "json", "yaml", "table", "mermaid", "csv"];
 
 impl ProjectPlan {
+    /// `PLAN_FORMATS` 중 하나로 계획을 출력합니다 (파일로 저장할 수 있게 줄바꿈으로 끝남)
+    pub fn render(&self, format: &str) -> Result<String> {
+        let text = match format {
+            "markdown" => self.to_markdown(),
+            "json" => serde_json::to_string_pretty(self)?,
+            "yaml" => format!("{}{}", YAML_HEADER, serde_yaml::to_string(self)?),
+            "table" => self.to_table()?,
+            "mermaid" => self.to_mermaid()?,
+            "csv" => return self.to_csv(),
+            _ => anyhow::bail!("지원하지 않는 형식: {} ({})", format, PLAN_FORMATS.join(", ")),
+        };
+        Ok(format!("{}\n", text.trim_end()))
+    }
+    
     pub fn to_markdown(&self) -> String {
         let mut output = String::new();
         
@@ -16,7 +39,7 @@ impl ProjectPlan {
             for obj in &self.objectives {
                 output.push_str(&format!("- {}\n", obj));
             }
-            output.push_str("\n");
+            output.push('\n');
         }
         
         // 단계별 계획
@@ -36,8 +59,18 @@ impl ProjectPlan {
                     output.push_str("**작업 목록**:\n\n");
                     for task in &phase.tasks {
                         output.push_str(&format!("- **{}** ({})\n", task.name, task.priority));
-                        output.push_str(&format!("  - {}\n", task.description));
-                        output.push_str(&format!("  - 예상 소요: {}\n", task.duration));
+                        if !task.description.is_empty() {
+                            output.push_str(&format!("  - {}\n", task.description));
+                        }
+                        if !task.duration.is_zero() {
+                            output.push_str(&format!("  - 예상 소요: {}\n", task.duration));
+                        }
+                        if let Some(assignee) = &task.assignee {
+                            output.push_str(&format!("  - 담당: {}\n", assignee));
+                        }
+                        if !task.depends_on.is_empty() {
+                            output.push_str(&format!("  - 선행 작업: {}\n", task.depends_on.join(", ")));
+                        }
                         
                         if !task.acceptance_criteria.is_empty() {
                             output.push_str("  - 완료 기준:\n");
@@ -45,8 +78,16 @@ impl ProjectPlan {
                                 output.push_str(&format!("    - {}\n", criterion));
                             }
                         }
+                        
+                        if !task.subtasks.is_empty() {
+                            output.push_str("  - 하위 작업:\n");
+                            for subtask in &task.subtasks {
+                                let check = if subtask.completed { "x" } else { " " };
+                                output.push_str(&format!("    - [{}] {}\n", check, subtask.name));
+                            }
+                        }
                     }
-                    output.push_str("\n");
+                    output.push('\n');
                 }
             }
         }
@@ -63,7 +104,7 @@ impl ProjectPlan {
                     for deliverable in &milestone.deliverables {
                         output.push_str(&format!("- {}\n", deliverable));
                     }
-                    output.push_str("\n");
+                    output.push('\n');
                 }
             }
         }
@@ -73,10 +114,27 @@ impl ProjectPlan {
             output.push_str("## 위험 요소\n\n");
             for risk in &self.risks {
                 let risk_score = format!("{}/{}", risk.probability, risk.impact);
-                output.push_str(&format!("- **{}** ({})\n", risk.description, risk_score));
+                let status = if risk.status == RiskStatus::Open { String::new() } else { format!(" [{}]", risk.status.label()) };
+                output.push_str(&format!("- **{}** ({}){}\n", risk.description, risk_score, status));
                 output.push_str(&format!("  - 대응: {}\n", risk.mitigation));
             }
-            output.push_str("\n");
+            output.push('\n');
+        }
+        
+        if !self.resources.is_empty() {
+            output.push_str("## 필요 자원\n\n");
+            for resource in &self.resources {
+                output.push_str(&format!("- {}\n", resource));
+            }
+            output.push('\n');
+        }
+        
+        if !self.test_strategy.is_empty() {
+            output.push_str("## 테스트 전략\n\n");
+            for item in &self.test_strategy {
+                output.push_str(&format!("- {}\n", item));
+            }
+            output.push('\n');
         }
         
         // 총 기간
@@ -85,32 +143,114 @@ impl ProjectPlan {
         output
     }
     
-    pub fn to_table(&self) -> String {
+    pub fn to_table(&self) -> Result<String> {
+        let schedule = self.schedule()?;
         let mut table = Table::new();
-        table.add_row(row!["단계", "작업", "우선순위", "예상 시간"]);
+        table.add_row(row!["id", "단계", "작업", "우선순위", "예상 시간", "선행 작업", "시작-종료", "주경로"]);
         
         for phase in &self.phases {
             for task in &phase.tasks {
+                let (start, finish) = schedule.times.get(&task.id).copied().unwrap_or_default();
                 table.add_row(row![
+                    task.id,
                     phase.name,
                     task.name,
                     format!("{:?}", task.priority),
-                    task.duration.to_string()
+                    task.duration.to_string(),
+                    task.depends_on.join(", "),
+                    format!("{:.1}-{:.1}h", start, finish),
+                    if schedule.is_critical(&task.id) { plain_text("●") } else { "".into() }
                 ]);
             }
         }
         
-        table.to_string()
+        Ok(format!(
+            "{}\n주경로: {}\n최소 일정: {:.1}시간 (하루 8시간 기준 약 {:.1}일, 인원 제약 없음)\n",
+            table,
+            schedule.critical_path.join(&format!(" {} ", plain_text("→"))),
+            schedule.total_hours,
+            schedule.total_hours / 8.0
+        ))
+    }
+    
+    /// 스프레드시트용 작업 목록 (한 줄에 작업 하나).
+    /// Excel이 한글을 UTF-8로 읽도록 BOM을 붙입니다
+    pub fn to_csv(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record([
+            "단계", "작업 id", "작업", "설명", "우선순위", "규모", "담당",
+            "최소 시간", "예상 시간", "최대 시간", "선행 작업", "완료일", "실제 시간",
+        ])?;
+        
+        for phase in &self.phases {
+            for task in &phase.tasks {
+                let hours = |h: f32| if task.duration.is_zero() { String::new() } else { format!("{:.1}", h) };
+                writer.write_record([
+                    phase.name.clone(),
+                    task.id.clone(),
+                    task.name.clone(),
+                    task.description.clone(),
+                    task.priority.label().to_string(),
+                    task.effort.label().to_string(),
+                    task.assignee.clone().unwrap_or_default(),
+                    hours(task.duration.min_hours),
+                    hours(task.duration.likely_hours),
+                    hours(task.duration.max_hours),
+                    task.depends_on.join(", "),
+                    task.completed_at.map(|t| t.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+                    task.actual_hours.map(|h| format!("{:.1}", h)).unwrap_or_default(),
+                ])?;
+            }
+        }
+        
+        let data = writer.into_inner().map_err(|e| anyhow::anyhow!("CSV 생성 실패: {}", e))?;
+        Ok(format!("\u{feff}{}", String::from_utf8(data)?))
+    }
+    
+    /// Mermaid 간트 차트. 선행 작업은 `after`로, 주경로 작업은 `crit`로 표시합니다
+    pub fn to_mermaid(&self) -> Result<String> {
+        let schedule = self.schedule()?;
+        let mermaid_id = |id: &str| format!("t{}", id.replace('.', "_"));
+        let label = |text: &str| text.replace([':', ';', '#'], " ");
+        
+        let mut output = String::from("gantt\n");
+        output.push_str(&format!("    title {}\n", label(&self.title)));
+        output.push_str("    dateFormat YYYY-MM-DD\n");
+        let start_date = self.created_at.format("%Y-%m-%d").to_string();
+        
+        for phase in &self.phases {
+            output.push_str(&format!("    section {}\n", label(&phase.name)));
+            for task in &phase.tasks {
+                let mut tags = Vec::new();
+                if task.completed_at.is_some() {
+                    tags.push("done".to_string());
+                }
+                if schedule.is_critical(&task.id) {
+                    tags.push("crit".to_string());
+                }
+                tags.push(mermaid_id(&task.id));
+                let deps: Vec<String> = task.depends_on.iter()
+                    .filter(|id| **id != task.id && schedule.times.contains_key(*id))
+                    .map(|id| mermaid_id(id))
+                    .collect();
+                tags.push(if deps.is_empty() { start_date.clone() } else { format!("after {}", deps.join(" ")) });
+                // 간트 차트는 길이가 0인 막대를 그리지 않으므로 추정치가 없으면 1시간으로 표시
+                tags.push(format!("{}h", task.duration.likely_hours.max(1.0).round()));
+                output.push_str(&format!("    {} :{}\n", label(&task.name), tags.join(", ")));
+            }
+        }
+        
+        Ok(output)
     }
 }
 
 impl std::fmt::Display for Priority {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let text = match self {
-            Priority::Critical => "긴급".red().bold(),
-            Priority::High => "높음".yellow(),
-            Priority::Medium => "보통".normal(),
-            Priority::Low => "낮음".dimmed(),
+            Priority::Critical => self.label().red().bold(),
+            Priority::High => self.label().yellow(),
+            Priority::Medium => self.label().normal(),
+            Priority::Low => self.label().dimmed(),
         };
         write!(f, "{}", text)
     }
@@ -119,9 +259,30 @@ impl std::fmt::Display for Priority {
 impl std::fmt::Display for RiskLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let text = match self {
-            RiskLevel::High => "높음".red(),
-            RiskLevel::Medium => "중간".yellow(),
-            RiskLevel::Low => "낮음".green(),
+            RiskLevel::High => self.label().red(),
+            RiskLevel::Medium => self.label().yellow(),
+            RiskLevel::Low => self.label().green(),
+        };
+        write!(f, "{}", text)
+    }
+}
+
+impl RiskStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RiskStatus::Open => "열림",
+            RiskStatus::Mitigated => "완화됨",
+            RiskStatus::Occurred => "발생함",
+        }
+    }
+}
+
+impl std::fmt::Display for RiskStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            RiskStatus::Open => self.label().yellow(),
+            RiskStatus::Mitigated => self.label().green(),
+            RiskStatus::Occurred => self.label().red(),
         };
         write!(f, "{}", text)
     }
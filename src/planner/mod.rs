@@ -1,10 +1,16 @@
 mod types;
 mod formatter;
+mod parser;
+mod store;
+mod schedule;
 
 pub use types::*;
+pub use formatter::PLAN_FORMATS;
+pub use parser::{parse_plan, parse_duration};
+pub use store::{plans_dir, save_plan, list_plans, load_plan, import_plan, Calibration};
+pub use schedule::Schedule;
 
 use anyhow::Result;
-use chrono::Utc;
 use crate::config::Config;
 use crate::api::OpenAIClient;
 
@@ -19,39 +25,46 @@ impl ProjectPlanner {
     }
     
     pub async fn create_plan(&self, description: &str, detail_level: u8, include_estimates: bool) -> Result<ProjectPlan> {
+        let detail_level = detail_level.clamp(1, 5);
+        let include_estimates = include_estimates || detail_level >= 5;
         let prompt = self.build_prompt(description, detail_level, include_estimates);
-        let _response = self.client.query(&prompt).await?;
+        let response = self.client.query(&prompt).await?;
+        let mut plan = parse_plan(&response, description);
         
-        // 간단한 파싱 로직 (실제로는 더 정교한 파싱 필요)
-        let plan = ProjectPlan {
-            title: "프로젝트 계획".to_string(),
-            description: description.to_string(),
-            objectives: Vec::new(),
-            phases: Vec::new(),
-            milestones: Vec::new(),
-            risks: Vec::new(),
-            dependencies: Vec::new(),
-            total_duration: EstimatedDuration {
-                min_hours: 0.0,
-                max_hours: 0.0,
-                likely_hours: 0.0,
-            },
-            created_at: Utc::now(),
-        };
+        // 상세 수준에 필요한 구조가 빠졌으면 빠진 항목을 알려주고 한 번 더 요청
+        let missing = missing_for_detail(&plan, detail_level, include_estimates);
+        if !missing.is_empty() {
+            let retry_prompt = format!(
+                "{}\n\n이전 응답에 다음 항목이 빠져 있었습니다: {}. 형식을 지켜 계획 전체를 다시 작성해주세요.\n\n이전 응답:\n{}",
+                prompt, missing.join(", "), response
+            );
+            let retried = parse_plan(&self.client.query(&retry_prompt).await?, description);
+            if missing_for_detail(&retried, detail_level, include_estimates).len() < missing.len() {
+                plan = retried;
+            }
+        }
+        prune_to_detail(&mut plan, detail_level);
         
-        // AI 응답을 파싱하여 계획 구조 채우기
-        // TODO: 실제 파싱 로직 구현
+        // 완료한 계획으로 계산한 보정 계수가 있으면 추정치에 반영
+        if let Some(calibration) = Calibration::load() {
+            if !plan.total_duration.is_zero() {
+                plan.apply_calibration(calibration.factor);
+            }
+        }
         
         Ok(plan)
     }
     
+    /// 상세 수준별 구조: 1 단계만, 2 작업과 우선순위, 3 작업 설명과 완료 기준,
+    /// 4 하위 작업, 5 예상 시간·담당·필요 자원·테스트 전략
     fn build_prompt(&self, description: &str, detail_level: u8, include_estimates: bool) -> String {
         let mut prompt = String::new();
         
-        prompt.push_str("당신은 프로젝트 계획 전문가입니다. 다음 프로젝트에 대한 상세한 계획을 작성해주세요.\n\n");
+        prompt.push_str("당신은 프로젝트 계획 전문가입니다. 다음 프로젝트에 대한 계획을 작성해주세요.\n\n");
         prompt.push_str(&format!("프로젝트 설명: {}\n\n", description));
+        prompt.push_str(&format!("상세 수준: {}/5 — {}\n\n", detail_level, DETAIL_GUIDES[usize::from(detail_level) - 1]));
         
-        prompt.push_str("다음 형식으로 작성해주세요:\n\n");
+        prompt.push_str("다음 형식으로 작성하고, 형식에 없는 항목은 추가하지 마세요:\n\n");
         prompt.push_str("# 프로젝트명\n\n");
         prompt.push_str("## 목표\n");
         prompt.push_str("- 목표 1\n");
@@ -60,20 +73,35 @@ impl ProjectPlanner {
         prompt.push_str("## 단계별 계획\n");
         prompt.push_str("### 1단계: [단계명]\n");
         prompt.push_str("설명: ...\n");
+        
+        if detail_level == 1 {
+            if include_estimates {
+                prompt.push_str("예상 기간: X-Y시간\n");
+            }
+            prompt.push('\n');
+            return prompt;
+        }
+        
         prompt.push_str("작업:\n");
         prompt.push_str("- [ ] 작업 1 (우선순위: 높음)\n");
-        prompt.push_str("  - 설명: ...\n");
-        prompt.push_str("  - 완료 기준: ...\n");
-        
-        if detail_level >= 2 {
+        if detail_level >= 3 {
+            prompt.push_str("  - 설명: ...\n");
+            prompt.push_str("  - 완료 기준:\n");
+            prompt.push_str("    - 기준 1\n");
+            prompt.push_str("    - 기준 2\n");
+        }
+        if detail_level >= 4 {
             prompt.push_str("  - 하위 작업:\n");
             prompt.push_str("    - [ ] 세부 작업 1\n");
             prompt.push_str("    - [ ] 세부 작업 2\n");
         }
-        
         if include_estimates {
             prompt.push_str("  - 예상 소요 시간: X-Y시간\n");
         }
+        if detail_level >= 5 {
+            prompt.push_str("  - 담당: [역할]\n");
+        }
+        prompt.push_str("  - 선행 작업: 1.1, 1.2\n");
         
         prompt.push_str("\n## 마일스톤\n");
         prompt.push_str("- **M1**: [마일스톤명] - [설명]\n");
@@ -85,8 +113,93 @@ impl ProjectPlanner {
         prompt.push_str("  - 대응 방안: ...\n\n");
         
         prompt.push_str("## 의존성\n");
-        prompt.push_str("- [의존성 설명]\n");
+        prompt.push_str("- [의존성 설명]\n\n");
+        
+        if detail_level >= 5 {
+            prompt.push_str("## 필요 자원\n");
+            prompt.push_str("- [인력, 도구, 환경]\n\n");
+            prompt.push_str("## 테스트 전략\n");
+            prompt.push_str("- [테스트 종류와 범위]\n\n");
+        }
+        
+        prompt.push_str("작업 id는 `단계 번호.단계 안의 작업 순서`입니다(예: 2단계의 첫 작업은 2.1). ");
+        prompt.push_str("선행 작업에는 먼저 끝나야 하는 작업의 id만 쓰고, 없으면 그 줄을 생략하세요. ");
+        prompt.push_str("서로 기다릴 필요가 없는 작업은 선행 작업으로 묶지 마세요.\n");
         
         prompt
     }
-} 
\ No newline at end of file
+}
+
+/// 상세 수준(1-5)별로 모델에 알려주는 작성 범위
+const DETAIL_GUIDES: [&str; 5] = [
+    "단계와 단계 설명만 작성하고 작업 목록은 쓰지 마세요.",
+    "단계마다 작업 이름과 우선순위까지만 작성하세요.",
+    "작업마다 설명과 확인 가능한 완료 기준을 작성하세요.",
+    "작업마다 설명, 완료 기준, 하위 작업을 작성하세요.",
+    "작업마다 설명, 완료 기준, 하위 작업, 예상 시간, 담당 역할을 작성하고 필요 자원과 테스트 전략도 작성하세요.",
+];
+
+/// 파싱한 계획에서 상세 수준에 필요한데 빠진 구조
+fn missing_for_detail(plan: &ProjectPlan, detail_level: u8, include_estimates: bool) -> Vec<&'static str> {
+    let mut missing = Vec::new();
+    if plan.phases.is_empty() {
+        missing.push("단계");
+    }
+    if detail_level >= 2 && plan.phases.iter().any(|p| p.tasks.is_empty()) {
+        missing.push("단계별 작업");
+    }
+    if detail_level >= 3 && plan.tasks().any(|t| t.acceptance_criteria.is_empty()) {
+        missing.push("완료 기준");
+    }
+    if detail_level >= 4 && plan.tasks().all(|t| t.subtasks.is_empty()) {
+        missing.push("하위 작업");
+    }
+    let has_estimates = if detail_level == 1 {
+        plan.phases.iter().all(|p| !p.duration.is_zero())
+    } else {
+        plan.tasks().all(|t| !t.duration.is_zero())
+    };
+    if include_estimates && !has_estimates {
+        missing.push("예상 시간");
+    }
+    if detail_level >= 5 {
+        if plan.tasks().all(|t| t.assignee.is_none()) {
+            missing.push("담당");
+        }
+        if plan.resources.is_empty() {
+            missing.push("필요 자원");
+        }
+        if plan.test_strategy.is_empty() {
+            missing.push("테스트 전략");
+        }
+    }
+    missing
+}
+
+/// 모델이 상세 수준보다 많이 쓴 구조를 걷어 냅니다
+fn prune_to_detail(plan: &mut ProjectPlan, detail_level: u8) {
+    if detail_level == 1 {
+        for phase in &mut plan.phases {
+            phase.tasks.clear();
+        }
+        plan.milestones.clear();
+        plan.risks.clear();
+        plan.dependencies.clear();
+    }
+    for task in plan.phases.iter_mut().flat_map(|p| p.tasks.iter_mut()) {
+        if detail_level < 3 {
+            task.description.clear();
+            task.acceptance_criteria.clear();
+        }
+        if detail_level < 4 {
+            task.subtasks.clear();
+        }
+        if detail_level < 5 {
+            task.assignee = None;
+        }
+    }
+    if detail_level < 5 {
+        plan.resources.clear();
+        plan.test_strategy.clear();
+    }
+}
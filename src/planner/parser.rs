@@ -0,0 +1,298 @@
+use chrono::Utc;
+use regex::Regex;
+use std::sync::OnceLock;
+use crate::planner::types::*;
+
+/// 목록 줄의 위치 (들여쓰기 단계와 `- ` 뒤 내용)
+struct Item<'a> {
+    depth: usize,
+    text: &'a str,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    None,
+    Objectives,
+    Phases,
+    Milestones,
+    Risks,
+    Dependencies,
+    Resources,
+    TestStrategy,
+}
+
+/// 작업 아래 `- 완료 기준:`, `- 하위 작업:`처럼 하위 목록을 여는 항목
+#[derive(Clone, Copy, PartialEq)]
+enum TaskList {
+    None,
+    Criteria,
+    Subtasks,
+}
+
+/// 플래너 프롬프트 형식의 마크다운 응답을 `ProjectPlan`으로 읽습니다.
+/// 형식에서 벗어난 줄은 건너뛰고, 기간은 작업 추정치를 단계와 전체로 합산합니다
+pub fn parse_plan(response: &str, description: &str) -> ProjectPlan {
+    let mut plan = ProjectPlan {
+        id: String::new(),
+        title: "프로젝트 계획".to_string(),
+        description: description.to_string(),
+        objectives: Vec::new(),
+        phases: Vec::new(),
+        milestones: Vec::new(),
+        risks: Vec::new(),
+        dependencies: Vec::new(),
+        resources: Vec::new(),
+        test_strategy: Vec::new(),
+        total_duration: EstimatedDuration::zero(),
+        calibration_factor: None,
+        created_at: Utc::now(),
+    };
+    let mut section = Section::None;
+    let mut task_list = TaskList::None;
+
+    for line in response.lines() {
+        let trimmed = line.trim();
+        if let Some(title) = trimmed.strip_prefix("# ") {
+            plan.title = strip_brackets(title).to_string();
+            continue;
+        }
+        if let Some(heading) = trimmed.strip_prefix("## ") {
+            section = match heading.trim() {
+                h if h.contains("목표") => Section::Objectives,
+                h if h.contains("단계") => Section::Phases,
+                h if h.contains("마일스톤") => Section::Milestones,
+                h if h.contains("위험") => Section::Risks,
+                h if h.contains("의존") => Section::Dependencies,
+                h if h.contains("자원") => Section::Resources,
+                h if h.contains("테스트") => Section::TestStrategy,
+                _ => Section::None,
+            };
+            continue;
+        }
+        if let Some(heading) = trimmed.strip_prefix("### ") {
+            if section == Section::Phases {
+                let id = (plan.phases.len() + 1).to_string();
+                let name = heading.split_once(':').map_or(heading, |(_, name)| name);
+                plan.phases.push(Phase {
+                    id,
+                    name: strip_brackets(name.trim()).to_string(),
+                    description: String::new(),
+                    tasks: Vec::new(),
+                    duration: EstimatedDuration::zero(),
+                    dependencies: Vec::new(),
+                });
+            }
+            continue;
+        }
+
+        let Some(item) = list_item(line) else {
+            if let (Section::Phases, Some(phase)) = (section, plan.phases.last_mut()) {
+                if let Some(desc) = field(trimmed, "설명") {
+                    phase.description = desc.to_string();
+                } else if let Some(duration) = field(trimmed, "예상 기간").and_then(parse_duration) {
+                    phase.duration = duration;
+                }
+            }
+            continue;
+        };
+
+        match section {
+            Section::Objectives if item.depth == 0 => plan.objectives.push(item.text.to_string()),
+            Section::Resources if item.depth == 0 => plan.resources.push(item.text.to_string()),
+            Section::TestStrategy if item.depth == 0 => plan.test_strategy.push(item.text.to_string()),
+            Section::Dependencies if item.depth == 0 => plan.dependencies.push(Dependency {
+                name: strip_brackets(item.text).to_string(),
+                type_: DependencyType::Technical,
+                description: String::new(),
+                critical: false,
+            }),
+            Section::Phases => {
+                let Some(phase) = plan.phases.last_mut() else { continue };
+                if item.depth == 0 {
+                    let (name, priority) = task_heading(item.text);
+                    phase.tasks.push(Task {
+                        id: format!("{}.{}", phase.id, phase.tasks.len() + 1),
+                        name,
+                        description: String::new(),
+                        assignee: None,
+                        priority,
+                        effort: EffortLevel::Trivial,
+                        duration: EstimatedDuration::zero(),
+                        subtasks: Vec::new(),
+                        acceptance_criteria: Vec::new(),
+                        depends_on: Vec::new(),
+                        actual_hours: None,
+                        completed_at: None,
+                    });
+                    task_list = TaskList::None;
+                    continue;
+                }
+                let Some(task) = phase.tasks.last_mut() else { continue };
+                if item.depth == 1 {
+                    task_list = TaskList::None;
+                    if let Some(desc) = field(item.text, "설명") {
+                        task.description = desc.to_string();
+                    } else if let Some(criteria) = field(item.text, "완료 기준") {
+                        if criteria.is_empty() {
+                            task_list = TaskList::Criteria;
+                        } else {
+                            task.acceptance_criteria.push(criteria.to_string());
+                        }
+                    } else if let Some(assignee) = field(item.text, "담당") {
+                        task.assignee = Some(strip_brackets(assignee).to_string()).filter(|a| !a.is_empty());
+                    } else if let Some(deps) = field(item.text, "선행 작업") {
+                        task.depends_on = task_ids(deps);
+                    } else if field(item.text, "하위 작업").is_some() {
+                        task_list = TaskList::Subtasks;
+                    } else if let Some(hours) = field(item.text, "예상 소요 시간").or_else(|| field(item.text, "예상 소요")) {
+                        if let Some(duration) = parse_duration(hours) {
+                            task.effort = EffortLevel::from_hours(duration.likely_hours);
+                            task.duration = duration;
+                        }
+                    }
+                } else {
+                    match task_list {
+                        TaskList::Criteria => task.acceptance_criteria.push(item.text.to_string()),
+                        TaskList::Subtasks => {
+                            let (name, completed) = checkbox(item.text);
+                            task.subtasks.push(SubTask { name: name.to_string(), completed });
+                        }
+                        TaskList::None => {}
+                    }
+                }
+            }
+            Section::Milestones => {
+                if item.depth == 0 {
+                    let text = item.text.trim_start_matches("**");
+                    let (_, rest) = text.split_once("**").unwrap_or(("", text));
+                    let rest = rest.trim_start_matches(':').trim();
+                    let (name, desc) = rest.split_once(" - ").unwrap_or((rest, ""));
+                    plan.milestones.push(Milestone {
+                        name: strip_brackets(name).to_string(),
+                        description: strip_brackets(desc).to_string(),
+                        date: None,
+                        deliverables: Vec::new(),
+                    });
+                } else if let (Some(milestone), Some(deliverables)) =
+                    (plan.milestones.last_mut(), field(item.text, "산출물"))
+                {
+                    milestone.deliverables.extend(
+                        deliverables.split(',').map(str::trim).filter(|d| !d.is_empty() && *d != "...").map(str::to_string),
+                    );
+                }
+            }
+            Section::Risks => {
+                if item.depth == 0 {
+                    let text = field(item.text, "위험").unwrap_or(item.text);
+                    plan.risks.push(Risk {
+                        description: strip_brackets(text).to_string(),
+                        probability: RiskLevel::Medium,
+                        impact: RiskLevel::Medium,
+                        mitigation: String::new(),
+                        status: RiskStatus::Open,
+                    });
+                } else if let Some(risk) = plan.risks.last_mut() {
+                    if let Some(levels) = field(item.text, "확률/영향") {
+                        let mut parts = levels.split('/').map(str::trim);
+                        risk.probability = parts.next().map_or(RiskLevel::Medium, RiskLevel::from_label);
+                        risk.impact = parts.next().map_or(RiskLevel::Medium, RiskLevel::from_label);
+                    } else if let Some(mitigation) = field(item.text, "대응 방안").or_else(|| field(item.text, "대응")) {
+                        risk.mitigation = mitigation.to_string();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // 모델이 쓴 선행 작업 중 계획에 없는 id와 자기 자신은 버림
+    let ids: Vec<String> = plan.tasks().map(|t| t.id.clone()).collect();
+    for phase in &mut plan.phases {
+        for task in &mut phase.tasks {
+            let own = task.id.clone();
+            task.depends_on.retain(|id| *id != own && ids.contains(id));
+        }
+        // 작업이 없는 단계(상세 수준 1)는 단계에 적힌 예상 기간을 그대로 사용
+        if !phase.tasks.is_empty() {
+            phase.duration = EstimatedDuration::sum(phase.tasks.iter().map(|t| &t.duration));
+        }
+    }
+    plan.total_duration = EstimatedDuration::sum(plan.phases.iter().map(|p| &p.duration));
+    plan
+}
+
+/// `- 항목`, `* 항목`, `1. 항목`을 들여쓰기 단계와 함께 읽습니다 (2칸 = 1단계)
+fn list_item(line: &str) -> Option<Item<'_>> {
+    let indent = line.len() - line.trim_start().len();
+    let trimmed = line.trim_start();
+    let text = trimmed.strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| {
+            let (number, rest) = trimmed.split_once(". ")?;
+            number.chars().all(|c| c.is_ascii_digit()).then_some(rest)
+        })?;
+    Some(Item { depth: indent / 2, text: text.trim() })
+}
+
+/// `이름: 값`에서 값을 꺼냅니다 (`**이름**: 값`도 허용)
+fn field<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    let rest = text.strip_prefix(name)
+        .or_else(|| text.strip_prefix(&format!("**{}**", name)))?;
+    let value = rest.trim_start().strip_prefix(':')?;
+    Some(value.trim())
+}
+
+fn strip_brackets(text: &str) -> &str {
+    let text = text.trim();
+    text.strip_prefix('[').and_then(|t| t.strip_suffix(']')).unwrap_or(text)
+}
+
+/// 체크박스(`[ ]`, `[x]`)를 떼어 내고 완료 여부를 돌려줍니다
+fn checkbox(text: &str) -> (&str, bool) {
+    if let Some(rest) = text.strip_prefix("[ ]") {
+        (rest.trim(), false)
+    } else if let Some(rest) = text.strip_prefix("[x]").or_else(|| text.strip_prefix("[X]")) {
+        (rest.trim(), true)
+    } else {
+        (text, false)
+    }
+}
+
+/// `[ ] 작업 이름 (우선순위: 높음)`에서 이름과 우선순위를 꺼냅니다
+fn task_heading(text: &str) -> (String, Priority) {
+    let (name, _) = checkbox(text);
+    let name = name.trim_start_matches("**");
+    let name = name.replacen("**", "", 1);
+    match name.rfind('(') {
+        Some(open) if name[open..].contains("우선순위") => {
+            let label = name[open..].trim_matches(|c| c == '(' || c == ')');
+            let label = label.split_once(':').map_or(label, |(_, l)| l).trim();
+            (name[..open].trim().to_string(), Priority::from_label(label))
+        }
+        _ => (name.trim().to_string(), Priority::Medium),
+    }
+}
+
+/// `1.2, 2.1` 같은 작업 id 목록 ("없음" 등은 빈 목록)
+fn task_ids(text: &str) -> Vec<String> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| Regex::new(r"\b\d+\.\d+\b").expect("정규식 오류"));
+    re.find_iter(text).map(|m| m.as_str().to_string()).collect()
+}
+
+/// `4-8시간`, `6시간`, `2~3일`, `4h` 같은 추정치. 하루는 8시간으로 계산합니다
+pub fn parse_duration(text: &str) -> Option<EstimatedDuration> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(r"(\d+(?:\.\d+)?)\s*(?:[-~–]\s*(\d+(?:\.\d+)?))?\s*(시간|일|h|d|hours?|days?)").expect("정규식 오류")
+    });
+    let caps = re.captures(text)?;
+    let unit = match &caps[3] {
+        "일" | "d" | "day" | "days" => 8.0,
+        _ => 1.0,
+    };
+    let min: f32 = caps[1].parse::<f32>().ok()? * unit;
+    let max: f32 = caps.get(2).and_then(|m| m.as_str().parse::<f32>().ok()).map_or(min, |m| m * unit);
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+    Some(EstimatedDuration { min_hours: min, max_hours: max, likely_hours: (min + max) / 2.0 })
+}
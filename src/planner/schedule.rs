@@ -0,0 +1,71 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use crate::planner::types::*;
+
+/// 작업 의존성만 고려한(인원 제약 없는) 최소 일정
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    /// 작업 id별 (가장 이른 시작, 종료) 시각. 계획 시작 기준 시간 단위
+    pub times: HashMap<String, (f32, f32)>,
+    /// 주경로(critical path) 작업 id, 시작 순서
+    pub critical_path: Vec<String>,
+    /// 전체 완료까지 걸리는 최소 시간
+    pub total_hours: f32,
+}
+
+impl Schedule {
+    pub fn is_critical(&self, task_id: &str) -> bool {
+        self.critical_path.iter().any(|id| id == task_id)
+    }
+}
+
+impl ProjectPlan {
+    /// 작업별 가장 가능성 높은 시간(`likely_hours`)과 `depends_on`으로 가장 이른 시작/종료 시각과 주경로를 계산합니다.
+    /// 계획에 없는 작업 id는 무시하고, 순환 의존성은 오류로 알립니다
+    pub fn schedule(&self) -> Result<Schedule> {
+        let tasks: HashMap<&str, &Task> = self.tasks().map(|t| (t.id.as_str(), t)).collect();
+
+        // 계획 순서를 유지하며 선행 작업이 모두 끝난 작업부터 배치
+        let mut times: HashMap<String, (f32, f32)> = HashMap::new();
+        let mut pending: Vec<&Task> = self.tasks().collect();
+        while !pending.is_empty() {
+            let before = pending.len();
+            pending.retain(|task| {
+                let deps = known_deps(task, &tasks);
+                if !deps.iter().all(|id| times.contains_key(*id)) {
+                    return true;
+                }
+                let start = deps.iter().map(|id| times[*id].1).fold(0.0, f32::max);
+                times.insert(task.id.clone(), (start, start + task.duration.likely_hours));
+                false
+            });
+            if pending.len() == before {
+                let ids: Vec<&str> = pending.iter().map(|t| t.id.as_str()).collect();
+                anyhow::bail!("작업 의존성에 순환이 있어 일정을 계산할 수 없습니다 (관련 작업: {})", ids.join(", "));
+            }
+        }
+
+        // 가장 늦게 끝나는 작업에서 시작해 종료 시각이 가장 늦은 선행 작업을 따라 거슬러 올라감
+        let latest = |ids: &mut dyn Iterator<Item = &str>| -> Option<String> {
+            ids.max_by(|a, b| times[*a].1.total_cmp(&times[*b].1)).map(str::to_string)
+        };
+        let mut critical_path = Vec::new();
+        let mut current = latest(&mut self.tasks().map(|t| t.id.as_str()));
+        while let Some(id) = current {
+            current = latest(&mut known_deps(tasks[id.as_str()], &tasks).into_iter());
+            critical_path.push(id);
+        }
+        critical_path.reverse();
+
+        let total_hours = times.values().map(|(_, finish)| *finish).fold(0.0, f32::max);
+        Ok(Schedule { times, critical_path, total_hours })
+    }
+}
+
+/// 계획에 있는 선행 작업 id (자기 자신과 없는 id 제외)
+fn known_deps<'a>(task: &'a Task, tasks: &HashMap<&str, &Task>) -> Vec<&'a str> {
+    task.depends_on.iter()
+        .map(String::as_str)
+        .filter(|id| *id != task.id && tasks.contains_key(id))
+        .collect()
+}
@@ -0,0 +1,207 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use crate::planner::types::*;
+use crate::paths::data_dir;
+
+const CALIBRATION_FILE: &str = "calibration.json";
+
+/// 완료한 작업의 추정 대비 실제 시간으로 계산한 보정 계수
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Calibration {
+    /// 실제 시간 합 / 보정 전 추정 시간 합
+    pub factor: f32,
+    /// 계산에 사용한 완료 작업 수
+    pub samples: usize,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub fn plans_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("plans"))
+}
+
+/// 계획을 `<data_dir>/plans/<id>.json`에 저장합니다. id가 없으면 생성 시각으로 만듭니다
+pub fn save_plan(plan: &mut ProjectPlan) -> Result<PathBuf> {
+    let dir = plans_dir()?;
+    std::fs::create_dir_all(&dir).context("계획 디렉토리 생성 실패")?;
+    check_id(&plan.id)?;
+    if plan.id.is_empty() {
+        let base = plan.created_at.format("%Y%m%d-%H%M%S").to_string();
+        plan.id = base.clone();
+        let mut suffix = 2;
+        while dir.join(format!("{}.json", plan.id)).exists() {
+            plan.id = format!("{}-{}", base, suffix);
+            suffix += 1;
+        }
+    }
+    let path = dir.join(format!("{}.json", plan.id));
+    std::fs::write(&path, serde_json::to_string_pretty(plan)?)
+        .with_context(|| format!("계획 저장 실패: {}", path.display()))?;
+    Ok(path)
+}
+
+/// 저장된 계획 전체 (생성 순서)
+pub fn list_plans() -> Result<Vec<ProjectPlan>> {
+    let dir = plans_dir()?;
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut plans: Vec<ProjectPlan> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json") && !p.ends_with(CALIBRATION_FILE))
+        .filter_map(|p| std::fs::read_to_string(p).ok())
+        .filter_map(|data| serde_json::from_str(&data).ok())
+        .collect();
+    plans.sort_by_key(|p| p.created_at);
+    Ok(plans)
+}
+
+/// id 또는 id 앞부분으로 저장된 계획을 찾습니다
+pub fn load_plan(id: &str) -> Result<ProjectPlan> {
+    let mut matches: Vec<ProjectPlan> = list_plans()?.into_iter().filter(|p| p.id.starts_with(id)).collect();
+    if let Some(exact) = matches.iter().position(|p| p.id == id) {
+        return Ok(matches.swap_remove(exact));
+    }
+    match matches.len() {
+        0 => anyhow::bail!("저장된 계획을 찾을 수 없습니다: {} (`ricci plan list`로 확인)", id),
+        1 => Ok(matches.remove(0)),
+        _ => {
+            let ids: Vec<&str> = matches.iter().map(|p| p.id.as_str()).collect();
+            anyhow::bail!("여러 계획이 일치합니다: {}", ids.join(", "))
+        }
+    }
+}
+
+/// 손으로 고친 YAML/JSON 계획을 읽어 검증하고 저장합니다.
+/// 파일에 저장된 계획의 `id`가 있으면 그 계획을 갱신하고(true), 없으면 새 계획으로 저장합니다(false)
+pub fn import_plan(path: &Path) -> Result<(ProjectPlan, bool)> {
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("계획 파일을 읽을 수 없습니다: {}", path.display()))?;
+    // JSON도 YAML로 읽을 수 있음
+    let mut plan: ProjectPlan = serde_yaml::from_str(&data)
+        .with_context(|| format!("계획 파일 형식 오류: {}", path.display()))?;
+    plan.normalize()?;
+    check_id(&plan.id)?;
+
+    let existing = !plan.id.is_empty() && plans_dir()?.join(format!("{}.json", plan.id)).exists();
+    save_plan(&mut plan)?;
+    Ok((plan, existing))
+}
+
+/// 계획 id는 파일 이름이 되므로 글자, 숫자, `-`, `_`, `.`만 허용하고 보정 계수 파일 이름은 쓸 수 없음 (빈 id는 새 계획)
+fn check_id(id: &str) -> Result<()> {
+    let valid = id.is_empty()
+        || (!id.starts_with('.')
+            && id.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            && format!("{}.json", id) != CALIBRATION_FILE);
+    anyhow::ensure!(valid, "계획 id에는 글자, 숫자, '-', '_', '.'만 쓸 수 있습니다 (calibration 제외): {}", id);
+    Ok(())
+}
+
+impl Calibration {
+    /// 저장된 보정 계수 (없거나 읽을 수 없으면 None)
+    pub fn load() -> Option<Self> {
+        let path = plans_dir().ok()?.join(CALIBRATION_FILE);
+        serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let dir = plans_dir()?;
+        std::fs::create_dir_all(&dir).context("계획 디렉토리 생성 실패")?;
+        std::fs::write(dir.join(CALIBRATION_FILE), serde_json::to_string_pretty(self)?)
+            .context("보정 계수 저장 실패")
+    }
+
+    pub fn reset() -> Result<()> {
+        let path = plans_dir()?.join(CALIBRATION_FILE);
+        if path.exists() {
+            std::fs::remove_file(path).context("보정 계수 삭제 실패")?;
+        }
+        Ok(())
+    }
+}
+
+impl ProjectPlan {
+    /// 모든 단계의 작업
+    pub fn tasks(&self) -> impl Iterator<Item = &Task> {
+        self.phases.iter().flat_map(|p| p.tasks.iter())
+    }
+
+    /// 작업 id(`2.1`) 또는 이름 일부로 작업을 찾습니다
+    pub fn find_task_mut(&mut self, query: &str) -> Result<&mut Task> {
+        let query_lower = query.to_lowercase();
+        let candidates: Vec<(usize, usize)> = self.phases.iter().enumerate()
+            .flat_map(|(pi, phase)| phase.tasks.iter().enumerate().map(move |(ti, task)| (pi, ti, task)))
+            .filter(|(_, _, task)| task.id == query || task.name.to_lowercase().contains(&query_lower))
+            .map(|(pi, ti, _)| (pi, ti))
+            .collect();
+        let exact = candidates.iter().find(|&&(pi, ti)| self.phases[pi].tasks[ti].id == query).copied();
+        let (pi, ti) = match (exact, candidates.as_slice()) {
+            (Some(found), _) | (None, &[found]) => found,
+            (None, []) => anyhow::bail!("계획 {}에서 작업을 찾을 수 없습니다: {}", self.id, query),
+            (None, _) => anyhow::bail!("여러 작업이 일치합니다. 작업 id로 지정해주세요: {}", query),
+        };
+        Ok(&mut self.phases[pi].tasks[ti])
+    }
+
+    /// 빠진 id를 채우고 기간 합계와 규모를 다시 계산한 뒤 중복 id, 없는 선행 작업, 순환 의존성을 검사합니다
+    pub fn normalize(&mut self) -> Result<()> {
+        if self.title.trim().is_empty() {
+            anyhow::bail!("title이 비어 있습니다");
+        }
+        for (pi, phase) in self.phases.iter_mut().enumerate() {
+            if phase.id.is_empty() {
+                phase.id = (pi + 1).to_string();
+            }
+            for (ti, task) in phase.tasks.iter_mut().enumerate() {
+                if task.id.is_empty() {
+                    task.id = format!("{}.{}", phase.id, ti + 1);
+                }
+                task.effort = EffortLevel::from_hours(task.duration.likely_hours);
+            }
+            if !phase.tasks.is_empty() {
+                phase.duration = EstimatedDuration::sum(phase.tasks.iter().map(|t| &t.duration));
+            }
+        }
+        self.total_duration = EstimatedDuration::sum(self.phases.iter().map(|p| &p.duration));
+
+        let mut seen = HashSet::new();
+        for task in self.tasks() {
+            if !seen.insert(task.id.as_str()) {
+                anyhow::bail!("작업 id가 중복됩니다: {}", task.id);
+            }
+        }
+        for task in self.tasks() {
+            let unknown: Vec<&str> = task.depends_on.iter()
+                .map(String::as_str)
+                .filter(|id| !seen.contains(id))
+                .collect();
+            if !unknown.is_empty() {
+                anyhow::bail!("작업 {}의 선행 작업이 계획에 없습니다: {}", task.id, unknown.join(", "));
+            }
+        }
+        self.schedule()?;
+        Ok(())
+    }
+
+    /// 보정을 적용하기 전 모델이 낸 원래 추정치
+    pub fn raw_estimate(&self, task: &Task) -> EstimatedDuration {
+        task.duration.scaled(1.0 / self.calibration_factor.unwrap_or(1.0))
+    }
+
+    /// 모든 작업·단계·전체 추정치에 보정 계수를 적용합니다
+    pub fn apply_calibration(&mut self, factor: f32) {
+        for phase in &mut self.phases {
+            for task in &mut phase.tasks {
+                task.duration = task.duration.scaled(factor);
+                task.effort = EffortLevel::from_hours(task.duration.likely_hours);
+            }
+            phase.duration = phase.duration.scaled(factor);
+        }
+        self.total_duration = self.total_duration.scaled(factor);
+        self.calibration_factor = Some(factor);
+    }
+}
@@ -3,87 +3,240 @@ use chrono::{DateTime, Utc};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectPlan {
+    /// 저장된 계획의 식별자 (`<data_dir>/plans/<id>.json`, 저장 전에는 빈 값)
+    #[serde(default)]
+    pub id: String,
     pub title: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub objectives: Vec<String>,
+    #[serde(default)]
     pub phases: Vec<Phase>,
+    #[serde(default)]
     pub milestones: Vec<Milestone>,
+    #[serde(default)]
     pub risks: Vec<Risk>,
+    #[serde(default)]
     pub dependencies: Vec<Dependency>,
+    /// 필요한 인력, 도구, 환경 (상세 수준 5)
+    #[serde(default)]
+    pub resources: Vec<String>,
+    /// 테스트 전략 (상세 수준 5)
+    #[serde(default)]
+    pub test_strategy: Vec<String>,
+    #[serde(default)]
     pub total_duration: EstimatedDuration,
+    /// 생성할 때 추정치에 곱한 보정 계수 (`ricci plan calibrate`)
+    #[serde(default)]
+    pub calibration_factor: Option<f32>,
+    #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Phase {
+    #[serde(default)]
     pub id: String,
     pub name: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub tasks: Vec<Task>,
+    #[serde(default)]
     pub duration: EstimatedDuration,
+    #[serde(default)]
     pub dependencies: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
+    #[serde(default)]
     pub id: String,
     pub name: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub assignee: Option<String>,
+    #[serde(default)]
     pub priority: Priority,
+    #[serde(default)]
     pub effort: EffortLevel,
+    #[serde(default)]
     pub duration: EstimatedDuration,
+    #[serde(default)]
     pub subtasks: Vec<SubTask>,
+    #[serde(default)]
     pub acceptance_criteria: Vec<String>,
+    /// 먼저 끝나야 하는 작업 id (`1.2` 형식)
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// 완료할 때 기록한 실제 소요 시간
+    #[serde(default)]
+    pub actual_hours: Option<f32>,
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubTask {
     pub name: String,
+    #[serde(default)]
     pub completed: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Milestone {
     pub name: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub date: Option<DateTime<Utc>>,
+    #[serde(default)]
     pub deliverables: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Risk {
     pub description: String,
+    #[serde(default)]
     pub probability: RiskLevel,
+    #[serde(default)]
     pub impact: RiskLevel,
+    #[serde(default)]
     pub mitigation: String,
+    #[serde(default)]
+    pub status: RiskStatus,
+}
+
+/// 위험 관리 대장에서의 상태 (`ricci plan risks`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum RiskStatus {
+    #[default]
+    #[serde(alias = "open", alias = "열림")]
+    Open,
+    #[serde(alias = "mitigated", alias = "완화됨")]
+    Mitigated,
+    #[serde(alias = "occurred", alias = "발생함")]
+    Occurred,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dependency {
     pub name: String,
+    #[serde(default)]
     pub type_: DependencyType,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub critical: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(try_from = "DurationInput")]
 pub struct EstimatedDuration {
     pub min_hours: f32,
     pub max_hours: f32,
     pub likely_hours: f32,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+/// YAML을 손으로 고칠 때 쓸 수 있는 기간 표기: `"4-8시간"`, `6`(시간), 또는 min/max(/likely) 필드
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DurationInput {
+    Hours(f32),
+    Text(String),
+    Range {
+        min_hours: f32,
+        max_hours: f32,
+        likely_hours: Option<f32>,
+    },
+}
+
+impl TryFrom<DurationInput> for EstimatedDuration {
+    type Error = String;
+
+    fn try_from(input: DurationInput) -> Result<Self, Self::Error> {
+        let duration = match input {
+            DurationInput::Hours(hours) => Self { min_hours: hours, max_hours: hours, likely_hours: hours },
+            DurationInput::Text(text) => crate::planner::parse_duration(&text)
+                .ok_or_else(|| format!("기간 형식이 올바르지 않습니다: {} (예: 4-8시간, 2일, 6h)", text))?,
+            DurationInput::Range { min_hours, max_hours, likely_hours } => Self {
+                min_hours,
+                max_hours,
+                likely_hours: likely_hours.unwrap_or((min_hours + max_hours) / 2.0),
+            },
+        };
+        if duration.min_hours < 0.0 || duration.min_hours > duration.max_hours {
+            return Err(format!("min_hours({})는 0 이상이고 max_hours({}) 이하여야 합니다", duration.min_hours, duration.max_hours));
+        }
+        Ok(duration)
+    }
+}
+
+impl EstimatedDuration {
+    pub fn zero() -> Self {
+        Self { min_hours: 0.0, max_hours: 0.0, likely_hours: 0.0 }
+    }
+
+    pub fn sum<'a>(durations: impl Iterator<Item = &'a EstimatedDuration>) -> Self {
+        durations.fold(Self::zero(), |acc, d| Self {
+            min_hours: acc.min_hours + d.min_hours,
+            max_hours: acc.max_hours + d.max_hours,
+            likely_hours: acc.likely_hours + d.likely_hours,
+        })
+    }
+
+    pub fn scaled(&self, factor: f32) -> Self {
+        Self {
+            min_hours: self.min_hours * factor,
+            max_hours: self.max_hours * factor,
+            likely_hours: self.likely_hours * factor,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.max_hours <= 0.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub enum Priority {
+    #[serde(alias = "critical", alias = "긴급")]
     Critical,
+    #[serde(alias = "high", alias = "높음")]
     High,
+    #[default]
+    #[serde(alias = "medium", alias = "보통")]
     Medium,
+    #[serde(alias = "low", alias = "낮음")]
     Low,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+impl Priority {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::Critical => "긴급",
+            Priority::High => "높음",
+            Priority::Medium => "보통",
+            Priority::Low => "낮음",
+        }
+    }
+
+    /// 계획 응답의 우선순위 표기 (긴급/높음/보통/낮음, 영문 포함)
+    pub fn from_label(label: &str) -> Self {
+        match label.trim().to_lowercase().as_str() {
+            "긴급" | "critical" => Priority::Critical,
+            "높음" | "high" => Priority::High,
+            "낮음" | "low" => Priority::Low,
+            _ => Priority::Medium,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub enum EffortLevel {
+    #[default]
     Trivial,  // < 2 hours
     Small,    // 2-8 hours
     Medium,   // 1-3 days
@@ -91,15 +244,60 @@ pub enum EffortLevel {
     Epic,     // > 10 days
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+impl EffortLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EffortLevel::Trivial => "아주 작음",
+            EffortLevel::Small => "작음",
+            EffortLevel::Medium => "보통",
+            EffortLevel::Large => "큼",
+            EffortLevel::Epic => "매우 큼",
+        }
+    }
+
+    pub fn from_hours(hours: f32) -> Self {
+        match hours {
+            h if h < 2.0 => EffortLevel::Trivial,
+            h if h <= 8.0 => EffortLevel::Small,
+            h if h <= 24.0 => EffortLevel::Medium,
+            h if h <= 80.0 => EffortLevel::Large,
+            _ => EffortLevel::Epic,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub enum RiskLevel {
+    #[serde(alias = "low", alias = "낮음")]
     Low,
+    #[default]
+    #[serde(alias = "medium", alias = "중간")]
     Medium,
+    #[serde(alias = "high", alias = "높음")]
     High,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+impl RiskLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RiskLevel::High => "높음",
+            RiskLevel::Medium => "중간",
+            RiskLevel::Low => "낮음",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Self {
+        match label.trim().to_lowercase().as_str() {
+            "높음" | "high" => RiskLevel::High,
+            "낮음" | "low" => RiskLevel::Low,
+            _ => RiskLevel::Medium,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub enum DependencyType {
+    #[default]
     Technical,
     Resource,
     External,
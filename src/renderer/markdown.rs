@@ -7,10 +7,14 @@ use syntect::util::as_24_bit_terminal_escaped;
 use termimad::*;
 use termimad::crossterm::style::Color as CrosstermColor;
 use std::io::Write;
+use super::plain::{is_plain, plain_text};
+
+pub const DEFAULT_THEME: &str = "base16-monokai.dark";
 
 pub struct MarkdownRenderer {
     syntax_set: SyntaxSet,
     theme_set: ThemeSet,
+    theme: String,
     mad_skin: MadSkin,
 }
 
@@ -22,6 +26,11 @@ impl Default for MarkdownRenderer {
 
 impl MarkdownRenderer {
     pub fn new() -> Self {
+        Self::with_theme(DEFAULT_THEME)
+    }
+    
+    /// 코드 블록 구문 강조에 사용할 syntect 테마를 지정합니다
+    pub fn with_theme(theme: &str) -> Self {
         let syntax_set = SyntaxSet::load_defaults_newlines();
         let theme_set = ThemeSet::load_defaults();
         
@@ -37,13 +46,16 @@ impl MarkdownRenderer {
         Self {
             syntax_set,
             theme_set,
+            theme: theme.to_string(),
             mad_skin,
         }
     }
     
     pub fn render_chunk(&self, text: &str) -> Result<()> {
-        // 코드 블록 처리
-        if text.contains("```") {
+        // 접근성 모드에서는 스타일 없이 원문 그대로
+        if is_plain() {
+            print!("{}", plain_text(text));
+        } else if text.contains("```") {
             self.render_with_code_blocks(text)?;
         } else {
             // 일반 텍스트는 그대로 출력
@@ -94,7 +106,15 @@ impl MarkdownRenderer {
         Ok(())
     }
     
+    pub fn available_themes() -> Vec<String> {
+        ThemeSet::load_defaults().themes.keys().cloned().collect()
+    }
+    
     pub fn render(&self, text: &str) -> Result<()> {
+        if is_plain() {
+            println!("{}", plain_text(text));
+            return Ok(());
+        }
         self.render_markdown(text)
     }
     
@@ -109,7 +129,8 @@ impl MarkdownRenderer {
             .or_else(|| self.syntax_set.find_syntax_by_extension(language))
             .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
         
-        let theme = &self.theme_set.themes["base16-monokai.dark"];
+        let theme = self.theme_set.themes.get(&self.theme)
+            .unwrap_or(&self.theme_set.themes[DEFAULT_THEME]);
         let mut highlighter = HighlightLines::new(syntax, theme);
         
         println!("\n{}", format!("```{}", language).dimmed());
@@ -1,3 +1,7 @@
 mod markdown;
+mod plain;
+mod sink;
 
-pub use markdown::MarkdownRenderer; 
\ No newline at end of file
+pub use markdown::{MarkdownRenderer, DEFAULT_THEME};
+pub use plain::{dumb_terminal, enable_plain_mode, is_plain, plain, plain_text};
+pub use sink::{JsonEmitter, NoticeLevel, NullSink, OutputFormat, OutputSink, PlainWriter};
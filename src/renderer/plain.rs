@@ -0,0 +1,87 @@
+use colored::*;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// 이모지, 상자 그리기 문자, 스플래시 아트와 색상을 끄는 접근성(plain) 모드를 켭니다.
+/// 화면 낭독기와 `TERM=dumb` 터미널에서 읽기 쉬운 출력을 위해 사용합니다
+pub fn enable_plain_mode() {
+    PLAIN.store(true, Ordering::Relaxed);
+    colored::control::set_override(false);
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// `TERM=dumb`이면 설정과 관계없이 plain 모드를 사용합니다
+pub fn dumb_terminal() -> bool {
+    std::env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+/// plain 모드에서 기호를 ASCII로 바꾸고 장식용 이모지를 지웁니다 (일반 모드에서는 그대로)
+pub fn plain_text(text: &str) -> Cow<'_, str> {
+    if !is_plain() || text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match replacement(c) {
+            Some(ascii) => result.push_str(ascii),
+            None if is_decoration(c) => {
+                // 이모지 뒤에 붙은 변형 선택자, 결합 문자와 띄어쓰기 하나까지 함께 제거
+                while chars.next_if(|&next| matches!(next, '\u{FE0F}' | '\u{200D}') || is_decoration(next)).is_some() {}
+                if result.is_empty() || result.ends_with([' ', '\n', '[', '(']) {
+                    chars.next_if_eq(&' ');
+                }
+            }
+            None => result.push(c),
+        }
+    }
+    Cow::Owned(result)
+}
+
+/// `plain_text`를 거친 문자열. `"✓".green()` 대신 `plain("✓").green()`처럼 색상과 함께 씁니다
+pub fn plain(text: &str) -> ColoredString {
+    plain_text(text).as_ref().normal()
+}
+
+/// 뜻이 있는 기호의 ASCII 대체 문자
+fn replacement(c: char) -> Option<&'static str> {
+    Some(match c {
+        '✓' | '✔' => "OK",
+        '✗' | '✘' => "X",
+        '⚠' => "!",
+        'ℹ' => "i",
+        '→' => "->",
+        '←' => "<-",
+        '↑' => "^",
+        '↓' => "v",
+        '•' | '▪' => "-",
+        '▸' | '❯' | '▶' => ">",
+        '●' | '★' => "*",
+        '⟳' => "...",
+        '═' => "=",
+        '─' | '━' => "-",
+        '│' | '┃' | '▌' => "|",
+        '└' | '├' | '┌' | '┐' | '┘' | '┤' | '┬' | '┴' | '┼' => "+",
+        '█' => "#",
+        '░' => ".",
+        _ => return None,
+    })
+}
+
+/// 읽어도 뜻이 없는 그림 문자 (이모지, 변형 선택자 등)
+fn is_decoration(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF
+        | 0x2190..=0x21FF
+        | 0x2300..=0x23FF
+        | 0x25A0..=0x25FF
+        | 0x2600..=0x27BF
+        | 0x2B00..=0x2BFF
+        | 0xFE0F
+        | 0x200D)
+}
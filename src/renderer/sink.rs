@@ -0,0 +1,196 @@
+use anyhow::Result;
+use colored::*;
+use serde::Serialize;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use super::markdown::MarkdownRenderer;
+use super::plain::{plain, plain_text};
+
+/// 안내 문구의 수준
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoticeLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// 어시스턴트와 핸들러의 출력 대상. 터미널, 일반 텍스트, JSON 줄 등으로 바꿔 끼울 수 있습니다
+pub trait OutputSink: Send + Sync {
+    /// 스트리밍 응답 조각
+    fn chunk(&self, text: &str) -> Result<()>;
+    /// 모델의 추론 조각 (펼쳐보기를 켰을 때만 호출)
+    fn reasoning(&self, text: &str) -> Result<()>;
+    /// 한 번에 완성된 마크다운 문서 (리뷰 결과 등)
+    fn document(&self, markdown: &str) -> Result<()>;
+    /// 상태 안내, 경고, 오류 한 줄
+    fn notice(&self, level: NoticeLevel, message: &str);
+    /// 스트리밍 응답 하나가 끝남
+    fn end(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 터미널 출력: 응답은 구문 강조한 마크다운, 경고와 오류는 표준 에러
+impl OutputSink for MarkdownRenderer {
+    fn chunk(&self, text: &str) -> Result<()> {
+        self.render_chunk(text)
+    }
+
+    fn reasoning(&self, text: &str) -> Result<()> {
+        print!("{}", text.dimmed());
+        std::io::stdout().flush()?;
+        Ok(())
+    }
+
+    /// 문서는 마크다운 원문 그대로 출력 (파이프로 저장해도 그대로 쓸 수 있게)
+    fn document(&self, markdown: &str) -> Result<()> {
+        println!("\n{}", plain(markdown));
+        Ok(())
+    }
+
+    fn notice(&self, level: NoticeLevel, message: &str) {
+        match level {
+            NoticeLevel::Info => println!("{}", message),
+            NoticeLevel::Warning => eprintln!("{}", message.yellow()),
+            NoticeLevel::Error => eprintln!("{}", message.red()),
+        }
+    }
+
+    fn end(&self) -> Result<()> {
+        println!();
+        Ok(())
+    }
+}
+
+/// 스타일 없는 텍스트를 쓰는 출력 (파일, 파이프용). 추론과 안내는 쓰지 않고 경고/오류만 표준 에러로 보냄
+pub struct PlainWriter<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> PlainWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer: Mutex::new(writer) }
+    }
+}
+
+impl<W: Write + Send> OutputSink for PlainWriter<W> {
+    fn chunk(&self, text: &str) -> Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        write!(writer, "{}", plain_text(text))?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn reasoning(&self, _text: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn document(&self, markdown: &str) -> Result<()> {
+        writeln!(self.writer.lock().unwrap(), "{}", plain_text(markdown))?;
+        Ok(())
+    }
+
+    fn notice(&self, level: NoticeLevel, message: &str) {
+        if level != NoticeLevel::Info {
+            eprintln!("{}", message);
+        }
+    }
+
+    fn end(&self) -> Result<()> {
+        writeln!(self.writer.lock().unwrap())?;
+        Ok(())
+    }
+}
+
+/// 출력 하나를 JSON 한 줄로 씁니다 (`{"type": "chunk", "text": ...}` 등). 다른 프로그램이 읽기 위한 형식
+pub struct JsonEmitter<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum JsonEvent<'a> {
+    Chunk { text: &'a str },
+    Reasoning { text: &'a str },
+    Document { markdown: &'a str },
+    Notice { level: NoticeLevel, message: &'a str },
+    End,
+}
+
+impl<W: Write + Send> JsonEmitter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer: Mutex::new(writer) }
+    }
+
+    fn emit(&self, event: JsonEvent) -> Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        writeln!(writer, "{}", serde_json::to_string(&event)?)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> OutputSink for JsonEmitter<W> {
+    fn chunk(&self, text: &str) -> Result<()> {
+        self.emit(JsonEvent::Chunk { text })
+    }
+
+    fn reasoning(&self, text: &str) -> Result<()> {
+        self.emit(JsonEvent::Reasoning { text })
+    }
+
+    fn document(&self, markdown: &str) -> Result<()> {
+        self.emit(JsonEvent::Document { markdown })
+    }
+
+    fn notice(&self, level: NoticeLevel, message: &str) {
+        // 안내를 쓰지 못해도 본 작업은 계속함
+        let _ = self.emit(JsonEvent::Notice { level, message });
+    }
+
+    fn end(&self) -> Result<()> {
+        self.emit(JsonEvent::End)
+    }
+}
+
+/// 아무것도 출력하지 않음 (라이브러리에서 반환값만 쓸 때)
+pub struct NullSink;
+
+impl OutputSink for NullSink {
+    fn chunk(&self, _text: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn reasoning(&self, _text: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn document(&self, _markdown: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn notice(&self, _level: NoticeLevel, _message: &str) {}
+}
+
+/// `--output-format`으로 고르는 표준 출력 형식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// 색상과 구문 강조가 있는 터미널 출력
+    #[default]
+    Terminal,
+    /// 스타일 없는 텍스트
+    Plain,
+    /// 출력 하나당 JSON 한 줄
+    Json,
+}
+
+impl OutputFormat {
+    pub fn sink(self, theme: &str) -> Arc<dyn OutputSink> {
+        match self {
+            Self::Terminal => Arc::new(MarkdownRenderer::with_theme(theme)),
+            Self::Plain => Arc::new(PlainWriter::new(std::io::stdout())),
+            Self::Json => Arc::new(JsonEmitter::new(std::io::stdout())),
+        }
+    }
+}
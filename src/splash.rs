@@ -1,38 +1,55 @@
 use colored::*;
 use figlet_rs::FIGfont;
 use std::io::{self, Write};
+use std::path::Path;
+use crate::assistant::expand_home;
+use crate::config::OutputPreferences;
+use crate::renderer::{is_plain, plain};
 
-pub fn display_splash() -> io::Result<()> {
-    // Clear screen
-    print!("\x1B[2J\x1B[1;1H");
+const DEFAULT_BANNER: &str = "RICCI CLI";
+
+pub fn display_splash(prefs: &OutputPreferences) -> io::Result<()> {
+    let banner = prefs.banner.as_deref().unwrap_or(DEFAULT_BANNER);
+    if !prefs.show_splash {
+        // tmux 창이나 스크립트에서는 화면을 지우지 않고 한 줄 제목만
+        print_title(banner);
+        return Ok(());
+    }
     
-    // Ricci CLI 타이틀을 FIGlet으로 표시
-    if let Ok(standard_font) = FIGfont::standard() {
-        if let Some(figure) = standard_font.convert("RICCI CLI") {
-            println!("{}", figure.to_string().cyan().bold());
+    if is_plain() {
+        // 접근성 모드: 화면 지우기와 아트 없이 제목만
+        println!("{} - AI CLI by IT신기술융합팀", banner);
+    } else {
+        // Clear screen
+        print!("\x1B[2J\x1B[1;1H");
+        
+        // 배너를 FIGlet으로 표시 (글꼴에 없는 문자라 변환할 수 없으면 글자 그대로)
+        match load_font(prefs.banner_font.as_deref()).and_then(|font| font.convert(banner).map(|figure| figure.to_string())) {
+            Some(figure) => println!("{}", figure.cyan().bold()),
+            None => println!("\n  {}", banner.cyan().bold()),
         }
+        
+        println!("\n{}", "═══════════════════════════════════════════════════════════════════════".bright_blue());
+        println!("{}", "    AI CLI by IT신기술융합팀".bright_magenta());
+        println!("{}", "═══════════════════════════════════════════════════════════════════════".bright_blue());
     }
     
-    println!("\n{}", "═══════════════════════════════════════════════════════════════════════".bright_blue());
-    println!("{}", "    AI CLI by IT신기술융합팀".bright_magenta());
-    println!("{}", "═══════════════════════════════════════════════════════════════════════".bright_blue());
-    
     // 사용법
-    println!("\n  {} 사용법:", "▸".bright_yellow());
+    println!("\n  {} 사용법:", plain("▸").bright_yellow());
     println!("    {}        - 셸 명령어(예: ls, cargo build)를 바로 실행합니다.", "명령어 입력".bright_cyan());
     println!("    {} 또는 {}      - AI와 대화하는 '대화 모드'로 전환합니다.", "c".bright_green(), "chat".bright_green());
     println!("    {} 또는 {}        - 도움말을 확인합니다.", "h".bright_magenta(), "/help".bright_magenta());
     println!("    {} 또는 {}     - 현재 대화로 작업 계획을 요약합니다.", "p".bright_cyan(), "/summary".bright_cyan());
 
     // 자동완성 안내
-    println!("\n  {} 자동완성:", "▸".bright_yellow());
-    println!("    {} (오른쪽 화살표)    - 입력 중 회색으로 표시되는 명령어를 완성합니다.", "→".bright_white());
+    println!("\n  {} 자동완성:", plain("▸").bright_yellow());
+    println!("    {} (오른쪽 화살표)    - 입력 중 회색으로 표시되는 명령어를 완성합니다.", plain("→").bright_white());
     println!("    {}                - 가능한 명령어 목록을 확인합니다.", "Tab".bright_white());
 
 
     // 버전 정보
     println!("\n  {} Version {} | {}를 입력하여 대화를 종료합니다.", 
-        "▸".bright_yellow(),
+        plain("▸").bright_yellow(),
         env!("CARGO_PKG_VERSION").bright_white(),
         "exit".bright_cyan()
     );
@@ -41,9 +58,24 @@ pub fn display_splash() -> io::Result<()> {
     Ok(())
 }
 
-pub fn display_mini_splash() {
+pub fn display_mini_splash(prefs: &OutputPreferences) {
+    print_title(prefs.banner.as_deref().unwrap_or(DEFAULT_BANNER));
+}
+
+fn print_title(banner: &str) {
     println!("{} - {}", 
-        "RICCI CLI".cyan().bold(),
+        banner.cyan().bold(),
         "AI Development Assistant".bright_magenta()
     );
+}
+
+/// 설정한 FIGlet 글꼴 파일을 읽습니다. 지정하지 않았거나 읽을 수 없으면 내장 standard 글꼴
+fn load_font(path: Option<&Path>) -> Option<FIGfont> {
+    if let Some(path) = path {
+        match FIGfont::from_file(&expand_home(path).to_string_lossy()) {
+            Ok(font) => return Some(font),
+            Err(e) => eprintln!("{} {} ({})", "배너 글꼴을 읽을 수 없어 기본 글꼴을 사용합니다:".yellow(), path.display(), e),
+        }
+    }
+    FIGfont::standard().ok()
 } 
\ No newline at end of file
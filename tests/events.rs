@@ -0,0 +1,70 @@
+//! `DevAssistant::subscribe`로 받는 이벤트 순서와 라이브러리용 빌더 동작을 `mock` 제공자로 확인합니다 (네트워크 없음)
+
+use ricci_cli::assistant::{AssistantEvent, DevAssistant, MemoryContextStore};
+use ricci_cli::paths::init_paths;
+use ricci_cli::Config;
+use std::sync::OnceLock;
+
+/// 사용량 기록 등 상태 파일이 실제 홈 디렉토리에 쓰이지 않도록 테스트 전체가 같은 임시 디렉토리를 씀
+/// (상태 경로는 처음 정한 값이 프로세스 끝까지 유지됨)
+fn mock_config() -> Config {
+    static DATA: OnceLock<tempfile::TempDir> = OnceLock::new();
+    let data = DATA.get_or_init(|| tempfile::tempdir().expect("임시 디렉토리 생성 실패"));
+    let mut config = Config::default();
+    config.model_preferences.default_provider = "mock".to_string();
+    config.data_dir = Some(data.path().to_path_buf());
+    init_paths(&config);
+    config
+}
+
+#[tokio::test]
+async fn streamed_answer_emits_token_chunks_then_done() -> anyhow::Result<()> {
+    let mut assistant = DevAssistant::builder(mock_config())
+        .silent()
+        .context_store(MemoryContextStore::default())
+        .build()?;
+    let mut events = assistant.subscribe();
+    let answer = assistant.ask("이벤트 테스트").await?;
+
+    let mut received = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        received.push(event);
+    }
+
+    let Some((AssistantEvent::Done { response, truncated }, chunks)) = received.split_last() else {
+        panic!("마지막 이벤트가 Done이 아닙니다: {:?}", received);
+    };
+    assert!(!chunks.is_empty(), "TokenChunk 이벤트가 없습니다");
+    let streamed: String = chunks.iter()
+        .map(|event| match event {
+            AssistantEvent::TokenChunk { text } => text.as_str(),
+            other => panic!("Done 전에 TokenChunk가 아닌 이벤트: {:?}", other),
+        })
+        .collect();
+    assert_eq!(&streamed, response);
+    assert_eq!(response, &answer);
+    assert!(!truncated);
+    Ok(())
+}
+
+#[tokio::test]
+async fn large_prompts_use_the_confirm_callback_instead_of_the_terminal() -> anyhow::Result<()> {
+    let mut config = mock_config();
+    config.model_preferences.confirm_prompt_tokens = 1;
+
+    // silent는 터미널에서 묻지 않고 보냄
+    let mut silent = DevAssistant::builder(config.clone())
+        .silent()
+        .context_store(MemoryContextStore::default())
+        .build()?;
+    silent.ask("확인 없이 보낼 큰 프롬프트").await?;
+
+    let mut declining = DevAssistant::builder(config)
+        .confirm(|_| false)
+        .silent()
+        .context_store(MemoryContextStore::default())
+        .build()?;
+    let error = declining.ask("거절할 큰 프롬프트").await.unwrap_err();
+    assert!(format!("{:#}", error).contains("요청을 취소했습니다"), "{:#}", error);
+    Ok(())
+}